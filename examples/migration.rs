@@ -0,0 +1,122 @@
+//! Migrating from the `(f64, f64)` placement functions to [PlacementOutput].
+//!
+//! `calculate_placement`, `placement_on_point` and `placement_on_trigger` are deprecated
+//! and return the coordinates of `compute_placement`, `compute_on_point` and
+//! `compute_on_trigger`, which take the same arguments. This example calls both forms
+//! with the `testing` mocks and checks that they agree, so the wrappers cannot drift.
+//!
+//! Run it with `cargo run --example migration`.
+
+use std::cell::Cell;
+
+use dioxus::html::geometry::{ClientPoint, PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{MockElement, settle};
+use dioxus_floating::{Floating, FloatingOptions, Placement, PlacementOutput, ScrollState};
+
+thread_local! {
+    static CHECKED: Cell<usize> = const { Cell::new(0) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn options() -> FloatingOptions {
+    FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(4.0.into()),
+        ..Default::default()
+    }
+}
+
+/// Asserts that an old `(x, y)` result matches the new output.
+fn check(old: (f64, f64), new: PlacementOutput) {
+    assert_eq!(old, (new.x, new.y));
+    CHECKED.with(|checked| checked.set(checked.get() + 1));
+}
+
+#[allow(deprecated)]
+fn synchronous() {
+    let floating = Floating::default();
+    let (scrollable, element, trigger) = (
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        rect(40.0, 480.0, 60.0, 20.0),
+    );
+
+    // before
+    let old = floating.calculate_placement(scrollable, element, trigger, options());
+    // after
+    let new = floating.compute_placement(scrollable, element, trigger, options());
+
+    check(old, new);
+}
+
+#[allow(deprecated)]
+async fn measured() {
+    let floating = Floating::default();
+    let scroll_state = ScrollState {
+        size: PixelsSize::new(500.0, 500.0),
+        bounds: PixelsSize::new(500.0, 500.0),
+        state: PixelsVector2D::new(0.0, 0.0),
+    };
+    let scrollable = MockElement::new(rect(0.0, 0.0, 500.0, 500.0)).mounted();
+    let element = MockElement::new(rect(0.0, 0.0, 100.0, 50.0)).mounted();
+    let trigger = MockElement::new(rect(40.0, 40.0, 60.0, 20.0)).mounted();
+    let point = ClientPoint::new(250.0, 490.0);
+
+    // before
+    let old = floating
+        .placement_on_trigger(
+            scroll_state,
+            scrollable.clone(),
+            element.clone(),
+            trigger.clone(),
+            options(),
+        )
+        .await;
+    // after
+    let new = floating
+        .compute_on_trigger(
+            scroll_state,
+            scrollable.clone(),
+            element.clone(),
+            trigger,
+            options(),
+        )
+        .await;
+    check(old, new);
+
+    // before
+    let old = floating
+        .placement_on_point(
+            scroll_state,
+            scrollable.clone(),
+            element.clone(),
+            point,
+            options(),
+        )
+        .await;
+    // after
+    let new = floating
+        .compute_on_point(scroll_state, scrollable, element, point, options())
+        .await;
+    check(old, new);
+}
+
+fn app() -> Element {
+    use_hook(|| spawn(measured()));
+    rsx! {}
+}
+
+fn main() {
+    synchronous();
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    assert_eq!(CHECKED.with(Cell::get), 3);
+    println!("the deprecated functions match their replacements");
+}
@@ -0,0 +1,86 @@
+//! Popovers in two `dioxus-desktop` windows.
+//!
+//! Each window is its own `VirtualDom`, and the state the hooks share (document
+//! listeners, measurement batches, the device pixel ratio) is kept per window: the
+//! popover of the secondary window is placed in its own, smaller viewport, and a press
+//! outside of it closes it without touching the popover of the primary window.
+//!
+//! Run it with `cargo run --example multi_window --features desktop`.
+
+use dioxus::desktop::{Config, LogicalSize, WindowBuilder};
+use dioxus::prelude::*;
+use dioxus_floating::{
+    FloatingOptions, Middleware, Placement, ScrollableView, WidgetConfig, use_floating_widget,
+};
+
+fn main() {
+    dioxus::LaunchBuilder::desktop()
+        .with_cfg(window("Primary", 900.0, 600.0))
+        .launch(Primary);
+}
+
+/// Returns the configuration of a window of the given size.
+fn window(title: &str, width: f64, height: f64) -> Config {
+    Config::new().with_window(
+        WindowBuilder::new()
+            .with_title(title)
+            .with_inner_size(LogicalSize::new(width, height)),
+    )
+}
+
+#[component]
+fn Primary() -> Element {
+    rsx! {
+        Page {
+            title: "Primary window",
+            button {
+                onclick: move |_| {
+                    // a narrow window: the popover next to its right edge is shifted
+                    dioxus::desktop::window()
+                        .new_window(VirtualDom::new(Secondary), window("Secondary", 320.0, 400.0));
+                },
+                "Open a secondary window"
+            }
+        }
+    }
+}
+
+#[component]
+fn Secondary() -> Element {
+    rsx! {
+        Page { title: "Secondary window" }
+    }
+}
+
+/// A scrollable page with a popover, dismissed by a press outside of it.
+#[component]
+fn Page(title: String, children: Element) -> Element {
+    let widget = use_floating_widget(WidgetConfig {
+        options: FloatingOptions {
+            placement: Placement::BottomEnd,
+            middleware: vec![Middleware::Flip, Middleware::Shift],
+            ..Default::default()
+        },
+        outside_press: true,
+        ..Default::default()
+    });
+
+    rsx! {
+        ScrollableView { style: "height: 100vh; overflow: auto; font-family: sans-serif",
+            div { style: "height: 1200px; padding: 16px",
+                h1 { "{title}" }
+                {children}
+                div { style: "display: flex; justify-content: flex-end; margin-top: 120px",
+                    button { ..widget.trigger_props(), "Toggle the popover" }
+                }
+                if (widget.is_open)() {
+                    div {
+                        ..widget.floating_props(),
+                        style: "width: 240px; padding: 12px; background: white; border: 1px solid #ccc",
+                        "Placed in this window's viewport; a press outside closes it, in this window only."
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Placements consumed from plain async code with [Floating::placement_stream].
+//!
+//! A controller that owns the business logic of a tooltip (not a component) follows
+//! its placement from a spawned task. The view is scrolled several times before the
+//! task runs: the stream skips to the latest placement instead of replaying each one.
+//! The `testing` mocks stand in for the DOM.
+//!
+//! Run it with `cargo run --example placement_stream`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{Floating, FloatingOptions, Placement, ScrollableContext};
+use futures_util::StreamExt;
+
+thread_local! {
+    static CONTROLLER: RefCell<Option<TooltipController>> = const { RefCell::new(None) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+/// The business logic of a tooltip: it records where the tooltip is, e.g., to decide
+/// whether a hint next to it still fits.
+#[derive(Clone)]
+struct TooltipController {
+    context: ScrollableContext,
+    trigger: MockElement,
+    positions: Rc<RefCell<Vec<(f64, f64, Placement)>>>,
+}
+
+impl TooltipController {
+    /// Follows the placements of the tooltip until its refs are dropped.
+    fn start(
+        &self,
+        element: Signal<Option<Rc<MountedData>>>,
+        trigger: Signal<Option<Rc<MountedData>>>,
+    ) {
+        let options = FloatingOptions {
+            placement: Placement::TopCenter,
+            placement_offset: Some(8.0.into()),
+            ..Default::default()
+        };
+        let mut placements =
+            consume_context::<Floating>().placement_stream(self.context, element, trigger, options);
+        let positions = self.positions.clone();
+        spawn(async move {
+            while let Some(output) = placements.next().await {
+                positions
+                    .borrow_mut()
+                    .push((output.x, output.y, output.placement));
+            }
+        });
+    }
+
+    /// Scrolls the view, the trigger moving with the content.
+    fn scroll_to(&mut self, y: f64) {
+        self.trigger.set_rect(rect(200.0, 300.0 - y, 100.0, 30.0));
+        self.context.publish_offset(PixelsVector2D::new(0.0, y));
+    }
+}
+
+fn app() -> Element {
+    use_hook(|| {
+        let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 600.0, 400.0));
+        config.scroll_state.size = PixelsSize::new(600.0, 3000.0);
+        let context = provide_mock_scrollable(config);
+        let trigger = MockElement::new(rect(200.0, 300.0, 100.0, 30.0));
+        let controller = TooltipController {
+            context,
+            trigger: trigger.clone(),
+            positions: Rc::default(),
+        };
+
+        let element = Signal::new(Some(
+            MockElement::new(rect(0.0, 0.0, 160.0, 60.0)).mounted(),
+        ));
+        let trigger = Signal::new(Some(trigger.mounted()));
+        controller.start(element, trigger);
+        CONTROLLER.with(|cell| *cell.borrow_mut() = Some(controller));
+    });
+    rsx! {}
+}
+
+fn main() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    let mut controller = CONTROLLER.with(|cell| cell.borrow().clone()).unwrap();
+    // a fling: three offsets before the task gets to run
+    dom.in_runtime(|| {
+        for y in [100.0, 200.0, 250.0] {
+            controller.scroll_to(y);
+        }
+    });
+    settle(&mut dom);
+
+    let positions = controller.positions.borrow();
+    for (x, y, placement) in positions.iter() {
+        println!("{placement:?} at ({x}, {y})");
+    }
+    // the initial placement, then the latest one; near the top edge it flips below
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions[0], (170.0, 232.0, Placement::TopCenter));
+    assert_eq!(positions[1], (170.0, 88.0, Placement::BottomCenter));
+}
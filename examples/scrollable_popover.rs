@@ -0,0 +1,159 @@
+//! A popover whose content is itself a [ScrollableView], with tooltips in the page and
+//! in the popover.
+//!
+//! The nesting rules of [ScrollableView] decide the boundary of each placement:
+//! - the popover positions itself in the component that renders its nested view, so it
+//!   sees the page;
+//! - a tooltip inside the popover sees the popover's view and is clipped by it, unless
+//!   it pins the page with [FloatingOptions::context], taken from
+//!   [use_parent_scroll_context] or captured with [use_scroll_context] above the nested
+//!   view.
+//!
+//! Hover the rows of the page and of the popover: the pinned tooltips overflow the
+//! popover, the others flip and shift to stay inside of it. Scroll either view and the
+//! tooltips follow their rows.
+//!
+//! Run it with `dx serve --example scrollable_popover --platform web`.
+
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_floating::{
+    FloatingOptions, FloatingResult, Placement, ScrollableContext, ScrollableView,
+    use_parent_scroll_context, use_placement, use_scroll_context,
+};
+
+fn main() {
+    dioxus::launch(App);
+}
+
+#[component]
+fn App() -> Element {
+    rsx! {
+        ScrollableView { style: "height: 100vh; overflow: auto; font-family: sans-serif;",
+            div { style: "height: 2000px; padding: 120px; display: flex; flex-direction: column; gap: 24px;",
+                Tooltip { label: "A row of the page", tip: "Anchored to the page" }
+                ScrollablePopover {}
+                Tooltip { label: "Another row of the page", tip: "Anchored to the page" }
+            }
+        }
+    }
+}
+
+/// How a tooltip inside the popover picks its boundary.
+#[derive(Clone, Copy, PartialEq)]
+enum Pin {
+    /// The nearest view, the popover's.
+    Nearest,
+    /// The enclosing view, from [use_parent_scroll_context].
+    Parent,
+    /// The page, captured above the popover's view.
+    Captured,
+}
+
+/// A trigger that toggles a popover with a scrolling list of rows.
+#[component]
+fn ScrollablePopover() -> Element {
+    // declared above the nested view: the page
+    let page = use_scroll_context();
+    let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let mut is_open = use_signal(|| false);
+    let placement = use_placement(element_ref, trigger_ref, FloatingOptions::default());
+
+    rsx! {
+        button {
+            style: "align-self: flex-start;",
+            onmounted: move |evt| trigger_ref.set(Some(evt.data.clone())),
+            onclick: move |_| {
+                if is_open() {
+                    element_ref.set(None);
+                }
+                is_open.toggle();
+            },
+            "Open scrollable popover"
+        }
+        if is_open() {
+            div {
+                onmounted: move |evt| element_ref.set(Some(evt.data.clone())),
+                style: "{floating_style(placement())} width: 260px;",
+                ScrollableView { style: "height: 220px; overflow: auto;",
+                    div { style: "display: flex; flex-direction: column; gap: 40px; padding: 12px; height: 900px;",
+                        for index in 0..9 {
+                            PopoverRow {
+                                key: "{index}",
+                                index,
+                                pin: [Pin::Nearest, Pin::Parent, Pin::Captured][index % 3],
+                                page,
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A row of the popover, with a tooltip pinned according to `pin`.
+#[component]
+fn PopoverRow(index: usize, pin: Pin, page: ScrollableContext) -> Element {
+    let parent = use_parent_scroll_context();
+    let (context, boundary) = match pin {
+        Pin::Nearest => (None, "the popover"),
+        Pin::Parent => (parent, "the page, through use_parent_scroll_context"),
+        Pin::Captured => (Some(page), "the page, captured above the popover"),
+    };
+
+    rsx! {
+        Tooltip {
+            label: "Row {index}",
+            tip: "A tooltip wider than the popover, bounded by {boundary}",
+            context,
+        }
+    }
+}
+
+/// A label with a tooltip on hover, bounded by `context` or the nearest view.
+#[component]
+fn Tooltip(label: String, tip: String, context: Option<ScrollableContext>) -> Element {
+    let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let mut is_hovered = use_signal(|| false);
+    let options = FloatingOptions {
+        placement: Placement::RightCenter,
+        context,
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+
+    rsx! {
+        span {
+            style: "align-self: flex-start;",
+            onmounted: move |evt| trigger_ref.set(Some(evt.data.clone())),
+            onmouseenter: move |_| is_hovered.set(true),
+            onmouseleave: move |_| {
+                is_hovered.set(false);
+                element_ref.set(None);
+            },
+            "{label}"
+        }
+        if is_hovered() {
+            div {
+                onmounted: move |evt| element_ref.set(Some(evt.data.clone())),
+                style: "{floating_style(placement())} padding: 4px 8px; white-space: nowrap;",
+                "{tip}"
+            }
+        }
+    }
+}
+
+fn floating_style(result: FloatingResult) -> String {
+    format!(
+        "position: fixed; inset: 0px auto auto 0px; margin: 0px; z-index: 10; \
+        background: #fff; border: 1px solid #888; box-shadow: 0 2px 8px rgba(0, 0, 0, 0.2); \
+        transform: translate3d({}px, {}px, 0px); opacity: {};",
+        result.x,
+        result.y,
+        if result.is_ready { 1 } else { 0 }
+    )
+}
@@ -0,0 +1,669 @@
+//! A reproduction environment exercising every placement and middleware combination.
+//!
+//! Run it with `dx serve --example showcase --platform web` and open a scenario, e.g.
+//! `/?scenario=grid&placement=top-end&flip=1&shift=0&scroll=300&debug=1`.
+//!
+//! Query parameters:
+//! - `scenario`: `grid` (default), `context-menu`, `tooltip`, `nested`,
+//!   `stress` (mounts and unmounts an open grid every 50ms; the console should stay clean),
+//!   `collapsing-panel` (a side panel animates its width; the open popover hugs the
+//!   edge of the view throughout), `momentum` (an open popover in a long view: on iOS,
+//!   fling it and let it bounce at the edges; the popover follows without freezing) or
+//!   `loading-edge` (a list that never scrolls past its loading region; the open popover
+//!   stays put at the edge instead of flickering).
+//! - `placement`: one of the 12 placements in kebab-case (default `bottom-start`).
+//! - `flip`, `shift`: `1` or `0` to enable or disable the middleware (both enabled by default).
+//! - `scroll`: initial vertical scroll offset of the view in pixels.
+//! - `debug`: `1` to overlay the computed position, resolved placement and shift, and
+//!   the time from the click on a popover trigger to its ready placement, and to list
+//!   the open floating elements of the view with their last measurement time.
+//! - `prewarm`: `1` to measure popover triggers on hover (see `Floating::prewarm`), so
+//!   opening them skips one measurement round trip.
+//! - `compact`: `1` for popovers touching their triggers instead of 8px away. Toggling it
+//!   moves the open popovers live.
+
+use std::rc::Rc;
+
+use dioxus::html::geometry::PixelsVector2D;
+use dioxus::prelude::*;
+use dioxus_floating::{
+    FloatingArrow, FloatingOptions, FloatingResult, Middleware, Offset, Placement, ScrollIntercept,
+    ScrollState, ScrollableView, WaitStrategy, use_floating, use_placement, use_placement_on_point,
+    use_scroll_context,
+};
+
+const PLACEMENTS: [(&str, Placement); 12] = [
+    ("top-start", Placement::TopStart),
+    ("top-center", Placement::TopCenter),
+    ("top-end", Placement::TopEnd),
+    ("bottom-start", Placement::BottomStart),
+    ("bottom-center", Placement::BottomCenter),
+    ("bottom-end", Placement::BottomEnd),
+    ("left-start", Placement::LeftStart),
+    ("left-center", Placement::LeftCenter),
+    ("left-end", Placement::LeftEnd),
+    ("right-start", Placement::RightStart),
+    ("right-center", Placement::RightCenter),
+    ("right-end", Placement::RightEnd),
+];
+
+/// Trigger positions (left %, top px) near every edge and corner of the view.
+const SPOTS: [(&str, f64, f64); 9] = [
+    ("top-left", 0.0, 0.0),
+    ("top", 45.0, 0.0),
+    ("top-right", 88.0, 0.0),
+    ("left", 0.0, 260.0),
+    ("center", 45.0, 260.0),
+    ("right", 88.0, 260.0),
+    ("bottom-left", 0.0, 1960.0),
+    ("bottom", 45.0, 1960.0),
+    ("bottom-right", 88.0, 1960.0),
+];
+
+fn main() {
+    dioxus::launch(App);
+}
+
+/// The scenario configuration parsed from the query string.
+#[derive(Debug, Clone, PartialEq)]
+struct Scenario {
+    name: String,
+    placement: Placement,
+    flip: bool,
+    shift: bool,
+    scroll: f64,
+    debug: bool,
+    prewarm: bool,
+    compact: bool,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            name: "grid".to_string(),
+            placement: Placement::BottomStart,
+            flip: true,
+            shift: true,
+            scroll: 0.0,
+            debug: false,
+            prewarm: false,
+            compact: false,
+        }
+    }
+}
+
+impl Scenario {
+    fn from_query(query: &str) -> Self {
+        let mut scenario = Scenario::default();
+        for (key, value) in query
+            .trim_start_matches('?')
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+        {
+            match key {
+                "scenario" => scenario.name = value.to_string(),
+                "placement" => {
+                    if let Some((_, placement)) = PLACEMENTS.iter().find(|(name, _)| *name == value)
+                    {
+                        scenario.placement = *placement;
+                    }
+                }
+                "flip" => scenario.flip = value == "1",
+                "shift" => scenario.shift = value == "1",
+                "scroll" => scenario.scroll = value.parse().unwrap_or_default(),
+                "debug" => scenario.debug = value == "1",
+                "prewarm" => scenario.prewarm = value == "1",
+                "compact" => scenario.compact = value == "1",
+                _ => {}
+            }
+        }
+
+        scenario
+    }
+
+    fn to_query(&self) -> String {
+        let placement = PLACEMENTS
+            .iter()
+            .find(|(_, placement)| *placement == self.placement)
+            .map(|(name, _)| *name)
+            .unwrap_or("bottom-start");
+
+        format!(
+            "?scenario={}&placement={placement}&flip={}&shift={}&scroll={}&debug={}&prewarm={}&compact={}",
+            self.name,
+            self.flip as u8,
+            self.shift as u8,
+            self.scroll,
+            self.debug as u8,
+            self.prewarm as u8,
+            self.compact as u8
+        )
+    }
+
+    fn options(&self) -> FloatingOptions {
+        let mut middleware = Vec::new();
+        if self.flip {
+            middleware.push(Middleware::Flip);
+        }
+        if self.shift {
+            middleware.push(Middleware::Shift);
+        }
+
+        FloatingOptions {
+            middleware,
+            placement: self.placement,
+            placement_offset: Some(if self.compact {
+                Offset::zero()
+            } else {
+                Offset::new(8.0, 8.0)
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+#[component]
+fn App() -> Element {
+    let mut scenario = use_signal(Scenario::default);
+
+    // read the scenario from the query string (web only)
+    use_hook(move || {
+        spawn(async move {
+            let mut eval = document::eval("dioxus.send(window.location.search);");
+            if let Ok(query) = eval.recv::<String>().await {
+                scenario.set(Scenario::from_query(&query));
+            }
+        });
+    });
+
+    rsx! {
+        div { style: "display: flex; flex-direction: column; height: 100vh; font-family: sans-serif;",
+            Toolbar { scenario }
+            match scenario().name.as_str() {
+                "context-menu" => rsx! { ContextMenuScenario { scenario: scenario() } },
+                "tooltip" => rsx! { TooltipScenario { scenario: scenario() } },
+                "nested" => rsx! { NestedScenario { scenario: scenario() } },
+                "stress" => rsx! { StressScenario { scenario: scenario() } },
+                "collapsing-panel" => rsx! { CollapsingPanelScenario { scenario: scenario() } },
+                "web-font" => rsx! { WebFontScenario { scenario: scenario() } },
+                "momentum" => rsx! { MomentumScenario { scenario: scenario() } },
+                "loading-edge" => rsx! { LoadingEdgeScenario { scenario: scenario() } },
+                _ => rsx! { GridScenario { scenario: scenario() } },
+            }
+        }
+    }
+}
+
+/// Controls to switch scenarios, with the shareable query string of the current one.
+#[component]
+fn Toolbar(scenario: Signal<Scenario>) -> Element {
+    rsx! {
+        div { style: "display: flex; gap: 8px; padding: 8px; border-bottom: 1px solid #ccc; flex-wrap: wrap;",
+            select {
+                value: scenario().name,
+                onchange: move |evt| scenario.write().name = evt.value(),
+                for name in [
+                    "grid",
+                    "context-menu",
+                    "tooltip",
+                    "nested",
+                    "stress",
+                    "collapsing-panel",
+                    "web-font",
+                    "momentum",
+                    "loading-edge",
+                ] {
+                    option { value: name, "{name}" }
+                }
+            }
+            select {
+                onchange: move |evt| {
+                    if let Some((_, placement)) = PLACEMENTS.iter().find(|(name, _)| *name == evt.value()) {
+                        scenario.write().placement = *placement;
+                    }
+                },
+                for (name, placement) in PLACEMENTS {
+                    option { value: name, selected: scenario().placement == placement, "{name}" }
+                }
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: scenario().flip,
+                    onchange: move |evt| scenario.write().flip = evt.checked(),
+                }
+                "flip"
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: scenario().shift,
+                    onchange: move |evt| scenario.write().shift = evt.checked(),
+                }
+                "shift"
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: scenario().debug,
+                    onchange: move |evt| scenario.write().debug = evt.checked(),
+                }
+                "debug"
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: scenario().prewarm,
+                    onchange: move |evt| scenario.write().prewarm = evt.checked(),
+                }
+                "prewarm"
+            }
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked: scenario().compact,
+                    onchange: move |evt| scenario.write().compact = evt.checked(),
+                }
+                "compact"
+            }
+            code { "{scenario().to_query()}" }
+        }
+    }
+}
+
+/// Triggers near every edge and corner of a scrollable view.
+#[component]
+fn GridScenario(scenario: Scenario) -> Element {
+    rsx! {
+        ScrollableView { style: "position: relative; flex: 1; overflow: auto;",
+            InitialScroll { offset: scenario.scroll }
+            if scenario.debug {
+                FloatingsPanel {}
+            }
+            div { style: "position: relative; height: 2000px; min-width: 100%;",
+                for (name, left, top) in SPOTS {
+                    div {
+                        key: "{name}",
+                        style: "position: absolute; left: {left}%; top: {top}px;",
+                        Popover { label: name.to_string(), scenario: scenario.clone() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Rapidly mounts and unmounts a grid of open popovers, so route-change teardowns
+/// happen while placements are being measured.
+#[component]
+fn StressScenario(scenario: Scenario) -> Element {
+    let mut is_mounted = use_signal(|| true);
+    use_future(move || async move {
+        loop {
+            WaitStrategy::Timeout(50).wait().await;
+            is_mounted.toggle();
+        }
+    });
+
+    rsx! {
+        if is_mounted() {
+            ScrollableView { style: "position: relative; flex: 1; overflow: auto;",
+                div { style: "position: relative; height: 2000px; min-width: 100%;",
+                    for (name, left, top) in SPOTS {
+                        div {
+                            key: "{name}",
+                            style: "position: absolute; left: {left}%; top: {top}px;",
+                            Popover { label: name.to_string(), scenario: scenario.clone(), open: true }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A context menu anchored to the click point.
+#[component]
+fn ContextMenuScenario(scenario: Scenario) -> Element {
+    let mut click_point = use_signal(|| None);
+    let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let placement = use_placement_on_point(element_ref, click_point, scenario.options());
+
+    rsx! {
+        ScrollableView { style: "flex: 1; overflow: auto;",
+            InitialScroll { offset: scenario.scroll }
+            div {
+                style: "height: 2000px; background: repeating-linear-gradient(#fff, #fff 40px, #f4f4f4 40px, #f4f4f4 80px);",
+                oncontextmenu: move |evt| {
+                    evt.prevent_default();
+                    click_point.set(Some(evt.client_coordinates()));
+                },
+                onclick: move |_| click_point.set(None),
+                "Right click anywhere"
+            }
+            if click_point().is_some() {
+                div {
+                    onmounted: move |evt| element_ref.set(Some(evt.data.clone())),
+                    style: "{floating_style(placement())} width: 180px; height: 220px;",
+                    "Context menu"
+                }
+                if scenario.debug {
+                    DebugOverlay { result: placement() }
+                }
+            }
+        }
+    }
+}
+
+/// Tooltips shown on hover.
+#[component]
+fn TooltipScenario(scenario: Scenario) -> Element {
+    rsx! {
+        ScrollableView { style: "flex: 1; overflow: auto;",
+            InitialScroll { offset: scenario.scroll }
+            if scenario.debug {
+                FloatingsPanel {}
+            }
+            div { style: "display: flex; flex-direction: column; gap: 120px; padding: 8px; height: 2000px;",
+                for index in 0..15 {
+                    Tooltip { key: "{index}", label: format!("Hover me #{index}"), scenario: scenario.clone() }
+                }
+            }
+        }
+    }
+}
+
+/// A scrollable view inside another one; popovers use the nearest view as boundary.
+#[component]
+fn NestedScenario(scenario: Scenario) -> Element {
+    rsx! {
+        ScrollableView { style: "flex: 1; overflow: auto;",
+            InitialScroll { offset: scenario.scroll }
+            div { style: "height: 1500px; padding: 120px;",
+                Popover { label: "outer".to_string(), scenario: scenario.clone() }
+                ScrollableView { style: "margin-top: 40px; height: 300px; width: 400px; overflow: auto; border: 1px solid #999;",
+                    div { style: "height: 1000px; padding: 24px;",
+                        Popover { label: "inner".to_string(), scenario: scenario.clone() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A side panel collapsing over 200ms next to a view with an open popover at its edge.
+#[component]
+fn CollapsingPanelScenario(scenario: Scenario) -> Element {
+    let mut is_collapsed = use_signal(|| false);
+    let width = if is_collapsed() { 48 } else { 320 };
+
+    rsx! {
+        div { style: "display: flex; flex: 1; min-height: 0;",
+            div { style: "width: {width}px; transition: width 200ms ease-in-out; background: #eee; overflow: hidden;",
+                button { onclick: move |_| is_collapsed.toggle(), "Toggle panel" }
+            }
+            ScrollableView { style: "flex: 1; overflow: auto;", track_resize_animation: true,
+                InitialScroll { offset: scenario.scroll }
+                if scenario.debug {
+                    FloatingsPanel {}
+                }
+                div { style: "height: 1500px; padding: 120px 8px;",
+                    Popover { label: "Edge".to_string(), scenario: scenario.clone(), open: true }
+                }
+            }
+        }
+    }
+}
+
+/// A centered popover opened on the first paint, with text in a web font that arrives
+/// later (throttle the network to see it): it grows when the font lands and is centered
+/// again as soon as `document.fonts.ready` resolves.
+#[component]
+fn WebFontScenario(scenario: Scenario) -> Element {
+    let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let options = FloatingOptions {
+        placement: Placement::BottomCenter,
+        ..scenario.options()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+
+    rsx! {
+        style {
+            r#"@font-face {{
+                font-family: "Showcase Display";
+                src: url("https://fonts.gstatic.com/s/abrilfatface/v23/zOL64pLDlL1D99S8g8PtiKchq-dmjQ.woff2") format("woff2");
+                font-display: swap;
+            }}"#
+        }
+        ScrollableView { style: "flex: 1; overflow: auto;",
+            div { style: "height: 1500px; padding: 120px 240px;",
+                button { onmounted: move |evt| trigger_ref.set(Some(evt.data.clone())), "Centered" }
+                div {
+                    onmounted: move |evt| element_ref.set(Some(evt.data.clone())),
+                    style: "{floating_style(placement())} white-space: nowrap; font: 28px \"Showcase Display\", serif;",
+                    "Measured with the fallback font"
+                }
+                if scenario.debug {
+                    DebugOverlay { result: placement() }
+                }
+            }
+        }
+    }
+}
+
+/// A long view with an open popover, to fling on iOS: the popover follows the momentum and
+/// the rubber band at the edges without freezing and jumping.
+#[component]
+fn MomentumScenario(scenario: Scenario) -> Element {
+    rsx! {
+        ScrollableView { style: "flex: 1; overflow: auto; -webkit-overflow-scrolling: touch;",
+            InitialScroll { offset: scenario.scroll }
+            if scenario.debug {
+                FloatingsPanel {}
+            }
+            div { style: "height: 4000px; padding: 240px 8px;",
+                Popover { label: "Fling me".to_string(), scenario: scenario.clone(), open: true }
+            }
+        }
+    }
+}
+
+/// A list whose rows below 1200px are still loading: scrolls past the start of the
+/// loading region are clamped by the view itself.
+#[component]
+fn LoadingEdgeScenario(scenario: Scenario) -> Element {
+    let loaded_height = 1200.0;
+
+    rsx! {
+        ScrollableView { style: "flex: 1; overflow: auto;",
+            on_scroll_intercept: move |state: ScrollState| {
+                let limit = (loaded_height - state.bounds.height).max(0.0);
+                if state.state.y > limit {
+                    ScrollIntercept::Replace(state.with_offset(PixelsVector2D::new(state.state.x, limit)))
+                } else {
+                    ScrollIntercept::Accept
+                }
+            },
+            InitialScroll { offset: scenario.scroll }
+            if scenario.debug {
+                FloatingsPanel {}
+            }
+            div { style: "height: {loaded_height}px; padding: 240px 8px; box-sizing: border-box;",
+                Popover { label: "Scroll down".to_string(), scenario: scenario.clone(), open: true }
+            }
+            div { style: "height: 2000px; background: repeating-linear-gradient(#eee 0 40px, #fff 40px 80px);",
+                "Loading..."
+            }
+        }
+    }
+}
+
+/// A trigger that toggles a popover with the scenario options.
+#[component]
+fn Popover(label: String, scenario: Scenario, #[props(default)] open: bool) -> Element {
+    let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let mut is_opened = use_signal(|| open);
+    let placement = use_placement(element_ref, trigger_ref, scenario.options());
+    let floating = use_floating();
+    let prewarm = scenario.prewarm;
+    // click to ready time, in the page clock; both ends pay the same eval delay
+    let mut clicked_at = use_signal(|| Option::<f64>::None);
+    let mut open_ms = use_signal(|| Option::<f64>::None);
+
+    use_effect(move || {
+        if !placement().is_ready {
+            return;
+        }
+        if let Some(start) = clicked_at.take() {
+            spawn(async move {
+                open_ms.set(now().await.map(|end| end - start));
+            });
+        }
+    });
+
+    rsx! {
+        button {
+            onmounted: move |evt| trigger_ref.set(Some(evt.data.clone())),
+            onpointerenter: move |_| {
+                if let Some(trigger) = trigger_ref().filter(|_| prewarm) {
+                    floating.prewarm(trigger);
+                }
+            },
+            onclick: move |_| async move {
+                if is_opened() {
+                    element_ref.set(None);
+                } else {
+                    clicked_at.set(now().await);
+                }
+                is_opened.toggle();
+            },
+            "{label}"
+        }
+        if is_opened() {
+            div {
+                onmounted: move |evt| element_ref.set(Some(evt.data.clone())),
+                style: "{floating_style(placement())} width: 160px; height: 120px;",
+                "{label}: {placement().placement:?}"
+                FloatingArrow { result: placement(), fill: "#fff", stroke: "#888" }
+            }
+            if scenario.debug {
+                DebugOverlay { result: placement(), open_ms: open_ms() }
+            }
+        }
+    }
+}
+
+/// A trigger that shows a tooltip on hover.
+#[component]
+fn Tooltip(label: String, scenario: Scenario) -> Element {
+    let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let mut is_hovered = use_signal(|| false);
+    let placement = use_placement(element_ref, trigger_ref, scenario.options());
+
+    rsx! {
+        span {
+            onmounted: move |evt| trigger_ref.set(Some(evt.data.clone())),
+            onmouseenter: move |_| is_hovered.set(true),
+            onmouseleave: move |_| {
+                is_hovered.set(false);
+                element_ref.set(None);
+            },
+            "{label}"
+        }
+        if is_hovered() {
+            div {
+                onmounted: move |evt| element_ref.set(Some(evt.data.clone())),
+                style: "{floating_style(placement())} padding: 4px 8px;",
+                "Tooltip for {label}"
+            }
+            if scenario.debug {
+                DebugOverlay { result: placement() }
+            }
+        }
+    }
+}
+
+/// Outlines the computed position and prints the placement metadata.
+#[component]
+fn DebugOverlay(result: FloatingResult, #[props(default)] open_ms: Option<f64>) -> Element {
+    rsx! {
+        div {
+            style: "position: fixed; inset: 0px auto auto 0px; pointer-events: none; \
+                transform: translate3d({result.x}px, {result.y}px, 0px); \
+                outline: 1px dashed red; width: 6px; height: 6px; background: red; z-index: 11;",
+        }
+        div {
+            style: "position: fixed; right: 8px; bottom: 8px; padding: 4px 8px; \
+                background: #222; color: #fff; font: 12px monospace; z-index: 11;",
+            "x={result.x:.1} y={result.y:.1} ready={result.is_ready} "
+            "placement={result.placement:?} shift=({result.shift.x:.1}, {result.shift.y:.1})"
+            if let Some(open_ms) = open_ms {
+                " open={open_ms:.1}ms"
+            }
+        }
+    }
+}
+
+/// Lists the floating elements open in the nearest view.
+#[component]
+fn FloatingsPanel() -> Element {
+    let ctx = use_scroll_context();
+    let active = ctx.active_floatings();
+
+    rsx! {
+        div {
+            style: "position: fixed; right: 8px; top: 8px; padding: 4px 8px; \
+                background: #222; color: #fff; font: 12px monospace; z-index: 11;",
+            "{active} open"
+            for floating in ctx.floatings() {
+                div { key: "{floating.name}",
+                    "{floating.name}: {floating.placement:?} ready={floating.is_ready}"
+                    if let Some(compute_ms) = floating.last_compute_ms {
+                        " {compute_ms:.1}ms"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Scrolls the nearest view once it has been measured.
+#[component]
+fn InitialScroll(offset: f64) -> Element {
+    let ctx = use_scroll_context();
+    let mut is_done = use_signal(|| false);
+
+    use_effect(move || {
+        if offset > 0.0 && !*is_done.peek() && (ctx.scroll_state)().is_some() {
+            is_done.set(true);
+            spawn(async move {
+                ctx.scroll(PixelsVector2D::new(0.0, offset), ScrollBehavior::Instant)
+                    .await;
+            });
+        }
+    });
+
+    rsx! {}
+}
+
+/// The page clock (`performance.now()`), `None` outside of a browser.
+async fn now() -> Option<f64> {
+    document::eval("dioxus.send(performance.now());")
+        .recv::<f64>()
+        .await
+        .ok()
+}
+
+fn floating_style(result: FloatingResult) -> String {
+    format!(
+        "position: fixed; inset: 0px auto auto 0px; margin: 0px; z-index: 10; \
+        background: #fff; border: 1px solid #888; box-shadow: 0 2px 8px rgba(0, 0, 0, 0.2); \
+        transform: translate3d({}px, {}px, 0px); opacity: {};",
+        result.x,
+        result.y,
+        if result.is_ready { 1 } else { 0 }
+    )
+}
@@ -0,0 +1,115 @@
+#[cfg(feature = "hooks")]
+use dioxus::prelude::*;
+
+use crate::Side;
+#[cfg(feature = "hooks")]
+use crate::{FloatingResult, listener::JsListener};
+
+#[cfg(feature = "hooks")]
+const LIVE_REGION_SCRIPT: &str = r#"
+    const region = document.createElement("div");
+    region.setAttribute("role", "status");
+    region.setAttribute("aria-live", "polite");
+    region.style.cssText = "position:absolute;width:1px;height:1px;margin:-1px;padding:0;"
+        + "overflow:hidden;clip:rect(0,0,0,0);white-space:nowrap;border:0;";
+    document.body.appendChild(region);
+    while (true) {
+        const message = await dioxus.recv();
+        if (message === true) break;
+        // clear first, so the same message is announced again
+        region.textContent = "";
+        requestAnimationFrame(() => { region.textContent = message; });
+    }
+    region.remove();
+"#;
+
+/// Options for announcing placement changes to screen-reader users.
+///
+/// When the resolved side of an open floating element changes (e.g., a menu
+/// flips above its trigger), the message is posted to a visually hidden
+/// `aria-live="polite"` region. The initial positioning is never announced.
+///
+/// # Example
+/// ```rust
+/// use dioxus_floating::{AnnounceOptions, FloatingOptions};
+///
+/// let options = FloatingOptions {
+///     announce: Some(AnnounceOptions::new("menu moved {side} the button")),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnounceOptions {
+    /// The message template. `{side}` is replaced with "above", "below",
+    /// "to the left of" or "to the right of".
+    pub template: String,
+}
+
+impl Default for AnnounceOptions {
+    fn default() -> Self {
+        Self::new("moved {side} the trigger")
+    }
+}
+
+impl AnnounceOptions {
+    /// Creates a new [AnnounceOptions] with the given message template.
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+        }
+    }
+
+    /// Returns the message for the given side.
+    pub fn message(&self, side: Side) -> String {
+        let side = match side {
+            Side::Top => "above",
+            Side::Bottom => "below",
+            Side::Left => "to the left of",
+            Side::Right => "to the right of",
+        };
+
+        self.template.replace("{side}", side)
+    }
+}
+
+/// Internal: Announces side changes of an open placement through a live region.
+///
+/// The region is created once per hook and removed when the owning component unmounts.
+#[cfg(feature = "hooks")]
+pub(crate) fn use_announcer(announce: Option<AnnounceOptions>, result: ReadSignal<FloatingResult>) {
+    let live_region = use_live_region(announce.is_some());
+
+    let mut last_side = use_signal(|| Option::<Side>::None);
+    use_effect(move || {
+        let result = result();
+        if !result.is_ready {
+            // closed: the next open is an initial positioning again
+            last_side.set(None);
+            return;
+        }
+
+        let side = result.placement.side();
+        let previous = last_side.replace(Some(side));
+        if let (Some(previous), Some(live_region), Some(announce)) =
+            (previous, live_region, announce.as_ref())
+            && previous != side
+        {
+            live_region.send(announce.message(side));
+        }
+    });
+}
+
+/// Internal: Creates a visually hidden live region if `enabled`; messages sent to it are
+/// announced. It is removed when the owning component unmounts.
+#[cfg(feature = "hooks")]
+pub(crate) fn use_live_region(enabled: bool) -> Option<JsListener> {
+    let live_region =
+        use_hook(|| enabled.then(|| JsListener::start(LIVE_REGION_SCRIPT, |_: bool| {})));
+    use_drop(move || {
+        if let Some(live_region) = live_region {
+            live_region.stop();
+        }
+    });
+
+    live_region
+}
@@ -0,0 +1,233 @@
+#[cfg(feature = "hooks")]
+use dioxus::prelude::*;
+
+use crate::geometry::{PixelsRect, PixelsVector2D};
+use crate::{FloatingResult, Placement, Side};
+
+/// Where an arrow pointing at the trigger goes on the floating element, see
+/// [FloatingResult::arrow].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArrowPosition {
+    /// The edge of the floating element facing the trigger (the opposite of the
+    /// resolved placement side).
+    pub edge: Side,
+    /// Distance from the start of the edge (left or top of the element) to the start
+    /// of the arrow, so the arrow is centered on the trigger.
+    pub offset: f64,
+}
+
+impl ArrowPosition {
+    /// Internal: Returns the position of an arrow of `width` on an element at `origin`,
+    /// pointing at the center of `trigger`.
+    pub(crate) fn pointing(
+        placement: Placement,
+        trigger: PixelsRect,
+        origin: PixelsVector2D,
+        width: f64,
+    ) -> ArrowPosition {
+        let trigger_offset = trigger.origin - origin.to_point();
+        let center = if placement.is_vertical() {
+            trigger_offset.x + trigger.width() / 2_f64
+        } else {
+            trigger_offset.y + trigger.height() / 2_f64
+        };
+
+        ArrowPosition {
+            edge: placement.side().opposite(),
+            offset: center - width / 2_f64,
+        }
+    }
+
+    /// Returns the offset from the left of the element, `None` on a left or right edge.
+    pub fn x(&self) -> Option<f64> {
+        matches!(self.edge, Side::Top | Side::Bottom).then_some(self.offset)
+    }
+
+    /// Returns the offset from the top of the element, `None` on a top or bottom edge.
+    pub fn y(&self) -> Option<f64> {
+        matches!(self.edge, Side::Left | Side::Right).then_some(self.offset)
+    }
+
+    /// Clamps the offset so an arrow of `width` stays `inset` away from both ends of an
+    /// edge of `edge_length`, e.g., inside the rounded corners of the element. An arrow
+    /// that does not fit is centered on the edge.
+    pub fn clamp(self, edge_length: f64, inset: f64, width: f64) -> ArrowPosition {
+        let max = edge_length - inset - width;
+        let offset = if max < inset {
+            (edge_length - width) / 2_f64
+        } else {
+            self.offset.clamp(inset, max)
+        };
+
+        ArrowPosition { offset, ..self }
+    }
+}
+
+impl FloatingResult {
+    /// Returns the edge and offset of an arrow of `width` (its base, along the edge)
+    /// pointing at the center of the trigger. Follows Flip (the edge) and Shift (the
+    /// offset), see [FloatingArrow] for a rendered one.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{FloatingResult, Placement, Side};
+    ///
+    /// // a 100px wide element below an 80px wide trigger, shifted 60px to the left
+    /// let result = FloatingResult {
+    ///     x: 140.0,
+    ///     y: 220.0,
+    ///     placement: Placement::BottomStart,
+    ///     trigger_rect: PixelsRect::new(PixelsVector2D::new(200.0, 200.0).to_point(), PixelsSize::new(80.0, 20.0)),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let arrow = result.arrow(12.0);
+    /// assert_eq!((arrow.edge, arrow.offset), (Side::Top, 94.0));
+    /// // kept inside the 8px rounded corners
+    /// assert_eq!(arrow.clamp(100.0, 8.0, 12.0).offset, 80.0);
+    /// ```
+    pub fn arrow(&self, width: f64) -> ArrowPosition {
+        ArrowPosition::pointing(
+            self.placement,
+            self.trigger_rect,
+            PixelsVector2D::new(self.x, self.y),
+            width,
+        )
+    }
+}
+
+/// The placement of the floating element an arrow belongs to, read by [FloatingArrow]
+/// without a `result` prop (and by `SpotlightOverlay` without a `trigger_rect`).
+///
+/// [crate::use_floating_widget] provides it to the component that calls it; provide it
+/// yourself with `use_context_provider` next to `use_placement`.
+#[cfg(feature = "hooks")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatingArrowContext(pub ReadSignal<FloatingResult>);
+
+/// An SVG arrow on the edge of a floating element, pointing at its trigger.
+///
+/// Render it inside the floating element (the element is its containing block): it
+/// sits outside the edge facing the trigger, points at the trigger center and moves
+/// with Flip and Shift. The base covers `stroke_width` pixels of the edge, and the
+/// stroke runs half of its width inside the arrow, so with a border of the same color
+/// and width the border and the arrow join without a seam on every side.
+///
+/// - `result`: the placement of the element, otherwise read from [FloatingArrowContext].
+/// - `width`, `height`: the base and the length of the arrow (12 and 6 by default).
+/// - `fill`, `stroke`: colors, usually the background and the border color of the element.
+/// - `stroke_width`: the border width of the element (1 by default, 0 without a border).
+/// - `corner_radius`: the `border-radius` of the element; the arrow stays clear of it.
+///
+/// # Example
+/// ```rust
+/// use std::rc::Rc;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{FloatingArrow, FloatingOptions, Strategy, use_placement};
+///
+/// #[component]
+/// fn Tooltip() -> Element {
+///     let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let placement = use_placement(element_ref, trigger_ref, FloatingOptions::default());
+///
+///     rsx! {
+///         button { onmounted: move |evt| trigger_ref.set(Some(evt.data())), "Hover me" }
+///         div {
+///             onmounted: move |evt| element_ref.set(Some(evt.data())),
+///             style: "{placement().style(Strategy::Fixed)} background: #fff; border: 1px solid #888; border-radius: 6px;",
+///             "Tooltip"
+///             FloatingArrow { result: placement(), fill: "#fff", stroke: "#888", corner_radius: 6.0 }
+///         }
+///     }
+/// }
+/// ```
+#[cfg(feature = "components")]
+#[component]
+pub fn FloatingArrow(
+    #[props(default)] result: Option<FloatingResult>,
+    #[props(default = 12.0)] width: f64,
+    #[props(default = 6.0)] height: f64,
+    #[props(default = "white".to_string(), into)] fill: String,
+    #[props(default, into)] stroke: Option<String>,
+    #[props(default = 1.0)] stroke_width: f64,
+    #[props(default)] corner_radius: f64,
+) -> Element {
+    let context = try_use_context::<FloatingArrowContext>();
+    let Some(result) = result.or_else(|| context.map(|context| (context.0)())) else {
+        tracing::warn!("FloatingArrow needs a `result` prop or a FloatingArrowContext");
+        return rsx! {};
+    };
+
+    let arrow = result.arrow(width);
+    // positioned in the padding box, which starts inside the border
+    let offset = arrow.offset - stroke_width;
+    let inset = (corner_radius - stroke_width).max(0_f64);
+    let along = format!(
+        "clamp({inset}px, {offset}px, calc(100% - {}px))",
+        inset + width
+    );
+    let (style, size) = match arrow.edge {
+        Side::Top => (format!("bottom: 100%; left: {along};"), (width, height)),
+        Side::Bottom => (format!("top: 100%; left: {along};"), (width, height)),
+        Side::Left => (format!("right: 100%; top: {along};"), (height, width)),
+        Side::Right => (format!("left: 100%; top: {along};"), (height, width)),
+    };
+    // the base overlaps the border, the stroke meets the middle of the border line
+    let half = stroke_width / 2_f64;
+    let fill_points = arrow_points(
+        arrow.edge,
+        height,
+        &[(0_f64, height), (width / 2_f64, half), (width, height)],
+    );
+    let stroke_points = arrow_points(
+        arrow.edge,
+        height,
+        &[
+            (0_f64, height - half),
+            (width / 2_f64, half),
+            (width, height - half),
+        ],
+    );
+    let (svg_width, svg_height) = size;
+    let stroke = stroke.filter(|_| stroke_width > 0_f64);
+
+    rsx! {
+        svg {
+            "aria-hidden": "true",
+            width: "{svg_width}",
+            height: "{svg_height}",
+            view_box: "0 0 {svg_width} {svg_height}",
+            style: "position: absolute; display: block; pointer-events: none; overflow: visible; {style}",
+            polygon { points: fill_points, fill: fill }
+            if let Some(stroke) = stroke {
+                polyline {
+                    points: stroke_points,
+                    fill: "none",
+                    stroke: stroke,
+                    stroke_width: "{stroke_width}",
+                    stroke_linejoin: "round",
+                }
+            }
+        }
+    }
+}
+
+/// Internal: Maps the points of an arrow on the top edge (pointing up, base at
+/// `y = height`) to the given edge and formats them for `points`.
+#[cfg(feature = "components")]
+fn arrow_points(edge: Side, height: f64, points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .map(|&(x, y)| match edge {
+            Side::Top => (x, y),
+            Side::Bottom => (x, height - y),
+            Side::Left => (y, x),
+            Side::Right => (height - y, x),
+        })
+        .map(|(x, y)| format!("{x},{y}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
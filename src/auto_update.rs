@@ -0,0 +1,238 @@
+#[cfg(feature = "hooks")]
+use std::rc::Rc;
+
+#[cfg(feature = "hooks")]
+use dioxus::core::Task;
+#[cfg(feature = "hooks")]
+use dioxus::prelude::*;
+
+#[cfg(feature = "hooks")]
+use crate::WaitStrategy;
+#[cfg(feature = "hooks")]
+use crate::listener::{JsListener, SharedListener};
+
+#[cfg(feature = "hooks")]
+const WINDOW_RESIZE_SCRIPT: &str = r#"
+    const notify = () => dioxus.send(true);
+    window.addEventListener("resize", notify);
+    await dioxus.recv();
+    window.removeEventListener("resize", notify);
+"#;
+
+#[cfg(feature = "hooks")]
+const ANIMATION_FRAME_SCRIPT: &str = r#"
+    let frame = 0;
+    const tick = () => {
+        dioxus.send(true);
+        frame = requestAnimationFrame(tick);
+    };
+    frame = requestAnimationFrame(tick);
+    await dioxus.recv();
+    cancelAnimationFrame(frame);
+"#;
+
+#[cfg(feature = "hooks")]
+const FONTS_READY_SCRIPT: &str = r#"
+    // without the Font Loading API, nothing is reported
+    if (document.fonts && document.fonts.ready) {
+        document.fonts.ready.then(() => dioxus.send(true));
+    }
+    await dioxus.recv();
+"#;
+
+// shared by every placement hook of a window with AutoUpdate::fonts_ready
+#[cfg(feature = "hooks")]
+static FONTS_READY: SharedListener<bool, ()> = SharedListener::new(FONTS_READY_SCRIPT, |_| ());
+
+/// Sources that keep a placement up to date (similar to floating-ui's `autoUpdate`).
+///
+/// The default only follows the scroll state of the nearest [crate::ScrollableView].
+/// Every enabled source is installed by the placement hook and removed when the
+/// component that owns the hook is unmounted. While the page is hidden, the per-frame
+/// sources pause and the updates wait for it, see [crate::Floating::is_suspended].
+///
+/// # Example
+/// ```rust
+/// use dioxus_floating::{AutoUpdate, FloatingOptions};
+///
+/// let options = FloatingOptions {
+///     auto_update: AutoUpdate::all(),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoUpdate {
+    /// Recompute when the scroll state of the [crate::ScrollableContext] or of an
+    /// enclosing view changes.
+    pub ancestor_scroll: bool,
+    /// Recompute when the floating element changes size (sampled every animation frame).
+    pub element_resize: bool,
+    /// Recompute when the trigger element changes size (sampled every animation frame).
+    pub trigger_resize: bool,
+    /// Recompute when the window is resized.
+    pub window_resize: bool,
+    /// Recompute on every animation frame. A catch-all for anchors that move
+    /// for reasons nothing else observes.
+    pub animation_frame: bool,
+    /// Recompute once when the web fonts of the page finish loading
+    /// (`document.fonts.ready`), if the placement was computed before, e.g., with the
+    /// narrower fallback font. One listener is shared by all hooks. Disable it if the
+    /// app preloads its fonts.
+    pub fonts_ready: bool,
+}
+
+impl Default for AutoUpdate {
+    /// Returns `ancestor_scroll` and `fonts_ready`.
+    fn default() -> Self {
+        Self {
+            ancestor_scroll: true,
+            fonts_ready: true,
+            ..Self::none()
+        }
+    }
+}
+
+impl AutoUpdate {
+    /// Enables every source.
+    pub fn all() -> Self {
+        Self {
+            ancestor_scroll: true,
+            element_resize: true,
+            trigger_resize: true,
+            window_resize: true,
+            animation_frame: true,
+            fonts_ready: true,
+        }
+    }
+
+    /// Disables every source. The placement is computed once the refs are available.
+    pub fn none() -> Self {
+        Self {
+            ancestor_scroll: false,
+            element_resize: false,
+            trigger_resize: false,
+            window_resize: false,
+            animation_frame: false,
+            fonts_ready: false,
+        }
+    }
+}
+
+/// Internal: Installs the enabled [AutoUpdate] sources and returns a counter
+/// bumped on every update they report.
+///
+/// The per-frame sources pause while `page_hidden` reports a hidden page, see
+/// [crate::Floating::is_suspended].
+#[cfg(feature = "hooks")]
+pub(crate) fn use_auto_update(
+    auto_update: AutoUpdate,
+    element_ref: ReadSignal<Option<Rc<MountedData>>>,
+    trigger_ref: Option<ReadSignal<Option<Rc<MountedData>>>>,
+    page_hidden: Signal<Option<bool>>,
+) -> ReadSignal<u64> {
+    let mut tick = use_signal(|| 0_u64);
+
+    let listener = use_hook(move || {
+        auto_update.window_resize.then(|| {
+            JsListener::start(WINDOW_RESIZE_SCRIPT, move |_: bool| {
+                *tick.write() += 1;
+            })
+        })
+    });
+    use_drop(move || {
+        if let Some(listener) = listener {
+            listener.stop();
+        }
+    });
+
+    // stopped while the page is hidden and started again when it is shown
+    let mut frame_listener = use_hook(|| CopyValue::new(Option::<JsListener>::None));
+    use_effect(move || {
+        let is_running = auto_update.animation_frame && !page_hidden().unwrap_or_default();
+        let running = *frame_listener.peek();
+        match running {
+            None if is_running => {
+                frame_listener.set(Some(JsListener::start(
+                    ANIMATION_FRAME_SCRIPT,
+                    move |_: bool| *tick.write() += 1,
+                )));
+            }
+            Some(listener) if !is_running => {
+                listener.stop();
+                frame_listener.set(None);
+            }
+            _ => {}
+        }
+    });
+    use_drop(move || {
+        if let Ok(listener) = frame_listener.try_peek()
+            && let Some(listener) = *listener
+        {
+            listener.stop();
+        }
+    });
+
+    let fonts_ready = use_hook(move || {
+        auto_update
+            .fonts_ready
+            .then(|| FONTS_READY.subscribe())
+            .flatten()
+    });
+    use_drop(move || {
+        if fonts_ready.is_some() {
+            FONTS_READY.unsubscribe();
+        }
+    });
+    // fonts that were ready before the first measurement need no recompute
+    let mut has_fonts =
+        use_hook(move || CopyValue::new(fonts_ready.is_some_and(|ready| ready.peek().is_some())));
+    use_effect(move || {
+        let is_ready = fonts_ready.is_some_and(|ready| ready.read().is_some());
+        if is_ready && !has_fonts.replace(true) {
+            *tick.write() += 1;
+        }
+    });
+
+    let mut resize_task = use_signal(|| Option::<Task>::None);
+    use_effect(move || {
+        let element = element_ref().filter(|_| auto_update.element_resize);
+        let trigger = trigger_ref
+            .and_then(|trigger_ref| trigger_ref())
+            .filter(|_| auto_update.trigger_resize);
+        let is_hidden = page_hidden().unwrap_or_default();
+
+        if let Some(task) = resize_task.take() {
+            task.cancel();
+        }
+        // paused while the page is hidden; the recompute when it is shown again covers
+        // the sizes it missed
+        if is_hidden || (element.is_none() && trigger.is_none()) {
+            return;
+        }
+
+        // spawned in the hook's scope, so it is dropped together with the component
+        let task = spawn(async move {
+            let mut last = None;
+            loop {
+                WaitStrategy::AnimationFrame.wait().await;
+
+                let sizes = (measure_size(&element).await, measure_size(&trigger).await);
+                if last.is_some_and(|last| last != sizes) {
+                    *tick.write() += 1;
+                }
+                last = Some(sizes);
+            }
+        });
+        resize_task.set(Some(task));
+    });
+
+    tick.into()
+}
+
+/// Internal: Returns the current size of an optional element as a comparable pair.
+#[cfg(feature = "hooks")]
+async fn measure_size(data: &Option<Rc<MountedData>>) -> Option<(f64, f64)> {
+    let rect = data.as_ref()?.get_client_rect().await.ok()?;
+
+    Some((rect.width(), rect.height()))
+}
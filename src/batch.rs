@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+
+use crate::window::with_window;
+
+/// Internal: Batches of measurements of a window, oldest first.
+#[derive(Default)]
+struct Batches {
+    next_id: u64,
+    queue: VecDeque<Batch>,
+}
+
+/// Internal: Measurements started together and the results waiting for them.
+struct Batch {
+    id: u64,
+    in_flight: usize,
+    // a batch stops accepting reads once a result arrived, so continuous
+    // updates (e.g., every animation frame) cannot postpone the publishing forever
+    is_closed: bool,
+    commits: Vec<Box<dyn FnOnce()>>,
+}
+
+/// Internal: A measurement (read phase) of one placement.
+///
+/// Results are not published right away: [ReadGuard::commit] queues them until every
+/// measurement of the batch is done, then all of them are published together (write phase).
+/// This way DOM reads of all open floating elements happen before any of the writes
+/// they cause, and the browser lays out once instead of once per element.
+///
+/// Dropping the guard without committing (e.g., a cancelled task) ends the read as well.
+pub(crate) struct ReadGuard {
+    // `None` outside of the Dioxus runtime, where nothing is batched
+    batch: Option<u64>,
+}
+
+/// Internal: Starts a measurement in the open batch, see [ReadGuard].
+pub(crate) fn begin_read() -> ReadGuard {
+    let batch = with_window(|batches: &mut Batches| {
+        if let Some(batch) = batches.queue.back_mut().filter(|batch| !batch.is_closed) {
+            batch.in_flight += 1;
+            return batch.id;
+        }
+
+        let id = batches.next_id;
+        batches.next_id += 1;
+        batches.queue.push_back(Batch {
+            id,
+            in_flight: 1,
+            is_closed: false,
+            commits: Vec::new(),
+        });
+
+        id
+    });
+
+    ReadGuard { batch }
+}
+
+impl ReadGuard {
+    /// Queues the publishing of the measured result and ends the read.
+    pub(crate) fn commit(self, publish: impl FnOnce() + 'static) {
+        let Some(id) = self.batch else {
+            publish();
+            return;
+        };
+        with_window(|batches: &mut Batches| {
+            if let Some(batch) = batches.queue.iter_mut().find(|b| b.id == id) {
+                batch.is_closed = true;
+                batch.commits.push(Box::new(publish));
+            }
+        });
+    }
+}
+
+impl Drop for ReadGuard {
+    fn drop(&mut self) {
+        // publish finished batches in the order they were started
+        let Some(id) = self.batch else {
+            return;
+        };
+        let commits = with_window(|batches: &mut Batches| {
+            if let Some(batch) = batches.queue.iter_mut().find(|b| b.id == id) {
+                batch.in_flight -= 1;
+            }
+
+            let mut commits = Vec::new();
+            while batches
+                .queue
+                .front()
+                .is_some_and(|batch| batch.in_flight == 0)
+            {
+                if let Some(batch) = batches.queue.pop_front() {
+                    commits.extend(batch.commits);
+                }
+            }
+
+            commits
+        })
+        // the runtime of the window is gone
+        .unwrap_or_default();
+
+        if !commits.is_empty() {
+            tracing::trace!("Publishing {} measured placements", commits.len());
+        }
+        commits.into_iter().for_each(|publish| publish());
+    }
+}
@@ -0,0 +1,203 @@
+#[cfg(feature = "hooks")]
+use dioxus::prelude::*;
+
+use crate::geometry::PixelsVector2D;
+use crate::{FloatingResult, Side};
+
+/// The endpoints of a leader line between a trigger and its floating element, see
+/// [FloatingResult::connector].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConnectorGeometry {
+    /// The point on the edge of the trigger facing the element (viewport-relative).
+    pub start: PixelsVector2D,
+    /// The point on the edge of the element facing the trigger (viewport-relative).
+    pub end: PixelsVector2D,
+    /// The side of the trigger the element is on.
+    pub side: Side,
+    /// `false` until the placement is ready (and while the trigger is not mounted,
+    /// see [use_connector]); hide the line meanwhile.
+    pub is_ready: bool,
+}
+
+impl Default for ConnectorGeometry {
+    fn default() -> Self {
+        Self {
+            start: PixelsVector2D::zero(),
+            end: PixelsVector2D::zero(),
+            side: Side::Bottom,
+            is_ready: false,
+        }
+    }
+}
+
+impl ConnectorGeometry {
+    /// Returns the length of the line.
+    pub fn length(&self) -> f64 {
+        (self.end - self.start).length()
+    }
+
+    /// Returns the line as SVG path data (`M x y L x y`), in viewport coordinates, e.g.,
+    /// for a `path` in a `position: fixed` overlay covering the viewport.
+    pub fn path(&self) -> String {
+        format!(
+            "M {} {} L {} {}",
+            self.start.x, self.start.y, self.end.x, self.end.y
+        )
+    }
+}
+
+impl FloatingResult {
+    /// Returns the endpoints of a line from the trigger to the element, on the facing
+    /// edges of the resolved placement side (e.g., the bottom edge of the trigger and
+    /// the top edge of the element for a `Bottom*` placement).
+    ///
+    /// Where the trigger and the element overlap on the cross axis, both endpoints are
+    /// at the middle of the overlap, so the line is straight. Otherwise (e.g., a shifted
+    /// element), each endpoint is the point of its edge closest to the other rect.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{FloatingResult, Placement, Side};
+    ///
+    /// let trigger = PixelsRect::new(PixelsVector2D::new(200.0, 200.0).to_point(), PixelsSize::new(80.0, 20.0));
+    /// let result = |x, y, placement| FloatingResult {
+    ///     x,
+    ///     y,
+    ///     is_ready: true,
+    ///     placement,
+    ///     trigger_rect: trigger,
+    ///     element_size: PixelsSize::new(100.0, 50.0),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // below, overlapping on 220..280: a straight line in the middle of the overlap
+    /// let line = result(220.0, 230.0, Placement::BottomStart).connector();
+    /// assert_eq!((line.start, line.end), (PixelsVector2D::new(250.0, 220.0), PixelsVector2D::new(250.0, 230.0)));
+    ///
+    /// // to the right and shifted down, no overlap on 200..220 and 300..350
+    /// let line = result(290.0, 300.0, Placement::RightStart).connector();
+    /// assert_eq!(line.side, Side::Right);
+    /// assert_eq!((line.start, line.end), (PixelsVector2D::new(280.0, 220.0), PixelsVector2D::new(290.0, 300.0)));
+    /// ```
+    pub fn connector(&self) -> ConnectorGeometry {
+        let trigger = self.trigger_rect;
+        let element = self.element_rect();
+        let side = self.placement.side();
+
+        // the cross-axis extents of both rects
+        let (trigger_range, element_range) = if self.placement.is_vertical() {
+            (
+                (trigger.min_x(), trigger.max_x()),
+                (element.min_x(), element.max_x()),
+            )
+        } else {
+            (
+                (trigger.min_y(), trigger.max_y()),
+                (element.min_y(), element.max_y()),
+            )
+        };
+        let overlap = (
+            trigger_range.0.max(element_range.0),
+            trigger_range.1.min(element_range.1),
+        );
+        let (start_cross, end_cross) = if overlap.0 <= overlap.1 {
+            let middle = (overlap.0 + overlap.1) / 2_f64;
+            (middle, middle)
+        } else {
+            let center = |(min, max): (f64, f64)| (min + max) / 2_f64;
+            (
+                center(element_range).clamp(trigger_range.0, trigger_range.1),
+                center(trigger_range).clamp(element_range.0, element_range.1),
+            )
+        };
+
+        let (start, end) = match side {
+            Side::Top => (
+                PixelsVector2D::new(start_cross, trigger.min_y()),
+                PixelsVector2D::new(end_cross, element.max_y()),
+            ),
+            Side::Bottom => (
+                PixelsVector2D::new(start_cross, trigger.max_y()),
+                PixelsVector2D::new(end_cross, element.min_y()),
+            ),
+            Side::Left => (
+                PixelsVector2D::new(trigger.min_x(), start_cross),
+                PixelsVector2D::new(element.max_x(), end_cross),
+            ),
+            Side::Right => (
+                PixelsVector2D::new(trigger.max_x(), start_cross),
+                PixelsVector2D::new(element.min_x(), end_cross),
+            ),
+        };
+
+        ConnectorGeometry {
+            start,
+            end,
+            side,
+            is_ready: self.is_ready,
+        }
+    }
+}
+
+/// Returns the endpoints of a leader line between a trigger and its floating element
+/// (see [FloatingResult::connector]), e.g., for an SVG line in an overlay layer.
+///
+/// Share the placement with the overlay through [crate::use_placement_in]. The geometry
+/// is not ready while `trigger_ref` is empty.
+///
+/// # Example
+/// ```rust
+/// use std::rc::Rc;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{FloatingOptions, FloatingResult, use_connector, use_placement_in};
+///
+/// #[component]
+/// fn Annotated() -> Element {
+///     let shared = use_signal(FloatingResult::default);
+///     let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     use_placement_in(shared, element_ref, trigger_ref, FloatingOptions::default());
+///
+///     rsx! {
+///         button { onmounted: move |evt| trigger_ref.set(Some(evt.data())), "Target" }
+///         div {
+///             onmounted: move |evt| element_ref.set(Some(evt.data())),
+///             style: "{shared().style(Default::default())}",
+///             "Note"
+///         }
+///         Leader { trigger_ref, result: shared }
+///     }
+/// }
+///
+/// #[component]
+/// fn Leader(trigger_ref: ReadSignal<Option<Rc<MountedData>>>, result: ReadSignal<FloatingResult>) -> Element {
+///     let line = use_connector(trigger_ref, result);
+///     rsx! {
+///         svg { style: "position: fixed; inset: 0; width: 100vw; height: 100vh; pointer-events: none;",
+///             if line().is_ready {
+///                 path { d: line().path(), stroke: "#888", fill: "none" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[cfg(feature = "hooks")]
+pub fn use_connector(
+    trigger_ref: impl Into<ReadSignal<Option<std::rc::Rc<MountedData>>>>,
+    result: impl Into<ReadSignal<FloatingResult>>,
+) -> ReadSignal<ConnectorGeometry> {
+    let trigger_ref = trigger_ref.into();
+    let result = result.into();
+
+    use_memo(move || {
+        let is_mounted = trigger_ref.read().is_some();
+        let connector = result().connector();
+        ConnectorGeometry {
+            is_ready: connector.is_ready && is_mounted,
+            ..connector
+        }
+    })
+    .into()
+}
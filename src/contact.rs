@@ -0,0 +1,108 @@
+use crate::{FloatingResult, Side};
+
+/// How far apart (in pixels) the facing edges of the element and the trigger may be and
+/// still touch, so rounding the position to device pixels does not break the contact.
+const CONTACT_EPSILON: f64 = 0.5;
+
+impl FloatingResult {
+    /// Returns the edge of the floating element flush with the trigger, e.g., to remove
+    /// the border of a callout that merges with its trigger.
+    ///
+    /// The edge faces the trigger (the opposite of the resolved placement side, so it
+    /// follows Flip). `None` if the edges do not touch: a main-axis offset, a main-axis
+    /// Shift over the trigger, or a cross-axis Shift that moved the element past the end
+    /// of the trigger.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{FloatingResult, Placement, Side};
+    ///
+    /// let result = |y, placement| FloatingResult {
+    ///     x: 200.0,
+    ///     y,
+    ///     placement,
+    ///     trigger_rect: PixelsRect::new(PixelsVector2D::new(200.0, 200.0).to_point(), PixelsSize::new(80.0, 20.0)),
+    ///     element_size: PixelsSize::new(100.0, 50.0),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(result(220.0, Placement::BottomStart).shared_edge(), Some(Side::Top));
+    /// // flipped up
+    /// assert_eq!(result(150.0, Placement::TopStart).shared_edge(), Some(Side::Bottom));
+    /// // an 8px offset
+    /// assert_eq!(result(228.0, Placement::BottomStart).shared_edge(), None);
+    /// ```
+    pub fn shared_edge(&self) -> Option<Side> {
+        self.contact().map(|(edge, _)| edge)
+    }
+
+    /// Returns where the element touches the trigger along its [FloatingResult::shared_edge],
+    /// as the start and end of the interval relative to the start of the edge (the left of
+    /// the element for a top or bottom edge, its top for a left or right edge).
+    ///
+    /// Render the border gap of a callout from the start to the end; the rest of the edge
+    /// keeps its border. `None` whenever [FloatingResult::shared_edge] is.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{FloatingResult, Placement};
+    ///
+    /// let result = |x| FloatingResult {
+    ///     x,
+    ///     y: 220.0,
+    ///     placement: Placement::BottomStart,
+    ///     trigger_rect: PixelsRect::new(PixelsVector2D::new(200.0, 200.0).to_point(), PixelsSize::new(80.0, 20.0)),
+    ///     element_size: PixelsSize::new(100.0, 50.0),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // aligned at the start: the gap spans the width of the trigger
+    /// assert_eq!(result(200.0).contact_range(), Some((0.0, 80.0)));
+    /// // shifted 60px to the left: the 40px of the trigger left under the element
+    /// assert_eq!(result(140.0).contact_range(), Some((60.0, 100.0)));
+    /// // shifted past the trigger
+    /// assert_eq!(result(80.0).contact_range(), None);
+    /// ```
+    pub fn contact_range(&self) -> Option<(f64, f64)> {
+        self.contact().map(|(_, range)| range)
+    }
+
+    /// Internal: Returns the shared edge and the element-local contact interval.
+    fn contact(&self) -> Option<(Side, (f64, f64))> {
+        let trigger = self.trigger_rect;
+        let element = self.element_rect();
+        let edge = self.placement.side().opposite();
+
+        let gap = match edge {
+            Side::Top => element.min_y() - trigger.max_y(),
+            Side::Bottom => trigger.min_y() - element.max_y(),
+            Side::Left => element.min_x() - trigger.max_x(),
+            Side::Right => trigger.min_x() - element.max_x(),
+        };
+        if gap.abs() > CONTACT_EPSILON {
+            return None;
+        }
+
+        let (trigger_range, element_range) = if self.placement.is_vertical() {
+            (
+                (trigger.min_x(), trigger.max_x()),
+                (element.min_x(), element.max_x()),
+            )
+        } else {
+            (
+                (trigger.min_y(), trigger.max_y()),
+                (element.min_y(), element.max_y()),
+            )
+        };
+        let start = trigger_range.0.max(element_range.0);
+        let end = trigger_range.1.min(element_range.1);
+        // touching at a corner only is no contact
+        if end - start <= 0_f64 {
+            return None;
+        }
+
+        Some((edge, (start - element_range.0, end - element_range.0)))
+    }
+}
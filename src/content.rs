@@ -0,0 +1,164 @@
+use dioxus::prelude::*;
+
+use crate::strict;
+
+/// Lets the content of a floating element report that its final content is rendered,
+/// e.g., once an async component behind a suspense boundary has resolved.
+///
+/// Every placement hook provides one to the descendants of its component (with several
+/// hooks in one component, the last one wins). [FloatingContentReady::mark_ready]
+/// measures the element again, and only that measurement sets
+/// [crate::FloatingResult::content_ready] with [crate::FloatingOptions::wait_for_content],
+/// so an open animation can start from the final geometry.
+///
+/// The usual way to mark it is [use_floating_content_ready] in the component that renders
+/// the resolved content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatingContentReady {
+    // bumped by every mark, the measurements record the one they saw
+    pub(crate) generation: Signal<u64>,
+    is_marked: Signal<bool>,
+}
+
+impl FloatingContentReady {
+    /// Internal: Creates an unmarked context in the current scope.
+    pub(crate) fn new() -> Self {
+        Self {
+            generation: Signal::new(0),
+            is_marked: Signal::new(false),
+        }
+    }
+
+    /// Reports that the final content is rendered and measures the element again.
+    pub fn mark_ready(&self) {
+        let (mut generation, mut is_marked) = (self.generation, self.is_marked);
+        *generation.write() += 1;
+        if !*is_marked.peek() {
+            is_marked.set(true);
+        }
+    }
+
+    /// Reports that the content is loading again, e.g., a new suspense.
+    pub fn reset(&self) {
+        let mut is_marked = self.is_marked;
+        if *is_marked.peek() {
+            is_marked.set(false);
+        }
+    }
+
+    /// Returns `true` once the content is marked as ready (reactive).
+    pub fn is_marked(&self) -> bool {
+        (self.is_marked)()
+    }
+}
+
+/// Marks the content of the nearest placement hook (see [FloatingContentReady]) as ready
+/// once the calling component is mounted, and as loading again when it unmounts.
+///
+/// Call it in the component that renders the final content, e.g., the async component
+/// inside a `SuspenseBoundary`, after its resources resolved: it mounts only then.
+///
+/// # Example
+///
+/// With the [crate::testing] mocks, the element grows when the content resolves:
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+/// use dioxus_floating::{FloatingOptions, FloatingResult, Offset, Placement, use_floating_content_ready, use_placement};
+///
+/// thread_local! {
+///     static ELEMENT: MockElement = MockElement::new(rect(0.0, 0.0, 100.0, 30.0));
+///     static LOADED: Cell<Option<Signal<bool>>> = Cell::new(None);
+///     static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+/// }
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+///     rsx! { Popover {} }
+/// }
+///
+/// #[component]
+/// fn Popover() -> Element {
+///     let element_ref = use_signal(|| Some(ELEMENT.with(MockElement::mounted)));
+///     let trigger_ref = use_signal(|| Some(MockElement::new(rect(40.0, 300.0, 60.0, 20.0)).mounted()));
+///     let options = FloatingOptions {
+///         placement: Placement::TopStart,
+///         placement_offset: Some(Offset::zero()),
+///         wait_for_content: true,
+///         ..Default::default()
+///     };
+///     let placement = use_placement(element_ref, trigger_ref, options);
+///     RESULT.with(|cell| cell.set(placement()));
+///     rsx! { Content {} }
+/// }
+///
+/// #[component]
+/// fn Content() -> Element {
+///     // stands in for a suspended resource
+///     let loaded = use_signal(|| false);
+///     LOADED.with(|cell| cell.set(Some(loaded)));
+///     if loaded() {
+///         rsx! { Loaded {} }
+///     } else {
+///         rsx! { "Loading" }
+///     }
+/// }
+///
+/// #[component]
+/// fn Loaded() -> Element {
+///     use_floating_content_ready();
+///     rsx! { "The final content" }
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// settle(&mut dom);
+///
+/// // positioned for the loading state, but the content is not final yet
+/// let result = RESULT.with(Cell::get);
+/// assert_eq!((result.is_ready, result.content_ready, result.y), (true, false, 270.0));
+///
+/// // the content resolves and the element triples in height
+/// ELEMENT.with(|element| element.set_rect(rect(0.0, 0.0, 100.0, 90.0)));
+/// let mut loaded = LOADED.with(Cell::get).unwrap();
+/// dom.in_runtime(|| loaded.set(true));
+/// settle(&mut dom);
+///
+/// let result = RESULT.with(Cell::get);
+/// assert_eq!((result.is_ready, result.content_ready, result.y), (true, true, 210.0));
+/// ```
+pub fn use_floating_content_ready() {
+    let content = use_hook(|| {
+        let content = try_consume_context::<FloatingContentReady>();
+        if content.is_none() {
+            tracing::warn!(
+                "use_floating_content_ready used outside of a placement hook's component"
+            );
+            strict::misuse(|| {
+                "use_floating_content_ready used outside of a placement hook's component; \
+                call it in a descendant of the component of use_placement"
+                    .to_string()
+            });
+        }
+        content
+    });
+
+    use_effect(move || {
+        if let Some(content) = content {
+            content.mark_ready();
+        }
+    });
+    use_drop(move || {
+        if let Some(content) = content {
+            content.reset();
+        }
+    });
+}
@@ -0,0 +1,87 @@
+use crate::listener::SharedListener;
+
+const PRESS_SCRIPT: &str = r#"
+    // the presses in progress by pointer id, from pointerdown to pointerup
+    const presses = new Map();
+    const down = (event) => {
+        // the floating elements and triggers the press started in
+        const owners = [];
+        for (const node of event.composedPath()) {
+            if (!(node instanceof Element)) continue;
+            if (node.id && node.id.startsWith("dioxus-floating-")) owners.push(node.id);
+            // a list of ids, e.g., with WidgetConfig::controls_container
+            const controls = node.getAttribute("aria-controls");
+            if (controls) owners.push(...controls.split(/\s+/));
+        }
+        presses.set(event.pointerId, {
+            x: event.clientX, y: event.clientY, travel: 0, scrolled: false, owners,
+        });
+    };
+    const move = (event) => {
+        const press = presses.get(event.pointerId);
+        if (!press) return;
+        const travel = Math.hypot(event.clientX - press.x, event.clientY - press.y);
+        press.travel = Math.max(press.travel, travel);
+    };
+    const up = (event) => {
+        move(event);
+        const press = presses.get(event.pointerId);
+        if (!press) return;
+        presses.delete(event.pointerId);
+        dioxus.send([press.travel, press.scrolled, press.owners]);
+    };
+    // the browser took over the pointer to pan: never a tap
+    const cancel = (event) => presses.delete(event.pointerId);
+    // any container scrolling during a press (e.g., a ScrollableView) makes it a pan
+    const scroll = () => presses.forEach((press) => press.scrolled = true);
+    const options = { capture: true, passive: true };
+    document.addEventListener("pointerdown", down, options);
+    document.addEventListener("pointermove", move, options);
+    document.addEventListener("pointerup", up, options);
+    document.addEventListener("pointercancel", cancel, options);
+    document.addEventListener("scroll", scroll, options);
+    await dioxus.recv();
+    document.removeEventListener("pointerdown", down, options);
+    document.removeEventListener("pointermove", move, options);
+    document.removeEventListener("pointerup", up, options);
+    document.removeEventListener("pointercancel", cancel, options);
+    document.removeEventListener("scroll", scroll, options);
+"#;
+
+// shared by every widget of a window with WidgetConfig::outside_press
+pub(crate) static PRESSES: SharedListener<(f64, bool, Vec<String>), Press> =
+    SharedListener::new(PRESS_SCRIPT, |(travel, scrolled, owners)| Press {
+        travel,
+        scrolled,
+        owners,
+    });
+
+/// Internal: A finished pointer press anywhere in the document, from `pointerdown` to
+/// `pointerup`. Presses the browser cancelled to pan are never reported.
+pub(crate) struct Press {
+    // the farthest distance from the pointerdown position, in CSS pixels
+    travel: f64,
+    // a scroll container scrolled while the pointer was down
+    scrolled: bool,
+    // the floating element ids of the elements the press started in (a trigger counts
+    // through its `aria-controls`)
+    owners: Vec<String>,
+}
+
+/// Internal: Reports a finished press as the listener would, see
+/// [crate::testing::report_press].
+pub(crate) fn report(travel: f64, scrolled: bool, owners: Vec<String>) {
+    PRESSES.report(Press {
+        travel,
+        scrolled,
+        owners,
+    });
+}
+
+impl Press {
+    /// Internal: Returns `true` if the press is a tap (it moved at most `slop` pixels and
+    /// scrolled nothing) that started outside of the floating element `id` and its trigger.
+    pub(crate) fn is_outside_tap(&self, id: &str, slop: f64) -> bool {
+        !self.scrolled && self.travel <= slop && !self.owners.iter().any(|owner| owner == id)
+    }
+}
@@ -1,16 +1,325 @@
+#[cfg(feature = "dioxus")]
 use std::rc::Rc;
 
-use dioxus::html::geometry::{ClientPoint, PixelsRect, PixelsSize, PixelsVector2D};
-use dioxus::logger::tracing;
+#[cfg(feature = "dioxus")]
 use dioxus::prelude::*;
 
+use crate::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use crate::{AnnounceOptions, ArrowPosition, AutoUpdate};
+#[cfg(feature = "hooks")]
+use crate::{FloatingUpdate, ScrollableContext};
+
 /// The core engine for calculating floating positions.
 ///
 /// `Floating` provides methods to compute the coordinates of elements
 /// based on their size, the trigger position, and the boundaries of
 /// the scrollable container.
+///
+/// The engine also holds app-wide defaults (wait strategy, rounding, logging label).
+/// [Floating::default] matches the behavior of previous versions; use
+/// [Floating::builder] to customize it and [crate::FloatingProvider] to share it
+/// with every hook below a component.
+///
+/// # Example
+/// ```rust
+/// use dioxus_floating::{Floating, Rounding, WaitStrategy};
+///
+/// let floating = Floating::builder()
+///     .wait(WaitStrategy::AnimationFrame)
+///     .rounding(Rounding::DevicePixel)
+///     .label("app")
+///     .build();
+///
+/// assert_eq!(floating.rounding(), Rounding::DevicePixel);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Floating {
+    wait: WaitStrategy,
+    rounding: Rounding,
+    label: Option<&'static str>,
+    frame_budget: Option<f64>,
+    slow_measurement_budget: f64,
+}
+
+impl Default for Floating {
+    /// Returns the engine with a 1ms wait, no rounding, no label, no frame budget and a
+    /// slow measurement budget of 8ms.
+    fn default() -> Self {
+        Self {
+            wait: WaitStrategy::default(),
+            rounding: Rounding::default(),
+            label: None,
+            frame_budget: None,
+            slow_measurement_budget: 8_f64,
+        }
+    }
+}
+
+/// Builder for a configured [Floating] engine.
 #[derive(Debug, Clone, Copy, Default)]
-pub struct Floating;
+pub struct FloatingBuilder {
+    floating: Floating,
+}
+
+impl FloatingBuilder {
+    /// Sets the [WaitStrategy] used by the hooks before measuring.
+    pub fn wait(mut self, wait: WaitStrategy) -> Self {
+        self.floating.wait = wait;
+        self
+    }
+
+    /// Sets the [Rounding] mode applied to calculated coordinates.
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.floating.rounding = rounding;
+        self
+    }
+
+    /// Sets a label that prefixes the engine's log messages.
+    pub fn label(mut self, label: &'static str) -> Self {
+        self.floating.label = Some(label);
+        self
+    }
+
+    /// Limits the time the placement hooks spend measuring per frame, e.g., `4.0` ms
+    /// for pages with many always-visible floating elements on low-end devices.
+    ///
+    /// Measurements beyond the budget are deferred to the next frame: placements with
+    /// visible triggers and recent invalidations go first, and deferred ones keep their
+    /// previous position until their turn. At least one measurement runs per frame.
+    /// Tune the budget with [Floating::frame_stats]. Unlimited by default.
+    pub fn frame_budget(mut self, budget_ms: f64) -> Self {
+        self.floating.frame_budget = Some(budget_ms);
+        self
+    }
+
+    /// Sets the duration a measurement of a placement hook (DOM reads and computation)
+    /// should stay under, in milliseconds. 8ms by default.
+    ///
+    /// Every hook keeps the durations of its last measurements (see
+    /// [crate::MeasurementTiming]); once 90% of them take longer, it logs a single warning
+    /// with its label and the numbers, e.g., on a low-end device where the reads through
+    /// `document::eval` are slow.
+    pub fn slow_measurement_budget(mut self, budget_ms: f64) -> Self {
+        self.floating.slow_measurement_budget = budget_ms;
+        self
+    }
+
+    /// Returns the configured [Floating] engine.
+    pub fn build(self) -> Floating {
+        self.floating
+    }
+}
+
+/// Defines how long the hooks wait before measuring the DOM.
+///
+/// Freshly rendered elements are not laid out yet, so measuring them
+/// immediately may return stale or zero rects. Set it app-wide with
+/// [FloatingBuilder::wait] or per hook with [FloatingOptions::wait].
+///
+/// On the web, timeouts use the browser timers directly; on other renderers (e.g.,
+/// desktop) every strategy goes through `document::eval`.
+///
+/// # Example
+///
+/// With the [crate::testing] mocks, the waits before the first measurement are recorded.
+///
+/// ```rust
+/// # #[cfg(feature = "hooks")] {
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockDocument, MockElement, MockScrollableConfig, MockWait, provide_mock_document, provide_mock_scrollable, settle};
+/// use dioxus_floating::{FloatingOptions, WaitStrategy, use_placement};
+///
+/// thread_local! {
+///     static WAIT: Cell<WaitStrategy> = Cell::new(WaitStrategy::None);
+///     static DOCUMENT: Cell<Option<MockDocument>> = Cell::new(None);
+///     static IS_READY: Cell<bool> = Cell::new(false);
+/// }
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     use_hook(|| {
+///         DOCUMENT.with(|document| document.set(Some(provide_mock_document())));
+///         provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)))
+///     });
+///     rsx! { Popover {} }
+/// }
+///
+/// #[component]
+/// fn Popover() -> Element {
+///     let trigger = use_hook(|| MockElement::new(rect(100.0, 100.0, 80.0, 20.0)));
+///     let element = use_hook(|| MockElement::new(rect(0.0, 0.0, 100.0, 50.0)));
+///     let trigger_ref = use_signal(|| Some(trigger.mounted()));
+///     let element_ref = use_signal(|| Some(element.mounted()));
+///
+///     let options = FloatingOptions { wait: Some(WAIT.with(Cell::get)), ..Default::default() };
+///     let placement = use_placement(element_ref, trigger_ref, options);
+///     IS_READY.with(|is_ready| is_ready.set(placement().is_ready));
+///
+///     rsx! {}
+/// }
+///
+/// fn waits_before_measuring(wait: WaitStrategy) -> Vec<MockWait> {
+///     WAIT.with(|cell| cell.set(wait));
+///     let mut dom = VirtualDom::new(app);
+///     dom.rebuild_in_place();
+///     settle(&mut dom);
+///     assert!(IS_READY.with(Cell::get));
+///     DOCUMENT.with(Cell::take).unwrap().waits()
+/// }
+///
+/// assert_eq!(waits_before_measuring(WaitStrategy::None), vec![]);
+/// assert_eq!(waits_before_measuring(WaitStrategy::Timeout(1)), vec![MockWait::Timeout(1)]);
+/// assert_eq!(waits_before_measuring(WaitStrategy::AnimationFrame), vec![MockWait::Frame]);
+/// assert_eq!(waits_before_measuring(WaitStrategy::AnimationFrames(3)), vec![MockWait::Frame; 3]);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaitStrategy {
+    /// Measure immediately, e.g., when the app drives its own timing.
+    None,
+    /// Wait for the given number of milliseconds.
+    Timeout(u32),
+    /// Wait for the next animation frame.
+    AnimationFrame,
+    /// Wait for the given number of animation frames, e.g., `2` for content that is
+    /// laid out in two passes.
+    AnimationFrames(u8),
+}
+
+impl Default for WaitStrategy {
+    /// Returns [WaitStrategy::Timeout] with 1ms.
+    fn default() -> Self {
+        WaitStrategy::Timeout(1)
+    }
+}
+
+/// Defines how calculated coordinates are rounded.
+///
+/// Halves are rounded toward the trigger, see [Rounding::round_toward].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Rounding {
+    /// Coordinates are used as is.
+    #[default]
+    None,
+    /// Coordinates are rounded to whole CSS pixels.
+    Pixel,
+    /// Coordinates are rounded to whole device pixels (uses `devicePixelRatio`).
+    DevicePixel,
+}
+
+/// Internal: Distance in (device) pixels from a half within which a value is rounded as
+/// a half, so the noise of the float arithmetic does not decide the direction.
+const ROUNDING_TIE_EPSILON: f64 = 1e-6;
+
+impl Rounding {
+    /// Rounds a value using the given device pixel ratio.
+    pub fn round(&self, value: f64, device_pixel_ratio: f64) -> f64 {
+        match *self {
+            Rounding::None => value,
+            Rounding::Pixel => value.round(),
+            Rounding::DevicePixel if device_pixel_ratio > 0_f64 => {
+                (value * device_pixel_ratio).round() / device_pixel_ratio
+            }
+            Rounding::DevicePixel => value.round(),
+        }
+    }
+
+    /// Rounds a value like [Rounding::round], but a half (e.g., the `.5` of a Center
+    /// alignment with odd sizes) goes toward `toward` instead of away from zero.
+    ///
+    /// [Floating::compute_placement] rounds toward the center of the trigger, so a
+    /// centered element keeps the same offset from its trigger wherever it is: with
+    /// rounding away from zero, it moves by a pixel when the coordinate changes its sign,
+    /// e.g., while scrolling back and forth.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::Rounding;
+    ///
+    /// assert_eq!(Rounding::Pixel.round_toward(10.5, 1.0, 50.0), 11.0);
+    /// assert_eq!(Rounding::Pixel.round_toward(-10.5, 1.0, 50.0), -10.0);
+    /// assert_eq!(Rounding::Pixel.round_toward(10.5, 1.0, 0.0), 10.0);
+    /// // not a half
+    /// assert_eq!(Rounding::Pixel.round_toward(10.4, 1.0, 50.0), 10.0);
+    /// // halves of device pixels
+    /// assert_eq!(Rounding::DevicePixel.round_toward(10.25, 2.0, 0.0), 10.0);
+    /// ```
+    pub fn round_toward(&self, value: f64, device_pixel_ratio: f64, toward: f64) -> f64 {
+        let scale = match *self {
+            Rounding::None => return value,
+            Rounding::DevicePixel if device_pixel_ratio > 0_f64 => device_pixel_ratio,
+            Rounding::Pixel | Rounding::DevicePixel => 1_f64,
+        };
+        let scaled = value * scale;
+        let floor = scaled.floor();
+        let rounded = if (scaled - floor - 0.5_f64).abs() <= ROUNDING_TIE_EPSILON {
+            if toward * scale > scaled {
+                floor + 1_f64
+            } else {
+                floor
+            }
+        } else {
+            scaled.round()
+        };
+
+        rounded / scale
+    }
+}
+
+/// Internal: The measured `devicePixelRatio` of a window, which differs between windows
+/// on different screens.
+#[cfg(feature = "hooks")]
+#[derive(Default)]
+struct DevicePixelRatio(Option<f64>);
+
+/// Internal: Returns the last measured `devicePixelRatio` of the current window (1.0
+/// until measured and outside of the runtime).
+pub(crate) fn device_pixel_ratio() -> f64 {
+    #[cfg(feature = "hooks")]
+    if let Some(Some(dpr)) = crate::window::with_window(|cached: &mut DevicePixelRatio| cached.0) {
+        return dpr;
+    }
+
+    1_f64
+}
+
+/// Internal: Caches a `devicePixelRatio` reported by a listener (e.g., after a zoom) for
+/// the current window.
+#[cfg(feature = "hooks")]
+pub(crate) fn set_device_pixel_ratio(dpr: f64) {
+    if dpr.is_finite() && dpr > 0_f64 {
+        crate::window::with_window(|cached: &mut DevicePixelRatio| cached.0 = Some(dpr));
+    }
+}
+
+/// Internal: Returns the part of `rect` inside `boundary`.
+///
+/// Unlike [PixelsRect::intersection], a rect with a zero width or height (e.g., the
+/// bounding box of a vertical line in an SVG chart) inside the boundary is visible, and
+/// its visible part keeps the zero extent.
+pub(crate) fn visible_part(rect: PixelsRect, boundary: PixelsRect) -> Option<PixelsRect> {
+    if !rect.is_empty() || boundary.is_empty() {
+        return rect.intersection(&boundary);
+    }
+    let min = rect.min().max(boundary.min());
+    let max = rect.max().min(boundary.max());
+
+    (min.x <= max.x && min.y <= max.y).then(|| PixelsRect::new(min, (max - min).to_size()))
+}
+
+/// Internal: The relative difference of the client size and the rect of a container that
+/// is a scale, see [ScrollState::zoom_factor].
+const ZOOM_TOLERANCE: f64 = 0.02;
+
+/// Internal: The difference of the client size and the rect of a container that is more
+/// than its borders, see [ScrollState::zoom_factor].
+const ZOOM_MIN_DIFFERENCE: f64 = 8_f64;
 
 /// Represents the geometric state of a scrollable container.
 #[derive(Debug, Clone, Copy)]
@@ -23,12 +332,344 @@ pub struct ScrollState {
     pub state: PixelsVector2D,
 }
 
+impl ScrollState {
+    /// Returns a copy of the state with a new scroll position, size and bounds untouched.
+    pub fn with_offset(self, offset: PixelsVector2D) -> ScrollState {
+        ScrollState {
+            state: offset,
+            ..self
+        }
+    }
+
+    /// Returns the maximum scroll position per axis (`size - bounds`, never negative).
+    pub fn max_offset(&self) -> PixelsVector2D {
+        PixelsVector2D::new(
+            (self.size.width - self.bounds.width).max(0_f64),
+            (self.size.height - self.bounds.height).max(0_f64),
+        )
+    }
+
+    /// Returns the scale of the client rect (border box) `rect` of the container relative
+    /// to [ScrollState::bounds], if they disagree, e.g., under a CSS `zoom` of the container
+    /// that scales its rect but not its client size.
+    ///
+    /// Only an axis without a scrollbar (the content does not overflow the other axis)
+    /// tells the scale apart from a scrollbar, and it counts if the extents differ by more
+    /// than 2% and 8px (borders). With two such axes, both must agree within 2%. `None`
+    /// if the sizes agree, or if the content overflows on both axes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::ScrollState;
+    ///
+    /// // `zoom: 1.25`: a 400x300 client area in a 500x375 rect
+    /// let rect = PixelsRect::new(PixelsVector2D::zero().to_point(), PixelsSize::new(500.0, 375.0));
+    /// let state = ScrollState {
+    ///     size: PixelsSize::new(400.0, 300.0),
+    ///     bounds: PixelsSize::new(400.0, 300.0),
+    ///     state: PixelsVector2D::zero(),
+    /// };
+    /// assert_eq!(state.zoom_factor(rect), Some(1.25));
+    ///
+    /// // unzoomed, a 1px border is not a scale
+    /// let rect = PixelsRect::new(PixelsVector2D::zero().to_point(), PixelsSize::new(402.0, 302.0));
+    /// assert_eq!(state.zoom_factor(rect), None);
+    /// ```
+    pub fn zoom_factor(&self, rect: PixelsRect) -> Option<f64> {
+        let scale = |extent: f64, client: f64| {
+            let scale = extent / client;
+            let is_scaled = (scale - 1_f64).abs() > ZOOM_TOLERANCE
+                && (extent - client).abs() > ZOOM_MIN_DIFFERENCE;
+            (client > 0_f64 && scale.is_finite() && is_scaled).then_some(scale)
+        };
+        // a scrollbar takes room on the other axis only
+        let x = (self.size.height <= self.bounds.height)
+            .then(|| scale(rect.width(), self.bounds.width));
+        let y = (self.size.width <= self.bounds.width)
+            .then(|| scale(rect.height(), self.bounds.height));
+
+        match (x, y) {
+            (Some(x), Some(y)) => x
+                .zip(y)
+                .filter(|(x, y)| (x / y - 1_f64).abs() <= ZOOM_TOLERANCE)
+                .map(|(x, y)| (x + y) / 2_f64),
+            (Some(scale), None) | (None, Some(scale)) => scale,
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the width of the vertical scrollbar and the height of the horizontal one
+    /// of a container whose client rect (border box) is `rect`.
+    ///
+    /// The gutter is the difference between the rect and [ScrollState::bounds] (the client
+    /// size, without scrollbars), counted only on the axes where the content overflows, so
+    /// borders of a container that does not scroll are not mistaken for a scrollbar.
+    /// Differences below 1px are rounding of the integer client size. A client size scaled
+    /// differently than the rect (see [ScrollState::zoom_factor]) is scaled like it first.
+    pub fn scrollbar_gutter(&self, rect: PixelsRect) -> PixelsSize {
+        let bounds = self.bounds * self.zoom_factor(rect).unwrap_or(1_f64);
+        let gutter = |extent: f64, client: f64, overflows: bool| {
+            let gutter = (extent - client).clamp(0_f64, extent.max(0_f64));
+            if overflows && gutter >= 1_f64 {
+                gutter
+            } else {
+                0_f64
+            }
+        };
+
+        PixelsSize::new(
+            // a vertical scrollbar takes width, a horizontal one height
+            gutter(
+                rect.width(),
+                bounds.width,
+                self.size.height > self.bounds.height,
+            ),
+            gutter(
+                rect.height(),
+                bounds.height,
+                self.size.width > self.bounds.width,
+            ),
+        )
+    }
+
+    /// Removes the scrollbars from the client rect of the container, so floating elements
+    /// are not shifted under them, see [ScrollState::scrollbar_gutter].
+    ///
+    /// The vertical scrollbar is on the right, or on the left with `rtl` (`direction: rtl`);
+    /// the horizontal one is at the bottom.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::ScrollState;
+    ///
+    /// // classic scrollbars: the 400x300 box has a 383px wide client area
+    /// let rect = PixelsRect::new(PixelsVector2D::new(10.0, 20.0).to_point(), PixelsSize::new(400.0, 300.0));
+    /// let state = ScrollState {
+    ///     size: PixelsSize::new(383.0, 2000.0),
+    ///     bounds: PixelsSize::new(383.0, 300.0),
+    ///     state: PixelsVector2D::zero(),
+    /// };
+    ///
+    /// let usable = state.exclude_scrollbars(rect, false);
+    /// assert_eq!((usable.min_x(), usable.max_x(), usable.height()), (10.0, 393.0, 300.0));
+    /// let usable = state.exclude_scrollbars(rect, true);
+    /// assert_eq!((usable.min_x(), usable.max_x()), (27.0, 410.0));
+    ///
+    /// // a horizontal scrollbar is at the bottom
+    /// let state = ScrollState { size: PixelsSize::new(900.0, 283.0), bounds: PixelsSize::new(400.0, 283.0), ..state };
+    /// assert_eq!(state.scrollbar_gutter(rect), PixelsSize::new(0.0, 17.0));
+    ///
+    /// // overlay scrollbars (or content that fits) leave the rect as is
+    /// let state = ScrollState { bounds: PixelsSize::new(400.0, 300.0), ..state };
+    /// assert_eq!(state.exclude_scrollbars(rect, false), rect);
+    /// ```
+    pub fn exclude_scrollbars(&self, rect: PixelsRect, rtl: bool) -> PixelsRect {
+        let gutter = self.scrollbar_gutter(rect);
+        let x = if rtl {
+            rect.min_x() + gutter.width
+        } else {
+            rect.min_x()
+        };
+
+        PixelsRect::new(
+            PixelsVector2D::new(x, rect.min_y()).to_point(),
+            PixelsSize::new(rect.width() - gutter.width, rect.height() - gutter.height),
+        )
+    }
+
+    /// Converts a state measured in a `flex-direction: column-reverse` container into
+    /// conventional coordinates: `0` at the top, `max_offset().y` at the bottom.
+    ///
+    /// Browsers report the vertical offset of such containers either as `0` at the bottom
+    /// and negative above it (the standard), or positive above it (older engines).
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::ScrollState;
+    ///
+    /// let state = |y: f64| ScrollState {
+    ///     size: PixelsSize::new(300.0, 1000.0),
+    ///     bounds: PixelsSize::new(300.0, 400.0),
+    ///     state: PixelsVector2D::new(0.0, y),
+    /// };
+    ///
+    /// // at the visual bottom
+    /// assert_eq!(state(0.0).from_reversed().state.y, 600.0);
+    /// // scrolled 100px up, standard negative offsets
+    /// assert_eq!(state(-100.0).from_reversed().state.y, 500.0);
+    /// // scrolled 100px up, legacy positive offsets
+    /// assert_eq!(state(100.0).from_reversed().state.y, 500.0);
+    /// // at the visual top
+    /// assert_eq!(state(-600.0).from_reversed().state.y, 0.0);
+    ///
+    /// // and back to the standard offset when scrolling
+    /// let normalized = state(-100.0).from_reversed();
+    /// assert_eq!(normalized.to_reversed_offset(normalized.state).y, -100.0);
+    /// ```
+    pub fn from_reversed(self) -> ScrollState {
+        let max = self.max_offset().y;
+        let raw = self.state.y;
+        let y = if raw <= 0_f64 { max + raw } else { max - raw };
+
+        self.with_offset(PixelsVector2D::new(self.state.x, y.clamp(0_f64, max)))
+    }
+
+    /// Converts a conventional offset back into the (standard, negative) offset of a
+    /// `column-reverse` container, see [ScrollState::from_reversed].
+    pub fn to_reversed_offset(&self, offset: PixelsVector2D) -> PixelsVector2D {
+        PixelsVector2D::new(offset.x, offset.y - self.max_offset().y)
+    }
+
+    /// Clamps a scroll position into `[0, max_offset]` per axis.
+    pub fn clamp_offset(&self, offset: PixelsVector2D) -> PixelsVector2D {
+        let max = self.max_offset();
+        PixelsVector2D::new(offset.x.clamp(0_f64, max.x), offset.y.clamp(0_f64, max.y))
+    }
+
+    /// Returns the scrolled fraction (`0..1`) along the axis of `axes`, the vertical one
+    /// for [TrackedAxes::Both]. `0` if the content fits.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{ScrollState, TrackedAxes};
+    ///
+    /// let state = ScrollState {
+    ///     size: PixelsSize::new(3000.0, 200.0),
+    ///     bounds: PixelsSize::new(600.0, 200.0),
+    ///     state: PixelsVector2D::new(1200.0, 0.0),
+    /// };
+    /// assert_eq!(state.progress(TrackedAxes::X), 0.5);
+    /// assert!(!state.is_at_end(TrackedAxes::X, 1.0));
+    /// ```
+    pub fn progress(&self, axes: TrackedAxes) -> f64 {
+        let (offset, max) = self.axis_offset(axes);
+        if max > 0_f64 {
+            (offset / max).clamp(0_f64, 1_f64)
+        } else {
+            0_f64
+        }
+    }
+
+    /// Returns `true` if the offset along the axis of `axes` (the vertical one for
+    /// [TrackedAxes::Both]) is within `threshold` pixels of the end, e.g., to load more.
+    pub fn is_at_end(&self, axes: TrackedAxes, threshold: f64) -> bool {
+        let (offset, max) = self.axis_offset(axes);
+        offset >= max - threshold
+    }
+
+    /// Internal: The offset and the max offset along the axis of `axes`.
+    fn axis_offset(&self, axes: TrackedAxes) -> (f64, f64) {
+        let max = self.max_offset();
+        match axes {
+            TrackedAxes::X => (self.state.x, max.x),
+            TrackedAxes::Y | TrackedAxes::Both => (self.state.y, max.y),
+        }
+    }
+}
+
+/// The scroll axes a [crate::ScrollableView] tracks, see its `axes` prop.
+///
+/// On an untracked axis, the offset, size and bounds of the published [ScrollState] are
+/// carried over from the previous state instead of the reported ones, so tiny offsets a
+/// browser reports on an axis that does not scroll cause no recomputes downstream.
+///
+/// # Example
+/// ```rust
+/// use dioxus_floating::geometry::{PixelsSize, PixelsVector2D};
+/// use dioxus_floating::{ScrollState, TrackedAxes};
+///
+/// let state = |x: f64, y: f64| ScrollState {
+///     size: PixelsSize::new(3000.0, 200.0),
+///     bounds: PixelsSize::new(600.0, 200.0),
+///     state: PixelsVector2D::new(x, y),
+/// };
+///
+/// // vertical jitter of a horizontal strip is ignored
+/// assert!(TrackedAxes::X.merge(Some(state(100.0, 0.0)), state(100.0, 0.5)).is_none());
+///
+/// // a horizontal scroll is published, the vertical offset carried over
+/// let merged = TrackedAxes::X.merge(Some(state(100.0, 0.0)), state(140.0, 0.5)).unwrap();
+/// assert_eq!(merged.state, PixelsVector2D::new(140.0, 0.0));
+///
+/// // both axes: every reported state is published as is
+/// assert!(TrackedAxes::Both.merge(Some(state(100.0, 0.0)), state(100.0, 0.5)).is_some());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TrackedAxes {
+    /// Both axes (default).
+    #[default]
+    Both,
+    /// Only the horizontal axis, e.g., a film strip.
+    X,
+    /// Only the vertical axis, e.g., a list.
+    Y,
+}
+
+impl TrackedAxes {
+    /// Returns `true` if the horizontal axis is tracked.
+    pub fn x(&self) -> bool {
+        matches!(self, TrackedAxes::Both | TrackedAxes::X)
+    }
+
+    /// Returns `true` if the vertical axis is tracked.
+    pub fn y(&self) -> bool {
+        matches!(self, TrackedAxes::Both | TrackedAxes::Y)
+    }
+
+    /// Merges a reported state into the `previous` published one: the untracked axis
+    /// keeps its previous values. Returns `None` if nothing changed on the tracked axes.
+    pub fn merge(self, previous: Option<ScrollState>, next: ScrollState) -> Option<ScrollState> {
+        let Some(previous) = previous.filter(|_| self != TrackedAxes::Both) else {
+            return Some(next);
+        };
+
+        let merged = if self.x() {
+            ScrollState {
+                size: PixelsSize::new(next.size.width, previous.size.height),
+                bounds: PixelsSize::new(next.bounds.width, previous.bounds.height),
+                state: PixelsVector2D::new(next.state.x, previous.state.y),
+            }
+        } else {
+            ScrollState {
+                size: PixelsSize::new(previous.size.width, next.size.height),
+                bounds: PixelsSize::new(previous.bounds.width, next.bounds.height),
+                state: PixelsVector2D::new(previous.state.x, next.state.y),
+            }
+        };
+        let is_changed = merged.size != previous.size
+            || merged.bounds != previous.bounds
+            || merged.state != previous.state;
+
+        is_changed.then_some(merged)
+    }
+}
+
+/// A partial update of a [ScrollState], see [Floating::merge_scroll_state].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PartialScrollState {
+    /// New total size of the scrollable content, if changed.
+    pub size: Option<PixelsSize>,
+    /// New visible dimensions of the container, if changed.
+    pub bounds: Option<PixelsSize>,
+    /// New scroll position, if changed.
+    pub state: Option<PixelsVector2D>,
+}
+
 /// Defines the preferred side and alignment of the floating element relative to its trigger.
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// A trigger may have no extent on the aligned axis (e.g., the bounding box of a vertical
+/// line in an SVG chart). Then `Start` begins at its single edge, `Center` is centered on
+/// it and `End` ends at it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Placement {
     TopStart,
     TopCenter,
     TopEnd,
+    #[default]
     BottomStart,
     BottomCenter,
     BottomEnd,
@@ -70,6 +711,95 @@ impl Placement {
         )
     }
 
+    /// Returns the [Side] of the trigger the element is placed on.
+    pub fn side(&self) -> Side {
+        if self.is_vertical() {
+            if self.is_top() {
+                Side::Top
+            } else {
+                Side::Bottom
+            }
+        } else if self.is_left() {
+            Side::Left
+        } else {
+            Side::Right
+        }
+    }
+
+    /// Returns the placement on the opposite side with the same alignment (e.g., TopEnd -> BottomEnd).
+    pub fn opposite(&self) -> Placement {
+        match *self {
+            Placement::TopStart => Placement::BottomStart,
+            Placement::TopCenter => Placement::BottomCenter,
+            Placement::TopEnd => Placement::BottomEnd,
+            Placement::BottomStart => Placement::TopStart,
+            Placement::BottomCenter => Placement::TopCenter,
+            Placement::BottomEnd => Placement::TopEnd,
+            Placement::LeftStart => Placement::RightStart,
+            Placement::LeftCenter => Placement::RightCenter,
+            Placement::LeftEnd => Placement::RightEnd,
+            Placement::RightStart => Placement::LeftStart,
+            Placement::RightCenter => Placement::LeftCenter,
+            Placement::RightEnd => Placement::LeftEnd,
+        }
+    }
+
+    /// Returns the CSS `transform-origin` pointing at the trigger (e.g., `left top` for
+    /// BottomStart), so scale animations grow out of the trigger.
+    pub fn transform_origin(&self) -> &'static str {
+        match *self {
+            Placement::TopStart => "left bottom",
+            Placement::TopCenter => "center bottom",
+            Placement::TopEnd => "right bottom",
+            Placement::BottomStart => "left top",
+            Placement::BottomCenter => "center top",
+            Placement::BottomEnd => "right top",
+            Placement::LeftStart => "right top",
+            Placement::LeftCenter => "right center",
+            Placement::LeftEnd => "right bottom",
+            Placement::RightStart => "left top",
+            Placement::RightCenter => "left center",
+            Placement::RightEnd => "left bottom",
+        }
+    }
+
+    /// Returns the placement on `side` with the same alignment (e.g., TopEnd on the left
+    /// side -> LeftEnd).
+    pub fn on_side(&self, side: Side) -> Placement {
+        let modifier = self.get_modifier();
+        match (side, modifier) {
+            (Side::Top, PlacementModifier::Start) => Placement::TopStart,
+            (Side::Top, PlacementModifier::Center) => Placement::TopCenter,
+            (Side::Top, PlacementModifier::End) => Placement::TopEnd,
+            (Side::Bottom, PlacementModifier::Start) => Placement::BottomStart,
+            (Side::Bottom, PlacementModifier::Center) => Placement::BottomCenter,
+            (Side::Bottom, PlacementModifier::End) => Placement::BottomEnd,
+            (Side::Left, PlacementModifier::Start) => Placement::LeftStart,
+            (Side::Left, PlacementModifier::Center) => Placement::LeftCenter,
+            (Side::Left, PlacementModifier::End) => Placement::LeftEnd,
+            (Side::Right, PlacementModifier::Start) => Placement::RightStart,
+            (Side::Right, PlacementModifier::Center) => Placement::RightCenter,
+            (Side::Right, PlacementModifier::End) => Placement::RightEnd,
+        }
+    }
+
+    /// Returns the placement on the same side with the opposite alignment (e.g., TopStart -> TopEnd).
+    ///
+    /// Center placements are returned unchanged.
+    pub fn opposite_alignment(&self) -> Placement {
+        match *self {
+            Placement::TopStart => Placement::TopEnd,
+            Placement::TopEnd => Placement::TopStart,
+            Placement::BottomStart => Placement::BottomEnd,
+            Placement::BottomEnd => Placement::BottomStart,
+            Placement::LeftStart => Placement::LeftEnd,
+            Placement::LeftEnd => Placement::LeftStart,
+            Placement::RightStart => Placement::RightEnd,
+            Placement::RightEnd => Placement::RightStart,
+            center => center,
+        }
+    }
+
     /// Returns the [PlacementModifier] (Start, Center, or End) for the current placement.
     pub fn get_modifier(&self) -> PlacementModifier {
         match *self {
@@ -89,129 +819,1724 @@ impl Placement {
     }
 }
 
-/// Modifiers that define alignment on the transverse axis.
-pub enum PlacementModifier {
-    Center,
-    Start,
-    End,
+/// The side of the trigger a floating element is placed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    /// Returns the opposite side.
+    pub fn opposite(&self) -> Side {
+        match *self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+/// Modifiers that define alignment on the transverse axis.
+pub enum PlacementModifier {
+    Center,
+    Start,
+    End,
+}
+
+/// How the floating element was opened, see [FloatingOptions::open_reason].
+///
+/// # Example
+/// ```rust
+/// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::{Floating, FloatingOptions, OpenReason, Placement};
+///
+/// let rect = |x: f64, y: f64, w: f64, h: f64| {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// };
+/// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+/// let element = rect(0.0, 0.0, 100.0, 300.0);
+/// // 300px below the trigger: the menu overflows, but its first 200px fit
+/// let trigger = rect(0.0, 280.0, 100.0, 20.0);
+/// let options = |open_reason| FloatingOptions {
+///     placement: Placement::BottomStart,
+///     open_reason,
+///     ..Default::default()
+/// };
+///
+/// let pointer = Floating::default().compute_placement(boundary, element, trigger, options(Some(OpenReason::Pointer)));
+/// assert_eq!(pointer.placement, Placement::TopStart);
+///
+/// let keyboard = Floating::default().compute_placement(boundary, element, trigger, options(Some(OpenReason::Keyboard)));
+/// assert_eq!(keyboard.placement, Placement::BottomStart);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpenReason {
+    /// Opened with a pointer (mouse, touch, pen).
+    Pointer,
+    /// Opened with the keyboard. The element prefers to stay on the preferred side
+    /// while at least [FloatingOptions::keyboard_min_visible] pixels of it fit.
+    Keyboard,
+}
+
+/// The CSS `position` the computed coordinates are applied with, see [crate::FloatingResult::style].
+///
+/// The engine always computes viewport-relative coordinates (from `getBoundingClientRect`).
+/// They match the rendered position only if the containing block of the floating element
+/// is the viewport:
+/// - [Strategy::Fixed]: no ancestor may have a `transform`, `filter`, `perspective`
+///   or `contain: paint`, as they turn it into the containing block.
+/// - [Strategy::Absolute]: the offset parent must be at the viewport origin and not
+///   scrolled, e.g., render the element at the end of `body`.
+///
+/// Render the element in a portal-like container at the root if neither holds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Strategy {
+    /// `position: fixed` (default).
+    #[default]
+    Fixed,
+    /// `position: absolute`.
+    Absolute,
+}
+
+impl Strategy {
+    /// Returns the CSS `position` value.
+    pub fn as_css(&self) -> &'static str {
+        match self {
+            Strategy::Fixed => "fixed",
+            Strategy::Absolute => "absolute",
+        }
+    }
+}
+
+/// What the floating element is aligned to on the cross axis, see
+/// [FloatingOptions::cross_axis_anchor].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AnchorSource {
+    /// The trigger (default): Start, Center and End align to its edges.
+    #[default]
+    Trigger,
+    /// The boundary inset by [FloatingOptions::padding]: Start, Center and End align to
+    /// its edges, e.g., a toolbar above the selected row that spans the container.
+    Boundary,
+}
+
+/// The rect that keeps the element in on one axis, see [FloatingOptions::boundary_x].
+///
+/// # Example
+///
+/// With the [crate::testing] mocks, a short, wide container that only scrolls
+/// horizontally keeps a tall popover in itself horizontally, but lets it extend below
+/// itself as long as it stays in the page:
+///
+/// ```rust
+/// # #[cfg(feature = "hooks")] {
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+/// use dioxus_floating::{Boundary, FloatingOptions, FloatingResult, Offset, Placement, use_placement};
+///
+/// thread_local! {
+///     static PER_AXIS: Cell<bool> = Cell::new(false);
+///     static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+/// }
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     // the chart: 100px high, the page around it: 1000px high
+///     use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 200.0, 800.0, 100.0))));
+///     let page = use_hook(|| MockElement::new(rect(0.0, 0.0, 800.0, 1000.0)).mounted());
+///     let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 100.0, 150.0)).mounted()));
+///     let trigger_ref = use_signal(|| Some(MockElement::new(rect(750.0, 270.0, 60.0, 20.0)).mounted()));
+///     let options = FloatingOptions {
+///         placement: Placement::BottomStart,
+///         placement_offset: Some(Offset::zero()),
+///         boundary_y: if PER_AXIS.with(Cell::get) { Boundary::Element(page) } else { Boundary::ScrollContext },
+///         ..Default::default()
+///     };
+///     let placement = use_placement(element_ref, trigger_ref, options);
+///     RESULT.with(|cell| cell.set(placement()));
+///     rsx! {}
+/// }
+///
+/// fn place(per_axis: bool) -> (f64, f64) {
+///     PER_AXIS.with(|cell| cell.set(per_axis));
+///     let mut dom = VirtualDom::new(app);
+///     dom.rebuild_in_place();
+///     settle(&mut dom);
+///     let result = RESULT.with(Cell::get);
+///     assert!(result.is_ready);
+///     (result.x, result.y)
+/// }
+///
+/// // the container on both axes: fits on neither side in the chart, flipped above where
+/// // it overflows less
+/// assert_eq!(place(false), (700.0, 120.0));
+/// // the page vertically: right below the trigger, still shifted into the chart horizontally
+/// assert_eq!(place(true), (700.0, 290.0));
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub enum Boundary {
+    /// The nearest [crate::ScrollableView] (default), without its scrollbars and with its
+    /// boundary adjustment.
+    #[default]
+    ScrollContext,
+    /// The visual viewport (see [crate::use_visual_viewport]), e.g., to let an element
+    /// extend beyond a container that does not scroll on this axis. Browsers without the
+    /// VisualViewport API and other renderers keep the scroll context.
+    Viewport,
+    /// The client rect of an element, measured together with the trigger.
+    #[cfg(feature = "dioxus")]
+    Element(Rc<MountedData>),
+}
+
+impl Boundary {
+    /// Internal: Returns the element of [Boundary::Element].
+    #[cfg(feature = "hooks")]
+    pub(crate) fn element(&self) -> Option<Rc<MountedData>> {
+        match self {
+            Boundary::Element(element) => Some(element.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Boundary {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Boundary::ScrollContext, Boundary::ScrollContext)
+            | (Boundary::Viewport, Boundary::Viewport) => true,
+            #[cfg(feature = "dioxus")]
+            (Boundary::Element(a), Boundary::Element(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for Boundary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Boundary::ScrollContext => f.write_str("ScrollContext"),
+            Boundary::Viewport => f.write_str("Viewport"),
+            #[cfg(feature = "dioxus")]
+            Boundary::Element(_) => f.write_str("Element(..)"),
+        }
+    }
+}
+
+/// Disables CSS transitions of the element right after its position jumps, see
+/// [FloatingOptions::suppress_transitions].
+///
+/// A change of the resolved [Placement] (e.g., a flip) or a move of at least
+/// `min_jump` pixels is a jump; smaller tracking moves (e.g., scrolling) keep the
+/// transitions.
+///
+/// # Example
+/// ```rust
+/// use dioxus_floating::{FloatingResult, Placement, SuppressTransitions};
+///
+/// let suppress = SuppressTransitions::default();
+/// let at = |x: f64, y: f64, placement| FloatingResult { x, y, placement, is_ready: true, ..Default::default() };
+///
+/// // tracking a scroll
+/// assert!(!suppress.is_jump(&at(100.0, 100.0, Placement::BottomStart), &at(100.0, 96.0, Placement::BottomStart)));
+/// // a flip
+/// assert!(suppress.is_jump(&at(100.0, 100.0, Placement::BottomStart), &at(100.0, 40.0, Placement::TopStart)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuppressTransitions {
+    /// Distance in pixels from which a move is a jump.
+    pub min_jump: f64,
+    /// How long transitions stay disabled after a jump, in milliseconds; at least a
+    /// frame, so the jump is rendered without a transition.
+    pub duration_ms: u32,
+    /// Keep the transitions of small moves when the user prefers reduced motion. By
+    /// default, every move disables them then, so the element never glides (see
+    /// `prefers_reduced_motion`).
+    pub ignore_reduced_motion: bool,
+}
+
+impl Default for SuppressTransitions {
+    /// Returns a 24px jump distance and a 50ms duration.
+    fn default() -> Self {
+        Self {
+            min_jump: 24_f64,
+            duration_ms: 50,
+            ignore_reduced_motion: false,
+        }
+    }
+}
+
+impl SuppressTransitions {
+    /// Returns `true` if the move from `from` to `to` is a jump.
+    pub fn is_jump(&self, from: &crate::FloatingResult, to: &crate::FloatingResult) -> bool {
+        let distance = (to.x - from.x).hypot(to.y - from.y);
+        from.placement != to.placement || distance >= self.min_jump
+    }
+}
+
+/// When the placement hooks publish the first measurement of the floating element,
+/// see [FloatingOptions::measure_after].
+///
+/// # Example
+///
+/// With the [crate::testing] mocks, the element is pre-rendered without a size and
+/// shown later. The mocks have no animation frames, so a layout invalidation stands in
+/// for the next frame.
+///
+/// ```rust
+/// # #[cfg(feature = "hooks")] {
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+/// use dioxus_floating::{FloatingOptions, FloatingResult, MeasureAfter, ScrollableContext, use_placement};
+///
+/// thread_local! {
+///     static ELEMENT: MockElement = MockElement::new(PixelsRect::zero());
+///     static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+///     static CONTEXT: Cell<Option<ScrollableContext>> = Cell::new(None);
+/// }
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     let context = use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+///     CONTEXT.with(|cell| cell.set(Some(context)));
+///     rsx! { Popover {} }
+/// }
+///
+/// #[component]
+/// fn Popover() -> Element {
+///     let trigger = use_hook(|| MockElement::new(rect(100.0, 100.0, 80.0, 20.0)));
+///     let trigger_ref = use_signal(|| Some(trigger.mounted()));
+///     let element_ref = use_signal(|| Some(ELEMENT.with(MockElement::mounted)));
+///
+///     let options = FloatingOptions { measure_after: MeasureAfter::Visible, ..Default::default() };
+///     let placement = use_placement(element_ref, trigger_ref, options);
+///     RESULT.with(|result| result.set(placement()));
+///
+///     rsx! {}
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// settle(&mut dom);
+///
+/// // `display: none`
+/// let result = RESULT.with(Cell::get);
+/// assert!(!result.is_ready);
+/// assert!(result.is_waiting_for_visibility);
+///
+/// // the class flips
+/// ELEMENT.with(|element| element.set_rect(rect(0.0, 0.0, 100.0, 50.0)));
+/// let mut generation = CONTEXT.with(Cell::get).unwrap().layout_generation;
+/// dom.in_runtime(|| *generation.write() += 1);
+/// settle(&mut dom);
+///
+/// let result = RESULT.with(Cell::get);
+/// assert!(result.is_ready);
+/// assert!(!result.is_waiting_for_visibility);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MeasureAfter {
+    /// As soon as the element is mounted (default). An element that is not rendered yet
+    /// (e.g., `display: none`) is measured with a zero size.
+    #[default]
+    Mount,
+    /// Once the element is rendered, i.e., its client rect has a non-zero size. Until then
+    /// it is measured again every animation frame, without a limit, and the hooks report
+    /// [crate::FloatingResult::is_waiting_for_visibility].
+    Visible,
+}
+
+/// The pointer that opened the floating element, see [FloatingOptions::touch_offset_extra].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerType {
+    /// A mouse or another precise pointer.
+    Mouse,
+    /// A finger.
+    Touch,
+    /// A stylus.
+    Pen,
+}
+
+impl PointerType {
+    /// Parses the `pointerType` of a DOM pointer event (`mouse`, `touch` or `pen`).
+    pub fn parse(pointer_type: &str) -> Option<PointerType> {
+        match pointer_type {
+            "mouse" => Some(PointerType::Mouse),
+            "touch" => Some(PointerType::Touch),
+            "pen" => Some(PointerType::Pen),
+            _ => None,
+        }
+    }
+}
+
+/// Strategic logic used to adjust the floating position when it overflows the viewport.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Middleware {
+    /// Flips the element to the opposite side if there isn't enough space (e.g., Top -> Bottom)
+    /// and it overflows the boundary less there. An element that fits on neither side stays
+    /// on the one with more room, for [Middleware::Shift] or [Middleware::Size] to handle.
+    Flip,
+    /// Shifts the element to keep it within the viewport, along the cross axis by default
+    /// (see [FloatingOptions::shift_axes]).
+    Shift,
+    /// Places the element on the side of the trigger with the most free space in the
+    /// boundary, whatever the side of [FloatingOptions::placement], e.g., for a popover
+    /// that may open anywhere in a long list.
+    ///
+    /// The free space of a side is the area of [PlacementOutput::available] there (after
+    /// the offset, [FloatingOptions::padding] and [FloatingOptions::boundary_padding]).
+    /// Between the placements of the side with the most space, the one that overflows the
+    /// boundary the least on the cross axis wins, and the earlier one on ties. It runs
+    /// before the other middleware: Shift applies to the chosen placement, the main axis
+    /// of Flip is skipped (the side is already the roomiest). The chosen placement is
+    /// [PlacementOutput::placement].
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Floating, FloatingOptions, Middleware, Placement};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let place = |trigger, allowed| {
+    ///     let options = FloatingOptions {
+    ///         middleware: vec![Middleware::Auto { allowed }, Middleware::Shift],
+    ///         placement: Placement::BottomStart,
+    ///         ..Default::default()
+    ///     };
+    ///     let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    ///     let element = rect(0.0, 0.0, 100.0, 50.0);
+    ///     Floating::default().compute_placement(boundary, element, trigger, options).placement
+    /// };
+    ///
+    /// // near the bottom edge: the most room is above
+    /// assert_eq!(place(rect(300.0, 440.0, 80.0, 20.0), &[]), Placement::TopStart);
+    /// // near the right edge, in the middle: the most room is on the left
+    /// assert_eq!(place(rect(440.0, 200.0, 40.0, 20.0), &[]), Placement::LeftStart);
+    /// // only top or bottom
+    /// let vertical = &[Placement::BottomStart, Placement::TopStart];
+    /// assert_eq!(place(rect(440.0, 200.0, 40.0, 20.0), vertical), Placement::BottomStart);
+    /// ```
+    Auto {
+        /// The placements to choose from; empty for the four sides with the alignment of
+        /// [FloatingOptions::placement], its own side first. A list built at runtime can
+        /// be leaked once (`Vec::leak`) and shared by every placement that uses it.
+        allowed: &'static [Placement],
+    },
+    /// Limits the size of the element to the space between the trigger and the boundary:
+    /// [PlacementOutput::max_width] and [PlacementOutput::max_height] (and the same fields
+    /// of [crate::FloatingResult]), e.g., for a long select menu that scrolls instead of
+    /// being clipped.
+    ///
+    /// The space is the one of [PlacementOutput::available] with the boundary also inset
+    /// by [FloatingOptions::padding]: the main axis between the trigger (plus the offset)
+    /// and the boundary, inset by [FloatingOptions::boundary_padding] and the padding. The
+    /// element is placed as if it had the limited size, so apply the limits (e.g.,
+    /// `max-height: {h}px; overflow-y: auto`) to keep it next to the trigger. With
+    /// [Middleware::Flip], an element that fits on neither side goes to the side with the
+    /// most room, then is limited there. Unbounded elements are not limited.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Floating, FloatingOptions, Middleware, Offset, Placement};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let options = FloatingOptions {
+    ///     middleware: vec![Middleware::Flip, Middleware::Shift, Middleware::Size],
+    ///     placement: Placement::BottomStart,
+    ///     placement_offset: Some(Offset::zero()),
+    ///     ..Default::default()
+    /// };
+    /// // a 2000px tall menu: 300px of room below the trigger, 180px above
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let menu = rect(0.0, 0.0, 200.0, 2000.0);
+    /// let trigger = rect(100.0, 180.0, 80.0, 20.0);
+    ///
+    /// let output = Floating::default().compute_placement(boundary, menu, trigger, options);
+    /// assert_eq!(output.placement, Placement::BottomStart);
+    /// assert_eq!(output.max_height, Some(300.0));
+    /// assert_eq!(output.max_width, Some(500.0));
+    /// assert_eq!(output.y, 200.0);
+    /// ```
+    Size,
+    /// Computes where an arrow goes on the element, pointing at the center of the
+    /// trigger: [PlacementOutput::arrow] (and [crate::FloatingResult::arrow_position]).
+    ///
+    /// The arrow runs last, so it follows the side chosen by Flip (or Auto) and stays on
+    /// the trigger when Shift moves the element. Its offset along the edge is clamped
+    /// to `corner_radius` from both corners, so it never slides off the rounded part.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Floating, FloatingOptions, Middleware, Placement, Side};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let options = FloatingOptions {
+    ///     middleware: vec![
+    ///         Middleware::Flip,
+    ///         Middleware::Shift,
+    ///         Middleware::Arrow { width: 10.0, corner_radius: 4.0 },
+    ///     ],
+    ///     placement: Placement::BottomStart,
+    ///     ..Default::default()
+    /// };
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let element = rect(0.0, 0.0, 100.0, 50.0);
+    ///
+    /// // near the right edge: shifted 40px to the left, the arrow follows the trigger
+    /// let trigger = rect(440.0, 100.0, 40.0, 20.0);
+    /// let output = Floating::default().compute_placement(boundary, element, trigger, options);
+    /// let arrow = output.arrow.unwrap();
+    /// assert_eq!(arrow.edge, Side::Top);
+    /// assert_eq!(arrow.x(), Some(55.0));
+    /// ```
+    Arrow {
+        /// Size of the arrow base along the edge, in pixels.
+        width: f64,
+        /// Distance the arrow keeps from both corners of the edge, e.g., the
+        /// `border-radius` of the element.
+        corner_radius: f64,
+    },
+    /// Reports a trigger that left the boundary, e.g., scrolled out of a
+    /// [crate::ScrollableView] while the element stays open: [PlacementOutput::is_hidden]
+    /// once no part of it is inside, [PlacementOutput::is_trigger_clipped] while only a
+    /// part is. Bind the first to `visibility: hidden`; a partially clipped trigger may
+    /// still deserve its element.
+    ///
+    /// The position is computed as usual, so the element shows up at the right place
+    /// when the trigger scrolls back: the placement hooks measure it again as soon as it
+    /// is predicted to be visible, like with [FloatingOptions::anchor_to_visible].
+    /// Unbounded elements are never hidden.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Floating, FloatingOptions, Middleware};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let options = FloatingOptions {
+    ///     middleware: vec![Middleware::Flip, Middleware::Shift, Middleware::Hide],
+    ///     ..Default::default()
+    /// };
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let element = rect(0.0, 0.0, 100.0, 50.0);
+    /// let place = |trigger| Floating::default().compute_placement(boundary, element, trigger, options.clone());
+    ///
+    /// // scrolled halfway past the top edge
+    /// let output = place(rect(100.0, -10.0, 80.0, 20.0));
+    /// assert!(output.is_trigger_clipped && !output.is_hidden);
+    ///
+    /// // scrolled out
+    /// let output = place(rect(100.0, -40.0, 80.0, 20.0));
+    /// assert!(output.is_hidden && !output.is_trigger_clipped);
+    /// ```
+    Hide,
+}
+
+impl Middleware {
+    /// Returns [Middleware::Auto] choosing between the four sides.
+    pub fn auto() -> Self {
+        Middleware::Auto { allowed: &[] }
+    }
+}
+
+/// Axes a [Middleware] is allowed to act on, relative to the [Placement].
+///
+/// The main axis is the one the element is placed along (vertical for Top/Bottom,
+/// horizontal for Left/Right), the cross axis is the alignment axis.
+///
+/// # Example
+/// With `shift_axes: Axes::Both`, a large element next to a corner trigger is kept
+/// inside the boundary on both axes, even with Flip disabled:
+/// ```rust
+/// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::{Axes, Floating, FloatingOptions, Middleware, Placement};
+///
+/// let rect = |x: f64, y: f64, w: f64, h: f64| {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// };
+/// let floating = Floating::default();
+/// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+/// let element = rect(0.0, 0.0, 200.0, 150.0);
+///
+/// let corners = [
+///     (rect(0.0, 0.0, 40.0, 20.0), Placement::TopEnd),
+///     (rect(460.0, 0.0, 40.0, 20.0), Placement::TopStart),
+///     (rect(0.0, 480.0, 40.0, 20.0), Placement::BottomEnd),
+///     (rect(460.0, 480.0, 40.0, 20.0), Placement::BottomStart),
+/// ];
+/// for (trigger, placement) in corners {
+///     let options = FloatingOptions {
+///         middleware: vec![Middleware::Shift],
+///         shift_axes: Axes::Both,
+///         placement,
+///         ..Default::default()
+///     };
+///     let output = floating.compute_placement(boundary, element, trigger, options);
+///
+///     assert_eq!(output.visible_ratio, 1.0, "{placement:?}");
+///     assert_ne!(output.shift.x, 0.0, "{placement:?}");
+///     assert_ne!(output.shift.y, 0.0, "{placement:?}");
+///     assert_eq!(output.placement, placement);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Axes {
+    /// Only the main axis (e.g., Flip from Top to Bottom).
+    Main,
+    /// Only the cross axis (e.g., Shift from left to right, Flip from Start to End).
+    Cross,
+    /// Both axes.
+    Both,
+}
+
+impl Axes {
+    /// Returns `true` if the main axis is included.
+    pub fn main(&self) -> bool {
+        matches!(self, Axes::Main | Axes::Both)
+    }
+
+    /// Returns `true` if the cross axis is included.
+    pub fn cross(&self) -> bool {
+        matches!(self, Axes::Cross | Axes::Both)
+    }
+}
+
+/// Offset options for the floating element, in absolute directions whatever the
+/// [Placement].
+///
+/// Superseded by [Offset] in [FloatingOptions::placement_offset], which is relative to the
+/// placement; these offsets apply only while it is `None`.
+#[deprecated(
+    since = "0.3.0",
+    note = "set `FloatingOptions::placement_offset` to an `Offset`, which is relative to the placement"
+)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffsetOptions {
+    /// Offset along the main axis. (Vertical, X)
+    pub main_axis: f64,
+    /// Offset along the cross axis. (Horizontal, Y)
+    pub cross_axis: f64,
+}
+
+#[allow(deprecated)]
+impl Default for OffsetOptions {
+    fn default() -> Self {
+        Self {
+            main_axis: 1_f64,
+            cross_axis: 1_f64,
+        }
+    }
+}
+
+#[allow(deprecated)]
+impl OffsetOptions {
+    /// Creates a new [OffsetOptions] with the specified offsets.
+    pub fn new(main_axis: f64, cross_axis: f64) -> Self {
+        Self {
+            main_axis,
+            cross_axis,
+        }
+    }
+
+    /// Creates a new [OffsetOptions] with the same offset for both axes.
+    pub fn rect(offset: f64) -> Self {
+        Self {
+            main_axis: offset,
+            cross_axis: offset,
+        }
+    }
+
+    /// Creates a new [OffsetOptions] with zero offset for both axes.
+    pub fn zero() -> Self {
+        Self::rect(0_f64)
+    }
+
+    /// Internal: Returns the gap between the trigger and the element, and the offset along
+    /// the positive cross axis, of `placement`: the gap of vertical placements is
+    /// `cross_axis` (y), of horizontal ones `main_axis` (x).
+    fn gap_and_shift(&self, placement: Placement) -> (f64, f64) {
+        if placement.is_vertical() {
+            (self.cross_axis, self.main_axis)
+        } else {
+            (self.main_axis, self.cross_axis)
+        }
+    }
+
+    /// Internal: Widens the gap of `placement` by `extra` pixels, see
+    /// [OffsetOptions::gap_and_shift].
+    fn widen_gap(&mut self, placement: Placement, extra: f64) {
+        if placement.is_vertical() {
+            self.cross_axis += extra;
+        } else {
+            self.main_axis += extra;
+        }
+    }
+
+    /// Internal: Returns `true` if both offsets are finite numbers.
+    pub(crate) fn is_finite(&self) -> bool {
+        self.main_axis.is_finite() && self.cross_axis.is_finite()
+    }
+}
+
+/// The offset of the floating element relative to its [Placement], see
+/// [FloatingOptions::placement_offset].
+///
+/// A single number is the gap from the trigger (`Offset::from(8.0)`). Negative values are
+/// allowed on both axes, e.g., to overlap the trigger.
+///
+/// # Example
+/// A dropdown 8px below its button and 12px right of the start edge, and the same
+/// option on a Left placement (12px down) and an End alignment (12px in from the end):
+/// ```rust
+/// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::{Floating, FloatingOptions, Offset, Placement};
+///
+/// let rect = |x: f64, y: f64, w: f64, h: f64| {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// };
+/// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+/// let element = rect(0.0, 0.0, 100.0, 50.0);
+/// let trigger = rect(200.0, 200.0, 80.0, 20.0);
+/// let place = |placement| {
+///     let options = FloatingOptions {
+///         placement,
+///         placement_offset: Some(Offset::new(8.0, 12.0)),
+///         ..Default::default()
+///     };
+///     let output = Floating::default().compute_placement(boundary, element, trigger, options);
+///     (output.x, output.y)
+/// };
+///
+/// assert_eq!(place(Placement::BottomStart), (212.0, 228.0));
+/// assert_eq!(place(Placement::LeftStart), (92.0, 212.0));
+/// assert_eq!(place(Placement::BottomEnd), (168.0, 228.0));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Offset {
+    /// The gap between the trigger and the element, along the main axis (vertical for
+    /// Top/Bottom placements, horizontal for Left/Right ones).
+    pub main_axis: f64,
+    /// Moves the element along the cross axis, away from the edge it is aligned to:
+    /// toward the end for Start and Center alignments, toward the start for End ones.
+    pub cross_axis: f64,
+}
+
+impl Offset {
+    /// Creates a new [Offset] with a gap and a cross-axis offset.
+    pub fn new(main_axis: f64, cross_axis: f64) -> Self {
+        Self {
+            main_axis,
+            cross_axis,
+        }
+    }
+
+    /// Creates a new [Offset] without a gap or a cross-axis offset.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Internal: Returns the cross-axis offset as a distance along the positive axis for
+    /// the alignment of `placement`.
+    fn cross_axis_for(&self, placement: Placement) -> f64 {
+        match placement.get_modifier() {
+            PlacementModifier::End => -self.cross_axis,
+            PlacementModifier::Start | PlacementModifier::Center => self.cross_axis,
+        }
+    }
+}
+
+impl From<f64> for Offset {
+    /// Returns a gap of `main_axis` pixels, without a cross-axis offset.
+    fn from(main_axis: f64) -> Self {
+        Self::new(main_axis, 0_f64)
+    }
+}
+
+/// A padding length, in pixels or relative to the boundary, see [Padding].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaddingValue {
+    /// A fixed length in CSS pixels.
+    Px(f64),
+    /// A percentage (`2.0` for 2%) of the boundary width for the left and right sides,
+    /// of its height for the top and bottom sides.
+    Percent(f64),
+}
+
+impl PaddingValue {
+    /// Returns the length in pixels for a boundary dimension of `length` pixels.
+    pub fn resolve(&self, length: f64) -> f64 {
+        match *self {
+            PaddingValue::Px(px) => px,
+            PaddingValue::Percent(percent) => length * percent / 100_f64,
+        }
+    }
+
+    /// Internal: Returns `true` if the value is a finite number.
+    pub(crate) fn is_finite(&self) -> bool {
+        match *self {
+            PaddingValue::Px(value) | PaddingValue::Percent(value) => value.is_finite(),
+        }
+    }
+}
+
+impl Default for PaddingValue {
+    /// Returns `Px(0.0)`.
+    fn default() -> Self {
+        PaddingValue::Px(0_f64)
+    }
+}
+
+impl From<f64> for PaddingValue {
+    /// Returns [PaddingValue::Px].
+    fn from(px: f64) -> Self {
+        PaddingValue::Px(px)
+    }
+}
+
+/// The space Flip and Shift keep clear inside each edge of the boundary, see
+/// [FloatingOptions::boundary_padding].
+///
+/// Percentages resolve against the boundary rect of each computation, so the space
+/// scales with the container. The resolved pixels are recorded in the `padding` step of
+/// [PlacementOutput::steps] with [FloatingOptions::explain].
+///
+/// # Example
+///
+/// 16px on the left, 2% of the boundary width on the right: Shift keeps a wide element
+/// near the right edge 10px clear of it in a 500px container, 40px in a 2000px one.
+///
+/// ```rust
+/// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::{Floating, FloatingOptions, Offset, Padding, PaddingValue};
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// let options = FloatingOptions {
+///     placement_offset: Some(Offset::zero()),
+///     boundary_padding: Padding {
+///         left: PaddingValue::Px(16.0),
+///         right: PaddingValue::Percent(2.0),
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// let x_near = |boundary_width: f64, trigger_x: f64| {
+///     let boundary = rect(0.0, 0.0, boundary_width, 500.0);
+///     let element = rect(0.0, 0.0, 200.0, 50.0);
+///     let trigger = rect(trigger_x, 100.0, 60.0, 20.0);
+///     Floating::default().compute_placement(boundary, element, trigger, options.clone()).x
+/// };
+///
+/// // the right edge: 500 - 2% = 490, 2000 - 2% = 1960
+/// assert_eq!(x_near(500.0, 420.0), 290.0);
+/// assert_eq!(x_near(2000.0, 1920.0), 1760.0);
+/// // the left edge is 16px at both sizes (Start aligned, shifted right)
+/// assert_eq!(x_near(500.0, 0.0), 16.0);
+/// assert_eq!(x_near(2000.0, 0.0), 16.0);
+///
+/// assert_eq!(
+///     options.boundary_padding.resolve(PixelsSize::new(2000.0, 500.0)),
+///     Padding { left: PaddingValue::Px(16.0), right: PaddingValue::Px(40.0), ..Default::default() }
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Padding {
+    /// The space below the top edge.
+    pub top: PaddingValue,
+    /// The space left of the right edge.
+    pub right: PaddingValue,
+    /// The space above the bottom edge.
+    pub bottom: PaddingValue,
+    /// The space right of the left edge.
+    pub left: PaddingValue,
+}
+
+impl Padding {
+    /// Creates a [Padding] with the same value on every side.
+    pub fn all(value: PaddingValue) -> Self {
+        Self {
+            top: value,
+            right: value,
+            bottom: value,
+            left: value,
+        }
+    }
+
+    /// Returns the padding in pixels ([PaddingValue::Px] on every side) for a boundary of
+    /// the given size.
+    pub fn resolve(&self, size: PixelsSize) -> Padding {
+        Padding {
+            top: PaddingValue::Px(self.top.resolve(size.height)),
+            right: PaddingValue::Px(self.right.resolve(size.width)),
+            bottom: PaddingValue::Px(self.bottom.resolve(size.height)),
+            left: PaddingValue::Px(self.left.resolve(size.width)),
+        }
+    }
+
+    /// Internal: Returns `true` if every side is a finite number.
+    pub(crate) fn is_finite(&self) -> bool {
+        [self.top, self.right, self.bottom, self.left]
+            .iter()
+            .all(PaddingValue::is_finite)
+    }
+
+    /// Internal: Returns `boundary` without the padding; an empty rect at its center
+    /// if the padding does not fit.
+    fn inset(&self, boundary: PixelsRect) -> PixelsRect {
+        let size = boundary.size;
+        let (top, right, bottom, left) = (
+            self.top.resolve(size.height),
+            self.right.resolve(size.width),
+            self.bottom.resolve(size.height),
+            self.left.resolve(size.width),
+        );
+        let width = size.width - left - right;
+        let height = size.height - top - bottom;
+
+        PixelsRect::new(
+            PixelsVector2D::new(
+                boundary.min_x()
+                    + if width < 0_f64 {
+                        size.width / 2_f64
+                    } else {
+                        left
+                    },
+                boundary.min_y()
+                    + if height < 0_f64 {
+                        size.height / 2_f64
+                    } else {
+                        top
+                    },
+            )
+            .to_point(),
+            PixelsSize::new(width.max(0_f64), height.max(0_f64)),
+        )
+    }
+}
+
+/// Configuration for the floating position calculation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatingOptions {
+    /// List of [Middleware] strategies to apply.
+    pub middleware: Vec<Middleware>,
+    /// Offsets of the floating element in pixels, along x (`main_axis`) and y
+    /// (`cross_axis`) whatever the placement. Ignored with
+    /// [FloatingOptions::placement_offset].
+    #[allow(deprecated)]
+    pub offset: OffsetOptions,
+    /// The gap between the trigger and the floating element and the cross-axis offset, in
+    /// pixels relative to the placement, see [Offset]; `Some(8.0.into())` for a plain gap.
+    /// Replaces [FloatingOptions::offset] when set.
+    pub placement_offset: Option<Offset>,
+    /// Distance between the floating element and the scrollable container edges.
+    pub padding: f64,
+    /// The preferred [Placement] strategy.
+    pub placement: Placement,
+    /// [Axes] the [Middleware::Flip] strategy acts on.
+    ///
+    /// On the cross axis, Flip swaps Start and End alignment.
+    pub flip_axes: Axes,
+    /// [Axes] the [Middleware::Shift] strategy acts on.
+    ///
+    /// On the main axis, Shift slides the element into the boundary even if it
+    /// then overlaps the trigger, so it is mostly useful with Flip disabled.
+    pub shift_axes: Axes,
+    /// Sources that trigger a recomputation in the placement hooks.
+    pub auto_update: AutoUpdate,
+    /// Axes `(x, y)` on which the trigger does not move when the container scrolls
+    /// (e.g., a sticky table header).
+    ///
+    /// When any axis is set, scroll-driven recomputes in `use_placement` skip the
+    /// measurement: the last measured trigger rect is moved by the scroll delta on
+    /// the non-sticky axes and the position is updated synchronously.
+    pub sticky_axes: (bool, bool),
+    /// Announces side changes (e.g., after a Flip) to screen readers.
+    pub announce: Option<AnnounceOptions>,
+    /// Prefer flips that change only the side over those that also change the alignment
+    /// (default `true`). The alignment is swapped only if the current one overflows and the
+    /// swapped one fits; with `false`, it is swapped whenever the swapped one overflows less.
+    /// [PlacementOutput::alignment_changed] reports a changed alignment.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Axes, Floating, FloatingOptions, Placement};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let element = rect(0.0, 0.0, 200.0, 100.0);
+    /// let options = FloatingOptions { placement: Placement::BottomEnd, flip_axes: Axes::Both, ..Default::default() };
+    ///
+    /// let unflipped = Floating::default().compute_placement(boundary, element, rect(250.0, 100.0, 80.0, 20.0), options.clone());
+    /// // near the bottom edge: only the side changes, the right edge stays in place
+    /// let flipped = Floating::default().compute_placement(boundary, element, rect(250.0, 450.0, 80.0, 20.0), options);
+    /// assert_eq!(flipped.placement, Placement::TopEnd);
+    /// assert_eq!(flipped.x, unflipped.x);
+    /// assert!(!flipped.alignment_changed);
+    /// ```
+    pub preserve_alignment: bool,
+    /// How the element was opened. With [OpenReason::Keyboard], the main-axis Flip is
+    /// skipped while the first [FloatingOptions::keyboard_min_visible] pixels fit on the
+    /// preferred side; the rest overflows and should scroll internally.
+    pub open_reason: Option<OpenReason>,
+    /// Pixels of the element (along the main axis) that must fit on the preferred side
+    /// to skip the Flip for keyboard-opened elements, e.g., three item heights.
+    pub keyboard_min_visible: f64,
+    /// Pins the [ScrollableContext] used by the placement hooks instead of the nearest one.
+    ///
+    /// See [crate::use_parent_scroll_context] and the nesting rules of [crate::ScrollableView].
+    #[cfg(feature = "hooks")]
+    pub context: Option<ScrollableContext>,
+    /// Align the element to the visible part of the trigger (its intersection with the
+    /// boundary) instead of the full trigger rect, e.g., for a wide table row scrolled
+    /// partially out of view. The Shift tether uses the visible part as well.
+    ///
+    /// If no part of the trigger is visible, the full rect is used and
+    /// [PlacementOutput::is_hidden] is set. When a hidden trigger scrolls back, the
+    /// placement hooks measure it again right away (even without scroll-driven updates
+    /// or with [FloatingOptions::sticky_axes]) and clear the flag only together with
+    /// the fresh position, so kept-alive elements never show a stale one.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Floating, FloatingOptions, Offset, Placement};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let element = rect(0.0, 0.0, 60.0, 40.0);
+    /// // half of the trigger is clipped by the left edge, (0, 100) - (100, 120) is visible
+    /// let trigger = rect(-100.0, 100.0, 200.0, 20.0);
+    ///
+    /// for (placement, x) in [
+    ///     (Placement::BottomStart, 0.0),
+    ///     (Placement::BottomCenter, 20.0),
+    ///     (Placement::BottomEnd, 40.0),
+    /// ] {
+    ///     let options = FloatingOptions {
+    ///         placement,
+    ///         placement_offset: Some(Offset::zero()),
+    ///         anchor_to_visible: true,
+    ///         ..Default::default()
+    ///     };
+    ///     let output = Floating::default().compute_placement(boundary, element, trigger, options);
+    ///     assert_eq!(output.x, x);
+    ///     assert!(!output.is_hidden);
+    /// }
+    ///
+    /// // scrolled out completely
+    /// let options = FloatingOptions { anchor_to_visible: true, ..Default::default() };
+    /// let output = Floating::default().compute_placement(boundary, element, rect(-300.0, 100.0, 200.0, 20.0), options);
+    /// assert!(output.is_hidden);
+    /// ```
+    pub anchor_to_visible: bool,
+    /// Records the decisions of the synchronous pipeline in [PlacementOutput::steps],
+    /// e.g., to snapshot-test why a flip did or did not happen. Off by default.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Floating, FloatingOptions};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let element = rect(0.0, 0.0, 200.0, 100.0);
+    /// let trigger = rect(100.0, 450.0, 80.0, 20.0);
+    ///
+    /// let options = FloatingOptions { explain: true, ..Default::default() };
+    /// let output = Floating::default().compute_placement(boundary, element, trigger, options);
+    /// let names: Vec<_> = output.steps.iter().map(|step| step.name).collect();
+    /// assert_eq!(names, ["base", "flip", "shift-cross", "rounding"]);
+    /// // the flip moved the element above the trigger
+    /// assert!(output.steps[1].after.1 < output.steps[1].before.1);
+    ///
+    /// let output = Floating::default().compute_placement(boundary, element, trigger, FloatingOptions::default());
+    /// assert!(output.steps.is_empty());
+    /// ```
+    pub explain: bool,
+    /// The pointer that opened the element (set by [crate::use_floating_widget]).
+    pub open_pointer_type: Option<PointerType>,
+    /// Pixels added to the gap between the trigger and the element while it was opened
+    /// with [PointerType::Touch] or [PointerType::Pen], so the finger does not cover it
+    /// (default 0). The gap takes part in Flip and Shift like the regular offset.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Floating, FloatingOptions, PointerType};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let element = rect(0.0, 0.0, 200.0, 100.0);
+    /// let trigger = rect(100.0, 100.0, 80.0, 20.0);
+    /// let options = |open_pointer_type| FloatingOptions {
+    ///     open_pointer_type,
+    ///     touch_offset_extra: 16.0,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let click = Floating::default().compute_placement(boundary, element, trigger, options(Some(PointerType::Mouse)));
+    /// let tap = Floating::default().compute_placement(boundary, element, trigger, options(Some(PointerType::Touch)));
+    /// assert_eq!(tap.y - click.y, 16.0);
+    /// ```
+    pub touch_offset_extra: f64,
+    /// A name for this placement in the warnings of the hooks, e.g., `"user-menu"`.
+    pub debug_label: Option<&'static str>,
+    /// The [Strategy] the coordinates are applied with, used by [crate::use_floating_widget]
+    /// and by the position self-check of debug builds.
+    pub strategy: Strategy,
+    /// Disables CSS transitions of the element for a moment after its position jumps,
+    /// e.g., on a flip, while small tracking moves stay animated. The placement hooks
+    /// report it in [crate::FloatingResult::suppress_transition] and the style helpers
+    /// add `transition: none`.
+    ///
+    /// Alternatively, apply the position with [crate::FloatingResult::inset_style_attrs]
+    /// and leave `transform` to the animation.
+    pub suppress_transitions: Option<SuppressTransitions>,
+    /// What the element is aligned to on the cross axis; the main axis always follows
+    /// the trigger. With [AnchorSource::Boundary], the cross-axis Flip and Shift are
+    /// skipped, as the element is already at the boundary. Use
+    /// [PlacementOutput::trigger_offset] to point an arrow at the trigger.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{AnchorSource, Floating, FloatingOptions, Offset, Placement};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let element = rect(0.0, 0.0, 100.0, 40.0);
+    /// let trigger = rect(200.0, 200.0, 80.0, 20.0);
+    /// let options = |placement, cross_axis_anchor| FloatingOptions {
+    ///     placement,
+    ///     cross_axis_anchor,
+    ///     placement_offset: Some(Offset::zero()),
+    ///     padding: 8.0,
+    ///     ..Default::default()
+    /// };
+    /// let place = |placement, source| {
+    ///     let output = Floating::default().compute_placement(boundary, element, trigger, options(placement, source));
+    ///     (output.x, output.y)
+    /// };
+    ///
+    /// // vertical: the main axis (y) follows the trigger, the cross axis (x) the source
+    /// assert_eq!(place(Placement::TopStart, AnchorSource::Trigger), (200.0, 160.0));
+    /// assert_eq!(place(Placement::TopStart, AnchorSource::Boundary), (8.0, 160.0));
+    /// assert_eq!(place(Placement::TopEnd, AnchorSource::Boundary), (392.0, 160.0));
+    ///
+    /// // horizontal: the main axis (x) follows the trigger, the cross axis (y) the source
+    /// assert_eq!(place(Placement::RightStart, AnchorSource::Trigger), (280.0, 200.0));
+    /// assert_eq!(place(Placement::RightStart, AnchorSource::Boundary), (280.0, 8.0));
+    /// assert_eq!(place(Placement::RightCenter, AnchorSource::Boundary), (280.0, 230.0));
+    ///
+    /// // the trigger is still reported relative to the element, e.g., for an arrow
+    /// let output = Floating::default().compute_placement(boundary, element, trigger, options(Placement::TopStart, AnchorSource::Boundary));
+    /// assert_eq!(output.trigger_offset(), PixelsVector2D::new(192.0, 40.0));
+    /// ```
+    pub cross_axis_anchor: AnchorSource,
+    /// Intersect the boundary with the visual viewport (see [crate::use_visual_viewport]),
+    /// so Flip opens the element above a focused input while an on-screen keyboard
+    /// covers the bottom of the page. Browsers without the VisualViewport API keep the
+    /// boundary as is.
+    pub clip_to_visual_viewport: bool,
+    /// The [WaitStrategy] of this hook before measuring, instead of the engine's
+    /// (see [FloatingBuilder::wait]).
+    pub wait: Option<WaitStrategy>,
+    /// When the hooks publish the first measurement of the element, e.g.,
+    /// [MeasureAfter::Visible] for an element pre-rendered with `display: none` that is
+    /// shown later by a class toggle.
+    pub measure_after: MeasureAfter,
+    /// Measures the size of the element before CSS transforms instead of its bounding
+    /// rect, e.g., for an entry animation (`@starting-style` or a transition) that scales
+    /// it in from 0.95: the first measurement would otherwise see the scaled size and
+    /// misalign Center placements by a few pixels until a later one.
+    ///
+    /// - `false` (the default): the size of the bounding rect of the element
+    ///   (`getBoundingClientRect()`), transforms included.
+    /// - `true`: its layout size (`offsetWidth` and `offsetHeight`), which ignores
+    ///   transforms and is rounded to whole pixels, around the center of the bounding rect.
+    ///   Only the web renderer reports it; other renderers and elements without one (e.g.,
+    ///   SVG) keep the bounding rect.
+    ///
+    /// The trigger (or point and range) and the boundary are always measured with their
+    /// bounding rects.
+    pub measure_untransformed: bool,
+    /// Keep [FloatingResult::content_ready] unset until the content of the element reports
+    /// its final content through [crate::FloatingContentReady] (e.g., an async component
+    /// behind a suspense boundary) and the element has been measured again with it.
+    pub wait_for_content: bool,
+    /// The [Boundary] Flip and Shift keep the element in horizontally. The hooks combine
+    /// the horizontal extent of this one with the vertical extent of
+    /// [FloatingOptions::boundary_y] into the boundary rect, e.g., for a container that
+    /// only scrolls horizontally and lets elements extend above and below itself.
+    pub boundary_x: Boundary,
+    /// The [Boundary] Flip and Shift keep the element in vertically, see
+    /// [FloatingOptions::boundary_x].
+    pub boundary_y: Boundary,
+    /// The space Flip and Shift keep clear inside each edge of the boundary, in pixels or
+    /// relative to the boundary size, see [Padding]. Unlike [FloatingOptions::padding],
+    /// it moves the edges the element is kept in; negative values move them outwards.
+    pub boundary_padding: Padding,
+    /// A last-resort guard: at least this many pixels of the element stay inside the
+    /// boundary on each axis (all of it along an axis where it is smaller), whatever the
+    /// middleware do. Applied after them, it overrides the Shift tether that keeps the
+    /// element next to a trigger scrolled out of view; [PlacementOutput::min_visible_engaged]
+    /// reports it. `0` (default) disables it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Floating, FloatingOptions, Offset, Placement};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let element = rect(0.0, 0.0, 200.0, 100.0);
+    /// // scrolled out to the left: the tether keeps the menu beside it, out of view
+    /// let trigger = rect(-300.0, 100.0, 100.0, 20.0);
+    /// let options = FloatingOptions {
+    ///     placement_offset: Some(Offset::zero()),
+    ///     placement: Placement::BottomStart,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let output = Floating::default().compute_placement(boundary, element, trigger, options.clone());
+    /// assert_eq!(output.x, -200.0);
+    /// assert_eq!(output.visible_ratio, 0.0);
+    ///
+    /// let options = FloatingOptions { min_visible: 40.0, ..options };
+    /// let output = Floating::default().compute_placement(boundary, element, trigger, options);
+    /// assert_eq!(output.x, -160.0);
+    /// assert!(output.min_visible_engaged);
+    /// ```
+    pub min_visible: f64,
+    /// Ignores the boundary, e.g., for a drag-and-drop preview that may travel outside
+    /// the container into a sibling drop zone: Flip, Shift, Hide,
+    /// [FloatingOptions::min_visible] and [FloatingOptions::anchor_to_visible] are skipped
+    /// and the element follows its anchor wherever it goes. [PlacementOutput::available] and
+    /// [PlacementOutput::visible_ratio] still refer to the boundary.
+    ///
+    /// The placement hooks apply a change on the next computation, without measuring
+    /// again: set it while dragging, and the element is kept in the boundary again as
+    /// soon as it is cleared. To only widen the boundary, use a negative
+    /// [FloatingOptions::boundary_padding] instead.
+    pub unbounded: bool,
+    /// Compute the position once, in the coordinates of the scroll content, for elements
+    /// rendered inside the scrolled content that scroll with it, e.g., static annotations.
+    ///
+    /// The placement hooks publish the first measurement with all inputs available and
+    /// then stop: they subscribe to nothing but [FloatingOptions::update], install no
+    /// [AutoUpdate] sources and skip [FloatingOptions::anchor_to_visible] and
+    /// [Middleware::Hide]. The coordinates are relative to the content origin (the origin
+    /// of the scroll container at scroll offset zero): apply them with [Strategy::Absolute]
+    /// (the strategy of the hooks with this option) in a scroll container with
+    /// `position: relative` and no border.
+    pub compute_once: bool,
+    /// Holds the last result while a smooth programmatic scroll of the view is in progress
+    /// (see [crate::ScrollableContext::is_smooth_scrolling]) and measures once when it
+    /// ends, instead of following every intermediate offset.
+    pub freeze_during_smooth_scroll: bool,
+    /// Measures the element again whenever [crate::FloatingUpdate::update] is called, e.g.,
+    /// to re-arm a [FloatingOptions::compute_once] placement after its content changed.
+    #[cfg(feature = "hooks")]
+    pub update: Option<FloatingUpdate>,
+}
+
+impl FloatingOptions {
+    /// Returns `true` if the [Middleware::Flip] strategy is enabled.
+    pub fn can_flip(&self) -> bool {
+        self.middleware.contains(&Middleware::Flip)
+    }
+
+    /// Returns `true` if the [Middleware::Shift] strategy is enabled.
+    pub fn can_shift(&self) -> bool {
+        self.middleware.contains(&Middleware::Shift)
+    }
+
+    /// Internal: Returns `true` if [Middleware::Hide] is enabled.
+    pub(crate) fn can_hide(&self) -> bool {
+        self.middleware.contains(&Middleware::Hide)
+    }
+
+    /// Internal: Returns the allowed placements of [Middleware::Auto], if enabled.
+    fn auto_placements(&self) -> Option<&[Placement]> {
+        self.middleware.iter().find_map(|strategy| match strategy {
+            Middleware::Auto { allowed } => Some(*allowed),
+            _ => None,
+        })
+    }
+
+    /// Internal: Returns the width and corner radius of [Middleware::Arrow], if enabled.
+    fn arrow_options(&self) -> Option<(f64, f64)> {
+        self.middleware.iter().find_map(|strategy| match strategy {
+            Middleware::Arrow {
+                width,
+                corner_radius,
+            } => Some((*width, *corner_radius)),
+            _ => None,
+        })
+    }
+
+    /// Internal: Adds [FloatingOptions::touch_offset_extra] to the gap between the trigger
+    /// and the element if it was opened by touch or pen.
+    fn with_pointer_gap(mut self) -> Self {
+        if matches!(
+            self.open_pointer_type,
+            Some(PointerType::Touch | PointerType::Pen)
+        ) {
+            if let Some(offset) = &mut self.placement_offset {
+                offset.main_axis += self.touch_offset_extra;
+            } else {
+                self.offset
+                    .widen_gap(self.placement, self.touch_offset_extra);
+            }
+        }
+        self
+    }
+
+    /// Internal: Returns the gap between the trigger and the element, and the offset along
+    /// the positive cross axis, of `placement`: from [FloatingOptions::placement_offset],
+    /// or else from the absolute [FloatingOptions::offset].
+    fn offsets_for(&self, placement: Placement) -> (f64, f64) {
+        match self.placement_offset {
+            Some(offset) => (offset.main_axis, offset.cross_axis_for(placement)),
+            None => self.offset.gap_and_shift(placement),
+        }
+    }
+
+    /// Merges region defaults (e.g., [crate::ScrollableView]'s `floating_defaults`) under these options.
+    ///
+    /// - A field of `self` wins if it differs from [FloatingOptions::default], otherwise the
+    ///   field of `defaults` is used. `Option` fields therefore fall back only when `None`.
+    /// - Middleware lists are concatenated, `defaults` first, without duplicates.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::{FloatingOptions, Middleware, Placement};
+    ///
+    /// let defaults = FloatingOptions {
+    ///     middleware: vec![Middleware::Shift],
+    ///     padding: 8.0,
+    ///     placement: Placement::TopCenter,
+    ///     ..Default::default()
+    /// };
+    /// let options = FloatingOptions {
+    ///     middleware: vec![Middleware::Flip, Middleware::Shift],
+    ///     placement: Placement::RightStart,
+    ///     ..Default::default()
+    /// }
+    /// .with_defaults(&defaults);
+    ///
+    /// assert_eq!(options.middleware, vec![Middleware::Shift, Middleware::Flip]);
+    /// assert_eq!(options.padding, 8.0);
+    /// assert_eq!(options.placement, Placement::RightStart);
+    ///
+    /// // a per-call value equal to the default does not override the region
+    /// let options = FloatingOptions::default().with_defaults(&defaults);
+    /// assert_eq!(options.placement, Placement::TopCenter);
+    /// ```
+    pub fn with_defaults(self, defaults: &FloatingOptions) -> FloatingOptions {
+        fn pick<T: PartialEq>(own: T, default: T, base: T) -> T {
+            if own != base { own } else { default }
+        }
+
+        let base = FloatingOptions::default();
+        let mut middleware = defaults.middleware.clone();
+        for strategy in self.middleware {
+            if !middleware.contains(&strategy) {
+                middleware.push(strategy);
+            }
+        }
+
+        FloatingOptions {
+            middleware,
+            offset: pick(self.offset, defaults.offset.clone(), base.offset),
+            placement_offset: self.placement_offset.or(defaults.placement_offset),
+            padding: pick(self.padding, defaults.padding, base.padding),
+            placement: pick(self.placement, defaults.placement, base.placement),
+            flip_axes: pick(self.flip_axes, defaults.flip_axes, base.flip_axes),
+            shift_axes: pick(self.shift_axes, defaults.shift_axes, base.shift_axes),
+            auto_update: pick(self.auto_update, defaults.auto_update, base.auto_update),
+            sticky_axes: pick(self.sticky_axes, defaults.sticky_axes, base.sticky_axes),
+            announce: self.announce.or_else(|| defaults.announce.clone()),
+            preserve_alignment: pick(
+                self.preserve_alignment,
+                defaults.preserve_alignment,
+                base.preserve_alignment,
+            ),
+            open_reason: self.open_reason.or(defaults.open_reason),
+            keyboard_min_visible: pick(
+                self.keyboard_min_visible,
+                defaults.keyboard_min_visible,
+                base.keyboard_min_visible,
+            ),
+            #[cfg(feature = "hooks")]
+            context: self.context.or(defaults.context),
+            anchor_to_visible: pick(
+                self.anchor_to_visible,
+                defaults.anchor_to_visible,
+                base.anchor_to_visible,
+            ),
+            explain: pick(self.explain, defaults.explain, base.explain),
+            open_pointer_type: self.open_pointer_type.or(defaults.open_pointer_type),
+            touch_offset_extra: pick(
+                self.touch_offset_extra,
+                defaults.touch_offset_extra,
+                base.touch_offset_extra,
+            ),
+            debug_label: self.debug_label.or(defaults.debug_label),
+            strategy: pick(self.strategy, defaults.strategy, base.strategy),
+            suppress_transitions: self.suppress_transitions.or(defaults.suppress_transitions),
+            cross_axis_anchor: pick(
+                self.cross_axis_anchor,
+                defaults.cross_axis_anchor,
+                base.cross_axis_anchor,
+            ),
+            clip_to_visual_viewport: pick(
+                self.clip_to_visual_viewport,
+                defaults.clip_to_visual_viewport,
+                base.clip_to_visual_viewport,
+            ),
+            wait: self.wait.or(defaults.wait),
+            measure_after: pick(
+                self.measure_after,
+                defaults.measure_after,
+                base.measure_after,
+            ),
+            measure_untransformed: pick(
+                self.measure_untransformed,
+                defaults.measure_untransformed,
+                base.measure_untransformed,
+            ),
+            wait_for_content: pick(
+                self.wait_for_content,
+                defaults.wait_for_content,
+                base.wait_for_content,
+            ),
+            boundary_x: pick(
+                self.boundary_x,
+                defaults.boundary_x.clone(),
+                base.boundary_x,
+            ),
+            boundary_y: pick(
+                self.boundary_y,
+                defaults.boundary_y.clone(),
+                base.boundary_y,
+            ),
+            boundary_padding: pick(
+                self.boundary_padding,
+                defaults.boundary_padding,
+                base.boundary_padding,
+            ),
+            min_visible: pick(self.min_visible, defaults.min_visible, base.min_visible),
+            unbounded: pick(self.unbounded, defaults.unbounded, base.unbounded),
+            compute_once: pick(self.compute_once, defaults.compute_once, base.compute_once),
+            freeze_during_smooth_scroll: pick(
+                self.freeze_during_smooth_scroll,
+                defaults.freeze_during_smooth_scroll,
+                base.freeze_during_smooth_scroll,
+            ),
+            #[cfg(feature = "hooks")]
+            update: self.update.or(defaults.update),
+        }
+    }
+}
+
+impl Default for FloatingOptions {
+    /// Returns default options: [Middleware::Flip] on the main axis and [Middleware::Shift]
+    /// on the cross axis, offset: 1.0, padding: 0.0, and [Placement::BottomStart].
+    fn default() -> Self {
+        FloatingOptions {
+            middleware: vec![Middleware::Flip, Middleware::Shift],
+            offset: Default::default(),
+            placement_offset: None,
+            padding: 0_f64,
+            placement: Placement::BottomStart,
+            flip_axes: Axes::Main,
+            shift_axes: Axes::Cross,
+            auto_update: AutoUpdate::default(),
+            sticky_axes: (false, false),
+            announce: None,
+            preserve_alignment: true,
+            open_reason: None,
+            keyboard_min_visible: 96_f64,
+            #[cfg(feature = "hooks")]
+            context: None,
+            anchor_to_visible: false,
+            explain: false,
+            open_pointer_type: None,
+            touch_offset_extra: 0_f64,
+            debug_label: None,
+            strategy: Strategy::Fixed,
+            suppress_transitions: None,
+            cross_axis_anchor: AnchorSource::Trigger,
+            clip_to_visual_viewport: false,
+            wait: None,
+            measure_after: MeasureAfter::Mount,
+            measure_untransformed: false,
+            wait_for_content: false,
+            boundary_x: Boundary::ScrollContext,
+            boundary_y: Boundary::ScrollContext,
+            boundary_padding: Padding::default(),
+            min_visible: 0_f64,
+            unbounded: false,
+            compute_once: false,
+            freeze_during_smooth_scroll: false,
+            #[cfg(feature = "hooks")]
+            update: None,
+        }
+    }
 }
 
-/// Strategic logic used to adjust the floating position when it overflows the viewport.
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum Middleware {
-    /// Flips the element to the opposite side if there isn't enough space (e.g., Top -> Bottom).
-    Flip,
-    /// Shifts the element along the transverse axis to keep it within the viewport.
-    Shift,
+/// The detailed result of a synchronous placement calculation.
+///
+/// Returned by [Floating::compute_placement] and the `compute_on_*` methods.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlacementOutput {
+    /// Calculated X coordinate (viewport-relative pixels).
+    pub x: f64,
+    /// Calculated Y coordinate (viewport-relative pixels).
+    pub y: f64,
+    /// The resolved [Placement] after middleware (e.g., after a Flip).
+    pub placement: Placement,
+    /// Distance the Shift middleware moved the element on each axis.
+    pub shift: PixelsVector2D,
+    /// Space available to the element on the resolved side: between the trigger (plus
+    /// the offset) and the boundary on the main axis, the boundary size on the cross axis.
+    pub available: PixelsSize,
+    /// The trigger rect the position was computed from.
+    pub trigger_rect: PixelsRect,
+    /// The size of the element the position was computed for.
+    pub element_size: PixelsSize,
+    /// `true` if a flip changed the alignment (e.g., BottomStart -> TopEnd), so the
+    /// element jumps on the cross axis; animate it instead of moving it instantly.
+    pub alignment_changed: bool,
+    /// Fraction (0..1) of the element area inside the boundary at the final position.
+    pub visible_ratio: f64,
+    /// `true` if the element should be hidden: with [FloatingOptions::anchor_to_visible]
+    /// or [Middleware::Hide], no part of the trigger is inside the boundary.
+    pub is_hidden: bool,
+    /// `true` if only a part of the trigger is inside the boundary, with [Middleware::Hide].
+    pub is_trigger_clipped: bool,
+    /// The decision trail of the pipeline, filled only with [FloatingOptions::explain].
+    pub steps: Vec<PlacementStep>,
+    /// `true` if the element at its final position covers part of the trigger, e.g.,
+    /// after a flip whose overflow was clamped by a main-axis Shift.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Axes, Floating, FloatingOptions, Offset, Placement};
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    /// let element = rect(0.0, 0.0, 200.0, 300.0);
+    /// let trigger = rect(100.0, 260.0, 80.0, 20.0);
+    /// let options = FloatingOptions {
+    ///     placement_offset: Some(Offset::zero()),
+    ///     placement: Placement::BottomStart,
+    ///     shift_axes: Axes::Both,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // fits neither below nor above: flipped up where it overflows less, then shifted
+    /// // down over the trigger
+    /// let output = Floating::default().compute_placement(boundary, element, trigger, options);
+    /// assert_eq!(output.placement, Placement::TopStart);
+    /// assert!(output.overlaps_trigger);
+    /// assert_eq!(output.trigger_overlap, Some(trigger));
+    ///
+    /// // enough room below
+    /// let output = Floating::default().compute_placement(boundary, element, rect(100.0, 100.0, 80.0, 20.0), FloatingOptions::default());
+    /// assert!(!output.overlaps_trigger);
+    /// assert_eq!(output.trigger_overlap, None);
+    /// ```
+    pub overlaps_trigger: bool,
+    /// The part of the trigger covered by the element, see [PlacementOutput::overlaps_trigger].
+    pub trigger_overlap: Option<PixelsRect>,
+    /// `true` if [FloatingOptions::min_visible] moved the element back into the boundary
+    /// because the middleware left less of it visible.
+    pub min_visible_engaged: bool,
+    /// The largest width the element may have, with [Middleware::Size].
+    pub max_width: Option<f64>,
+    /// The largest height the element may have, with [Middleware::Size].
+    pub max_height: Option<f64>,
+    /// Where the arrow goes on the element at its final position, with
+    /// [Middleware::Arrow].
+    pub arrow: Option<ArrowPosition>,
 }
 
-/// Offset options for the floating element.
+/// One step of the placement pipeline, see [FloatingOptions::explain].
 #[derive(Debug, Clone, PartialEq)]
-pub struct OffsetOptions {
-    /// Offset along the main axis. (Vertical, X)
-    pub main_axis: f64,
-    /// Offset along the cross axis. (Horizontal, Y)
-    pub cross_axis: f64,
+pub struct PlacementStep {
+    /// Name of the step: `auto`, `size`, `base`, `padding`, `unbounded`, `flip`,
+    /// `flip-alignment`, `shift-cross`, `shift-main`, `min-visible` or `rounding`.
+    pub name: &'static str,
+    /// Coordinates before the step.
+    pub before: (f64, f64),
+    /// Coordinates after the step (equal to `before` if the step did nothing).
+    pub after: (f64, f64),
+    /// The values the step decided on, e.g., the overflow that triggered a flip.
+    pub reason: String,
 }
 
-impl Default for OffsetOptions {
-    fn default() -> Self {
-        Self {
-            main_axis: 1_f64,
-            cross_axis: 1_f64,
-        }
+/// Internal: Records a pipeline step if [FloatingOptions::explain] is set.
+///
+/// The reason is formatted lazily, so nothing is allocated when explaining is off.
+fn explain(
+    steps: &mut Vec<PlacementStep>,
+    options: &FloatingOptions,
+    name: &'static str,
+    before: (f64, f64),
+    after: (f64, f64),
+    reason: impl FnOnce() -> String,
+) {
+    if options.explain {
+        steps.push(PlacementStep {
+            name,
+            before,
+            after,
+            reason: reason(),
+        });
     }
 }
 
-impl OffsetOptions {
-    /// Creates a new [OffsetOptions] with the specified offsets.
-    pub fn new(main_axis: f64, cross_axis: f64) -> Self {
-        Self {
-            main_axis,
-            cross_axis,
-        }
+impl PlacementOutput {
+    /// Returns the position of the trigger relative to the element, e.g., to point an
+    /// arrow at it regardless of how the element was aligned.
+    pub fn trigger_offset(&self) -> PixelsVector2D {
+        self.trigger_rect.origin - PixelsVector2D::new(self.x, self.y).to_point()
     }
 
-    /// Creates a new [OffsetOptions] with the same offset for both axes.
-    pub fn rect(offset: f64) -> Self {
-        Self {
-            main_axis: offset,
-            cross_axis: offset,
+    /// Returns the shift along the main axis of the resolved placement.
+    ///
+    /// Non-zero only when [Middleware::Shift] acts on [Axes::Main]; the absolute
+    /// value grows as the element slides towards (or over) its trigger.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::{Axes, Floating, FloatingOptions, Middleware, Offset, Placement};
+    ///
+    /// let rect = |x, y, w, h| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let scrollable = rect(0.0, 0.0, 200.0, 200.0);
+    /// let element = rect(0.0, 0.0, 100.0, 50.0);
+    /// let near_top = rect(50.0, 10.0, 40.0, 20.0);
+    /// let near_bottom = rect(50.0, 170.0, 40.0, 20.0);
+    /// let options = |placement| FloatingOptions {
+    ///     middleware: vec![Middleware::Shift],
+    ///     shift_axes: Axes::Both,
+    ///     placement_offset: Some(Offset::zero()),
+    ///     placement,
+    ///     ..Default::default()
+    /// };
+    /// let floating = Floating::default();
+    ///
+    /// // Top* near the top edge slides down over the trigger instead of flipping
+    /// let out = floating.compute_placement(scrollable, element, near_top, options(Placement::TopStart));
+    /// assert_eq!((out.y, out.main_axis_shift()), (0.0, 40.0));
+    /// // Top* near the bottom edge fits
+    /// let out = floating.compute_placement(scrollable, element, near_bottom, options(Placement::TopStart));
+    /// assert_eq!((out.y, out.main_axis_shift()), (120.0, 0.0));
+    /// // Bottom* near the bottom edge slides up
+    /// let out = floating.compute_placement(scrollable, element, near_bottom, options(Placement::BottomStart));
+    /// assert_eq!((out.y, out.main_axis_shift()), (150.0, -40.0));
+    /// // Bottom* near the top edge fits
+    /// let out = floating.compute_placement(scrollable, element, near_top, options(Placement::BottomStart));
+    /// assert_eq!((out.y, out.main_axis_shift()), (30.0, 0.0));
+    /// ```
+    pub fn main_axis_shift(&self) -> f64 {
+        if self.placement.is_vertical() {
+            self.shift.y
+        } else {
+            self.shift.x
         }
     }
 
-    /// Creates a new [OffsetOptions] with zero offset for both axes.
-    pub fn zero() -> Self {
-        Self::rect(0_f64)
+    /// Returns the shift along the cross axis of the resolved placement.
+    pub fn cross_axis_shift(&self) -> f64 {
+        if self.placement.is_vertical() {
+            self.shift.x
+        } else {
+            self.shift.y
+        }
     }
 }
 
-/// Configuration for the floating position calculation.
-#[derive(Debug, Clone)]
-pub struct FloatingOptions {
-    /// List of [Middleware] strategies to apply.
-    pub middleware: Vec<Middleware>,
-    /// Distance between the trigger and the floating element in pixels.
-    pub offset: OffsetOptions,
-    /// Distance between the floating element and the scrollable container edges.
-    pub padding: f64,
-    /// The preferred [Placement] strategy.
-    pub placement: Placement,
-}
+impl Floating {
+    /// Returns a [FloatingBuilder] starting from the default configuration.
+    pub fn builder() -> FloatingBuilder {
+        FloatingBuilder::default()
+    }
 
-impl FloatingOptions {
-    /// Returns `true` if the [Middleware::Flip] strategy is enabled.
-    pub fn can_flip(&self) -> bool {
-        self.middleware.contains(&Middleware::Flip)
+    /// Returns the default [WaitStrategy] of this engine.
+    pub fn wait_strategy(&self) -> WaitStrategy {
+        self.wait
     }
 
-    /// Returns `true` if the [Middleware::Shift] strategy is enabled.
-    pub fn can_shift(&self) -> bool {
-        self.middleware.contains(&Middleware::Shift)
+    /// Returns the [Rounding] mode of this engine.
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
     }
-}
 
-impl Default for FloatingOptions {
-    /// Returns default options: [Middleware::Flip] and [Middleware::Shift] enabled,
-    /// offset: 1.0, padding: 0.0, and [Placement::BottomStart].
-    fn default() -> Self {
-        FloatingOptions {
-            middleware: vec![Middleware::Flip, Middleware::Shift],
-            offset: OffsetOptions::default(),
-            padding: 0_f64,
-            placement: Placement::BottomStart,
-        }
+    /// Returns the logging label of this engine.
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
     }
-}
 
-impl Floating {
-    /// Asynchronously captures the initial [ScrollState] from a mounted element.
-    ///
-    /// This method is usually called once when the [ScrollableView] is first mounted
-    /// or when its underlying DOM element changes. It performs multiple async
-    /// JS calls to measure the layout.
-    ///
-    /// Returns a default state (zeros) if the element is no longer accessible.
-    pub async fn generate_scroll_state_from_mounted(&self, data: Rc<MountedData>) -> ScrollState {
-        let rect = data.get_client_rect().await;
-        let scroll = data.get_scroll_size().await;
-        let offset = data.get_scroll_offset().await;
-
-        let size = scroll
-            .map(|s| PixelsSize::new(s.width, s.height))
-            .unwrap_or(PixelsSize::new(0_f64, 0_f64));
-        let bounds = rect
-            .map(|r| PixelsSize::new(r.width(), r.height()))
-            .unwrap_or(PixelsSize::new(0_f64, 0_f64));
-        let state = offset
-            .map(|o| PixelsVector2D::new(o.x, o.y))
-            .unwrap_or(PixelsVector2D::new(0_f64, 0_f64));
+    /// Returns the per-frame measurement budget, see [FloatingBuilder::frame_budget].
+    pub fn frame_budget(&self) -> Option<f64> {
+        self.frame_budget
+    }
 
-        ScrollState {
-            size,
-            bounds,
-            state,
-        }
+    /// Returns the duration a single measurement should stay under, see
+    /// [FloatingBuilder::slow_measurement_budget].
+    pub fn slow_measurement_budget(&self) -> f64 {
+        self.slow_measurement_budget
     }
 
     /// Synchronously generates a new [ScrollState] from a [ScrollEvent].
@@ -239,6 +2564,7 @@ impl Floating {
     /// #    }
     /// # }
     /// ```
+    #[cfg(feature = "dioxus")]
     pub fn generate_scroll_state(&self, evt: ScrollEvent) -> ScrollState {
         ScrollState {
             size: PixelsSize::new(evt.scroll_width() as f64, evt.scroll_height() as f64),
@@ -247,119 +2573,80 @@ impl Floating {
         }
     }
 
-    /// Calculates the optimal position for a floating element anchored to a specific point (e.g., a mouse click).
+    /// Merges a partial update into an existing [ScrollState].
     ///
-    /// This method treats the input [ClientPoint] as a 1x1 pixel trigger. It is ideal for
-    /// context menus where the anchor position is dynamic and precise.
-    ///
-    /// The returned coordinates (X, Y) are relative to the viewport and are ready
-    /// for use with `position: fixed` and `transform: translate3d`.
-    pub async fn placement_on_point(
+    /// Useful for custom scroll implementations (drag-to-scroll, wheel conversion) that
+    /// know only part of the state and want to publish a consistent one without re-measuring.
+    /// Missing parts of a missing state are zero.
+    pub fn merge_scroll_state(
         &self,
-        scroll_state: ScrollState,
-        scrollable_ref: Rc<MountedData>,
-        element_ref: Rc<MountedData>,
-        trigger: ClientPoint,
-        options: FloatingOptions,
-    ) -> (f64, f64) {
-        let scrollable_rect = scrollable_ref
-            .get_client_rect()
-            .await
-            .unwrap_or(PixelsRect::new(
-                PixelsVector2D::new(0_f64, 0_f64).to_point(),
-                scroll_state.bounds,
-            ));
-        let trigger_rect = PixelsRect::new(
-            PixelsVector2D::new(trigger.x, trigger.y).to_point(),
-            PixelsSize::new(1_f64, 1_f64),
-        );
+        old: Option<ScrollState>,
+        partial: PartialScrollState,
+    ) -> ScrollState {
+        let old = old.unwrap_or(ScrollState {
+            size: PixelsSize::zero(),
+            bounds: PixelsSize::zero(),
+            state: PixelsVector2D::zero(),
+        });
 
-        match element_ref.get_client_rect().await {
-            Ok(element_rect) => {
-                self.calculate_placement(scrollable_rect, element_rect, trigger_rect, options)
-            }
-            Err(_) => (trigger_rect.min_x(), trigger_rect.min_y()),
-        }
-    }
-
-    /// Calculates the optimal position for a floating element anchored to another DOM element (e.g., a button).
-    ///
-    /// This method measures the actual dimensions of the trigger element via `get_client_rect()`.
-    /// It is designed for standard dropdown menus, tooltips, and popovers where
-    /// the floating element needs to align perfectly with its anchor.
-    ///
-    /// The returned coordinates (X, Y) are viewport-relative.
-    pub async fn placement_on_trigger(
-        &self,
-        scroll_state: ScrollState,
-        scrollable_ref: Rc<MountedData>,
-        element_ref: Rc<MountedData>,
-        trigger_ref: Rc<MountedData>,
-        options: FloatingOptions,
-    ) -> (f64, f64) {
-        let scrollable_rect = scrollable_ref
-            .get_client_rect()
-            .await
-            .unwrap_or(PixelsRect::new(
-                PixelsVector2D::new(0_f64, 0_f64).to_point(),
-                scroll_state.bounds,
-            ));
-        let trigger_rect = trigger_ref
-            .get_client_rect()
-            .await
-            .unwrap_or(PixelsRect::new(
-                PixelsVector2D::new(0_f64, 0_f64).to_point(),
-                PixelsSize::new(1_f64, 1_f64),
-            ));
-
-        match element_ref.get_client_rect().await {
-            Ok(element_rect) => {
-                self.calculate_placement(scrollable_rect, element_rect, trigger_rect, options)
-            }
-            Err(_) => (trigger_rect.min_x(), trigger_rect.min_y()),
+        ScrollState {
+            size: partial.size.unwrap_or(old.size),
+            bounds: partial.bounds.unwrap_or(old.bounds),
+            state: partial.state.unwrap_or(old.state),
         }
     }
 
     /// Internal: Computes the initial (ideal) coordinates for the floating element
     /// without considering viewport boundaries or middleware.
+    ///
+    /// The boundary is only used with [AnchorSource::Boundary] on the cross axis.
     fn compute_base_coords(
         &self,
         element: PixelsRect,
         trigger: PixelsRect,
+        boundary: PixelsRect,
         options: FloatingOptions,
     ) -> (f64, f64) {
         let x: f64;
         let y: f64;
 
+        // the rect the cross axis aligns to
+        let cross = match options.cross_axis_anchor {
+            AnchorSource::Trigger => trigger,
+            AnchorSource::Boundary => boundary.inflate(-options.padding, -options.padding),
+        };
+
+        let (gap, shift) = options.offsets_for(options.placement);
         // make basic placement element position
         (x, y) = if options.placement.is_vertical() {
             let x = match options.placement.get_modifier() {
+                // a single division, so the offset from the start is the same at any position
                 PlacementModifier::Center => {
-                    trigger.min_x() + (trigger.width() / 2_f64) - (element.width() / 2_f64)
+                    cross.min_x() + (cross.width() - element.width()) / 2_f64
                 }
-                PlacementModifier::Start => trigger.min_x(),
-                PlacementModifier::End => trigger.max_x() - element.width(),
+                PlacementModifier::Start => cross.min_x(),
+                PlacementModifier::End => cross.max_x() - element.width(),
             };
             let y = if options.placement.is_top() {
-                trigger.min_y() - element.height() - options.offset.cross_axis
+                trigger.min_y() - element.height() - gap
             } else {
-                trigger.max_y() + options.offset.cross_axis
+                trigger.max_y() + gap
             };
-            (x + options.offset.main_axis, y)
+            (x + shift, y)
         } else {
             let x = if options.placement.is_left() {
-                trigger.min_x() - element.width() - options.offset.main_axis
+                trigger.min_x() - element.width() - gap
             } else {
-                trigger.max_x() + options.offset.main_axis
+                trigger.max_x() + gap
             };
             let y = match options.placement.get_modifier() {
                 PlacementModifier::Center => {
-                    trigger.min_y() + (trigger.height() / 2_f64) - (element.height() / 2_f64)
+                    cross.min_y() + (cross.height() - element.height()) / 2_f64
                 }
-                PlacementModifier::Start => trigger.min_y(),
-                PlacementModifier::End => trigger.max_y() - element.height(),
+                PlacementModifier::Start => cross.min_y(),
+                PlacementModifier::End => cross.max_y() - element.height(),
             };
-            (x, y + options.offset.cross_axis)
+            (x, y + shift)
         };
 
         (x, y)
@@ -374,26 +2661,151 @@ impl Floating {
         element: PixelsRect,
         trigger: PixelsRect,
         options: FloatingOptions,
-    ) -> (f64, f64) {
+        steps: &mut Vec<PlacementStep>,
+    ) -> PlacementOutput {
         let (mut x, mut y) = initial_pos;
+        let vertical = options.placement.is_vertical();
+        let mut placement = options.placement;
+
+        // the visible ratio is measured against the whole boundary
+        let boundary = scrollable;
+        let scrollable = options.boundary_padding.inset(boundary);
+        if options.boundary_padding != Padding::default() {
+            explain(steps, &options, "padding", (x, y), (x, y), || {
+                format!(
+                    "resolved = {:?}, boundary = {scrollable:?}",
+                    options.boundary_padding.resolve(boundary.size)
+                )
+            });
+        }
+
+        // nothing derived from the boundary moves an unbounded element
+        let is_bounded = !options.unbounded;
+        if options.unbounded {
+            explain(steps, &options, "unbounded", (x, y), (x, y), || {
+                "flip, shift and min_visible skipped".to_string()
+            });
+        }
+
+        // how far an extent from `start` sticks out of `min..max`, on both ends
+        let overflow = |start: f64, size: f64, min: f64, max: f64| {
+            (min - start).max(0_f64) + (start + size - max).max(0_f64)
+        };
 
-        // flip middleware
-        if options.can_flip() {
-            if options.placement.is_vertical() {
-                if options.placement.is_top() && y < scrollable.min_y() {
-                    y = trigger.max_y() + options.offset.cross_axis;
-                } else if !options.placement.is_top() && y + element.height() > scrollable.max_y() {
-                    y = trigger.min_y() - element.height() - options.offset.cross_axis;
+        // flip middleware: the opposite side only if the element overflows less there, so
+        // an element that fits on neither side stays on the roomier one
+        if is_bounded && options.can_flip() && options.flip_axes.main() {
+            let before = (x, y);
+            if options.auto_placements().is_some() {
+                explain(steps, &options, "flip", before, (x, y), || {
+                    "skipped: the side was chosen by Middleware::Auto".to_string()
+                });
+            } else if self.keeps_side(x, y, scrollable, element, &options) {
+                explain(steps, &options, "flip", before, (x, y), || {
+                    format!(
+                        "skipped: keyboard-opened, {} px fit on the preferred side",
+                        options.keyboard_min_visible
+                    )
+                });
+            } else if vertical {
+                let (gap, _) = options.offsets_for(options.placement);
+                let flipped_y = if options.placement.is_top() {
+                    trigger.max_y() + gap
+                } else {
+                    trigger.min_y() - element.height() - gap
+                };
+                let (min, max) = (scrollable.min_y(), scrollable.max_y());
+                let current = overflow(y, element.height(), min, max);
+                let flipped = overflow(flipped_y, element.height(), min, max);
+                if flipped < current {
+                    y = flipped_y;
+                    placement = options.placement.opposite();
+                }
+                explain(steps, &options, "flip", before, (x, y), || {
+                    format!(
+                        "y = {}, overflow = {current}, flipped overflow = {flipped}, boundary y = {min}..{max}",
+                        before.1
+                    )
+                });
+            } else {
+                let (gap, _) = options.offsets_for(options.placement);
+                let flipped_x = if options.placement.is_left() {
+                    trigger.max_x() + gap
+                } else {
+                    trigger.min_x() - element.width() - gap
+                };
+                let (min, max) = (scrollable.min_x(), scrollable.max_x());
+                let current = overflow(x, element.width(), min, max);
+                let flipped = overflow(flipped_x, element.width(), min, max);
+                if flipped < current {
+                    x = flipped_x;
+                    placement = options.placement.opposite();
                 }
-            } else if options.placement.is_left() && x < scrollable.min_x() {
-                x = trigger.max_x() + options.offset.main_axis;
-            } else if !options.placement.is_left() && x + element.width() > scrollable.max_x() {
-                x = trigger.min_x() - element.width() - options.offset.main_axis;
+                explain(steps, &options, "flip", before, (x, y), || {
+                    format!(
+                        "x = {}, overflow = {current}, flipped overflow = {flipped}, boundary x = {min}..{max}",
+                        before.0
+                    )
+                });
+            }
+        }
+        // a boundary-anchored cross axis is already at the boundary
+        let is_cross_anchored = options.cross_axis_anchor == AnchorSource::Trigger;
+        if is_bounded && options.can_flip() && options.flip_axes.cross() && is_cross_anchored {
+            let swapped = options.placement.opposite_alignment();
+            if swapped != options.placement {
+                let (swapped_x, swapped_y) = self.compute_base_coords(
+                    element,
+                    trigger,
+                    scrollable,
+                    FloatingOptions {
+                        placement: swapped,
+                        ..options.clone()
+                    },
+                );
+                // swap alignment only if the current one overflows and the swapped one fits
+                // (or, without preserve_alignment, overflows less)
+                let should_swap = |current: f64, swapped: f64| {
+                    current > 0_f64
+                        && if options.preserve_alignment {
+                            swapped == 0_f64
+                        } else {
+                            swapped < current
+                        }
+                };
+                let before = (x, y);
+                let (current, swapped) = if vertical {
+                    let (min, max) = (scrollable.min_x(), scrollable.max_x());
+                    let current = overflow(x, element.width(), min, max);
+                    let swapped = overflow(swapped_x, element.width(), min, max);
+                    if should_swap(current, swapped) {
+                        x = swapped_x;
+                        placement = placement.opposite_alignment();
+                    }
+                    (current, swapped)
+                } else {
+                    let (min, max) = (scrollable.min_y(), scrollable.max_y());
+                    let current = overflow(y, element.height(), min, max);
+                    let swapped = overflow(swapped_y, element.height(), min, max);
+                    if should_swap(current, swapped) {
+                        y = swapped_y;
+                        placement = placement.opposite_alignment();
+                    }
+                    (current, swapped)
+                };
+                explain(steps, &options, "flip-alignment", before, (x, y), || {
+                    format!(
+                        "overflow = {current}, swapped overflow = {swapped}, preserve_alignment = {}",
+                        options.preserve_alignment
+                    )
+                });
             }
         }
         // shift middleware
-        if options.can_shift() {
-            if options.placement.is_vertical() {
+        let (shift_start_x, shift_start_y) = (x, y);
+        if is_bounded && options.can_shift() && options.shift_axes.cross() && is_cross_anchored {
+            let before = (x, y);
+            if vertical {
                 // Вычисляем границы: насколько далеко мы можем уйти влево или вправо,
                 // чтобы не оторваться от триггера.
                 let min_allowed_x = trigger.min_x() - element.width() + options.padding;
@@ -422,33 +2834,490 @@ impl Floating {
 
                 y = y.clamp(min_allowed_y, max_allowed_y);
             }
+            explain(steps, &options, "shift-cross", before, (x, y), || {
+                if vertical {
+                    format!(
+                        "x = {}, width = {}, boundary x = {}..{}, tether x = {}..{}",
+                        before.0,
+                        element.width(),
+                        scrollable.min_x(),
+                        scrollable.max_x(),
+                        trigger.min_x() - element.width() + options.padding,
+                        trigger.max_x() - options.padding
+                    )
+                } else {
+                    format!(
+                        "y = {}, height = {}, boundary y = {}..{}, tether y = {}..{}",
+                        before.1,
+                        element.height(),
+                        scrollable.min_y(),
+                        scrollable.max_y(),
+                        trigger.min_y() - element.height() + options.padding,
+                        trigger.max_y() - options.padding
+                    )
+                }
+            });
+        }
+        if is_bounded && options.can_shift() && options.shift_axes.main() {
+            let before = (x, y);
+            // no tether on the main axis: the element may slide over the trigger
+            if vertical {
+                if y < scrollable.min_y() {
+                    y = scrollable.min_y();
+                }
+                if y + element.height() > scrollable.max_y() {
+                    y = scrollable.max_y() - element.height();
+                }
+            } else {
+                if x < scrollable.min_x() {
+                    x = scrollable.min_x();
+                }
+                if x + element.width() > scrollable.max_x() {
+                    x = scrollable.max_x() - element.width();
+                }
+            }
+            explain(steps, &options, "shift-main", before, (x, y), || {
+                format!("boundary: {scrollable:?}, element size: {:?}", element.size)
+            });
         }
 
-        (x, y)
+        // the last resort, over every tether
+        let mut min_visible_engaged = false;
+        if is_bounded && options.min_visible > 0_f64 {
+            let before = (x, y);
+            let keep_visible = |start: f64, extent: f64, min: f64, max: f64| {
+                let visible = options.min_visible.min(extent).min(max - min).max(0_f64);
+                start.clamp(min - extent + visible, max - visible)
+            };
+            x = keep_visible(x, element.width(), scrollable.min_x(), scrollable.max_x());
+            y = keep_visible(y, element.height(), scrollable.min_y(), scrollable.max_y());
+            min_visible_engaged = (x, y) != before;
+            explain(steps, &options, "min-visible", before, (x, y), || {
+                format!(
+                    "min visible = {}, boundary: {scrollable:?}, element size: {:?}",
+                    options.min_visible, element.size
+                )
+            });
+        }
+
+        PlacementOutput {
+            x,
+            y,
+            placement,
+            shift: PixelsVector2D::new(x - shift_start_x, y - shift_start_y),
+            alignment_changed: placement != options.placement
+                && placement != options.placement.opposite(),
+            available: self.available_space(placement, scrollable, trigger, &options),
+            trigger_rect: trigger,
+            element_size: element.size,
+            visible_ratio: self.visible_ratio(
+                PixelsRect::new(PixelsVector2D::new(x, y).to_point(), element.size),
+                boundary,
+            ),
+            is_hidden: false,
+            is_trigger_clipped: false,
+            steps: Vec::new(),
+            overlaps_trigger: false,
+            trigger_overlap: None,
+            min_visible_engaged,
+            max_width: None,
+            max_height: None,
+            arrow: None,
+        }
+    }
+
+    /// Returns the fraction (0..1) of the `element` area that lies inside the `boundary`.
+    ///
+    /// The boundary is used as given, so pass the adjusted one (see
+    /// [crate::ScrollableContext::adjust_boundary]) to account for insets.
+    /// A zero-area element counts as fully visible if its origin is inside the boundary.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::Floating;
+    ///
+    /// let rect = |x: f64, y: f64, w: f64, h: f64| {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// };
+    /// let floating = Floating::default();
+    /// let boundary = rect(0.0, 0.0, 500.0, 500.0);
+    ///
+    /// // fully inside
+    /// assert_eq!(floating.visible_ratio(rect(10.0, 10.0, 100.0, 50.0), boundary), 1.0);
+    /// // clipped by the right edge by a quarter
+    /// assert_eq!(floating.visible_ratio(rect(425.0, 10.0, 100.0, 50.0), boundary), 0.75);
+    /// // fully outside
+    /// assert_eq!(floating.visible_ratio(rect(600.0, 10.0, 100.0, 50.0), boundary), 0.0);
+    /// ```
+    pub fn visible_ratio(&self, element: PixelsRect, boundary: PixelsRect) -> f64 {
+        let area = element.area();
+        if area <= 0_f64 {
+            return if boundary.contains(element.origin) {
+                1_f64
+            } else {
+                0_f64
+            };
+        }
+
+        element
+            .intersection(&boundary)
+            .map(|visible| (visible.area() / area).clamp(0_f64, 1_f64))
+            .unwrap_or(0_f64)
+    }
+
+    /// Internal: Returns the space available to the element on the side of `placement`.
+    fn available_space(
+        &self,
+        placement: Placement,
+        scrollable: PixelsRect,
+        trigger: PixelsRect,
+        options: &FloatingOptions,
+    ) -> PixelsSize {
+        let (gap, _) = options.offsets_for(placement);
+        let available = if placement.is_vertical() {
+            let height = if placement.is_top() {
+                trigger.min_y() - scrollable.min_y() - gap
+            } else {
+                scrollable.max_y() - trigger.max_y() - gap
+            };
+            PixelsSize::new(scrollable.width(), height)
+        } else {
+            let width = if placement.is_left() {
+                trigger.min_x() - scrollable.min_x() - gap
+            } else {
+                scrollable.max_x() - trigger.max_x() - gap
+            };
+            PixelsSize::new(width, scrollable.height())
+        };
+
+        PixelsSize::new(available.width.max(0_f64), available.height.max(0_f64))
+    }
+
+    /// Internal: Returns the placement [Middleware::Auto] chooses: the allowed one with the
+    /// largest available area, then the smallest cross-axis overflow, the earlier one on
+    /// ties. `None` without the middleware and for unbounded elements.
+    fn auto_placement(
+        &self,
+        scrollable: PixelsRect,
+        element: PixelsRect,
+        trigger: PixelsRect,
+        options: &FloatingOptions,
+    ) -> Option<Placement> {
+        let allowed = options.auto_placements()?;
+        if options.unbounded {
+            return None;
+        }
+
+        let preferred = options.placement;
+        let candidates = if allowed.is_empty() {
+            let side = preferred.side();
+            let across = if preferred.is_vertical() {
+                [Side::Right, Side::Left]
+            } else {
+                [Side::Bottom, Side::Top]
+            };
+            [side, side.opposite(), across[0], across[1]]
+                .map(|side| preferred.on_side(side))
+                .to_vec()
+        } else {
+            allowed.to_vec()
+        };
+        let boundary = options
+            .boundary_padding
+            .inset(scrollable)
+            .inflate(-options.padding, -options.padding);
+        let score = |placement: Placement| {
+            let available = self.available_space(placement, boundary, trigger, options);
+            let (x, y) = self.compute_base_coords(
+                element,
+                trigger,
+                scrollable,
+                FloatingOptions {
+                    placement,
+                    ..options.clone()
+                },
+            );
+            let (start, size, min, max) = if placement.is_vertical() {
+                (x, element.width(), boundary.min_x(), boundary.max_x())
+            } else {
+                (y, element.height(), boundary.min_y(), boundary.max_y())
+            };
+            let overflow = (min - start).max(0_f64) + (start + size - max).max(0_f64);
+            (available.area(), -overflow)
+        };
+
+        candidates
+            .into_iter()
+            .map(|placement| (placement, score(placement)))
+            .reduce(|best, next| if next.1 > best.1 { next } else { best })
+            .map(|(placement, _)| placement)
+    }
+
+    /// Internal: Returns the placement and the largest size of the element with
+    /// [Middleware::Size]: the preferred side, or with [Middleware::Flip] the opposite
+    /// one if the element does not fit and there is more room there. `None` without the
+    /// middleware and for unbounded elements.
+    fn size_constraint(
+        &self,
+        scrollable: PixelsRect,
+        element: PixelsRect,
+        trigger: PixelsRect,
+        options: &FloatingOptions,
+    ) -> Option<(Placement, PixelsSize)> {
+        if !options.middleware.contains(&Middleware::Size) || options.unbounded {
+            return None;
+        }
+
+        let boundary = options
+            .boundary_padding
+            .inset(scrollable)
+            .inflate(-options.padding, -options.padding);
+        let preferred = options.placement;
+        let main = |size: PixelsSize| {
+            if preferred.is_vertical() {
+                size.height
+            } else {
+                size.width
+            }
+        };
+        let room = |placement| self.available_space(placement, boundary, trigger, options);
+        let needed = main(element.size);
+        let preferred_room = main(room(preferred));
+        // the side was chosen already, or a keyboard-opened element fits well enough
+        let keeps_side = !options.can_flip()
+            || !options.flip_axes.main()
+            || options.auto_placements().is_some()
+            || (options.open_reason == Some(OpenReason::Keyboard)
+                && preferred_room >= options.keyboard_min_visible.min(needed));
+        let placement = if !keeps_side
+            && needed > preferred_room
+            && main(room(preferred.opposite())) > preferred_room
+        {
+            preferred.opposite()
+        } else {
+            preferred
+        };
+
+        Some((placement, room(placement)))
+    }
+
+    /// Internal: Returns `true` if a keyboard-opened element keeps its preferred side
+    /// because enough of it fits there along the main axis.
+    fn keeps_side(
+        &self,
+        x: f64,
+        y: f64,
+        scrollable: PixelsRect,
+        element: PixelsRect,
+        options: &FloatingOptions,
+    ) -> bool {
+        if options.open_reason != Some(OpenReason::Keyboard) {
+            return false;
+        }
+
+        let (start, extent, min, max) = if options.placement.is_vertical() {
+            (y, element.height(), scrollable.min_y(), scrollable.max_y())
+        } else {
+            (x, element.width(), scrollable.min_x(), scrollable.max_x())
+        };
+        let visible = (start + extent).min(max) - start.max(min);
+
+        visible >= options.keyboard_min_visible.min(extent)
+    }
+
+    /// Returns the coordinates of [Floating::compute_placement].
+    ///
+    /// Kept with its original signature for existing code, see `examples/migration.rs`.
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `compute_placement` with the same arguments and read `x` and `y` of the returned `PlacementOutput`"
+    )]
+    pub fn calculate_placement(
+        &self,
+        scrollable: PixelsRect,
+        element: PixelsRect,
+        trigger: PixelsRect,
+        options: FloatingOptions,
+    ) -> (f64, f64) {
+        let output = self.compute_placement(scrollable, element, trigger, options);
+
+        (output.x, output.y)
     }
 
     /// The main entry point for synchronous position calculation.
     ///
     /// This method takes pre-measured rectangles and applies the full positioning
-    /// pipeline: base calculation followed by middleware adjustments.
+    /// pipeline: base calculation followed by middleware adjustments, and returns
+    /// the detailed [PlacementOutput] (coordinates, resolved placement, shift amounts).
     ///
     /// It is useful for manual calculations or when you have already obtained
     /// the necessary [PixelsRect] data.
-    pub fn calculate_placement(
+    pub fn compute_placement(
         &self,
         scrollable: PixelsRect,
         element: PixelsRect,
         trigger: PixelsRect,
         options: FloatingOptions,
-    ) -> (f64, f64) {
-        let base_pos = self.compute_base_coords(element, trigger, options.clone());
-        let final_pos =
-            self.apply_middleware(base_pos, scrollable, element, trigger, options.clone());
+    ) -> PlacementOutput {
+        crate::strict::check_options(&options);
+        // with Hide, the full trigger against the boundary: (hidden, clipped)
+        let can_hide = options.can_hide() && !options.unbounded;
+        let trigger_visibility = can_hide.then(|| match visible_part(trigger, scrollable) {
+            Some(visible) => (false, visible != trigger),
+            None => (true, false),
+        });
+        // anchor to the visible part of the trigger, or hide if nothing is visible
+        let (trigger, is_hidden) = if options.anchor_to_visible && !options.unbounded {
+            match visible_part(trigger, scrollable) {
+                Some(visible) => (visible, false),
+                None => (trigger, true),
+            }
+        } else {
+            (trigger, false)
+        };
+        let mut steps = Vec::new();
+        // the side decides the axis of the pointer gap
+        let options = match self.auto_placement(scrollable, element, trigger, &options) {
+            Some(placement) => {
+                // the coordinates of both sides only for the explanation, see [explain]
+                if options.explain {
+                    let auto = FloatingOptions {
+                        placement,
+                        ..options.clone()
+                    };
+                    let before =
+                        self.compute_base_coords(element, trigger, scrollable, options.clone());
+                    let after = self.compute_base_coords(element, trigger, scrollable, auto);
+                    explain(&mut steps, &options, "auto", before, after, || {
+                        format!(
+                            "preferred = {:?}, chosen = {placement:?}",
+                            options.placement
+                        )
+                    });
+                }
+                FloatingOptions {
+                    placement,
+                    ..options
+                }
+            }
+            None => options,
+        }
+        .with_pointer_gap();
+        // place the element at the size it is limited to
+        let (options, element, max_size) = match self
+            .size_constraint(scrollable, element, trigger, &options)
+        {
+            Some((placement, max_size)) => {
+                let limited = PixelsRect::new(element.origin, element.size.min(max_size));
+                // the coordinates before and after only for the explanation, see [explain]
+                if options.explain {
+                    let sized = FloatingOptions {
+                        placement,
+                        ..options.clone()
+                    };
+                    let before =
+                        self.compute_base_coords(element, trigger, scrollable, options.clone());
+                    let after = self.compute_base_coords(limited, trigger, scrollable, sized);
+                    explain(&mut steps, &options, "size", before, after, || {
+                        format!(
+                            "preferred = {:?}, chosen = {placement:?}, size = {:?}, max = {max_size:?}",
+                            options.placement, element.size
+                        )
+                    });
+                }
+                (
+                    FloatingOptions {
+                        placement,
+                        ..options
+                    },
+                    limited,
+                    Some(max_size),
+                )
+            }
+            None => (options, element, None),
+        };
+        let base_pos = self.compute_base_coords(element, trigger, scrollable, options.clone());
+        explain(&mut steps, &options, "base", base_pos, base_pos, || {
+            format!(
+                "placement = {:?}, trigger = {trigger:?}, hidden = {is_hidden}",
+                options.placement
+            )
+        });
+        let mut output = self.apply_middleware(
+            base_pos,
+            scrollable,
+            element,
+            trigger,
+            options.clone(),
+            &mut steps,
+        );
+        output.is_hidden = is_hidden;
+        if let Some((is_escaped, is_clipped)) = trigger_visibility {
+            output.is_hidden |= is_escaped;
+            output.is_trigger_clipped = is_clipped;
+        }
+        output.max_width = max_size.map(|size| size.width);
+        output.max_height = max_size.map(|size| size.height);
 
         tracing::debug!(
-            "Calculated for scrollable: {scrollable:?}, element: {element:?}, trigger: {trigger:?}, option: {options:?}"
+            "{}Calculated for scrollable: {scrollable:?}, element: {element:?}, trigger: {trigger:?}, option: {options:?}",
+            self.log_prefix()
         );
 
-        final_pos
+        let device_pixel_ratio = device_pixel_ratio();
+        let before = (output.x, output.y);
+        // halves toward the trigger, so the offset from it never alternates
+        let center = trigger.center();
+        output.x = self
+            .rounding
+            .round_toward(output.x, device_pixel_ratio, center.x);
+        output.y = self
+            .rounding
+            .round_toward(output.y, device_pixel_ratio, center.y);
+        explain(
+            &mut steps,
+            &options,
+            "rounding",
+            before,
+            (output.x, output.y),
+            || {
+                format!(
+                    "{:?}, device pixel ratio = {device_pixel_ratio}",
+                    self.rounding
+                )
+            },
+        );
+        output.steps = steps;
+
+        // touching edges (e.g., a zero offset) are not an overlap
+        let element_rect = PixelsRect::new(
+            PixelsVector2D::new(output.x, output.y).to_point(),
+            element.size,
+        );
+        output.trigger_overlap = element_rect.intersection(&trigger);
+        output.overlaps_trigger = output.trigger_overlap.is_some();
+        // after Shift and rounding, along the edge facing the trigger
+        output.arrow = options.arrow_options().map(|(width, corner_radius)| {
+            let edge_length = if output.placement.is_vertical() {
+                element.width()
+            } else {
+                element.height()
+            };
+            ArrowPosition::pointing(
+                output.placement,
+                trigger,
+                PixelsVector2D::new(output.x, output.y),
+                width,
+            )
+            .clamp(edge_length, corner_radius, width)
+        });
+
+        output
+    }
+
+    /// Internal: Returns the `[label] ` prefix for log messages.
+    pub(crate) fn log_prefix(&self) -> String {
+        self.label.map(|l| format!("[{l}] ")).unwrap_or_default()
     }
 }
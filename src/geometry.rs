@@ -0,0 +1,59 @@
+//! The geometry types of the engine.
+//!
+//! With the `dioxus` feature (enabled by `hooks`) these are the types of
+//! `dioxus::html::geometry`, so measured rects pass straight into the engine. Without it,
+//! the engine defines the same `euclid` types itself and does not depend on Dioxus, e.g.,
+//! to compute placements in a server binary.
+//!
+//! # Example
+//!
+//! A placement decision with the engine alone (`default-features = false, features =
+//! ["engine"]`), e.g., to position a popover in a generated report:
+//!
+//! ```rust
+//! use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+//! use dioxus_floating::{Floating, FloatingOptions, Offset, Placement};
+//!
+//! let rect = |x: f64, y: f64, w: f64, h: f64| {
+//!     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+//! };
+//! let page = rect(0.0, 0.0, 600.0, 800.0);
+//! let popover = rect(0.0, 0.0, 240.0, 120.0);
+//! // a cell near the bottom of the page
+//! let cell = rect(40.0, 740.0, 100.0, 24.0);
+//!
+//! let output = Floating::default().compute_placement(
+//!     page,
+//!     popover,
+//!     cell,
+//!     FloatingOptions {
+//!         placement: Placement::BottomStart,
+//!         placement_offset: Some(Offset::zero()),
+//!         ..Default::default()
+//!     },
+//! );
+//!
+//! // no room below: flipped above the cell
+//! assert_eq!(output.placement, Placement::TopStart);
+//! assert_eq!((output.x, output.y), (40.0, 620.0));
+//! ```
+
+#[cfg(feature = "dioxus")]
+pub use dioxus::html::geometry::{Pixels, PixelsRect, PixelsSize, PixelsVector2D, euclid};
+
+#[cfg(not(feature = "dioxus"))]
+pub use euclid;
+
+/// A pixel unit: one unit corresponds to one CSS pixel.
+#[cfg(not(feature = "dioxus"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pixels;
+/// A size in [Pixels].
+#[cfg(not(feature = "dioxus"))]
+pub type PixelsSize = euclid::Size2D<f64, Pixels>;
+/// A rectangle in [Pixels].
+#[cfg(not(feature = "dioxus"))]
+pub type PixelsRect = euclid::Rect<f64, Pixels>;
+/// A 2D vector in [Pixels].
+#[cfg(not(feature = "dioxus"))]
+pub type PixelsVector2D = euclid::Vector2D<f64, Pixels>;
@@ -0,0 +1,1755 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use dioxus::html::geometry::{ClientPoint, PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+
+use crate::floating::visible_part;
+use crate::measure::Container;
+use crate::registry::TimingWindow;
+use crate::{
+    AutoUpdate, Boundary, BoundaryAdjustment, Floating, FloatingContentReady, FloatingOptions,
+    FloatingResult, MeasureAfter, MeasurementTiming, Middleware, OpenReason, Placement,
+    PointerType, Rounding, ScrollState, ScrollableContext, Side, Strategy, UpdateReason,
+    WaitStrategy, announce, auto_update, batch, measure, motion, page, prewarm, registry,
+    scheduler, scrollable_view, strict, viewport,
+};
+
+/// Returns the [Floating] engine instance.
+///
+/// This hook returns the engine provided by the nearest [FloatingProvider]
+/// (or [use_floating_with]), falling back to the default settings,
+/// and ensures it persists across component re-renders.
+pub fn use_floating() -> Floating {
+    use_hook(|| try_consume_context::<Floating>().unwrap_or_default())
+}
+
+/// Provides a configured [Floating] engine to the current component and its children.
+///
+/// Every `use_placement` below inherits the engine defaults (wait strategy,
+/// rounding, label). Individual [FloatingOptions] still apply per call.
+pub fn use_floating_with(floating: Floating) -> Floating {
+    use_context_provider(move || floating)
+}
+
+/// Shares a configured [Floating] engine with every hook in its subtree.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{Floating, FloatingProvider, Rounding};
+///
+/// #[component]
+/// fn App() -> Element {
+///     rsx! {
+///         FloatingProvider {
+///             floating: Floating::builder().rounding(Rounding::Pixel).build(),
+///             "Any floating elements inside use pixel rounding"
+///         }
+///     }
+/// }
+/// ```
+#[cfg(feature = "components")]
+#[component]
+pub fn FloatingProvider(floating: Floating, children: Element) -> Element {
+    use_floating_with(floating);
+
+    rsx! {
+        {children}
+    }
+}
+
+/// Accesses the nearest [ScrollableContext] provided by a [ScrollableView].
+///
+/// # Panics
+/// This hook will panic if used outside of a [ScrollableView] component.
+/// Use `try_use_context::<ScrollableContext>()` if you need a non-panicking version.
+pub fn use_scroll_context() -> ScrollableContext {
+    use_context::<ScrollableContext>()
+}
+
+/// Returns the context of the [ScrollableView] enclosing the nearest one, if any.
+///
+/// Pass it as [FloatingOptions::context] to position an element against the outer
+/// view from inside a nested one (e.g., a tooltip inside a scrollable popover that
+/// should not be clipped by the popover).
+pub fn use_parent_scroll_context() -> Option<ScrollableContext> {
+    try_use_context::<scrollable_view::ParentScrollContext>().and_then(|parent| parent.0)
+}
+
+/// A shorthand hook to access the current [ScrollState] from the context.
+///
+/// Returns a [Signal] containing the dimensions and scroll offsets of
+/// the nearest [ScrollableView].
+pub fn use_scroll_state() -> Signal<Option<ScrollState>> {
+    let ctx = use_scroll_context();
+
+    ctx.scroll_state
+}
+
+/// A shorthand hook to access the [MountedData] of the parent [ScrollableView].
+///
+/// Useful when you need to programmatically control the scroll container
+/// (e.g., calling `scroll_to`) from a child component.
+pub fn use_scrollable_ref() -> Signal<Option<Rc<MountedData>>> {
+    let ctx = use_scroll_context();
+
+    ctx.scrollable_ref
+}
+
+/// Reactive hook for positioning a floating element relative to a trigger element (anchor).
+///
+/// This hook automatically finds the nearest [ScrollableView] context to handle
+/// scrolling and overflow boundary detection.
+///
+/// # Behavior
+/// - It recalculates the position whenever the trigger, the element itself,
+///   or the parent's scroll state changes.
+/// - It waits according to the engine's [WaitStrategy] (1ms by default) to ensure
+///   the browser has performed a Layout pass before measuring dimensions.
+/// - If the trigger can no longer be measured (e.g., a keyed list re-created its node),
+///   the last position is kept and [FloatingResult::is_trigger_lost] is set after a few
+///   attempts. Setting a new [MountedData] on `trigger_ref` recomputes immediately.
+/// - The result follows every scroll frame; [crate::use_settled_placement] derives a
+///   copy that only updates once the element comes to rest.
+/// - The options of every render are compared with the previous ones. A change of the
+///   positioning options (e.g., [FloatingOptions::offset] from a "compact mode" signal
+///   read by the component) recomputes the placement live, without measuring again; equal
+///   options recompute nothing. The options that set up the measurement (e.g.,
+///   [FloatingOptions::auto_update], [FloatingOptions::strategy] or the boundaries) are
+///   read when the hook is created.
+/// - If the result alternates between two positions while the trigger and the element
+///   stay the same, a warning is logged and the first position is kept. This happens
+///   when the element changes the content it is positioned in (e.g., a scrollbar
+///   appears at one position); render it outside of the [ScrollableView] content
+///   (a portal) to fix it.
+///
+/// # Warning
+/// This hook must be used within a [ScrollableView] component. If no context
+/// is found, it will log a warning and return default (zero) coordinates.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::PixelsVector2D;
+/// use dioxus_floating::{use_placement, FloatingOptions};
+///
+/// fn MyElement() -> Element {
+///     let mut element_ref = use_signal(|| None);
+///     let mut trigger_ref = use_signal(|| None);
+///     let mut is_opened = use_signal(|| false);
+///
+///     let placement = use_placement(element_ref, trigger_ref, FloatingOptions::default());
+///
+///     rsx! {
+///         // The trigger element
+///         button {
+///             onmounted: move |e| trigger_ref.set(Some(e.data.clone())),
+///             onclick: move |_| is_opened.toggle(),
+///             "Toggle Dropdown"
+///         }
+///     
+///         // The floating element
+///         if is_opened() {
+///             div {
+///                 onmounted: move |e| element_ref.set(Some(e.data.clone())),
+///                 // Use is_ready to prevent the element from "jumping" into position
+///                 class: if placement().is_ready { "opacity-100" } else { "opacity-0" },
+///                 style: "position: fixed; transform: translate3d({placement().x}px, {placement().y}px, 0);",
+///                 "I am a dropdown content"
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Example: Custom Style Generation
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{use_placement, FloatingOptions};
+///
+/// #[component]
+/// fn MyComponent() -> Element {
+///     let el = use_signal(|| None);
+///     let tr = use_signal(|| None);
+///     let pos = use_placement(el, tr, FloatingOptions::default());
+///
+///     let style = use_memo(move || {
+///         pos.with(|p| format!(
+///             "position: fixed; transform: translate3d({}px, {}px, 0); opacity: {};",
+///             p.x, p.y, if p.is_ready { 1 } else { 0 }
+///         ))
+///     });
+///     rsx!{}
+/// }
+/// ```
+pub fn use_placement<E, T>(
+    // Signal containing the reference to the floating element.
+    element_ref: E,
+    // Signal containing the reference to the trigger (anchor) element.
+    trigger_ref: T,
+    // Positioning options including [Placement], [Middleware], and offsets.
+    options: FloatingOptions,
+) -> ReadSignal<FloatingResult>
+where
+    E: Into<ReadSignal<Option<Rc<MountedData>>>>,
+    T: Into<ReadSignal<Option<Rc<MountedData>>>>,
+{
+    let trigger_ref = trigger_ref.into();
+
+    use_placement_with_anchor(
+        "use_placement",
+        element_ref.into(),
+        Some(trigger_ref),
+        move || trigger_ref().map(Anchor::Trigger),
+        options,
+        PlacementOverrides::default(),
+    )
+}
+
+/// Same as [use_placement], but writes the result into a signal owned by the caller,
+/// e.g., to share the position of a popover with another component (see
+/// [crate::use_connector]). Pass `MY_SIGNAL.resolve()` for a [GlobalSignal].
+///
+/// The signal is written only when the result changes, and reset to the default
+/// (not ready) result when the component of the hook unmounts.
+///
+/// # Example
+///
+/// With the [crate::testing] mocks:
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+/// use dioxus_floating::{FloatingOptions, FloatingResult, Offset, Placement, use_placement_in};
+///
+/// static POPOVER: GlobalSignal<FloatingResult> = Signal::global(FloatingResult::default);
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+///     let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 100.0, 50.0)).mounted()));
+///     let trigger_ref = use_signal(|| Some(MockElement::new(rect(40.0, 40.0, 60.0, 20.0)).mounted()));
+///     let options = FloatingOptions {
+///         placement: Placement::BottomStart,
+///         placement_offset: Some(Offset::zero()),
+///         ..Default::default()
+///     };
+///     use_placement_in(POPOVER.resolve(), element_ref, trigger_ref, options);
+///     rsx! {}
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// settle(&mut dom);
+/// let result = dom.in_runtime(|| *POPOVER.peek());
+/// assert!(result.is_ready);
+/// assert_eq!((result.x, result.y), (40.0, 60.0));
+/// ```
+pub fn use_placement_in<E, T>(
+    mut signal: Signal<FloatingResult>,
+    element_ref: E,
+    trigger_ref: T,
+    options: FloatingOptions,
+) where
+    E: Into<ReadSignal<Option<Rc<MountedData>>>>,
+    T: Into<ReadSignal<Option<Rc<MountedData>>>>,
+{
+    let result = use_placement(element_ref, trigger_ref, options);
+
+    use_effect(move || {
+        let result = result();
+        if *signal.peek() != result {
+            signal.set(result);
+        }
+    });
+    use_drop(move || {
+        // the signal may be owned by a component that unmounts with this one
+        if let Ok(mut shared) = signal.try_write() {
+            *shared = FloatingResult::default();
+        }
+    });
+}
+
+/// Reactive hook for positioning a floating element relative to a specific point (e.g., mouse click).
+///
+/// This is specifically designed for context menus or custom popups that appear at
+/// a given [ClientPoint]. It automatically subscribes to the nearest [ScrollableView]
+/// to handle positioning within a scrollable area.
+///
+/// # Note on Usage:
+/// Unlike `use_placement`, this hook expects a point in viewport coordinates.
+/// If you are using this for a context menu, ensure you capture the coordinates
+/// from the `MouseEvent`.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{use_placement_on_point, FloatingOptions};
+///
+/// #[component]
+/// fn MyComponent() -> Element {
+///     let mut click_point = use_signal(|| None);
+///     let mut element_ref = use_signal(|| None);
+///
+///     let placement = use_placement_on_point(
+///         element_ref,
+///         click_point,
+///         FloatingOptions::default(),
+///     );
+///
+///     rsx! {
+///         div {
+///             oncontextmenu: move |e| {
+///                 e.prevent_default();
+///                 click_point.set(Some(e.client_coordinates()));
+///             },
+///             "Right click here to open menu"
+///         }
+///     
+///         // Render the element as soon as we have a target point
+///         if click_point().is_some() {
+///             div {
+///                 onmounted: move |e| element_ref.set(Some(e.data.clone())),
+///                 // Keep it invisible until positioning is calculated
+///                 class: if placement().is_ready { "opacity-100" } else { "opacity-0" },
+///                 style: "position: fixed; transform: translate3d({placement().x}px, {placement().y}px, 0);",
+///                 "Context Menu Content"
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Example: Custom Style Generation
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{use_placement_on_point, FloatingOptions};
+///
+/// #[component]
+/// fn MyComponent() -> Element {
+///     let el = use_signal(|| None);
+///     let mut click = use_signal(|| None);
+///     let pos = use_placement_on_point(el, click, FloatingOptions::default());
+///     let style = use_memo(move || {
+///         pos.with(|p| format!(
+///             "position: fixed; transform: translate3d({}px, {}px, 0); opacity: {};",
+///             p.x, p.y, if p.is_ready { 1 } else { 0 }
+///         ))
+///     });
+///     rsx! {
+///         button {
+///             onclick: move |evt: MouseEvent| { click.set(Some(evt.client_coordinates())) }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_placement_on_point<E, T>(
+    element_ref: E,
+    trigger_point: T,
+    options: FloatingOptions,
+) -> ReadSignal<FloatingResult>
+where
+    E: Into<ReadSignal<Option<Rc<MountedData>>>>,
+    T: Into<ReadSignal<Option<ClientPoint>>>,
+{
+    let trigger_point = trigger_point.into();
+
+    use_placement_with_anchor(
+        "use_placement_on_point",
+        element_ref.into(),
+        None,
+        move || trigger_point().map(Anchor::Point),
+        options,
+        PlacementOverrides::default(),
+    )
+}
+
+/// Reactive hook for positioning a floating element relative to a range spanning two
+/// elements, e.g., the start and end cells of a selection in a calendar.
+///
+/// Both anchors are measured and their bounding box (the union of both rects, in any
+/// relative order, possibly spanning several rows) is used as the trigger, so Center
+/// alignment refers to the whole range. If only one endpoint is mounted or measurable
+/// (e.g., the other one was scrolled out and unmounted), the element is anchored to
+/// that one and [FloatingResult::is_range_partial] is set.
+///
+/// The placement is recomputed when either ref changes, on scroll, and on the
+/// [crate::AutoUpdate] sources of the element and the start anchor.
+///
+/// # Example
+///
+/// ```rust
+/// use std::rc::Rc;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{use_placement_on_range, FloatingOptions, Placement};
+///
+/// #[component]
+/// fn Selection() -> Element {
+///     let mut start_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let mut end_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let options = FloatingOptions { placement: Placement::TopCenter, ..Default::default() };
+///     let placement = use_placement_on_range(element_ref, start_ref, end_ref, options);
+///
+///     rsx! {
+///         div { onmounted: move |e| start_ref.set(Some(e.data())), "3" }
+///         div { onmounted: move |e| end_ref.set(Some(e.data())), "17" }
+///         div {
+///             onmounted: move |e| element_ref.set(Some(e.data())),
+///             style: "position: fixed; transform: translate3d({placement().x}px, {placement().y}px, 0);",
+///             "15 days"
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Example: Endpoints on Different Rows
+///
+/// With the [crate::testing] mocks, the end cell is on a row above the start cell;
+/// the element is centered over the union of both.
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+/// use dioxus_floating::{FloatingOptions, FloatingResult, Offset, Placement, use_placement_on_range};
+///
+/// thread_local! {
+///     static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+/// }
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+///     rsx! { Selection {} }
+/// }
+///
+/// #[component]
+/// fn Selection() -> Element {
+///     let start = use_hook(|| MockElement::new(rect(300.0, 200.0, 40.0, 20.0)));
+///     let end = use_hook(|| MockElement::new(rect(100.0, 100.0, 40.0, 20.0)));
+///     let element = use_hook(|| MockElement::new(rect(0.0, 0.0, 100.0, 50.0)));
+///     let start_ref = use_signal(|| Some(start.mounted()));
+///     let end_ref = use_signal(|| Some(end.mounted()));
+///     let element_ref = use_signal(|| Some(element.mounted()));
+///
+///     let options = FloatingOptions {
+///         placement_offset: Some(Offset::zero()),
+///         placement: Placement::TopCenter,
+///         ..Default::default()
+///     };
+///     let placement = use_placement_on_range(element_ref, start_ref, end_ref, options);
+///     RESULT.with(|result| result.set(placement()));
+///
+///     rsx! {}
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// settle(&mut dom);
+///
+/// let result = RESULT.with(Cell::get);
+/// // the union spans (100, 100) - (340, 220)
+/// assert_eq!(result.trigger_rect, rect(100.0, 100.0, 240.0, 120.0));
+/// assert_eq!((result.x, result.y), (170.0, 50.0));
+/// assert!(!result.is_range_partial);
+/// ```
+pub fn use_placement_on_range<E, S, T>(
+    // Signal containing the reference to the floating element.
+    element_ref: E,
+    // Signal containing the reference to the first endpoint of the range.
+    start_ref: S,
+    // Signal containing the reference to the last endpoint of the range.
+    end_ref: T,
+    // Positioning options including [Placement], [Middleware], and offsets.
+    options: FloatingOptions,
+) -> ReadSignal<FloatingResult>
+where
+    E: Into<ReadSignal<Option<Rc<MountedData>>>>,
+    S: Into<ReadSignal<Option<Rc<MountedData>>>>,
+    T: Into<ReadSignal<Option<Rc<MountedData>>>>,
+{
+    let start_ref = start_ref.into();
+    let end_ref = end_ref.into();
+
+    use_placement_with_anchor(
+        "use_placement_on_range",
+        element_ref.into(),
+        Some(start_ref),
+        move || match (start_ref(), end_ref()) {
+            (None, None) => None,
+            (start, end) => Some(Anchor::Range(start, end)),
+        },
+        options,
+        PlacementOverrides::default(),
+    )
+}
+
+/// Synchronous placement hook driven by rect signals instead of element refs.
+///
+/// This is the reactive core of [use_placement]: it runs the middleware pipeline
+/// whenever any of the rects changes, without timers, [MountedData] or a
+/// [ScrollableContext]. Use it when you measure the elements yourself, or to drive
+/// the pipeline with plain rect signals in tests.
+///
+/// The result is not ready while any of the rects is `None`.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::{use_placement_core, FloatingOptions};
+///
+/// #[component]
+/// fn MyComponent() -> Element {
+///     let rect = |x, y, w, h| {
+///         Some(PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h)))
+///     };
+///     let element = use_signal(move || rect(0.0, 0.0, 100.0, 50.0));
+///     let anchor = use_signal(move || rect(10.0, 10.0, 40.0, 20.0));
+///     let boundary = use_signal(move || rect(0.0, 0.0, 800.0, 600.0));
+///
+///     let placement = use_placement_core(element, anchor, boundary, FloatingOptions::default());
+///     rsx! { "{placement().x}, {placement().y}" }
+/// }
+/// ```
+pub fn use_placement_core<E, A, B>(
+    element_rect: E,
+    anchor_rect: A,
+    boundary_rect: B,
+    options: FloatingOptions,
+) -> ReadSignal<FloatingResult>
+where
+    E: Into<ReadSignal<Option<PixelsRect>>>,
+    A: Into<ReadSignal<Option<PixelsRect>>>,
+    B: Into<ReadSignal<Option<PixelsRect>>>,
+{
+    use_placement_memo(
+        element_rect.into(),
+        anchor_rect.into(),
+        boundary_rect.into(),
+        options,
+        None,
+        None,
+        None,
+    )
+}
+
+/// Internal: The memo behind [use_placement_core]; `open_reason` and `pointer_type`
+/// override [FloatingOptions::open_reason] and [FloatingOptions::open_pointer_type]
+/// reactively (e.g., set by [use_floating_widget]). `update_reason` is the reason of the
+/// measured rects, any change of unattributed rects is a [UpdateReason::Resize].
+fn use_placement_memo(
+    element_rect: ReadSignal<Option<PixelsRect>>,
+    anchor_rect: ReadSignal<Option<PixelsRect>>,
+    boundary_rect: ReadSignal<Option<PixelsRect>>,
+    options: FloatingOptions,
+    open_reason: Option<ReadSignal<Option<OpenReason>>>,
+    pointer_type: Option<ReadSignal<Option<PointerType>>>,
+    update_reason: Option<ReadSignal<UpdateReason>>,
+) -> ReadSignal<FloatingResult> {
+    let floating = use_floating();
+    // the resolved side of the last ready result, to report flips
+    let mut last_side = use_hook(|| CopyValue::new(Option::<Side>::None));
+    // the options of the latest render; an equal value recomputes nothing
+    let mut latest_options = use_signal(|| options.clone());
+    let mut options_changed = use_hook(|| CopyValue::new(false));
+    if *latest_options.peek() != options {
+        latest_options.set(options);
+        options_changed.set(true);
+    }
+
+    let result = use_memo(move || {
+        // a zero-area boundary (e.g., a collapsed view) is not ready yet
+        let boundary_rect = boundary_rect().filter(|boundary| !boundary.is_empty());
+        let zip = boundary_rect.zip(element_rect()).zip(anchor_rect());
+        // the reason of the last measurement does not apply to new options
+        let mut cause = update_reason.map_or(UpdateReason::Resize, |reason| reason());
+        if options_changed.replace(false) {
+            cause = UpdateReason::Manual;
+        }
+
+        match zip {
+            Some(((boundary, element), anchor)) => {
+                let mut options = latest_options();
+                if let Some(reason) = open_reason.and_then(|reason| reason()) {
+                    options.open_reason = Some(reason);
+                }
+                if let Some(pointer_type) = pointer_type.and_then(|pointer_type| pointer_type()) {
+                    options.open_pointer_type = Some(pointer_type);
+                }
+                let output = floating.compute_placement(boundary, element, anchor, options);
+                tracing::debug!(
+                    "Floating placement updated: x={}, y={}, ready=true",
+                    output.x,
+                    output.y
+                );
+
+                let side = output.placement.side();
+                FloatingResult {
+                    update_reason: match last_side.replace(Some(side)) {
+                        None => UpdateReason::Initial,
+                        Some(last) if last != side => UpdateReason::Flip,
+                        Some(_) => cause,
+                    },
+                    ..FloatingResult::ready(output)
+                }
+            }
+            None => {
+                last_side.set(None);
+                FloatingResult::default()
+            }
+        }
+    });
+
+    result.into()
+}
+
+/// A handle that makes the placement hooks measure again, see [FloatingOptions::update].
+///
+/// One handle can be shared by any number of placements, e.g., every annotation of a
+/// document computed with [FloatingOptions::compute_once], re-armed after an edit.
+///
+/// # Example
+///
+/// An annotation rendered in the scrolled content, computed once and re-armed after the
+/// content changed, with the [crate::testing] mocks:
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+/// use dioxus_floating::{FloatingOptions, FloatingResult, FloatingUpdate, Offset, Placement, ScrollableContext, use_floating_update, use_placement};
+///
+/// thread_local! {
+///     static TRIGGER: MockElement = MockElement::new(rect(40.0, 150.0, 60.0, 20.0));
+///     static HANDLES: Cell<Option<(ScrollableContext, FloatingUpdate)>> = Cell::new(None);
+///     static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+/// }
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     let context = use_hook(|| {
+///         let mut config = MockScrollableConfig::new(rect(0.0, 100.0, 500.0, 400.0));
+///         config.scroll_state.size = PixelsSize::new(500.0, 2000.0);
+///         config.scroll_state.state = PixelsVector2D::new(0.0, 300.0);
+///         provide_mock_scrollable(config)
+///     });
+///     let update = use_floating_update();
+///     HANDLES.with(|cell| cell.set(Some((context, update))));
+///     let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 100.0, 50.0)).mounted()));
+///     let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+///     let options = FloatingOptions {
+///         placement: Placement::BottomStart,
+///         placement_offset: Some(Offset::zero()),
+///         compute_once: true,
+///         update: Some(update),
+///         ..Default::default()
+///     };
+///     let placement = use_placement(element_ref, trigger_ref, options);
+///     RESULT.with(|cell| cell.set(placement()));
+///     rsx! {}
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// settle(&mut dom);
+/// // below the trigger: 170px in the viewport, 100 + 300 (scrolled) further in the content
+/// assert_eq!((RESULT.with(Cell::get).x, RESULT.with(Cell::get).y), (40.0, 370.0));
+///
+/// // the content scrolls (and the trigger with it): nothing to compute
+/// let (mut context, update) = HANDLES.with(Cell::get).unwrap();
+/// TRIGGER.with(|trigger| trigger.set_rect(rect(40.0, 50.0, 60.0, 20.0)));
+/// dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, 400.0)));
+/// settle(&mut dom);
+/// assert_eq!(RESULT.with(Cell::get).y, 370.0);
+///
+/// // text inserted above moves the trigger 20px down the content: re-arm
+/// TRIGGER.with(|trigger| trigger.set_rect(rect(40.0, 70.0, 60.0, 20.0)));
+/// dom.in_runtime(|| update.update());
+/// settle(&mut dom);
+/// assert_eq!(RESULT.with(Cell::get).y, 390.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatingUpdate {
+    tick: Signal<u64>,
+}
+
+impl FloatingUpdate {
+    /// Measures every placement that uses the handle again.
+    pub fn update(&self) {
+        let mut tick = self.tick;
+        *tick.write() += 1;
+    }
+}
+
+/// Creates a [FloatingUpdate] handle for the lifetime of the component.
+pub fn use_floating_update() -> FloatingUpdate {
+    use_hook(|| FloatingUpdate {
+        tick: Signal::new(0),
+    })
+}
+
+/// Internal: What a floating element is anchored to.
+#[derive(Debug, Clone)]
+pub(crate) enum Anchor {
+    Trigger(Rc<MountedData>),
+    Point(ClientPoint),
+    // the endpoints of a range, at least one is mounted
+    Range(Option<Rc<MountedData>>, Option<Rc<MountedData>>),
+}
+
+impl Anchor {
+    /// Internal: Returns `true` if both anchor to the same nodes or point.
+    fn is_same(&self, other: &Anchor) -> bool {
+        let is_same_node = |a: &Option<Rc<MountedData>>, b: &Option<Rc<MountedData>>| match (a, b) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+
+        match (self, other) {
+            (Anchor::Trigger(a), Anchor::Trigger(b)) => Rc::ptr_eq(a, b),
+            (Anchor::Point(a), Anchor::Point(b)) => a == b,
+            (Anchor::Range(a_start, a_end), Anchor::Range(b_start, b_end)) => {
+                is_same_node(a_start, b_start) && is_same_node(a_end, b_end)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Internal: The inputs of a run of the measurement effect, to attribute the next run to
+/// the one that changed, see [UpdateReason].
+#[derive(Debug, Clone)]
+struct UpdateInputs {
+    manual_tick: u64,
+    anchor: Anchor,
+    element: Rc<MountedData>,
+    // the auto update tick, the layout, content and visibility generations
+    generations: (u64, u64, u64, u64),
+    // the scroll size and bounds
+    extent: (PixelsSize, PixelsSize),
+    boundary_adjustment: Option<BoundaryAdjustment>,
+}
+
+impl UpdateInputs {
+    /// Internal: Returns why the effect runs with these inputs after `previous`.
+    fn reason_after(&self, previous: Option<&UpdateInputs>) -> UpdateReason {
+        let Some(previous) = previous else {
+            return UpdateReason::Initial;
+        };
+        let is_same_adjustment = match (&self.boundary_adjustment, &previous.boundary_adjustment) {
+            (Some(a), Some(b)) => a.is_same(b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+
+        if self.manual_tick != previous.manual_tick {
+            UpdateReason::Manual
+        } else if !self.anchor.is_same(&previous.anchor) {
+            UpdateReason::AnchorChanged
+        } else if !Rc::ptr_eq(&self.element, &previous.element)
+            || self.generations != previous.generations
+            || self.extent != previous.extent
+            || !is_same_adjustment
+        {
+            UpdateReason::Resize
+        } else {
+            // the offset of the view or of an enclosing one, or a reveal after a scroll
+            UpdateReason::Scroll
+        }
+    }
+}
+
+/// Internal: The last measurement of a trigger placement with sticky axes.
+#[derive(Debug, Clone)]
+struct StickyMeasurement {
+    element: Rc<MountedData>,
+    trigger: Rc<MountedData>,
+    update_tick: u64,
+    layout_generation: u64,
+    scroll_state: ScrollState,
+    scrollable_rect: PixelsRect,
+    trigger_rect: PixelsRect,
+}
+
+impl StickyMeasurement {
+    /// Returns `true` if only the scroll offset changed since the measurement.
+    fn is_valid_for(
+        &self,
+        element: &Rc<MountedData>,
+        trigger: &Rc<MountedData>,
+        update_tick: u64,
+        layout_generation: u64,
+        scroll_state: &ScrollState,
+    ) -> bool {
+        Rc::ptr_eq(&self.element, element)
+            && Rc::ptr_eq(&self.trigger, trigger)
+            && self.update_tick == update_tick
+            && self.layout_generation == layout_generation
+            && self.scroll_state.size == scroll_state.size
+            && self.scroll_state.bounds == scroll_state.bounds
+    }
+
+    /// Moves the measured trigger rect by the scroll delta on the non-sticky axes.
+    fn trigger_rect_at(&self, scroll_state: &ScrollState, sticky_axes: (bool, bool)) -> PixelsRect {
+        let delta = scroll_state.state - self.scroll_state.state;
+        let (sticky_x, sticky_y) = sticky_axes;
+
+        self.trigger_rect.translate(PixelsVector2D::new(
+            if sticky_x { 0_f64 } else { -delta.x },
+            if sticky_y { 0_f64 } else { -delta.y },
+        ))
+    }
+}
+
+/// Internal: Identifies placement hook instances, e.g., in the measurement scheduler.
+static NEXT_PLACEMENT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Internal: Number of consecutive failed trigger measurements after which
+/// the trigger is reported as lost.
+const TRIGGER_LOST_AFTER: u32 = 3;
+
+/// Internal: The measured inputs of [use_placement_core].
+#[derive(Debug, Clone, Copy)]
+struct PlacementRects {
+    element: Signal<Option<PixelsRect>>,
+    anchor: Signal<Option<PixelsRect>>,
+    boundary: Signal<Option<PixelsRect>>,
+    trigger_lost: Signal<bool>,
+    range_partial: Signal<bool>,
+    waiting_for_visibility: Signal<bool>,
+    pending: Signal<bool>,
+    // the durations of the published measurements
+    timing: Signal<MeasurementTiming>,
+    // the FloatingContentReady generation the published rects were measured at
+    content_generation: Signal<u64>,
+    // the client position of the scroll content origin at the published measurement,
+    // with FloatingOptions::compute_once
+    content_origin: Signal<Option<PixelsVector2D>>,
+    // why the published rects were measured
+    update_reason: Signal<UpdateReason>,
+    id: usize,
+}
+
+impl PlacementRects {
+    /// Internal: Drops all rects so the placement is no longer ready.
+    fn reset(&mut self) {
+        if self.element.peek().is_some()
+            || self.anchor.peek().is_some()
+            || self.boundary.peek().is_some()
+        {
+            self.element.set(None);
+            self.anchor.set(None);
+            self.boundary.set(None);
+            tracing::debug!("Floating placement reset: ready=false");
+        }
+        if *self.waiting_for_visibility.peek() {
+            self.waiting_for_visibility.set(false);
+        }
+        if *self.pending.peek() {
+            self.pending.set(false);
+        }
+    }
+
+    /// Internal: Returns `true` if the published anchor is outside the boundary, but is
+    /// predicted to be inside it at `scroll_state` (moved by the scroll delta since it
+    /// was measured at `measured_at`).
+    fn is_revealed_at(
+        &self,
+        scroll_state: &ScrollState,
+        measured_at: Signal<Option<ScrollState>>,
+    ) -> bool {
+        let zip = self
+            .anchor
+            .peek()
+            .zip(*self.boundary.peek())
+            .zip(*measured_at.peek());
+        let Some(((anchor, boundary), measured_at)) = zip else {
+            return false;
+        };
+        let predicted = anchor.translate(measured_at.state - scroll_state.state);
+
+        visible_part(anchor, boundary).is_none() && visible_part(predicted, boundary).is_some()
+    }
+
+    /// Internal: Publishes the reason of the measurements since the last publication. A
+    /// superseded measurement that lands after the latest one keeps the published reason.
+    fn publish_reason(&mut self, mut pending: CopyValue<Option<UpdateReason>>) {
+        let reason = pending.write().take().unwrap_or(*self.update_reason.peek());
+        if *self.update_reason.peek() != reason {
+            self.update_reason.set(reason);
+        }
+    }
+
+    /// Internal: `false` once the component that owns the rects was dropped.
+    ///
+    /// A batch may publish after the owner was unmounted (its measurement finished
+    /// together with the reads of other components), so commits check it first.
+    fn is_alive(&self) -> bool {
+        self.element.try_peek().is_ok()
+    }
+}
+
+/// Internal: Reactive inputs that replace options or measurements of
+/// [use_placement_with_anchor].
+#[derive(Clone, Copy, Default)]
+pub(crate) struct PlacementOverrides {
+    // replace FloatingOptions::open_reason and open_pointer_type (e.g., set by
+    // use_floating_widget)
+    pub(crate) open_reason: Option<ReadSignal<Option<OpenReason>>>,
+    pub(crate) pointer_type: Option<ReadSignal<Option<PointerType>>>,
+    // replaces the measured size of the element, e.g., the combined size of a FloatingRow
+    pub(crate) element_size: Option<ReadSignal<Option<PixelsSize>>>,
+}
+
+/// Internal: The reactive pipeline shared by the placement hooks.
+///
+/// Measures the elements into rect signals and feeds them to [use_placement_core].
+/// `anchor` is called inside the effect, so every signal it reads
+/// becomes a dependency of the placement.
+pub(crate) fn use_placement_with_anchor(
+    hook_name: &'static str,
+    element_ref: ReadSignal<Option<Rc<MountedData>>>,
+    trigger_ref: Option<ReadSignal<Option<Rc<MountedData>>>>,
+    anchor: impl Fn() -> Option<Anchor> + 'static,
+    options: FloatingOptions,
+    overrides: PlacementOverrides,
+) -> ReadSignal<FloatingResult> {
+    // region defaults of the nearest ScrollableView go under the per-call options
+    let nearest = try_use_context::<ScrollableContext>();
+    let context = options.context.or(nearest);
+    let options = match context {
+        Some(ctx) => match ctx.floating_defaults.peek().as_ref() {
+            Some(defaults) => options.with_defaults(defaults),
+            None => options,
+        },
+        None => options,
+    };
+    // a one-shot placement in content coordinates reacts to nothing else
+    let compute_once = options.compute_once;
+    let options = if compute_once {
+        let mut middleware = options.middleware.clone();
+        middleware.retain(|strategy| *strategy != Middleware::Hide);
+        FloatingOptions {
+            auto_update: AutoUpdate::none(),
+            anchor_to_visible: false,
+            middleware,
+            strategy: Strategy::Absolute,
+            ..options
+        }
+    } else {
+        options
+    };
+    let debug_label = options.debug_label;
+    let wait_for_content = options.wait_for_content;
+    let instance = use_hook(|| Rc::<str>::from(instance_name(hook_name, debug_label)));
+    let content = use_context_provider(FloatingContentReady::new);
+    let rects = use_placement_rects(
+        instance.clone(),
+        element_ref,
+        trigger_ref,
+        anchor,
+        &options,
+        content,
+    );
+    let clip_to_visual_viewport = options.clip_to_visual_viewport;
+    let is_viewport_x = options.boundary_x == Boundary::Viewport;
+    let is_viewport_y = options.boundary_y == Boundary::Viewport;
+    let visual_viewport =
+        viewport::use_clip_viewport(clip_to_visual_viewport || is_viewport_x || is_viewport_y);
+    let boundary = use_memo(move || {
+        let boundary = (rects.boundary)();
+        let Some(visible) = visual_viewport.and_then(|viewport| viewport()) else {
+            return boundary;
+        };
+        boundary.map(|boundary| {
+            let boundary = with_axes(
+                boundary,
+                is_viewport_x.then_some(visible),
+                is_viewport_y.then_some(visible),
+            );
+            // the part of the boundary that is not covered, e.g., by an on-screen keyboard
+            if clip_to_visual_viewport {
+                boundary.intersection(&visible).unwrap_or(boundary)
+            } else {
+                boundary
+            }
+        })
+    });
+    let announce = options.announce.clone();
+    let suppress_transitions = options.suppress_transitions;
+    #[cfg(debug_assertions)]
+    let strategy = options.strategy;
+    let element_size = overrides.element_size;
+    let element = use_memo(move || {
+        let element = (rects.element)();
+        match element_size {
+            Some(size) => element
+                .zip(size())
+                .map(|(element, size)| PixelsRect::new(element.origin, size)),
+            None => element,
+        }
+    });
+    let mut oscillation = use_hook(|| CopyValue::new(OscillationGuard::default()));
+    oscillation.write().set_options(&options);
+    let placement = use_placement_memo(
+        element.into(),
+        rects.anchor.into(),
+        boundary.into(),
+        options,
+        overrides.open_reason,
+        overrides.pointer_type,
+        Some(rects.update_reason.into()),
+    );
+    // the last ready result and a counter of jumps, for the transition suppression
+    let mut last_ready = use_hook(|| CopyValue::new(Option::<FloatingResult>::None));
+    let oscillating_instance = instance.clone();
+    let mut jumps = use_hook(|| CopyValue::new(0_u64));
+    let mut is_suppressing = use_signal(|| false);
+    let reduced_motion = motion::use_motion_listener(suppress_transitions.is_some());
+    let result = use_memo(move || {
+        let mut result = FloatingResult {
+            is_trigger_lost: (rects.trigger_lost)(),
+            is_range_partial: (rects.range_partial)(),
+            is_waiting_for_visibility: (rects.waiting_for_visibility)(),
+            is_pending: (rects.pending)(),
+            ..oscillation.write().guard(
+                &oscillating_instance,
+                placement(),
+                context
+                    .and_then(|context| *context.scroll_state.peek())
+                    .map_or(PixelsVector2D::zero(), |state| state.state),
+            )
+        };
+        if compute_once && let Some(origin) = (rects.content_origin)() {
+            result.x -= origin.x;
+            result.y -= origin.y;
+        }
+        // only a measurement after the latest mark counts
+        if wait_for_content {
+            result.content_ready = result.is_ready
+                && content.is_marked()
+                && (rects.content_generation)() == (content.generation)();
+        }
+        if let Some(suppress) = suppress_transitions {
+            let last = last_ready.replace(Some(result).filter(|result| result.is_ready));
+            let is_jump =
+                result.is_ready && last.is_some_and(|last| suppress.is_jump(&last, &result));
+            if is_jump {
+                *jumps.write() += 1;
+            }
+            // with reduced motion, every move is instant
+            let is_reduced = !suppress.ignore_reduced_motion
+                && motion::resolve(reduced_motion.and_then(|reported| reported()));
+            result.suppress_transition = is_reduced || is_jump || is_suppressing();
+        }
+        result
+    });
+    // keep transitions off for the configured time after the latest jump
+    let mut handled_jumps = use_hook(|| CopyValue::new(0_u64));
+    use_effect(move || {
+        let Some(suppress) = suppress_transitions else {
+            return;
+        };
+        let jump = *jumps.peek();
+        if !result().suppress_transition || jump == *handled_jumps.peek() {
+            return;
+        }
+        handled_jumps.set(jump);
+        is_suppressing.set(true);
+        spawn(async move {
+            WaitStrategy::Timeout(suppress.duration_ms).wait().await;
+            // a newer jump keeps them off for its own duration
+            if *jumps.peek() == jump {
+                is_suppressing.set(false);
+            }
+        });
+    });
+    announce::use_announcer(announce, result.into());
+    registry::use_registration(
+        context,
+        rects.id,
+        instance.clone(),
+        debug_label,
+        element_ref,
+        result.into(),
+        rects.timing.into(),
+    );
+    #[cfg(debug_assertions)]
+    crate::position_check::use_position_check(
+        instance,
+        element_ref,
+        result.into(),
+        strategy,
+        // the position of a replaced size is not the one of the element
+        !compute_once && element_size.is_none(),
+    );
+
+    result.into()
+}
+
+/// Internal: Detects a placement that alternates between two positions while the trigger,
+/// the element, the scroll offset and the options stay the same, and keeps the first one
+/// until one of them changes. The measured boundary is not part of the inputs: it is what
+/// alternates.
+///
+/// The usual cause is an element rendered inside the scroll content it is positioned
+/// in: at one position it extends the content (e.g., a scrollbar appears), which
+/// changes the boundary, which moves it back, and so on. Rendering the element outside
+/// of the content (a portal) is the proper fix.
+#[derive(Debug, Default)]
+struct OscillationGuard {
+    // the options of the history, any change starts a new one
+    options: Option<FloatingOptions>,
+    // the trigger rect, element size and scroll offset of the history
+    key: Option<(PixelsRect, PixelsSize, PixelsVector2D)>,
+    // the last two distinct positions for the key, oldest first
+    history: [Option<(f64, f64, Placement)>; 2],
+    // the position kept while it alternates
+    latched: Option<FloatingResult>,
+}
+
+impl OscillationGuard {
+    /// Internal: Forgets the history if `options` differ from the ones of the last render.
+    fn set_options(&mut self, options: &FloatingOptions) {
+        if self.options.as_ref() != Some(options) {
+            *self = Self {
+                options: Some(options.clone()),
+                ..Self::default()
+            };
+        }
+    }
+
+    /// Internal: Returns the result to publish instead of `result`, computed at
+    /// `scroll_offset`.
+    fn guard(
+        &mut self,
+        instance: &str,
+        result: FloatingResult,
+        scroll_offset: PixelsVector2D,
+    ) -> FloatingResult {
+        let key = (result.trigger_rect, result.element_size, scroll_offset);
+        if !result.is_ready || self.key != Some(key) {
+            self.key = result.is_ready.then_some(key);
+            self.history = [None, Some((result.x, result.y, result.placement))];
+            self.latched = None;
+            return result;
+        }
+
+        let position = Some((result.x, result.y, result.placement));
+        if let Some(latched) = self.latched {
+            // back and forth between the two: keep the first one
+            if self.history.contains(&position) {
+                return latched;
+            }
+            // a third position: something else changed
+            self.latched = None;
+            self.history = [None, position];
+            return result;
+        }
+
+        let [older, last] = self.history;
+        if position == last {
+            return result;
+        }
+        if position == older {
+            tracing::warn!(
+                "{instance}: the placement alternates between ({}, {}) and ({}, {}) for the same \
+                trigger and element. The element probably changes the scroll content it is \
+                positioned in (e.g., it extends the content and a scrollbar appears); render it \
+                outside of the ScrollableView content, e.g., in a portal. Keeping ({}, {}) until \
+                another input changes.",
+                result.x,
+                result.y,
+                last.map_or(0_f64, |last| last.0),
+                last.map_or(0_f64, |last| last.1),
+                result.x,
+                result.y,
+            );
+            self.latched = Some(result);
+            return result;
+        }
+        self.history = [last, position];
+
+        result
+    }
+}
+
+/// Internal: Returns `boundary` with its horizontal extent replaced by the one of `x` and
+/// its vertical extent by the one of `y`, see [FloatingOptions::boundary_x].
+fn with_axes(boundary: PixelsRect, x: Option<PixelsRect>, y: Option<PixelsRect>) -> PixelsRect {
+    let x = x.unwrap_or(boundary);
+    let y = y.unwrap_or(boundary);
+
+    PixelsRect::new(
+        PixelsVector2D::new(x.min_x(), y.min_y()).to_point(),
+        PixelsSize::new(x.width(), y.height()),
+    )
+}
+
+/// Internal: Identifies a hook instance in log messages: the hook name, the
+/// [FloatingOptions::debug_label] and, in debug builds, the scope of the component.
+fn instance_name(hook_name: &'static str, debug_label: Option<&'static str>) -> String {
+    let mut name = hook_name.to_string();
+    if let Some(label) = debug_label {
+        name.push_str(&format!(" [{label}]"));
+    }
+    if cfg!(debug_assertions) {
+        name.push_str(&format!(" in {:?}", dioxus::core::current_scope_id()));
+    }
+
+    name
+}
+
+/// Internal: Keeps the element, anchor and boundary rects of a placement up to date.
+fn use_placement_rects(
+    instance: Rc<str>,
+    element_ref: ReadSignal<Option<Rc<MountedData>>>,
+    trigger_ref: Option<ReadSignal<Option<Rc<MountedData>>>>,
+    anchor: impl Fn() -> Option<Anchor> + 'static,
+    options: &FloatingOptions,
+    content: FloatingContentReady,
+) -> PlacementRects {
+    let floating = use_floating();
+    let instance_id = use_hook(|| NEXT_PLACEMENT_ID.fetch_add(1, Ordering::Relaxed));
+    let mut rects = PlacementRects {
+        element: use_signal(|| None),
+        anchor: use_signal(|| None),
+        boundary: use_signal(|| None),
+        trigger_lost: use_signal(|| false),
+        range_partial: use_signal(|| false),
+        waiting_for_visibility: use_signal(|| false),
+        pending: use_signal(|| false),
+        timing: use_signal(MeasurementTiming::default),
+        content_generation: use_signal(|| 0),
+        content_origin: use_signal(|| None),
+        update_reason: use_signal(UpdateReason::default),
+        id: instance_id,
+    };
+    // identifies the latest measurement, only its result clears the pending flag
+    let mut measurement_generation = use_signal(|| 0_u64);
+    // the newest measurement published so far, an older one finishing later is stale
+    let mut published_measurement = use_hook(|| CopyValue::new(0_u64));
+    let mut trigger_failures = use_signal(|| 0_u32);
+    let mut timing_window = use_hook(|| CopyValue::new(TimingWindow::default()));
+    // consecutive measurements with zero-size rects, reported in strict mode
+    let mut zero_measurements = use_hook(|| CopyValue::new(0_u32));
+    let mut last_trigger = use_signal(|| Option::<Rc<MountedData>>::None);
+    let auto_update = options.auto_update;
+    // `Some(true)` while the page is hidden, see Floating::is_suspended
+    let page_hidden = page::use_page_listener();
+    let update_tick =
+        auto_update::use_auto_update(auto_update, element_ref, trigger_ref, page_hidden);
+    // a run was skipped while the page was hidden, the next one recomputes everything
+    let mut is_deferred = use_hook(|| CopyValue::new(false));
+    let mut sticky_measurement = use_signal(|| Option::<StickyMeasurement>::None);
+    let sticky_axes = options.sticky_axes;
+    let is_sticky = sticky_axes.0 || sticky_axes.1;
+    let measure_after = options.measure_after;
+    let measure_untransformed = options.measure_untransformed;
+    let wait = options.wait;
+    let boundary_elements = (options.boundary_x.element(), options.boundary_y.element());
+    // the client rects of the Boundary::Element boundaries, per axis
+    let mut boundary_rects =
+        use_signal(|| (Option::<PixelsRect>::None, Option::<PixelsRect>::None));
+    let mut visibility_tick = use_signal(|| 0_u64);
+    let compute_once = options.compute_once;
+    let freeze_during_smooth_scroll = options.freeze_during_smooth_scroll;
+    let manual_update = options.update;
+    // the manual update tick of the published one-shot measurement
+    let mut computed_at = use_hook(|| CopyValue::new(Option::<u64>::None));
+    // the inputs of the last run, and the reason of the runs not published yet
+    let mut last_inputs = use_hook(|| CopyValue::new(Option::<UpdateInputs>::None));
+    let mut pending_reason = use_hook(|| CopyValue::new(Option::<UpdateReason>::None));
+
+    // a trigger that can be hidden is measured again when it comes back
+    let tracks_visibility = options.anchor_to_visible || options.can_hide();
+    // the scroll state the published anchor rect was measured at
+    let mut anchor_scroll_state = use_signal(|| Option::<ScrollState>::None);
+    let mut reveal_tick = use_signal(|| 0_u64);
+    let mut is_revealing = use_signal(|| false);
+
+    let warned_no_context = use_hook(|| Rc::new(Cell::new(false)));
+
+    // context without panic, a pinned one wins over the nearest
+    let nearest = try_use_context::<ScrollableContext>();
+    let context = match options.context.or(nearest) {
+        Some(ctx) => ctx,
+        None => {
+            // the body runs on every render, warn once per instance
+            if !warned_no_context.replace(true) {
+                tracing::warn!(
+                    "{instance} hook used outside of ScrollableView. \
+                    Ensure your component is wrapped in a ScrollableView or provide a ScrollableContext."
+                );
+                strict::misuse(|| {
+                    format!(
+                        "{instance} hook used outside of ScrollableView; wrap the component in a \
+                        ScrollableView or pin a ScrollableContext with FloatingOptions::context"
+                    )
+                });
+            }
+            return rects;
+        }
+    };
+
+    // re-anchor on reveal: a trigger that was scrolled out is measured again as soon
+    // as it is predicted to be back. The published rects stay hidden until the fresh
+    // ones arrive, so the hidden flag clears together with the new position.
+    // With scroll-driven updates the main effect handles it, this one covers their absence.
+    use_effect(move || {
+        if !tracks_visibility || auto_update.ancestor_scroll {
+            return;
+        }
+        let Some(scroll_state) = (context.scroll_state)() else {
+            return;
+        };
+        if !*is_revealing.peek() && rects.is_revealed_at(&scroll_state, anchor_scroll_state) {
+            is_revealing.set(true);
+            *reveal_tick.write() += 1;
+        }
+    });
+
+    use_effect(move || {
+        // subscribe to the manual updates
+        let manual_tick = manual_update.map_or(0, |update| (update.tick)());
+        // computed once: nothing else is read, so nothing else runs this effect again
+        if compute_once && *computed_at.peek() == Some(manual_tick) {
+            return;
+        }
+        // frozen: the end of the smooth scroll runs this effect again
+        if freeze_during_smooth_scroll && (context.is_smooth_scrolling)() {
+            return;
+        }
+        // suspended: nothing else is read, so the invalidations while the page is hidden
+        // collapse into the run when it is shown again
+        if page_hidden().unwrap_or_default() {
+            is_deferred.set(true);
+            return;
+        }
+        let is_resume = is_deferred.replace(false);
+        // subscribe to the enabled auto update sources
+        let auto_tick = update_tick();
+        let tick = auto_tick + manual_tick;
+        // subscribe to reveals of a hidden trigger
+        reveal_tick();
+        // subscribe to the checks of an element that is not rendered yet
+        let visibility = visibility_tick();
+        let scroll_state = match *context.scroll_state.peek() {
+            Some(state) if !auto_update.ancestor_scroll => Some(state),
+            _ => (context.scroll_state)(),
+        };
+
+        // the enclosing views move the trigger as well when they scroll
+        if auto_update.ancestor_scroll {
+            for ancestor in (context.ancestors)() {
+                // a dropped ancestor (e.g., the view moved) no longer scrolls anything
+                let _ = ancestor.scroll_state.try_read();
+            }
+        }
+
+        // subscribe to layout invalidations (e.g., page zoom)
+        let generation = (context.layout_generation)();
+        // subscribe to the marks of the content, each one measures again
+        let content_generation = (content.generation)();
+
+        // subscribe to boundary adjustment changes
+        let boundary_adjustment = (context.boundary_adjustment)();
+        // classic scrollbars are not part of the usable boundary
+        let is_rtl = (context.rtl)();
+        let zoomed_instance = instance.clone();
+        let adjust = move |rect| {
+            let rect = match scroll_state {
+                Some(state) => {
+                    if let Some(zoom) = state.zoom_factor(rect) {
+                        tracing::debug!(
+                            "{zoomed_instance}: the rect of the view is scaled by {zoom} relative to its \
+                            client size (CSS zoom), the scrollbars are measured in the rect's scale"
+                        );
+                    }
+                    state.exclude_scrollbars(rect, is_rtl)
+                }
+                None => rect,
+            };
+            let rect = match &boundary_adjustment {
+                Some(adjustment) => adjustment.apply(rect),
+                None => rect,
+            };
+            let (x, y) = *boundary_rects.peek();
+            with_axes(rect, x, y)
+        };
+
+        let zip = scroll_state
+            .zip(context.container())
+            .zip(element_ref())
+            .zip(anchor());
+
+        let Some((((scroll_state, container), element), anchor)) = zip else {
+            // drop ready flag
+            rects.reset();
+            return;
+        };
+
+        // attribute the run to the input that changed; superseded and deferred
+        // measurements add up until one is published
+        let inputs = UpdateInputs {
+            manual_tick,
+            anchor: anchor.clone(),
+            element: element.clone(),
+            generations: (auto_tick, generation, content_generation, visibility),
+            extent: (scroll_state.size, scroll_state.bounds),
+            boundary_adjustment: context.boundary_adjustment.peek().clone(),
+        };
+        let reason = inputs.reason_after(last_inputs.peek().as_ref());
+        last_inputs.set(Some(inputs));
+        let pending = pending_reason
+            .peek()
+            .map_or(reason, |pending| pending.merge(reason));
+        pending_reason.set(Some(pending));
+
+        if let Anchor::Trigger(trigger) = &anchor {
+            // a new trigger node is a fresh anchor
+            let is_same = last_trigger
+                .peek()
+                .as_ref()
+                .is_some_and(|last| Rc::ptr_eq(last, trigger));
+            if !is_same {
+                last_trigger.set(Some(trigger.clone()));
+                trigger_failures.set(0);
+                if *rects.trigger_lost.peek() {
+                    rects.trigger_lost.set(false);
+                }
+            }
+
+            // sticky trigger: only the scroll offset changed, skip the measurement,
+            // unless a hidden trigger comes back (it is measured instead of predicted)
+            let is_reveal = tracks_visibility
+                && (*is_revealing.peek()
+                    || rects.is_revealed_at(&scroll_state, anchor_scroll_state));
+            let measurement = sticky_measurement.peek().clone();
+            if let Some(measurement) = measurement.filter(|m| {
+                is_sticky
+                    && !is_resume
+                    && !is_reveal
+                    && !*rects.waiting_for_visibility.peek()
+                    && *rects.content_generation.peek() == content_generation
+                    && m.is_valid_for(&element, trigger, tick, generation, &scroll_state)
+            }) {
+                anchor_scroll_state.set(Some(scroll_state));
+                rects.publish_reason(pending_reason);
+                rects.anchor.set(Some(
+                    measurement.trigger_rect_at(&scroll_state, sticky_axes),
+                ));
+                rects
+                    .boundary
+                    .set(Some(adjust(measurement.scrollable_rect)));
+                return;
+            }
+            if is_reveal && !*is_revealing.peek() {
+                is_revealing.set(true);
+            }
+        }
+
+        // with a frame budget, visible triggers and recent invalidations are measured first
+        let priority = scheduler::Priority {
+            is_visible: rects
+                .anchor
+                .peek()
+                .zip(*rects.boundary.peek())
+                .is_none_or(|(anchor, boundary)| visible_part(anchor, boundary).is_some()),
+            invalidated_at: scrollable_view::now_ms(),
+        };
+
+        let measurement = *measurement_generation.peek() + 1;
+        measurement_generation.set(measurement);
+        if !*rects.pending.peek() {
+            rects.pending.set(true);
+        }
+        // superseded measurements may still land, but the flag waits for the latest one
+        let finish = move || {
+            let mut pending = rects.pending;
+            if rects.is_alive() && *measurement_generation.peek() == measurement && *pending.peek()
+            {
+                pending.set(false);
+            }
+        };
+
+        let instance = instance.clone();
+        let boundary_elements = boundary_elements.clone();
+        spawn(async move {
+            let Some(_slot) =
+                scheduler::acquire(floating.frame_budget(), instance_id, priority).await
+            else {
+                // replaced by a newer measurement while waiting for the budget
+                return;
+            };
+            // measurements of all placements are read first and published together
+            let read = batch::begin_read();
+
+            // wait render virtual dom elements; on resume the DOM is up to date, and the
+            // first frame after `visibilitychange` runs before the page is painted
+            let wait = if is_resume {
+                WaitStrategy::AnimationFrame
+            } else {
+                wait.unwrap_or(floating.wait_strategy())
+            };
+            wait.wait().await;
+            if floating.rounding() == Rounding::DevicePixel {
+                floating.refresh_device_pixel_ratio().await;
+            }
+            let started = scrollable_view::now_ms();
+
+            let mut is_range_partial = false;
+            let (scrollable_rect, element_rect, anchor_rect) = match anchor {
+                Anchor::Trigger(trigger) => {
+                    // rects measured ahead of the open, see Floating::prewarm
+                    let prewarmed = match &container {
+                        // measured before the page was hidden
+                        _ if is_resume => None,
+                        Container::Mounted(scrollable) => {
+                            prewarm::take(&trigger, scrollable, generation, &scroll_state)
+                        }
+                        Container::Provided(_) => None,
+                    };
+                    let (scrollable_rect, element_rect, trigger_rect) = match prewarmed {
+                        Some((trigger_rect, scrollable_rect)) => (
+                            scrollable_rect,
+                            measure::element_rect(&element, measure_untransformed).await,
+                            Some(trigger_rect),
+                        ),
+                        None => {
+                            floating
+                                .measure_on_trigger(
+                                    scroll_state,
+                                    container,
+                                    element.clone(),
+                                    trigger.clone(),
+                                    measure_untransformed,
+                                )
+                                .await
+                        }
+                    };
+
+                    let Some(trigger_rect) = trigger_rect else {
+                        if *is_revealing.peek() {
+                            is_revealing.set(false);
+                        }
+                        // keep the last position instead of publishing a fallback
+                        if element_rect.is_some() {
+                            *trigger_failures.write() += 1;
+                            if *trigger_failures.peek() == TRIGGER_LOST_AFTER {
+                                tracing::warn!(
+                                    "{instance}: the trigger could not be measured {TRIGGER_LOST_AFTER} times in a row. \
+                                    It was probably replaced; provide the new MountedData to the trigger ref."
+                                );
+                                rects.trigger_lost.set(true);
+                            }
+                        }
+                        finish();
+                        return;
+                    };
+                    if *trigger_failures.peek() > 0 {
+                        trigger_failures.set(0);
+                        rects.trigger_lost.set(false);
+                    }
+
+                    if is_sticky {
+                        // the offset may have changed while measuring
+                        let scroll_state = context.scroll_state.peek().unwrap_or(scroll_state);
+                        sticky_measurement.set(Some(StickyMeasurement {
+                            element,
+                            trigger,
+                            update_tick: tick,
+                            layout_generation: generation,
+                            scroll_state,
+                            scrollable_rect,
+                            trigger_rect,
+                        }));
+                    }
+
+                    (scrollable_rect, element_rect, trigger_rect)
+                }
+                Anchor::Point(point) => {
+                    floating
+                        .measure_on_point(
+                            scroll_state,
+                            container,
+                            element,
+                            point,
+                            measure_untransformed,
+                        )
+                        .await
+                }
+                Anchor::Range(start, end) => {
+                    let (scrollable_rect, element_rect, range_rect) = floating
+                        .measure_on_range(
+                            scroll_state,
+                            container,
+                            element,
+                            start,
+                            end,
+                            measure_untransformed,
+                        )
+                        .await;
+                    // keep the last position if neither endpoint could be measured
+                    let Some((range_rect, is_partial)) = range_rect else {
+                        finish();
+                        return;
+                    };
+                    is_range_partial = is_partial;
+
+                    (scrollable_rect, element_rect, range_rect)
+                }
+            };
+
+            if cfg!(all(feature = "strict", debug_assertions)) {
+                // an element waiting for visibility is expected to have no size; an anchor
+                // may have a zero width or height (e.g., a line in an SVG chart), but not both
+                let is_zero = anchor_rect.size == PixelsSize::zero()
+                    || (measure_after != MeasureAfter::Visible
+                        && element_rect.is_some_and(|rect| rect.is_empty()));
+                if !is_zero {
+                    zero_measurements.set(0);
+                } else {
+                    *zero_measurements.write() += 1;
+                }
+                if *zero_measurements.peek() == TRIGGER_LOST_AFTER {
+                    strict::misuse(|| {
+                        format!(
+                            "{instance}: zero-size rects measured {TRIGGER_LOST_AFTER} times in a row \
+                            (element: {element_rect:?}, anchor: {anchor_rect:?}); the elements are \
+                            probably not rendered, or the refs point to the wrong nodes"
+                        )
+                    });
+                }
+            }
+
+            // not rendered yet (e.g., `display: none`): unpublish and measure again next frame
+            if measure_after == MeasureAfter::Visible
+                && element_rect.is_some_and(|rect| rect.is_empty())
+            {
+                let instance = instance.clone();
+                read.commit(move || {
+                    if !rects.is_alive() {
+                        return;
+                    }
+                    if !*rects.waiting_for_visibility.peek() {
+                        tracing::debug!("{instance}: waiting for the element to become visible");
+                        rects.waiting_for_visibility.set(true);
+                    }
+                    if rects.element.peek().is_some() {
+                        rects.element.set(None);
+                    }
+                });
+                drop(_slot);
+
+                // a newer measurement checks on its own
+                if measure::next_animation_frame().await
+                    && rects.is_alive()
+                    && *visibility_tick.peek() == visibility
+                {
+                    *visibility_tick.write() += 1;
+                }
+                return;
+            }
+
+            let measure_boundary = async |element: Option<Rc<MountedData>>| match element {
+                Some(element) => element.get_client_rect().await.ok(),
+                None => None,
+            };
+            let measured_boundaries = (
+                measure_boundary(boundary_elements.0).await,
+                measure_boundary(boundary_elements.1).await,
+            );
+
+            let compute_ms = scrollable_view::now_ms() - started;
+            // the offset may have changed while measuring
+            let measured_at = context
+                .scroll_state
+                .try_peek()
+                .ok()
+                .and_then(|state| *state)
+                .unwrap_or(scroll_state);
+            read.commit(move || {
+                if !rects.is_alive() || *published_measurement.peek() > measurement {
+                    return;
+                }
+                published_measurement.set(measurement);
+                anchor_scroll_state.set(Some(measured_at));
+                rects.publish_reason(pending_reason);
+                let (timing, is_first_report) = timing_window
+                    .write()
+                    .record(compute_ms, floating.slow_measurement_budget());
+                if is_first_report {
+                    tracing::warn!(
+                        label = %instance,
+                        measurements = timing.count,
+                        p90_ms = timing.p90_ms,
+                        over_budget = timing.over_budget,
+                        budget_ms = timing.budget_ms,
+                        "{instance}: 90% of the recent measurements took up to {:.1}ms, over the \
+                        budget of {}ms. The DOM reads through document::eval are slow on this device: \
+                        prefer the web renderer, which reads the DOM directly, or spread the \
+                        measurements with FloatingBuilder::frame_budget.",
+                        timing.p90_ms,
+                        timing.budget_ms
+                    );
+                }
+                rects.timing.set(timing);
+                if *rects.content_generation.peek() != content_generation {
+                    rects.content_generation.set(content_generation);
+                }
+                if compute_once {
+                    computed_at.set(Some(manual_tick));
+                    rects
+                        .content_origin
+                        .set(Some(scrollable_rect.origin.to_vector() - measured_at.state));
+                }
+                if *is_revealing.peek() {
+                    is_revealing.set(false);
+                }
+                if *boundary_rects.peek() != measured_boundaries {
+                    boundary_rects.set(measured_boundaries);
+                }
+                rects.boundary.set(Some(adjust(scrollable_rect)));
+                rects.anchor.set(Some(anchor_rect));
+                if *rects.range_partial.peek() != is_range_partial {
+                    rects.range_partial.set(is_range_partial);
+                }
+                if *rects.waiting_for_visibility.peek() {
+                    rects.waiting_for_visibility.set(false);
+                }
+                // keep the last element rect if the element is no longer accessible
+                if let Some(element_rect) = element_rect {
+                    rects.element.set(Some(element_rect));
+                }
+                finish();
+            });
+        });
+    });
+
+    rects
+}
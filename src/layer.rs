@@ -0,0 +1,145 @@
+use dioxus::prelude::*;
+
+/// The z-index of the first layer if [use_floating_layer] gets no base.
+const DEFAULT_LAYER_BASE: i32 = 1000;
+
+/// Internal: The layers claimed by the mounted [use_floating_layer] hooks of the app,
+/// `(claim, z-index)` in claim order.
+#[derive(Clone, Copy)]
+struct LayerStack {
+    layers: CopyValue<Vec<(u64, i32)>>,
+    next_claim: CopyValue<u64>,
+}
+
+impl LayerStack {
+    /// Internal: Claims a layer above every claimed one, `base` and `parent`.
+    fn claim(&mut self, base: i32, parent: Option<i32>) -> (u64, i32) {
+        let top = self.layers.read().iter().map(|(_, z)| *z).max();
+        let z_index = [Some(base), top.map(|z| z + 1), parent.map(|z| z + 1)]
+            .into_iter()
+            .flatten()
+            .max()
+            .unwrap_or(base);
+        let claim = *self.next_claim.peek();
+        self.next_claim.set(claim + 1);
+        self.layers.write().push((claim, z_index));
+
+        (claim, z_index)
+    }
+
+    /// Internal: Releases a layer; the other layers keep their z-index.
+    fn release(&mut self, claim: u64) {
+        if let Ok(mut layers) = self.layers.try_write() {
+            layers.retain(|(other, _)| *other != claim);
+        }
+    }
+}
+
+/// Internal: The layer of the nearest [use_floating_layer] above a component.
+#[derive(Clone, Copy)]
+struct ParentLayer(i32);
+
+/// Claims a z-index for a floating element above every open one, e.g., for a menu, its
+/// submenu and a confirmation tooltip in it, with a modal overlay opened in between.
+///
+/// Call it in the component that is rendered while the element is open: the layer is
+/// claimed when the component mounts and released when it unmounts. A claim is above
+/// all claimed layers, above `base` (1000 by default) and above the layer of the nearest
+/// [use_floating_layer] in an ancestor component, so nested elements always stack over
+/// their parents. Layers never change once claimed: releasing one does not move the
+/// others, so open elements do not pop. The counter starts over at `base` once every
+/// layer is released.
+///
+/// Pass the layer to the style helpers with [crate::FloatingResult::with_layer].
+///
+/// # Example
+///
+/// A menu, its submenu and a tooltip in the submenu, then a modal overlay with a
+/// popover of its own:
+///
+/// ```rust
+/// use std::cell::RefCell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::use_floating_layer;
+///
+/// thread_local! {
+///     static LAYERS: RefCell<Vec<(&'static str, i32)>> = RefCell::new(Vec::new());
+///     static OPEN: RefCell<Option<(Signal<bool>, Signal<bool>)>> = RefCell::new(None);
+/// }
+///
+/// fn app() -> Element {
+///     let is_tooltip_open = use_signal(|| true);
+///     let is_overlay_open = use_signal(|| false);
+///     OPEN.with(|open| *open.borrow_mut() = Some((is_tooltip_open, is_overlay_open)));
+///     rsx! {
+///         Layer { name: "menu",
+///             Layer { name: "submenu",
+///                 if is_tooltip_open() {
+///                     Layer { name: "tooltip" }
+///                 }
+///             }
+///         }
+///         if is_overlay_open() {
+///             Layer { name: "overlay",
+///                 Layer { name: "dialog popover" }
+///             }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Layer(name: &'static str, children: Element) -> Element {
+///     let layer = use_floating_layer(None);
+///     use_hook(|| LAYERS.with(|layers| layers.borrow_mut().push((name, layer()))));
+///     rsx! { {children} }
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// let (mut is_tooltip_open, mut is_overlay_open) = OPEN.with(|open| open.borrow().unwrap());
+///
+/// // the tooltip closes and opens again above its submenu, the others stay put
+/// dom.in_runtime(|| is_tooltip_open.set(false));
+/// dom.render_immediate_to_vec();
+/// dom.in_runtime(|| is_tooltip_open.set(true));
+/// dom.render_immediate_to_vec();
+///
+/// // the overlay covers all of them, its popover is above it
+/// dom.in_runtime(|| is_overlay_open.set(true));
+/// dom.render_immediate_to_vec();
+///
+/// assert_eq!(
+///     LAYERS.with(|layers| layers.borrow().clone()),
+///     vec![
+///         ("menu", 1000),
+///         ("submenu", 1001),
+///         ("tooltip", 1002),
+///         ("tooltip", 1002),
+///         ("overlay", 1003),
+///         ("dialog popover", 1004),
+///     ]
+/// );
+/// ```
+pub fn use_floating_layer(base: Option<i32>) -> ReadSignal<i32> {
+    let parent = try_use_context::<ParentLayer>();
+    let (mut stack, claim, z_index) = use_hook(move || {
+        let mut stack = try_consume_context::<LayerStack>().unwrap_or_else(|| {
+            dioxus::core::provide_root_context(LayerStack {
+                layers: CopyValue::new_in_scope(Vec::new(), ScopeId::ROOT),
+                next_claim: CopyValue::new_in_scope(0, ScopeId::ROOT),
+            })
+        });
+        let (claim, z_index) = stack.claim(
+            base.unwrap_or(DEFAULT_LAYER_BASE),
+            parent.map(|parent| parent.0),
+        );
+        // the floating elements of descendants stack over this one
+        provide_context(ParentLayer(z_index));
+
+        (stack, claim, z_index)
+    });
+    use_drop(move || stack.release(claim));
+
+    use_signal(|| z_index).into()
+}
@@ -0,0 +1,168 @@
+use std::rc::Rc;
+
+use dioxus::core::Task;
+use dioxus::html::geometry::{PixelsRect, PixelsVector2D};
+use dioxus::prelude::*;
+
+use crate::floating::visible_part;
+use crate::{WaitStrategy, use_scroll_context};
+
+/// Mounts its children, typically a component with a placement hook, only while the
+/// trigger is near the visible part of the nearest [crate::ScrollableView] or `open`,
+/// e.g., the informational popover of every row of a large table.
+///
+/// Until then, nothing below it exists: no hooks, no effects, no subscriptions, so a row
+/// that is never scrolled into view or hovered only costs the trigger measurement. The
+/// position of the trigger in the content is measured when it mounts and again when the
+/// layout changes (the content size or [crate::ScrollableContext::layout_generation]);
+/// scrolling only compares it with the published [crate::ScrollState]. A trigger counts
+/// as near within `margin` pixels (200 by default) of the view, so the children are ready
+/// before it scrolls in.
+///
+/// Set `open` when the user interacts with the trigger (e.g., on `pointerenter` or
+/// `focus`): the children mount at once, wherever the trigger is. Once the trigger is
+/// closed and off-screen for `idle_ms` milliseconds (5 seconds by default), the children
+/// unmount again, and their scopes drop with every hook, task and subscription inside.
+/// Coming back within the idle period keeps them.
+///
+/// # Panics
+/// Like [use_scroll_context], it panics outside of a [crate::ScrollableView].
+///
+/// # Example
+/// ```rust
+/// use std::rc::Rc;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{FloatingOptions, LazyFloating, Strategy, use_placement};
+///
+/// #[component]
+/// fn Row(name: String) -> Element {
+///     let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let mut hovered = use_signal(|| false);
+///     rsx! {
+///         tr {
+///             td {
+///                 onmounted: move |event| trigger_ref.set(Some(event.data())),
+///                 onpointerenter: move |_| hovered.set(true),
+///                 onpointerleave: move |_| hovered.set(false),
+///                 "{name}"
+///             }
+///             LazyFloating { trigger: trigger_ref, open: hovered(),
+///                 Info { trigger_ref, hovered }
+///             }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Info(trigger_ref: Signal<Option<Rc<MountedData>>>, hovered: Signal<bool>) -> Element {
+///     let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let placement = use_placement(element_ref, trigger_ref, FloatingOptions::default());
+///     rsx! {
+///         if hovered() {
+///             div {
+///                 onmounted: move |event| element_ref.set(Some(event.data())),
+///                 style: placement().style(Strategy::Absolute),
+///                 "Details"
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn LazyFloating(
+    #[props(into)] trigger: ReadSignal<Option<Rc<MountedData>>>,
+    #[props(default)] open: bool,
+    #[props(default = 200.0)] margin: f64,
+    #[props(default = 5000)] idle_ms: u32,
+    children: Element,
+) -> Element {
+    let context = use_scroll_context();
+    // the rect of the trigger in the content, independent of the offset
+    let mut trigger_rect = use_signal(|| Option::<PixelsRect>::None);
+    let mut measurement = use_hook(|| CopyValue::new(0_u64));
+    let content_size = use_memo(move || (context.scroll_state)().map(|state| state.size));
+
+    use_effect(move || {
+        let _ = (context.layout_generation)();
+        let is_ready = content_size().is_some();
+        let trigger = trigger();
+        let container = context.container();
+        *measurement.write() += 1;
+        let current = *measurement.peek();
+        spawn(async move {
+            let rect = match (trigger, container) {
+                (Some(trigger), Some(container)) if is_ready => {
+                    let trigger = trigger.get_client_rect().await.ok();
+                    let state = *context.scroll_state.peek();
+                    match (trigger, state) {
+                        (Some(trigger), Some(state)) => {
+                            let container = container.rect(&state).await;
+                            Some(trigger.translate(state.state - container.origin.to_vector()))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            // a newer measurement replaces this one
+            if *measurement.peek() == current && *trigger_rect.peek() != rect {
+                trigger_rect.set(rect);
+            }
+        });
+    });
+
+    let mut latest_margin = use_signal(|| margin);
+    if *latest_margin.peek() != margin {
+        latest_margin.set(margin);
+    }
+    let is_near = use_memo(move || {
+        let (Some(rect), Some(state)) = (trigger_rect(), (context.scroll_state)()) else {
+            return false;
+        };
+        let margin = latest_margin().max(0_f64);
+        let near = PixelsRect::new(
+            (state.state - PixelsVector2D::new(margin, margin)).to_point(),
+            state.bounds + PixelsVector2D::new(margin, margin).to_size() * 2_f64,
+        );
+
+        visible_part(rect, near).is_some()
+    });
+
+    let mut latest_open = use_signal(|| open);
+    if *latest_open.peek() != open {
+        latest_open.set(open);
+    }
+    let mut latest_idle_ms = use_hook(|| CopyValue::new(idle_ms));
+    latest_idle_ms.set(idle_ms);
+    let mut is_mounted = use_signal(|| open);
+    let mut idle = use_hook(|| CopyValue::new(Option::<Task>::None));
+
+    use_effect(move || {
+        let is_wanted = is_near() || latest_open();
+        if let Some(task) = idle.write().take() {
+            task.cancel();
+        }
+        if is_wanted {
+            if !*is_mounted.peek() {
+                is_mounted.set(true);
+            }
+            return;
+        }
+        if !*is_mounted.peek() {
+            return;
+        }
+        let task = spawn(async move {
+            WaitStrategy::Timeout(*latest_idle_ms.peek()).wait().await;
+            idle.set(None);
+            is_mounted.set(false);
+        });
+        idle.set(Some(task));
+    });
+
+    rsx! {
+        if open || is_mounted() {
+            {children}
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use dioxus::core::Task;
+use dioxus::prelude::*;
+use serde::de::DeserializeOwned;
+
+use crate::window::with_window;
+
+/// Internal: A JS listener started through `document::eval`.
+///
+/// The script reports every event with `dioxus.send(...)` and must remove its
+/// listeners once `await dioxus.recv()` resolves, which happens on [JsListener::stop].
+#[derive(Clone, Copy)]
+pub(crate) struct JsListener {
+    eval: document::Eval,
+    task: Task,
+}
+
+impl JsListener {
+    /// Starts the script and calls `on_message` for every message it sends.
+    ///
+    /// Must be called inside the Dioxus runtime; the receiving task belongs to
+    /// the current scope.
+    pub(crate) fn start<T, F>(script: &str, on_message: F) -> Self
+    where
+        T: DeserializeOwned + 'static,
+        F: FnMut(T) + 'static,
+    {
+        Self::start_with(script, on_message, false)
+    }
+
+    /// Like [JsListener::start], but the receiving task belongs to the root scope, so it
+    /// outlives the component that started it (e.g., a listener shared by many hooks).
+    pub(crate) fn start_forever<T, F>(script: &str, on_message: F) -> Self
+    where
+        T: DeserializeOwned + 'static,
+        F: FnMut(T) + 'static,
+    {
+        Self::start_with(script, on_message, true)
+    }
+
+    fn start_with<T, F>(script: &str, mut on_message: F, forever: bool) -> Self
+    where
+        T: DeserializeOwned + 'static,
+        F: FnMut(T) + 'static,
+    {
+        let eval = document::eval(script);
+        let mut receiver = eval;
+        let receive = async move {
+            while let Ok(message) = receiver.recv::<T>().await {
+                on_message(message);
+            }
+        };
+        let task = if forever {
+            dioxus::core::spawn_forever(receive)
+        } else {
+            spawn(receive)
+        };
+
+        Self { eval, task }
+    }
+
+    /// Sends a message to the script (received with `await dioxus.recv()`).
+    pub(crate) fn send(&self, data: impl serde::Serialize) {
+        let _ = self.eval.send(data);
+    }
+
+    /// Asks the script to remove its listeners and stops receiving messages.
+    pub(crate) fn stop(&self) {
+        let _ = self.eval.send(true);
+        self.task.cancel();
+    }
+}
+
+/// Internal: A [JsListener] shared by every hook instance of a window that subscribes
+/// to it, e.g., one document listener for any number of components.
+///
+/// The first subscriber starts the script, the last one to unsubscribe stops it. The
+/// latest message, converted with `convert`, is published in a signal owned by the
+/// root scope, so it outlives the component that started the listener. The running
+/// state is kept per window (see [crate::window::with_window]), so each window listens
+/// to its own document.
+pub(crate) struct SharedListener<M, T: 'static> {
+    script: &'static str,
+    convert: fn(M) -> T,
+}
+
+/// Internal: The running state of a [SharedListener] in one window.
+struct SharedState<T: 'static> {
+    listener: Option<JsListener>,
+    value: Signal<Option<T>>,
+    subscribers: usize,
+}
+
+/// Internal: The states of the [SharedListener]s of one window by script.
+type SharedStates<T> = HashMap<&'static str, SharedState<T>>;
+
+impl<M: DeserializeOwned + 'static, T: 'static> SharedListener<M, T> {
+    pub(crate) const fn new(script: &'static str, convert: fn(M) -> T) -> Self {
+        Self { script, convert }
+    }
+
+    /// Registers a subscriber, starting the script for the first one in the window.
+    /// Returns the value signal of the window, `None` (and nothing to unsubscribe)
+    /// outside of the Dioxus runtime.
+    pub(crate) fn subscribe(&self) -> Option<Signal<Option<T>>> {
+        with_window(|states: &mut SharedStates<T>| {
+            let state = states.entry(self.script).or_insert_with(|| SharedState {
+                listener: None,
+                value: Signal::new_in_scope(None, ScopeId::ROOT),
+                subscribers: 0,
+            });
+
+            state.subscribers += 1;
+            if state.listener.is_none() {
+                let mut value = state.value;
+                let convert = self.convert;
+                state.listener = Some(JsListener::start_forever(self.script, move |message: M| {
+                    value.set(Some(convert(message)));
+                }));
+            }
+
+            state.value
+        })
+    }
+
+    /// Returns the latest value in the current window without subscribing, `None`
+    /// while the script is not running there (or outside of the runtime).
+    pub(crate) fn peek(&self) -> Option<Option<T>>
+    where
+        T: Clone,
+    {
+        with_window(|states: &mut SharedStates<T>| {
+            let state = states
+                .get(self.script)
+                .filter(|state| state.listener.is_some())?;
+
+            state.value.try_peek().ok().map(|value| value.clone())
+        })
+        .flatten()
+    }
+
+    /// Returns the value signal of the current window without subscribing, `None` while
+    /// the script is not running there (or outside of the runtime). Reading it follows
+    /// the messages of the running script.
+    pub(crate) fn running(&self) -> Option<Signal<Option<T>>> {
+        with_window(|states: &mut SharedStates<T>| {
+            states
+                .get(self.script)
+                .filter(|state| state.listener.is_some())
+                .map(|state| state.value)
+        })
+        .flatten()
+    }
+
+    /// Publishes `value` in the current window as if the script had sent it, e.g., for
+    /// the [crate::testing] mocks. The next subscriber sees it if none is running yet.
+    pub(crate) fn report(&self, value: T) {
+        with_window(|states: &mut SharedStates<T>| {
+            let state = states.entry(self.script).or_insert_with(|| SharedState {
+                listener: None,
+                value: Signal::new_in_scope(None, ScopeId::ROOT),
+                subscribers: 0,
+            });
+            state.value.set(Some(value));
+        });
+    }
+
+    /// Unregisters a subscriber, stopping the script after the last one in the window.
+    pub(crate) fn unsubscribe(&self) {
+        with_window(|states: &mut SharedStates<T>| {
+            let Some(state) = states.get_mut(self.script) else {
+                return;
+            };
+
+            state.subscribers = state.subscribers.saturating_sub(1);
+            if state.subscribers == 0
+                && let Some(listener) = state.listener.take()
+            {
+                listener.stop();
+                // the next subscriber must not see a value from before its listener
+                state.value.set(None);
+            }
+        });
+    }
+}
@@ -0,0 +1,755 @@
+use std::rc::Rc;
+
+use dioxus::html::geometry::{ClientPoint, PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+
+use crate::floating::{device_pixel_ratio, set_device_pixel_ratio};
+use crate::{
+    Floating, FloatingOptions, FrameStats, PlacementOutput, ScrollState, ScrollableContext,
+    WaitStrategy,
+};
+
+impl WaitStrategy {
+    /// Waits according to the strategy.
+    pub async fn wait(&self) {
+        match *self {
+            WaitStrategy::None => {}
+            WaitStrategy::Timeout(ms) => timeout(ms).await,
+            WaitStrategy::AnimationFrame => {
+                next_animation_frame().await;
+            }
+            WaitStrategy::AnimationFrames(frames) => {
+                for _ in 0..frames {
+                    next_animation_frame().await;
+                }
+            }
+        }
+    }
+}
+
+/// Internal: The script behind [next_animation_frame].
+pub(crate) const FRAME_SCRIPT: &str = "requestAnimationFrame(() => dioxus.send(true));";
+
+/// Internal: The script behind timeouts outside of the web.
+pub(crate) fn timeout_script(ms: u32) -> String {
+    format!("setTimeout(() => dioxus.send(true), {ms});")
+}
+
+/// Internal: Waits for the next animation frame. Returns `false` if the renderer has
+/// no frames (e.g., a headless [VirtualDom] in tests), so the wait did not happen.
+pub(crate) async fn next_animation_frame() -> bool {
+    let mut eval = document::eval(FRAME_SCRIPT);
+    eval.recv::<bool>().await.is_ok()
+}
+
+/// Internal: Reads the scroll state of a container in one step instead of a round trip
+/// per value: from the DOM with the web renderer, and from a [crate::testing::MockElement].
+/// `None` for other renderers; `Some(None)` if the element was removed.
+fn read_scroll_state(data: &MountedData) -> Option<Option<ScrollState>> {
+    if let Some(mock) = data.downcast::<crate::testing::MockElement>() {
+        return Some(mock.read_scroll_state());
+    }
+
+    read_dom_scroll_state(data)
+}
+
+/// Internal: Reads the scroll state of an element of the web renderer.
+#[cfg(target_arch = "wasm32")]
+fn read_dom_scroll_state(data: &MountedData) -> Option<Option<ScrollState>> {
+    let element = data.downcast::<web_sys::Element>()?;
+    // a detached element reports zeros instead of failing
+    if !element.is_connected() {
+        tracing::debug!("Skipping the scroll state of a removed element");
+        return Some(None);
+    }
+
+    Some(Some(ScrollState {
+        size: PixelsSize::new(
+            element.scroll_width() as f64,
+            element.scroll_height() as f64,
+        ),
+        bounds: PixelsSize::new(
+            element.client_width() as f64,
+            element.client_height() as f64,
+        ),
+        state: PixelsVector2D::new(element.scroll_left() as f64, element.scroll_top() as f64),
+    }))
+}
+
+/// Internal: Returns `None`, only the web renderer can be read outside of its events.
+#[cfg(not(target_arch = "wasm32"))]
+fn read_dom_scroll_state(_data: &MountedData) -> Option<Option<ScrollState>> {
+    None
+}
+
+/// Internal: Returns the client size (`clientWidth`, `clientHeight`: without borders and
+/// scrollbars) of an element of the web renderer.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn client_size(data: &MountedData) -> Option<PixelsSize> {
+    let element = data.downcast::<web_sys::Element>()?;
+
+    Some(PixelsSize::new(
+        element.client_width() as f64,
+        element.client_height() as f64,
+    ))
+}
+
+/// Internal: Returns `None`, only the web renderer exposes the client size.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn client_size(_data: &MountedData) -> Option<PixelsSize> {
+    None
+}
+
+/// Internal: Measures the rect of a floating element, with its layout size instead of
+/// the size of its bounding rect if `untransformed`, see
+/// [FloatingOptions::measure_untransformed]. `None` if it is no longer accessible.
+pub(crate) async fn element_rect(data: &MountedData, untransformed: bool) -> Option<PixelsRect> {
+    let rect = data.get_client_rect().await.ok()?;
+
+    Some(if untransformed {
+        with_layout_size(rect, data)
+    } else {
+        rect
+    })
+}
+
+/// Internal: Returns the rect of an element measured as `rect` with its layout size
+/// instead, kept around the same center. Unchanged if the layout size is not available.
+fn with_layout_size(rect: PixelsRect, data: &MountedData) -> PixelsRect {
+    let size = match data.downcast::<crate::testing::MockElement>() {
+        Some(mock) => mock.layout_size(),
+        None => match layout_size(data) {
+            Some(size) => size,
+            None => return rect,
+        },
+    };
+
+    PixelsRect::new(rect.center() - size.to_vector() / 2_f64, size)
+}
+
+/// Internal: Returns the layout size (`offsetWidth`, `offsetHeight`: before CSS
+/// transforms) of an HTML element of the web renderer.
+#[cfg(target_arch = "wasm32")]
+fn layout_size(data: &MountedData) -> Option<PixelsSize> {
+    use web_sys::wasm_bindgen::JsCast;
+
+    let element = data.downcast::<web_sys::Element>()?;
+    let element = element.dyn_ref::<web_sys::HtmlElement>()?;
+
+    Some(PixelsSize::new(
+        element.offset_width() as f64,
+        element.offset_height() as f64,
+    ))
+}
+
+/// Internal: Returns `None`, only the web renderer exposes the layout size.
+#[cfg(not(target_arch = "wasm32"))]
+fn layout_size(_data: &MountedData) -> Option<PixelsSize> {
+    None
+}
+
+/// Internal: Reads the scroll offset of a container directly instead of from a scroll
+/// event: from the DOM with the web renderer, and from a [crate::testing::MockElement].
+/// `None` for other renderers or if the element was removed.
+#[cfg(feature = "components")]
+pub(crate) fn scroll_offset(data: &MountedData) -> Option<PixelsVector2D> {
+    if let Some(mock) = data.downcast::<crate::testing::MockElement>() {
+        return mock.sample_scroll_offset();
+    }
+
+    read_dom_scroll_offset(data)
+}
+
+/// Internal: Returns the scroll offset (`scrollLeft`, `scrollTop`) of an element of the
+/// web renderer.
+#[cfg(all(feature = "components", target_arch = "wasm32"))]
+fn read_dom_scroll_offset(data: &MountedData) -> Option<PixelsVector2D> {
+    let element = data.downcast::<web_sys::Element>()?;
+
+    Some(PixelsVector2D::new(
+        element.scroll_left() as f64,
+        element.scroll_top() as f64,
+    ))
+}
+
+/// Internal: Returns `None`, only the web renderer exposes the offset outside of events.
+#[cfg(all(feature = "components", not(target_arch = "wasm32")))]
+fn read_dom_scroll_offset(_data: &MountedData) -> Option<PixelsVector2D> {
+    None
+}
+
+/// Internal: Whether the current window behaves like iOS during momentum scrolls, see
+/// [crate::testing::emulate_momentum_pauses].
+#[cfg(feature = "components")]
+#[derive(Default)]
+pub(crate) struct EmulatedMomentumPauses(pub(crate) bool);
+
+/// Internal: Returns `true` if the browser may pause the scroll events of a momentum
+/// scroll, or if the current window emulates it.
+#[cfg(feature = "components")]
+pub(crate) fn pauses_momentum_scroll_events() -> bool {
+    crate::window::with_window(|emulated: &mut EmulatedMomentumPauses| emulated.0)
+        .unwrap_or_default()
+        || browser_pauses_momentum_scroll_events()
+}
+
+/// Internal: Returns `true` on iOS and iPadOS, which reports itself as a Mac with a
+/// touch screen.
+#[cfg(all(feature = "components", target_arch = "wasm32"))]
+fn browser_pauses_momentum_scroll_events() -> bool {
+    let Some(navigator) = web_sys::window().map(|window| window.navigator()) else {
+        return false;
+    };
+    let user_agent = navigator.user_agent().unwrap_or_default();
+
+    ["iPhone", "iPad", "iPod"]
+        .iter()
+        .any(|device| user_agent.contains(device))
+        || (user_agent.contains("Macintosh") && navigator.max_touch_points() > 1)
+}
+
+/// Internal: Returns `false`, the mitigation reads the offset through the web renderer.
+#[cfg(all(feature = "components", not(target_arch = "wasm32")))]
+fn browser_pauses_momentum_scroll_events() -> bool {
+    false
+}
+
+/// Internal: Waits for `ms` milliseconds with the browser timers.
+#[cfg(target_arch = "wasm32")]
+async fn timeout(ms: u32) {
+    gloo_timers::future::TimeoutFuture::new(ms).await;
+}
+
+/// Internal: Waits for `ms` milliseconds with the timers of the renderer's webview.
+#[cfg(not(target_arch = "wasm32"))]
+async fn timeout(ms: u32) {
+    let mut eval = document::eval(&timeout_script(ms));
+    let _ = eval.recv::<bool>().await;
+}
+
+/// Internal: The scroll container a placement is measured against.
+#[derive(Debug, Clone)]
+pub(crate) enum Container {
+    /// The element of a [crate::ScrollableView], measured with the other rects.
+    Mounted(Rc<MountedData>),
+    /// The rect of the container of a [crate::ScrollStateProvider], as provided.
+    Provided(PixelsRect),
+}
+
+impl Container {
+    /// Internal: Returns the rect of the container, or its bounds at the origin if it
+    /// cannot be measured.
+    pub(crate) async fn rect(&self, scroll_state: &ScrollState) -> PixelsRect {
+        match self {
+            Container::Mounted(scrollable) => {
+                scrollable
+                    .get_client_rect()
+                    .await
+                    .unwrap_or(PixelsRect::new(
+                        PixelsVector2D::new(0_f64, 0_f64).to_point(),
+                        scroll_state.bounds,
+                    ))
+            }
+            Container::Provided(rect) => *rect,
+        }
+    }
+}
+
+impl Floating {
+    /// Returns the counters (measured and deferred placements, time spent) of the last
+    /// frame with time-sliced measurements in the current window, see
+    /// [FloatingBuilder::frame_budget]. The placements of each window share a budget.
+    pub fn frame_stats(&self) -> FrameStats {
+        crate::scheduler::last_frame_stats()
+    }
+
+    /// Waits according to the engine's [WaitStrategy].
+    pub async fn wait(&self) {
+        self.wait_strategy().wait().await
+    }
+
+    /// Measures the trigger and its [crate::ScrollableView] ahead of an open, e.g., on
+    /// `pointerenter` or `focus`, so the first measurement after the open only needs to
+    /// measure the floating element (one round trip less on desktop).
+    ///
+    /// The rects are used once, by the next placement of the trigger in the nearest
+    /// view, and only while they are fresh: measured less than 2s ago, before the last
+    /// scroll or layout invalidation of the view. Otherwise the open measures as usual.
+    /// Does nothing outside of a view. [crate::WidgetConfig::prewarm] calls it for you.
+    ///
+    /// # Example
+    ///
+    /// With the [crate::testing] mocks, the open uses the rect measured while hovering.
+    ///
+    /// ```rust
+    /// use std::cell::Cell;
+    ///
+    /// use dioxus::prelude::*;
+    /// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+    /// use dioxus_floating::{FloatingOptions, Offset, use_floating, use_placement};
+    ///
+    /// thread_local! {
+    ///     static TRIGGER: MockElement = MockElement::new(rect(100.0, 100.0, 50.0, 20.0));
+    ///     static Y: Cell<f64> = Cell::new(0.0);
+    ///     static SIGNALS: Cell<Option<(Signal<bool>, Signal<bool>)>> = Cell::new(None);
+    /// }
+    ///
+    /// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// }
+    ///
+    /// fn app() -> Element {
+    ///     use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+    ///     let floating = use_floating();
+    ///     let trigger = use_hook(|| TRIGGER.with(MockElement::mounted));
+    ///     let trigger_ref = use_signal(|| Some(trigger.clone()));
+    ///     let mut element_ref = use_signal(|| None);
+    ///     let is_hovered = use_signal(|| false);
+    ///     let is_open = use_signal(|| false);
+    ///     SIGNALS.with(|signals| signals.set(Some((is_hovered, is_open))));
+    ///     let options = FloatingOptions { placement_offset: Some(Offset::zero()), ..Default::default() };
+    ///     let placement = use_placement(element_ref, trigger_ref, options);
+    ///
+    ///     use_effect(move || {
+    ///         if is_hovered() {
+    ///             floating.prewarm(trigger.clone());
+    ///         }
+    ///     });
+    ///     use_effect(move || {
+    ///         element_ref.set(is_open().then(|| MockElement::new(rect(0.0, 0.0, 80.0, 40.0)).mounted()));
+    ///     });
+    ///     use_effect(move || Y.with(|y| y.set(placement().y)));
+    ///     rsx! {}
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild_in_place();
+    /// settle(&mut dom);
+    /// let (mut is_hovered, mut is_open) = SIGNALS.with(Cell::get).unwrap();
+    ///
+    /// dom.in_runtime(|| is_hovered.set(true));
+    /// settle(&mut dom);
+    /// // the trigger moves without a scroll, the pre-measured rect is still trusted
+    /// TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 300.0, 50.0, 20.0)));
+    /// dom.in_runtime(|| is_open.set(true));
+    /// settle(&mut dom);
+    /// assert_eq!(Y.with(Cell::get), 120.0);
+    ///
+    /// // used once: reopening measures the trigger again
+    /// dom.in_runtime(|| is_open.set(false));
+    /// settle(&mut dom);
+    /// dom.in_runtime(|| is_open.set(true));
+    /// settle(&mut dom);
+    /// assert_eq!(Y.with(Cell::get), 320.0);
+    /// ```
+    pub fn prewarm(&self, trigger: Rc<MountedData>) {
+        let Some(context) = try_consume_context::<ScrollableContext>() else {
+            return;
+        };
+
+        spawn(async move {
+            let (Ok(scroll_state), Ok(scrollable), Ok(layout_generation)) = (
+                context.scroll_state.try_peek().map(|state| *state),
+                context.scrollable_ref.try_peek().map(|data| data.clone()),
+                context
+                    .layout_generation
+                    .try_peek()
+                    .map(|generation| *generation),
+            ) else {
+                return;
+            };
+            let (Some(scroll_state), Some(scrollable)) = (scroll_state, scrollable) else {
+                return;
+            };
+
+            let Ok(scrollable_rect) = scrollable.get_client_rect().await else {
+                return;
+            };
+            let Ok(trigger_rect) = trigger.get_client_rect().await else {
+                return;
+            };
+            crate::prewarm::store(crate::prewarm::Prewarmed {
+                trigger: Rc::downgrade(&trigger),
+                scrollable: Rc::downgrade(&scrollable),
+                layout_generation,
+                scroll_state,
+                measured_at: crate::scrollable_view::now_ms(),
+                trigger_rect,
+                scrollable_rect,
+            });
+        });
+    }
+
+    /// Measures `window.devicePixelRatio` and caches it for [Rounding::DevicePixel], per
+    /// window.
+    ///
+    /// Returns the cached value (1.0 by default) if the measurement fails.
+    pub async fn refresh_device_pixel_ratio(&self) -> f64 {
+        let mut eval = document::eval("dioxus.send(window.devicePixelRatio || 1);");
+        if let Ok(dpr) = eval.recv::<f64>().await {
+            set_device_pixel_ratio(dpr);
+        }
+
+        device_pixel_ratio()
+    }
+
+    /// Asynchronously captures the initial [ScrollState] from a mounted element.
+    ///
+    /// This method is usually called once when the [ScrollableView] is first mounted
+    /// or when its underlying DOM element changes, and by [ScrollableContext::reload].
+    /// The web renderer reads the client size, scroll size and scroll offset in one step;
+    /// other renderers take a round trip for each of the client rect, scroll size and
+    /// scroll offset. The debug log reports which path was taken.
+    ///
+    /// Returns a default state (zeros) if the element is no longer accessible.
+    ///
+    /// # Example
+    ///
+    /// The [crate::testing] mocks read it in one step as well:
+    ///
+    /// ```rust
+    /// use std::pin::pin;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::Floating;
+    /// use dioxus_floating::testing::MockElement;
+    ///
+    /// let view = MockElement::new(PixelsRect::new(
+    ///     PixelsVector2D::zero().to_point(),
+    ///     PixelsSize::new(400.0, 300.0),
+    /// ));
+    /// view.set_scroll_size(PixelsSize::new(400.0, 1200.0));
+    ///
+    /// // nothing to wait for: the state is ready on the first poll
+    /// let floating = Floating::default();
+    /// let mut measure = pin!(floating.generate_scroll_state_from_mounted(view.mounted()));
+    /// let Poll::Ready(state) = measure.as_mut().poll(&mut Context::from_waker(Waker::noop())) else {
+    ///     panic!("the mock is read in one step");
+    /// };
+    /// assert_eq!(state.size, PixelsSize::new(400.0, 1200.0));
+    /// assert_eq!(state.bounds, PixelsSize::new(400.0, 300.0));
+    /// assert_eq!(view.round_trips(), 1);
+    /// ```
+    pub async fn generate_scroll_state_from_mounted(&self, data: Rc<MountedData>) -> ScrollState {
+        self.try_generate_scroll_state_from_mounted(data)
+            .await
+            .unwrap_or(ScrollState {
+                size: PixelsSize::zero(),
+                bounds: PixelsSize::zero(),
+                state: PixelsVector2D::zero(),
+            })
+    }
+
+    /// Internal: Same as [Floating::generate_scroll_state_from_mounted], but returns `None`
+    /// instead of a zeroed state if the element is gone (e.g., unmounted during teardown).
+    pub(crate) async fn try_generate_scroll_state_from_mounted(
+        &self,
+        data: Rc<MountedData>,
+    ) -> Option<ScrollState> {
+        if let Some(state) = read_scroll_state(&data) {
+            tracing::debug!("Scroll state read in one step: {state:?}");
+            return state;
+        }
+
+        // the element was removed: skip the remaining calls, the failure is expected
+        let rect = match data.get_client_rect().await {
+            Ok(rect) => rect,
+            Err(err) => {
+                tracing::debug!("Skipping the scroll state of a removed element: {err}");
+                return None;
+            }
+        };
+        let scroll = data.get_scroll_size().await;
+        let offset = data.get_scroll_offset().await;
+
+        let size = scroll
+            .map(|s| PixelsSize::new(s.width, s.height))
+            .unwrap_or(PixelsSize::new(0_f64, 0_f64));
+        let bounds = client_size(&data).unwrap_or(PixelsSize::new(rect.width(), rect.height()));
+        let state = offset
+            .map(|o| PixelsVector2D::new(o.x, o.y))
+            .unwrap_or(PixelsVector2D::new(0_f64, 0_f64));
+
+        let state = ScrollState {
+            size,
+            bounds,
+            state,
+        };
+        tracing::debug!("Scroll state measured in 3 round trips: {state:?}");
+
+        Some(state)
+    }
+
+    /// Returns the coordinates of [Floating::compute_on_point].
+    ///
+    /// Kept with its original signature for existing code, see `examples/migration.rs`.
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `compute_on_point` with the same arguments and read `x` and `y` of the returned `PlacementOutput`"
+    )]
+    pub async fn placement_on_point(
+        &self,
+        scroll_state: ScrollState,
+        scrollable_ref: Rc<MountedData>,
+        element_ref: Rc<MountedData>,
+        trigger: ClientPoint,
+        options: FloatingOptions,
+    ) -> (f64, f64) {
+        let output = self
+            .compute_on_point(scroll_state, scrollable_ref, element_ref, trigger, options)
+            .await;
+
+        (output.x, output.y)
+    }
+
+    /// Calculates the optimal position for a floating element anchored to a specific point (e.g., a mouse click).
+    ///
+    /// This method treats the input [ClientPoint] as a 1x1 pixel trigger. It is ideal for
+    /// context menus where the anchor position is dynamic and precise.
+    ///
+    /// The coordinates of the returned [PlacementOutput] are relative to the viewport and
+    /// are ready for use with `position: fixed` and `transform: translate3d`.
+    pub async fn compute_on_point(
+        &self,
+        scroll_state: ScrollState,
+        scrollable_ref: Rc<MountedData>,
+        element_ref: Rc<MountedData>,
+        trigger: ClientPoint,
+        options: FloatingOptions,
+    ) -> PlacementOutput {
+        let (scrollable_rect, element_rect, trigger_rect) = self
+            .measure_on_point(
+                scroll_state,
+                Container::Mounted(scrollable_ref),
+                element_ref,
+                trigger,
+                options.measure_untransformed,
+            )
+            .await;
+
+        self.compute_measured(scrollable_rect, element_rect, trigger_rect, options)
+    }
+
+    /// Internal: Measures the scrollable and element rects of a point placement.
+    ///
+    /// The point is returned as a 1x1 trigger rect.
+    pub(crate) async fn measure_on_point(
+        &self,
+        scroll_state: ScrollState,
+        container: Container,
+        element_ref: Rc<MountedData>,
+        trigger: ClientPoint,
+        untransformed: bool,
+    ) -> (PixelsRect, Option<PixelsRect>, PixelsRect) {
+        let scrollable_rect = container.rect(&scroll_state).await;
+        let trigger_rect = PixelsRect::new(
+            PixelsVector2D::new(trigger.x, trigger.y).to_point(),
+            PixelsSize::new(1_f64, 1_f64),
+        );
+        let element_rect = element_rect(&element_ref, untransformed).await;
+
+        (scrollable_rect, element_rect, trigger_rect)
+    }
+
+    /// Returns the coordinates of [Floating::compute_on_trigger].
+    ///
+    /// Kept with its original signature for existing code, see `examples/migration.rs`.
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `compute_on_trigger` with the same arguments and read `x` and `y` of the returned `PlacementOutput`"
+    )]
+    pub async fn placement_on_trigger(
+        &self,
+        scroll_state: ScrollState,
+        scrollable_ref: Rc<MountedData>,
+        element_ref: Rc<MountedData>,
+        trigger_ref: Rc<MountedData>,
+        options: FloatingOptions,
+    ) -> (f64, f64) {
+        let output = self
+            .compute_on_trigger(
+                scroll_state,
+                scrollable_ref,
+                element_ref,
+                trigger_ref,
+                options,
+            )
+            .await;
+
+        (output.x, output.y)
+    }
+
+    /// Calculates the optimal position for a floating element anchored to another DOM element (e.g., a button).
+    ///
+    /// This method measures the actual dimensions of the trigger element via `get_client_rect()`.
+    /// It is designed for standard dropdown menus, tooltips, and popovers where
+    /// the floating element needs to align perfectly with its anchor.
+    ///
+    /// The coordinates of the returned [PlacementOutput] are viewport-relative.
+    pub async fn compute_on_trigger(
+        &self,
+        scroll_state: ScrollState,
+        scrollable_ref: Rc<MountedData>,
+        element_ref: Rc<MountedData>,
+        trigger_ref: Rc<MountedData>,
+        options: FloatingOptions,
+    ) -> PlacementOutput {
+        let (scrollable_rect, element_rect, trigger_rect) = self
+            .measure_on_trigger(
+                scroll_state,
+                Container::Mounted(scrollable_ref),
+                element_ref,
+                trigger_ref,
+                options.measure_untransformed,
+            )
+            .await;
+        let trigger_rect = trigger_rect.unwrap_or(PixelsRect::new(
+            PixelsVector2D::new(0_f64, 0_f64).to_point(),
+            PixelsSize::new(1_f64, 1_f64),
+        ));
+
+        self.compute_measured(scrollable_rect, element_rect, trigger_rect, options)
+    }
+
+    /// Previews the placement of an element that is not rendered yet, e.g., to decide
+    /// in an `onclick` handler whether a menu should open upwards.
+    ///
+    /// Only the scrollable container and the trigger are measured; `assumed_element_size`
+    /// replaces the element rect, and the full middleware pipeline runs as usual. Pass
+    /// the resolved [PlacementOutput::placement] into the [FloatingOptions] of the element
+    /// to avoid a flip right after it opens.
+    ///
+    /// The preview may differ from the real result if the assumed size is wrong.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::rc::Rc;
+    ///
+    /// use dioxus::prelude::*;
+    /// use dioxus::html::geometry::PixelsSize;
+    /// use dioxus_floating::{FloatingOptions, Placement, use_floating, use_scroll_context};
+    ///
+    /// #[component]
+    /// fn MenuButton() -> Element {
+    ///     let floating = use_floating();
+    ///     let ctx = use_scroll_context();
+    ///     let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    ///     let mut placement = use_signal(|| None::<Placement>);
+    ///
+    ///     rsx! {
+    ///         button {
+    ///             onmounted: move |evt| trigger_ref.set(Some(evt.data())),
+    ///             onclick: move |_| async move {
+    ///                 let (Some(scrollable), Some(trigger)) = ((ctx.scrollable_ref)(), trigger_ref()) else {
+    ///                     return;
+    ///                 };
+    ///                 let size = PixelsSize::new(200.0, 300.0);
+    ///                 let output = floating
+    ///                     .preview_placement(scrollable, trigger, size, FloatingOptions::default())
+    ///                     .await;
+    ///                 // open the menu on the resolved side right away
+    ///                 placement.set(Some(output.placement));
+    ///             },
+    ///             "Menu"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub async fn preview_placement(
+        &self,
+        scrollable_ref: Rc<MountedData>,
+        trigger_ref: Rc<MountedData>,
+        assumed_element_size: PixelsSize,
+        options: FloatingOptions,
+    ) -> PlacementOutput {
+        let scrollable_rect = scrollable_ref.get_client_rect().await.unwrap_or_default();
+        let trigger_rect = trigger_ref
+            .get_client_rect()
+            .await
+            .unwrap_or(PixelsRect::new(
+                PixelsVector2D::new(0_f64, 0_f64).to_point(),
+                PixelsSize::new(1_f64, 1_f64),
+            ));
+        let element_rect = PixelsRect::new(
+            PixelsVector2D::new(0_f64, 0_f64).to_point(),
+            assumed_element_size,
+        );
+
+        self.compute_placement(scrollable_rect, element_rect, trigger_rect, options)
+    }
+
+    /// Internal: Measures the scrollable and element rects of a range placement and the
+    /// bounding box of its endpoints.
+    ///
+    /// The range rect is `None` if neither endpoint is accessible; the flag is `true`
+    /// if only one of them is.
+    pub(crate) async fn measure_on_range(
+        &self,
+        scroll_state: ScrollState,
+        container: Container,
+        element_ref: Rc<MountedData>,
+        start_ref: Option<Rc<MountedData>>,
+        end_ref: Option<Rc<MountedData>>,
+        untransformed: bool,
+    ) -> (PixelsRect, Option<PixelsRect>, Option<(PixelsRect, bool)>) {
+        let scrollable_rect = container.rect(&scroll_state).await;
+        let start_rect = match start_ref {
+            Some(start) => start.get_client_rect().await.ok(),
+            None => None,
+        };
+        let end_rect = match end_ref {
+            Some(end) => end.get_client_rect().await.ok(),
+            None => None,
+        };
+        let element_rect = element_rect(&element_ref, untransformed).await;
+
+        let range_rect = match (start_rect, end_rect) {
+            (Some(start), Some(end)) => Some((start.union(&end), false)),
+            (Some(single), None) | (None, Some(single)) => Some((single, true)),
+            (None, None) => None,
+        };
+
+        (scrollable_rect, element_rect, range_rect)
+    }
+
+    /// Internal: Measures the scrollable, element and trigger rects of a trigger placement.
+    ///
+    /// The element and trigger rects are `None` if they are no longer accessible.
+    pub(crate) async fn measure_on_trigger(
+        &self,
+        scroll_state: ScrollState,
+        container: Container,
+        element_ref: Rc<MountedData>,
+        trigger_ref: Rc<MountedData>,
+        untransformed: bool,
+    ) -> (PixelsRect, Option<PixelsRect>, Option<PixelsRect>) {
+        let scrollable_rect = container.rect(&scroll_state).await;
+        let trigger_rect = trigger_ref.get_client_rect().await.ok();
+        let element_rect = element_rect(&element_ref, untransformed).await;
+
+        (scrollable_rect, element_rect, trigger_rect)
+    }
+
+    /// Internal: Computes the placement from measured rects, falling back to the
+    /// trigger corner if the element could not be measured.
+    pub(crate) fn compute_measured(
+        &self,
+        scrollable: PixelsRect,
+        element: Option<PixelsRect>,
+        trigger: PixelsRect,
+        options: FloatingOptions,
+    ) -> PlacementOutput {
+        match element {
+            Some(element) => self.compute_placement(scrollable, element, trigger, options),
+            None => PlacementOutput {
+                x: trigger.min_x(),
+                y: trigger.min_y(),
+                placement: options.placement,
+                trigger_rect: trigger,
+                ..Default::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::cell::Cell;
+
+use dioxus::prelude::*;
+
+use crate::listener::SharedListener;
+
+const REDUCED_MOTION_SCRIPT: &str = r#"
+    const query = window.matchMedia("(prefers-reduced-motion: reduce)");
+    const report = () => dioxus.send(query.matches);
+    // the OS setting may change while the page is open
+    query.addEventListener("change", report);
+    report();
+    await dioxus.recv();
+    query.removeEventListener("change", report);
+"#;
+
+// shared by every consumer of the preference in a window
+static REDUCED_MOTION: SharedListener<bool, bool> =
+    SharedListener::new(REDUCED_MOTION_SCRIPT, |reduce| reduce);
+
+thread_local! {
+    // see set_reduced_motion_override
+    static OVERRIDE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Returns `true` if the user asked to minimize animations (`prefers-reduced-motion:
+/// reduce`), or the value of [set_reduced_motion_override].
+///
+/// The animated helpers consult it when they start an animation: smooth scrolls of
+/// [crate::ScrollableContext] become instant, [crate::SuppressTransitions] disables the
+/// transitions of every move and [crate::FloatingState] skips [crate::Phase::Closing].
+/// Each of them can opt out per call.
+///
+/// The media query is read by a single listener shared by the views, the placement hooks
+/// with [crate::FloatingOptions::suppress_transitions], [crate::use_floating_state] and
+/// [use_reduced_motion], and follows changes of the OS setting. `false` while none of
+/// them is mounted and outside of the web.
+pub fn prefers_reduced_motion() -> bool {
+    resolve(REDUCED_MOTION.peek().flatten())
+}
+
+/// Forces the result of [prefers_reduced_motion] (and [use_reduced_motion]) on the current
+/// thread, e.g., in tests or on targets without the media query; `None` reads the media
+/// query again.
+pub fn set_reduced_motion_override(reduce: Option<bool>) {
+    OVERRIDE.with(|value| value.set(reduce));
+}
+
+/// Returns [prefers_reduced_motion] as a signal that follows changes of the OS setting,
+/// e.g., to skip an animation of your own.
+///
+/// # Example
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_floating::use_reduced_motion;
+///
+/// #[component]
+/// fn Panel() -> Element {
+///     let reduce = use_reduced_motion();
+///     let transition = if reduce() { "none" } else { "opacity 150ms" };
+///     rsx! { div { style: "transition: {transition}" } }
+/// }
+/// ```
+pub fn use_reduced_motion() -> ReadSignal<bool> {
+    let reported = use_motion_listener(true);
+    use_memo(move || resolve(reported.and_then(|reported| reported()))).into()
+}
+
+/// Internal: Keeps the shared media query listener running while the component is
+/// mounted; `None` (and no listener) unless `enabled`. Read the returned signal to follow
+/// changes of the setting.
+pub(crate) fn use_motion_listener(enabled: bool) -> Option<Signal<Option<bool>>> {
+    let reported = use_hook(move || enabled.then(|| REDUCED_MOTION.subscribe()).flatten());
+    use_drop(move || {
+        if reported.is_some() {
+            REDUCED_MOTION.unsubscribe();
+        }
+    });
+
+    reported
+}
+
+/// Internal: Returns the preference from the reported media query, unless overridden.
+pub(crate) fn resolve(reported: Option<bool>) -> bool {
+    OVERRIDE.with(Cell::get).or(reported).unwrap_or_default()
+}
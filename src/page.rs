@@ -0,0 +1,86 @@
+use dioxus::prelude::*;
+
+use crate::Floating;
+use crate::listener::SharedListener;
+
+const PAGE_VISIBILITY_SCRIPT: &str = r#"
+    // a page that becomes visible fires the event before the animation frame callbacks
+    // of its first visible frame, so work waiting for the next frame lands before paint
+    const report = () => dioxus.send(document.visibilityState === "hidden");
+    document.addEventListener("visibilitychange", report);
+    report();
+    await dioxus.recv();
+    document.removeEventListener("visibilitychange", report);
+"#;
+
+// shared by every placement hook and view of a window
+static PAGE_VISIBILITY: SharedListener<bool, bool> =
+    SharedListener::new(PAGE_VISIBILITY_SCRIPT, |hidden| hidden);
+
+impl Floating {
+    /// Returns `true` while the page of the current window is hidden (Page Visibility
+    /// API), e.g., in a background tab or a minimized window.
+    ///
+    /// While suspended, the placement hooks measure nothing: their invalidations are
+    /// collapsed into one recompute on the first frame after the page is shown again,
+    /// before it is painted. The per-frame sampling of [crate::AutoUpdate] and of
+    /// [crate::ScrollableView] pauses as well. Check it to skip work of your own, or
+    /// follow it with [use_page_suspended].
+    ///
+    /// The visibility is read by a single listener shared by the placement hooks and the
+    /// views of the window. `false` while none of them is mounted and outside of the web.
+    pub fn is_suspended(&self) -> bool {
+        is_suspended()
+    }
+}
+
+/// Returns [Floating::is_suspended] as a signal that follows the visibility of the page,
+/// e.g., to pause an animation of your own in a background tab.
+///
+/// # Example
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_floating::use_page_suspended;
+///
+/// #[component]
+/// fn Spinner() -> Element {
+///     let suspended = use_page_suspended();
+///     let state = if suspended() { "paused" } else { "running" };
+///     rsx! { div { style: "animation-play-state: {state}" } }
+/// }
+/// ```
+pub fn use_page_suspended() -> ReadSignal<bool> {
+    let reported = use_page_listener();
+    use_memo(move || reported().unwrap_or_default()).into()
+}
+
+/// Internal: Keeps the shared page visibility listener running while the component is
+/// mounted. Read the returned signal, `Some(true)` while the page is hidden, to follow
+/// the changes.
+pub(crate) fn use_page_listener() -> Signal<Option<bool>> {
+    let reported = use_hook(|| {
+        PAGE_VISIBILITY
+            .subscribe()
+            .unwrap_or_else(|| Signal::new(None))
+    });
+    use_drop(|| PAGE_VISIBILITY.unsubscribe());
+
+    reported
+}
+
+/// Internal: [Floating::is_suspended] without an engine, e.g., in a sampling loop.
+pub(crate) fn is_suspended() -> bool {
+    PAGE_VISIBILITY.peek().flatten().unwrap_or_default()
+}
+
+/// Internal: The signal of the running listener of the current window without
+/// subscribing, for code outside of components (e.g., a placement stream).
+pub(crate) fn running_listener() -> Option<Signal<Option<bool>>> {
+    PAGE_VISIBILITY.running()
+}
+
+/// Internal: Reports a visibility change as the listener would, see
+/// [crate::testing::set_page_hidden].
+pub(crate) fn report(hidden: bool) {
+    PAGE_VISIBILITY.report(hidden);
+}
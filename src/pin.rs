@@ -0,0 +1,268 @@
+use dioxus::html::events as html_events;
+use dioxus::html::geometry::{PixelsRect, PixelsVector2D};
+use dioxus::prelude::*;
+
+use crate::FloatingResult;
+use crate::announce::use_live_region;
+use crate::listener::JsListener;
+
+/// Options for [use_pinned_panel].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinOptions {
+    /// How far an arrow key moves the panel, in CSS pixels. Defaults to 8.
+    pub step: f64,
+    /// How far an arrow key moves the panel with `Shift` held, in CSS pixels.
+    /// Defaults to 40.
+    pub large_step: f64,
+    /// The rect the pinned panel is kept inside of (in the coordinates of the placement,
+    /// e.g., the viewport for fixed placements). A panel larger than it is aligned to its
+    /// top left corner. Not clamped if `None` (the default).
+    pub bounds: Option<PixelsRect>,
+    /// The message announced through an `aria-live` region when the panel is moved or
+    /// returns to its anchor. `{x}` and `{y}` are replaced with the new position, rounded
+    /// to pixels. Nothing is announced if `None` (the default).
+    pub announce: Option<String>,
+}
+
+impl Default for PinOptions {
+    fn default() -> Self {
+        Self {
+            step: 8_f64,
+            large_step: 40_f64,
+            bounds: None,
+            announce: None,
+        }
+    }
+}
+
+/// A floating panel the user can move away from its anchored position, see
+/// [use_pinned_panel].
+#[derive(Clone, Copy)]
+pub struct PinnedPanel {
+    /// The placement to render: the anchored one, or the pinned position while pinned.
+    pub result: ReadSignal<FloatingResult>,
+    /// The pinned position, `None` while the panel follows its anchor.
+    pub pinned: ReadSignal<Option<PixelsVector2D>>,
+    position: Signal<Option<PixelsVector2D>>,
+    placement: ReadSignal<FloatingResult>,
+    options: CopyValue<PinOptions>,
+    live_region: Option<JsListener>,
+}
+
+impl PinnedPanel {
+    /// Pins the panel at `position`, kept inside [PinOptions::bounds].
+    pub fn pin(&mut self, position: PixelsVector2D) {
+        let position = self.clamp(position);
+        if *self.position.peek() != Some(position) {
+            self.position.set(Some(position));
+            self.announce(position);
+        }
+    }
+
+    /// Returns the panel to its anchored position.
+    pub fn unpin(&mut self) {
+        if self.position.peek().is_some() {
+            self.position.set(None);
+            let placement = *self.placement.peek();
+            self.announce(PixelsVector2D::new(placement.x, placement.y));
+        }
+    }
+
+    /// Moves the panel by `delta`, pinning it where it currently is first.
+    pub fn nudge(&mut self, delta: PixelsVector2D) {
+        let current = self.position.peek().unwrap_or_else(|| {
+            let placement = self.placement.peek();
+            PixelsVector2D::new(placement.x, placement.y)
+        });
+        self.pin(current + delta);
+    }
+
+    /// Handles a key pressed on the move handle and returns `true` if it was used.
+    ///
+    /// The arrow keys [PinnedPanel::nudge] the panel by [PinOptions::step] (or
+    /// [PinOptions::large_step] with `shift`), `Escape` unpins a pinned panel. Keys are
+    /// ignored until the placement is ready.
+    pub fn handle_key(&mut self, key: &Key, shift: bool) -> bool {
+        if !self.placement.peek().is_ready {
+            return false;
+        }
+        if *key == Key::Escape {
+            let is_pinned = self.position.peek().is_some();
+            self.unpin();
+            return is_pinned;
+        }
+
+        let options = self.options.read();
+        let step = if shift {
+            options.large_step
+        } else {
+            options.step
+        };
+        let delta = match key {
+            Key::ArrowLeft => PixelsVector2D::new(-step, 0_f64),
+            Key::ArrowRight => PixelsVector2D::new(step, 0_f64),
+            Key::ArrowUp => PixelsVector2D::new(0_f64, -step),
+            Key::ArrowDown => PixelsVector2D::new(0_f64, step),
+            _ => return false,
+        };
+        drop(options);
+        self.nudge(delta);
+
+        true
+    }
+
+    /// Returns the attributes and the `keydown` handler for the move handle of the panel.
+    ///
+    /// Handled keys do not scroll the page and do not reach the panel, so `Escape` on a
+    /// pinned handle unpins it instead of closing a [crate::FloatingWidget].
+    pub fn handle_props(&self) -> Vec<Attribute> {
+        let mut panel = *self;
+
+        vec![
+            Attribute::new("tabindex", "0", None, false),
+            Attribute::new(
+                "aria-keyshortcuts",
+                "ArrowLeft ArrowRight ArrowUp ArrowDown Escape",
+                None,
+                false,
+            ),
+            html_events::onkeydown(move |evt: KeyboardEvent| {
+                if panel.handle_key(&evt.key(), evt.modifiers().shift()) {
+                    evt.prevent_default();
+                    evt.stop_propagation();
+                }
+            }),
+        ]
+    }
+
+    /// Internal: Returns `position` kept inside the bounds.
+    fn clamp(&self, position: PixelsVector2D) -> PixelsVector2D {
+        let Some(bounds) = self.options.read().bounds else {
+            return position;
+        };
+        let size = self.placement.peek().element_size;
+        let clamp = |value: f64, min: f64, max: f64, length: f64| value.min(max - length).max(min);
+
+        PixelsVector2D::new(
+            clamp(position.x, bounds.min_x(), bounds.max_x(), size.width),
+            clamp(position.y, bounds.min_y(), bounds.max_y(), size.height),
+        )
+    }
+
+    /// Internal: Announces a new position, if enabled.
+    fn announce(&self, position: PixelsVector2D) {
+        if let (Some(live_region), Some(template)) =
+            (self.live_region, self.options.read().announce.as_ref())
+        {
+            live_region.send(
+                template
+                    .replace("{x}", &position.x.round().to_string())
+                    .replace("{y}", &position.y.round().to_string()),
+            );
+        }
+    }
+}
+
+/// Lets the user pin a floating panel (e.g., a palette or an inspector) away from its
+/// anchored position with the keyboard.
+///
+/// `placement` is the anchored placement, e.g., from [crate::use_placement]; render
+/// [PinnedPanel::result] instead. With the props of [PinnedPanel::handle_props] on a
+/// focusable move handle, the arrow keys move the panel by [PinOptions::step] (more with
+/// `Shift`), kept inside [PinOptions::bounds], and `Escape` returns it to its anchor.
+/// Pointer dragging can move it with [PinnedPanel::pin].
+///
+/// The return to the anchor is a single change of the result, like any other placement
+/// change, so a CSS transition of `top` and `left` (or `transform`) on the panel animates
+/// it instead of jumping. Moves and returns are announced if [PinOptions::announce] is set.
+///
+/// # Example
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::settle;
+/// use dioxus_floating::{FloatingResult, PinOptions, PinnedPanel, use_pinned_panel};
+///
+/// thread_local! {
+///     static PANEL: Cell<Option<PinnedPanel>> = Cell::new(None);
+/// }
+///
+/// fn app() -> Element {
+///     // the anchored placement, as use_placement would return it
+///     let placement = use_signal(|| FloatingResult {
+///         x: 100.0,
+///         y: 120.0,
+///         is_ready: true,
+///         element_size: PixelsSize::new(200.0, 100.0),
+///         ..Default::default()
+///     });
+///     let panel = use_pinned_panel(
+///         placement.into(),
+///         PinOptions {
+///             bounds: Some(PixelsRect::new(
+///                 PixelsVector2D::zero().to_point(),
+///                 PixelsSize::new(400.0, 300.0),
+///             )),
+///             ..Default::default()
+///         },
+///     );
+///     PANEL.with(|cell| cell.set(Some(panel)));
+///     rsx! {}
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// let mut panel = PANEL.with(Cell::get).unwrap();
+/// // presses `key` on the handle, returns if it was handled and the rendered position
+/// let mut press = move |dom: &mut VirtualDom, key: Key, shift: bool| {
+///     let handled = dom.in_runtime(|| panel.handle_key(&key, shift));
+///     settle(dom);
+///     let result = dom.in_runtime(|| *panel.result.peek());
+///     (handled, result.x, result.y)
+/// };
+///
+/// // arrow keys pin and move it, Shift moves it further
+/// assert_eq!(press(&mut dom, Key::ArrowRight, false), (true, 108.0, 120.0));
+/// assert_eq!(press(&mut dom, Key::ArrowDown, true), (true, 108.0, 160.0));
+///
+/// // it stays inside the bounds
+/// press(&mut dom, Key::ArrowDown, true);
+/// assert_eq!(press(&mut dom, Key::ArrowDown, true), (true, 108.0, 200.0));
+///
+/// // Escape returns it to its anchor; once unpinned, Escape is left to the panel
+/// assert_eq!(press(&mut dom, Key::Escape, false), (true, 100.0, 120.0));
+/// assert!(!press(&mut dom, Key::Escape, false).0);
+/// ```
+pub fn use_pinned_panel(placement: ReadSignal<FloatingResult>, options: PinOptions) -> PinnedPanel {
+    let live_region = use_live_region(options.announce.is_some());
+    let mut latest_options = use_hook(|| CopyValue::new(options.clone()));
+    if *latest_options.peek() != options {
+        latest_options.set(options);
+    }
+    let position = use_signal(|| Option::<PixelsVector2D>::None);
+    let result = use_memo(move || {
+        let placement = placement();
+        match position() {
+            Some(position) if placement.is_ready => FloatingResult {
+                x: position.x,
+                y: position.y,
+                // moved away from the trigger, nothing to point at
+                arrow_position: None,
+                ..placement
+            },
+            _ => placement,
+        }
+    });
+
+    PinnedPanel {
+        result: result.into(),
+        pinned: position.into(),
+        position,
+        placement,
+        options: latest_options,
+        live_region,
+    }
+}
@@ -0,0 +1,77 @@
+use dioxus::html::geometry::ClientPoint;
+use dioxus::prelude::*;
+
+use crate::listener::SharedListener;
+
+const POINTER_SCRIPT: &str = r#"
+    // report at most once per frame, with the latest position
+    let latest = null;
+    let frame = 0;
+    const move = (event) => {
+        latest = [event.clientX, event.clientY];
+        if (frame) return;
+        frame = requestAnimationFrame(() => {
+            frame = 0;
+            dioxus.send(latest);
+        });
+    };
+    document.addEventListener("pointermove", move, { passive: true });
+    await dioxus.recv();
+    document.removeEventListener("pointermove", move);
+    if (frame) cancelAnimationFrame(frame);
+"#;
+
+// shared by every use_pointer_position hook of a window
+static POINTER: SharedListener<(f64, f64), ClientPoint> =
+    SharedListener::new(POINTER_SCRIPT, |(x, y)| ClientPoint::new(x, y));
+
+/// Returns the latest client coordinates of the pointer anywhere in the document, or
+/// `None` until it moved while tracked.
+///
+/// Useful to open a floating element at the cursor without a triggering mouse event,
+/// e.g., a command palette opened with a keyboard shortcut, combined with
+/// [crate::use_placement_on_point].
+///
+/// All hook instances share a single `pointermove` listener on the document, installed
+/// by the first one and removed when the last one unmounts. The position is published
+/// at most once per animation frame.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::ClientPoint;
+/// use dioxus_floating::{FloatingOptions, use_placement_on_point, use_pointer_position};
+///
+/// #[component]
+/// fn Palette() -> Element {
+///     let pointer = use_pointer_position();
+///     let mut opened_at = use_signal(|| Option::<ClientPoint>::None);
+///     let mut element_ref = use_signal(|| None);
+///     let placement = use_placement_on_point(element_ref, opened_at, FloatingOptions::default());
+///
+///     rsx! {
+///         div {
+///             tabindex: 0,
+///             onkeydown: move |evt: KeyboardEvent| {
+///                 if evt.modifiers().meta() && evt.key() == Key::Character("k".into()) {
+///                     opened_at.set(pointer());
+///                 }
+///             },
+///             if opened_at().is_some() {
+///                 div {
+///                     onmounted: move |evt| element_ref.set(Some(evt.data())),
+///                     style: placement().style(Default::default()),
+///                     "Command palette"
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_pointer_position() -> ReadSignal<Option<ClientPoint>> {
+    let position = use_hook(|| POINTER.subscribe().unwrap_or_else(|| Signal::new(None)));
+    use_drop(|| POINTER.unsubscribe());
+
+    position.into()
+}
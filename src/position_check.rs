@@ -0,0 +1,88 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+
+use crate::{FloatingResult, Strategy, use_floating};
+
+/// Internal: Distance in pixels between the rendered and the computed position
+/// that is still considered a match (rounding, borders of the ancestors).
+const TOLERANCE: f64 = 1.5;
+
+/// Internal: Verifies in debug builds that the element is rendered where the engine
+/// placed it.
+///
+/// On the first ready frame of every open session the element is measured once and
+/// compared with the computed position, unless `is_enabled` is `false` (e.g., for
+/// coordinates that are not viewport-relative). A mismatch usually means that a transformed
+/// or positioned ancestor moves the element, or that the coordinates are applied
+/// with another [Strategy] than intended.
+pub(crate) fn use_position_check(
+    instance: Rc<str>,
+    element_ref: ReadSignal<Option<Rc<MountedData>>>,
+    result: ReadSignal<FloatingResult>,
+    strategy: Strategy,
+    is_enabled: bool,
+) {
+    let floating = use_floating();
+    let mut is_checked = use_signal(|| false);
+
+    use_effect(move || {
+        if !is_enabled {
+            return;
+        }
+        let result = result();
+        if !result.is_ready {
+            if *is_checked.peek() {
+                is_checked.set(false);
+            }
+            return;
+        }
+        if *is_checked.peek() {
+            return;
+        }
+        is_checked.set(true);
+
+        let Some(element) = element_ref.peek().clone() else {
+            return;
+        };
+        let instance = instance.clone();
+        spawn(async move {
+            // let the browser paint the new position first
+            floating.wait().await;
+            let Ok(rect) = element.get_client_rect().await else {
+                return;
+            };
+
+            let (dx, dy) = (rect.min_x() - result.x, rect.min_y() - result.y);
+            if dx.abs() > TOLERANCE || dy.abs() > TOLERANCE {
+                tracing::warn!(
+                    "{instance}: rendered {} of the computed position ({}, {}) with {strategy:?} strategy. \
+                    Check for a transformed or positioned ancestor or a mismatched strategy.",
+                    describe_offset(dx, dy),
+                    result.x,
+                    result.y,
+                );
+            }
+        });
+    });
+}
+
+/// Internal: Describes an offset, e.g., `134px right and 2px above`.
+fn describe_offset(dx: f64, dy: f64) -> String {
+    let horizontal = match dx {
+        dx if dx > TOLERANCE => Some(format!("{}px right", dx.round())),
+        dx if dx < -TOLERANCE => Some(format!("{}px left", (-dx).round())),
+        _ => None,
+    };
+    let vertical = match dy {
+        dy if dy > TOLERANCE => Some(format!("{}px below", dy.round())),
+        dy if dy < -TOLERANCE => Some(format!("{}px above", (-dy).round())),
+        _ => None,
+    };
+
+    [horizontal, vertical]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
@@ -0,0 +1,78 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use dioxus::html::geometry::PixelsRect;
+use dioxus::prelude::*;
+
+use crate::ScrollState;
+use crate::scrollable_view::now_ms;
+
+/// Internal: How long a pre-measured trigger stays usable, e.g., between hovering a
+/// button and clicking it.
+pub(crate) const PREWARM_MAX_AGE_MS: f64 = 2000_f64;
+
+thread_local! {
+    static PREWARMED: RefCell<Vec<Prewarmed>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Internal: The trigger and scrollable rects measured ahead of an open, see
+/// [crate::Floating::prewarm].
+pub(crate) struct Prewarmed {
+    pub(crate) trigger: Weak<MountedData>,
+    pub(crate) scrollable: Weak<MountedData>,
+    /// The layout generation and scroll state of the view at the measurement; the
+    /// rects are stale once either changed.
+    pub(crate) layout_generation: u64,
+    pub(crate) scroll_state: ScrollState,
+    pub(crate) measured_at: f64,
+    pub(crate) trigger_rect: PixelsRect,
+    pub(crate) scrollable_rect: PixelsRect,
+}
+
+impl Prewarmed {
+    /// Internal: Returns `true` if the rects still describe the view at `scroll_state`.
+    fn is_fresh(&self, layout_generation: u64, scroll_state: &ScrollState, now: f64) -> bool {
+        self.trigger.strong_count() > 0
+            && self.layout_generation == layout_generation
+            && self.scroll_state.state == scroll_state.state
+            && self.scroll_state.size == scroll_state.size
+            && self.scroll_state.bounds == scroll_state.bounds
+            && now - self.measured_at <= PREWARM_MAX_AGE_MS
+    }
+}
+
+/// Internal: Stores pre-measured rects, replacing an older entry of the same trigger.
+pub(crate) fn store(prewarmed: Prewarmed) {
+    PREWARMED.with(|entries| {
+        let mut entries = entries.borrow_mut();
+        let now = now_ms();
+        entries.retain(|entry| {
+            !entry.trigger.ptr_eq(&prewarmed.trigger)
+                && entry.trigger.strong_count() > 0
+                && now - entry.measured_at <= PREWARM_MAX_AGE_MS
+        });
+        entries.push(prewarmed);
+    });
+}
+
+/// Internal: Takes the fresh pre-measured `(trigger, scrollable)` rects of a trigger in
+/// a view, so only the first measurement after an open uses them.
+pub(crate) fn take(
+    trigger: &Rc<MountedData>,
+    scrollable: &Rc<MountedData>,
+    layout_generation: u64,
+    scroll_state: &ScrollState,
+) -> Option<(PixelsRect, PixelsRect)> {
+    PREWARMED.with(|entries| {
+        let mut entries = entries.borrow_mut();
+        let index = entries.iter().position(|entry| {
+            entry.trigger.ptr_eq(&Rc::downgrade(trigger))
+                && entry.scrollable.ptr_eq(&Rc::downgrade(scrollable))
+        })?;
+        let entry = entries.swap_remove(index);
+
+        entry
+            .is_fresh(layout_generation, scroll_state, now_ms())
+            .then_some((entry.trigger_rect, entry.scrollable_rect))
+    })
+}
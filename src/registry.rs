@@ -0,0 +1,129 @@
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+
+use crate::{FloatingResult, Placement, ScrollableContext};
+
+/// A placement hook whose floating element is mounted in a [crate::ScrollableView], see
+/// [ScrollableContext::floatings].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatingDescriptor {
+    /// The hook name, the [crate::FloatingOptions::debug_label] and, in debug builds, the
+    /// scope of the component, e.g., `use_placement [menu] in ScopeId(4)`.
+    pub name: Rc<str>,
+    /// The [crate::FloatingOptions::debug_label] of the hook.
+    pub label: Option<&'static str>,
+    /// The resolved placement of the last result.
+    pub placement: Placement,
+    /// `true` once the element is positioned.
+    pub is_ready: bool,
+    /// How long the last measurement took (DOM reads and computation), in milliseconds.
+    pub last_compute_ms: Option<f64>,
+    /// The durations of the recent measurements, e.g., to report slow devices to telemetry.
+    pub timing: MeasurementTiming,
+    pub(crate) id: usize,
+}
+
+/// Internal: The number of recent measurements [MeasurementTiming] covers.
+const TIMING_WINDOW: usize = 20;
+
+/// Internal: The number of measurements needed before a hook can be reported as slow.
+const TIMING_MIN_SAMPLES: usize = 10;
+
+/// The durations of the recent measurements of a placement hook (DOM reads and
+/// computation), see [FloatingDescriptor::timing] and
+/// [crate::FloatingBuilder::slow_measurement_budget].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MeasurementTiming {
+    /// How long the last measurement took, in milliseconds.
+    pub last_ms: Option<f64>,
+    /// The number of measurements since the hook was created.
+    pub count: usize,
+    /// The 90th percentile of the last 20 measurements, in milliseconds.
+    pub p90_ms: f64,
+    /// The number of the last 20 measurements that took longer than the budget.
+    pub over_budget: usize,
+    /// The budget of a measurement, in milliseconds.
+    pub budget_ms: f64,
+    /// `true` once at least 10 measurements were recorded and their 90th percentile is
+    /// over the budget. The hook logs a warning the first time it is set.
+    pub is_slow: bool,
+}
+
+/// Internal: The rolling window of the measurement durations of a placement hook.
+#[derive(Debug, Default)]
+pub(crate) struct TimingWindow {
+    samples: VecDeque<f64>,
+    count: usize,
+    is_reported: bool,
+}
+
+impl TimingWindow {
+    /// Internal: Records the duration of a measurement and returns the updated timing,
+    /// and `true` the first time it is slow.
+    pub(crate) fn record(&mut self, ms: f64, budget_ms: f64) -> (MeasurementTiming, bool) {
+        if self.samples.len() == TIMING_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(ms);
+        self.count += 1;
+
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        let p90_ms = sorted[(sorted.len() * 9).div_ceil(10) - 1];
+        let is_slow = sorted.len() >= TIMING_MIN_SAMPLES && p90_ms > budget_ms;
+        let is_first_report = is_slow && !self.is_reported;
+        self.is_reported |= is_slow;
+
+        let timing = MeasurementTiming {
+            last_ms: Some(ms),
+            count: self.count,
+            p90_ms,
+            over_budget: sorted.iter().filter(|&&sample| sample > budget_ms).count(),
+            budget_ms,
+            is_slow,
+        };
+        (timing, is_first_report)
+    }
+}
+
+/// Internal: Registers the placement `id` with its view while its element is mounted,
+/// and unregisters it when the element is removed or the owning component unmounts.
+pub(crate) fn use_registration(
+    context: Option<ScrollableContext>,
+    id: usize,
+    name: Rc<str>,
+    label: Option<&'static str>,
+    element_ref: ReadSignal<Option<Rc<MountedData>>>,
+    result: ReadSignal<FloatingResult>,
+    timing: ReadSignal<MeasurementTiming>,
+) {
+    use_effect(move || {
+        let is_mounted = element_ref.read().is_some();
+        let result = result();
+        let timing = timing();
+        let Some(mut context) = context else {
+            return;
+        };
+
+        if is_mounted {
+            context.register(FloatingDescriptor {
+                name: name.clone(),
+                label,
+                placement: result.placement,
+                is_ready: result.is_ready,
+                last_compute_ms: timing.last_ms,
+                timing,
+                id,
+            });
+        } else {
+            context.unregister(id);
+        }
+    });
+    use_drop(move || {
+        if let Some(mut context) = context {
+            context.unregister(id);
+        }
+    });
+}
@@ -0,0 +1,508 @@
+#[cfg(feature = "dioxus")]
+use dioxus::prelude::*;
+
+use crate::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use crate::{ArrowPosition, Placement, Strategy};
+
+/// The result of a floating position calculation.
+///
+/// This structure is returned by positioning hooks and contains raw coordinates
+/// and a readiness flag. It is designed to be used with `use_memo` to generate
+/// custom CSS styles.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FloatingResult {
+    // Calculated X coordinate (viewport-relative pixels).
+    pub x: f64,
+    // Calculated Y coordinate (viewport-relative pixels).
+    pub y: f64,
+    // Use this to toggle visibility (e.g., opacity) to prevent flickering.
+    pub is_ready: bool,
+    // Resolved placement after middleware (e.g., after a Flip).
+    pub placement: Placement,
+    // Distance the Shift middleware moved the element on each axis.
+    pub shift: PixelsVector2D,
+    // The trigger could not be measured several times in a row (e.g., its DOM node
+    // was replaced). The coordinates are the last valid ones; hide the element or
+    // provide a new trigger ref.
+    pub is_trigger_lost: bool,
+    // A flip changed the alignment, so the element jumps on the cross axis.
+    pub alignment_changed: bool,
+    // Space available to the element on the resolved side.
+    pub available: PixelsSize,
+    // The trigger rect the position was computed from, updated with every recompute
+    // (e.g., for a SpotlightOverlay that matches the element).
+    pub trigger_rect: PixelsRect,
+    // The size of the floating element the position was computed for.
+    pub element_size: PixelsSize,
+    // Fraction (0..1) of the element area inside the boundary (1.0 when fully visible).
+    pub visible_ratio: f64,
+    // The element should be hidden (the trigger is entirely outside the boundary
+    // with `anchor_to_visible` or `Middleware::Hide`), e.g., bind it to `visibility: hidden`.
+    pub is_hidden: bool,
+    // Only a part of the trigger is inside the boundary, with `Middleware::Hide`.
+    pub is_trigger_clipped: bool,
+    // Only one endpoint of a range anchor (see `use_placement_on_range`) is mounted
+    // or measurable, so the element is anchored to that one alone.
+    pub is_range_partial: bool,
+    // The element covers part of the trigger at its final position.
+    pub overlaps_trigger: bool,
+    // The part of the trigger covered by the element.
+    pub trigger_overlap: Option<PixelsRect>,
+    // `FloatingOptions::min_visible` moved the element back into the boundary.
+    pub min_visible_engaged: bool,
+    // The element is not rendered yet (zero size, e.g., `display: none`) and the hook
+    // waits for it, see `MeasureAfter::Visible`.
+    pub is_waiting_for_visibility: bool,
+    // The inputs changed and a measurement is in flight: the coordinates are about to
+    // change, e.g., freeze pointer interactions until it clears.
+    pub is_pending: bool,
+    // The position just jumped (e.g., a flip), so transitions of the element should be
+    // disabled for now, see `FloatingOptions::suppress_transitions`.
+    pub suppress_transition: bool,
+    // The final content is rendered and the position was measured with it, see
+    // `FloatingOptions::wait_for_content`. Same as `is_ready` without that option.
+    pub content_ready: bool,
+    // The z-index the style helpers set, see `FloatingResult::with_layer`.
+    pub z_index: Option<i32>,
+    // Why the result was published, e.g., replay an entrance animation after a flip only.
+    pub update_reason: UpdateReason,
+    // The largest size of the element with `Middleware::Size`, e.g., bind them to
+    // `max-width` / `max-height` with `overflow: auto`.
+    pub max_width: Option<f64>,
+    pub max_height: Option<f64>,
+    // Where the arrow goes on the element, with `Middleware::Arrow`: the edge facing the
+    // trigger and the clamped offset along it (`arrow_x` / `arrow_y`).
+    pub arrow_position: Option<ArrowPosition>,
+}
+
+/// Why a placement hook published a new [FloatingResult], see
+/// [FloatingResult::update_reason].
+///
+/// The placement hooks attribute every measurement to the input that triggered it, also
+/// when several placements are measured in one batch, and the middleware decision to the
+/// result. When several inputs change before a measurement, the first matching variant
+/// wins, in the order: `Initial`, `Flip`, `Manual`, `AnchorChanged`, `Resize`, `Scroll`.
+/// [crate::use_placement_core] measures nothing and only reports `Initial`, `Flip` and
+/// `Manual` (for changed options).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UpdateReason {
+    /// The first ready result since the element was opened (or the placement was not
+    /// ready), e.g., run the entrance animation.
+    #[default]
+    Initial,
+    /// A scroll of the view (or of an enclosing one) moved the trigger.
+    Scroll,
+    /// The layout changed: the view, the element or the trigger was resized, the
+    /// [crate::AutoUpdate] sources fired, or the measurements were invalidated.
+    Resize,
+    /// The resolved side changed, e.g., the Flip middleware moved the element to the
+    /// other side of the trigger.
+    Flip,
+    /// The element is anchored to something else: a new trigger node, point or range.
+    AnchorChanged,
+    /// A [crate::FloatingUpdate] handle asked for it, or the [crate::FloatingOptions]
+    /// changed.
+    Manual,
+}
+
+impl UpdateReason {
+    /// Returns the reason that wins when the inputs of both changed before a measurement.
+    pub fn merge(self, other: UpdateReason) -> UpdateReason {
+        if other.rank() > self.rank() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Internal: The precedence of the reason, higher wins.
+    fn rank(self) -> u8 {
+        match self {
+            UpdateReason::Scroll => 0,
+            UpdateReason::Resize => 1,
+            UpdateReason::AnchorChanged => 2,
+            UpdateReason::Manual => 3,
+            UpdateReason::Flip => 4,
+            UpdateReason::Initial => 5,
+        }
+    }
+}
+
+impl FloatingResult {
+    /// Internal: Builds a ready result from the engine output.
+    #[cfg(feature = "hooks")]
+    pub(crate) fn ready(output: crate::PlacementOutput) -> Self {
+        Self {
+            x: output.x,
+            y: output.y,
+            is_ready: true,
+            placement: output.placement,
+            shift: output.shift,
+            is_trigger_lost: false,
+            is_range_partial: false,
+            alignment_changed: output.alignment_changed,
+            available: output.available,
+            trigger_rect: output.trigger_rect,
+            element_size: output.element_size,
+            visible_ratio: output.visible_ratio,
+            is_hidden: output.is_hidden,
+            is_trigger_clipped: output.is_trigger_clipped,
+            overlaps_trigger: output.overlaps_trigger,
+            trigger_overlap: output.trigger_overlap,
+            min_visible_engaged: output.min_visible_engaged,
+            is_waiting_for_visibility: false,
+            is_pending: false,
+            suppress_transition: false,
+            content_ready: true,
+            z_index: None,
+            update_reason: UpdateReason::Initial,
+            max_width: output.max_width,
+            max_height: output.max_height,
+            arrow_position: output.arrow,
+        }
+    }
+
+    /// Returns the positioning style of the floating element for the given [Strategy]:
+    /// `position`, `inset`, `margin`, the `transform` to the computed coordinates and
+    /// an `opacity` of 0 until the result is ready.
+    ///
+    /// The coordinates are viewport-relative, see [Strategy] for when that matches the
+    /// rendered position. Debug builds of the placement hooks verify it after the first
+    /// ready frame and log a diagnostic if the element ended up elsewhere.
+    ///
+    /// Every move rewrites the whole string; for elements that follow scrolling, bind
+    /// the separate values of [FloatingResult::style_attrs] instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::{FloatingResult, Strategy};
+    ///
+    /// let result = FloatingResult { x: 10.0, y: 20.0, is_ready: true, ..Default::default() };
+    /// assert_eq!(
+    ///     result.style(Strategy::Fixed),
+    ///     "position: fixed; inset: 0px auto auto 0px; margin: 0px; \
+    ///     transform: translate3d(10px, 20px, 0px); opacity: 1;"
+    /// );
+    /// ```
+    pub fn style(&self, strategy: Strategy) -> String {
+        format!(
+            "position: {}; inset: 0px auto auto 0px; margin: 0px; \
+            transform: translate3d({}px, {}px, 0px); opacity: {};{}{}",
+            strategy.as_css(),
+            self.x,
+            self.y,
+            if self.is_ready { 1 } else { 0 },
+            if self.suppress_transition {
+                " transition: none;"
+            } else {
+                ""
+            },
+            match self.z_index {
+                Some(z_index) => format!(" z-index: {z_index};"),
+                None => String::new(),
+            }
+        )
+    }
+
+    /// Returns the result with a `z-index` for the style helpers, e.g., the layer of
+    /// [crate::use_floating_layer].
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::{FloatingResult, Strategy};
+    ///
+    /// let result = FloatingResult { is_ready: true, ..Default::default() }.with_layer(1001);
+    /// assert!(result.style(Strategy::Fixed).ends_with("opacity: 1; z-index: 1001;"));
+    /// assert_eq!(result.style_attrs(Strategy::Fixed).z_index.as_deref(), Some("1001"));
+    /// ```
+    pub fn with_layer(self, z_index: i32) -> Self {
+        Self {
+            z_index: Some(z_index),
+            ..self
+        }
+    }
+
+    /// Returns the positioning style of [FloatingResult::style] as separate values, see
+    /// [FloatingStyle].
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::{FloatingResult, Strategy};
+    ///
+    /// let result = FloatingResult { x: 10.0, y: 20.0, is_ready: true, ..Default::default() };
+    /// let style = result.style_attrs(Strategy::Fixed);
+    /// assert_eq!(style.transform.as_deref(), Some("translate3d(10px, 20px, 0px)"));
+    /// assert_eq!(style.opacity, "1");
+    /// ```
+    pub fn style_attrs(&self, strategy: Strategy) -> FloatingStyle {
+        FloatingStyle {
+            position: strategy.as_css(),
+            top: Some("0px".to_string()),
+            left: Some("0px".to_string()),
+            margin: "0px",
+            transform: Some(format!("translate3d({}px, {}px, 0px)", self.x, self.y)),
+            opacity: if self.is_ready { "1" } else { "0" }.to_string(),
+            transition: self.suppress_transition.then_some("none"),
+            z_index: self.z_index.map(|z_index| z_index.to_string()),
+        }
+    }
+
+    /// Returns the position as `top` and `left` instead of a `transform`, which is left
+    /// to the app, e.g., for an entrance animation with a CSS transition on `transform`
+    /// that must not slide the element whenever its coordinates change.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::{FloatingResult, Strategy};
+    ///
+    /// let result = FloatingResult { x: 10.0, y: 20.0, is_ready: true, ..Default::default() };
+    /// let style = result.inset_style_attrs(Strategy::Fixed);
+    /// assert_eq!((style.top.as_deref(), style.left.as_deref()), (Some("20px"), Some("10px")));
+    /// assert_eq!(style.transform, None);
+    /// ```
+    pub fn inset_style_attrs(&self, strategy: Strategy) -> FloatingStyle {
+        FloatingStyle {
+            top: Some(format!("{}px", self.y)),
+            left: Some(format!("{}px", self.x)),
+            transform: None,
+            ..self.style_attrs(strategy)
+        }
+    }
+
+    /// Returns the result as CSS custom properties (`--{prefix}-x: 10px; ...`), e.g., for
+    /// a shared stylesheet rule. See [FloatingResult::css_var_list] for the names.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_floating::FloatingResult;
+    ///
+    /// let result = FloatingResult { x: 10.0, y: 20.0, ..Default::default() };
+    /// assert!(result.css_vars("floating").starts_with("--floating-x: 10px; --floating-y: 20px;"));
+    /// ```
+    pub fn css_vars(&self, prefix: &str) -> String {
+        self.css_var_list(prefix)
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value};"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the result as `(name, value)` pairs of CSS custom properties:
+    /// `--{prefix}-x`, `-y`, `-available-width`, `-available-height`, `-transform-origin`,
+    /// `-trigger-width` and `-trigger-height`. Use distinct prefixes for nested floating elements.
+    pub fn css_var_list(&self, prefix: &str) -> Vec<(String, String)> {
+        vec![
+            (format!("--{prefix}-x"), format!("{}px", self.x)),
+            (format!("--{prefix}-y"), format!("{}px", self.y)),
+            (
+                format!("--{prefix}-available-width"),
+                format!("{}px", self.available.width),
+            ),
+            (
+                format!("--{prefix}-available-height"),
+                format!("{}px", self.available.height),
+            ),
+            (
+                format!("--{prefix}-transform-origin"),
+                self.placement.transform_origin().to_string(),
+            ),
+            (
+                format!("--{prefix}-trigger-width"),
+                format!("{}px", self.trigger_rect.width()),
+            ),
+            (
+                format!("--{prefix}-trigger-height"),
+                format!("{}px", self.trigger_rect.height()),
+            ),
+        ]
+    }
+
+    /// Returns the position of the trigger relative to the element, see
+    /// [crate::PlacementOutput::trigger_offset].
+    pub fn trigger_offset(&self) -> PixelsVector2D {
+        self.trigger_rect.origin - PixelsVector2D::new(self.x, self.y).to_point()
+    }
+
+    /// Returns the rect of the floating element at the computed position.
+    pub fn element_rect(&self) -> PixelsRect {
+        PixelsRect::new(
+            PixelsVector2D::new(self.x, self.y).to_point(),
+            self.element_size,
+        )
+    }
+
+    /// Returns the shift along the main axis of the resolved placement.
+    ///
+    /// Useful to fade a tooltip as it slides over its trigger.
+    pub fn main_axis_shift(&self) -> f64 {
+        if self.placement.is_vertical() {
+            self.shift.y
+        } else {
+            self.shift.x
+        }
+    }
+}
+
+/// The positioning style of a floating element as separate values, see
+/// [FloatingResult::style_attrs] and [FloatingResult::inset_style_attrs].
+///
+/// Dioxus diffs attributes one by one, so binding each value to its own style attribute
+/// (or spreading [FloatingStyle::attributes]) makes a move write only `transform`. The
+/// single string of [FloatingResult::style] is rewritten as a whole instead, and the
+/// browser parses all of its declarations again on every move. Prefer these values for
+/// elements that follow scrolling, e.g., many open tooltips.
+///
+/// # Example
+///
+/// With the [crate::testing] mocks, 10 open tooltips follow a scroll: the separate attributes
+/// write 10 declarations, the style string 50.
+///
+/// ```rust
+/// # #[cfg(feature = "hooks")] {
+/// use std::cell::Cell;
+///
+/// use dioxus::core::{AttributeValue, Mutation};
+/// use dioxus::prelude::*;
+/// use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+/// use dioxus_floating::{FloatingOptions, ScrollableContext, Strategy, use_placement};
+///
+/// thread_local! {
+///     static AS_STRING: Cell<bool> = Cell::new(false);
+///     static CONTEXT: Cell<Option<ScrollableContext>> = Cell::new(None);
+///     static TRIGGERS: Vec<MockElement> = (0..10)
+///         .map(|i| MockElement::new(rect(0.0, 40.0 * i as f64, 80.0, 20.0)))
+///         .collect();
+/// }
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     let context = use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+///     CONTEXT.with(|cell| cell.set(Some(context)));
+///     rsx! {
+///         for index in 0..10 {
+///             Tooltip { index }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Tooltip(index: usize) -> Element {
+///     let element = use_hook(|| MockElement::new(rect(0.0, 0.0, 60.0, 20.0)));
+///     let element_ref = use_signal(|| Some(element.mounted()));
+///     let trigger_ref = use_signal(|| Some(TRIGGERS.with(|triggers| triggers[index].mounted())));
+///     let placement = use_placement(element_ref, trigger_ref, FloatingOptions::default());
+///
+///     let result = placement();
+///     if AS_STRING.with(Cell::get) {
+///         return rsx! { div { style: result.style(Strategy::Fixed) } };
+///     }
+///     let style = result.style_attrs(Strategy::Fixed);
+///     rsx! {
+///         div {
+///             position: style.position,
+///             top: style.top,
+///             left: style.left,
+///             margin: style.margin,
+///             transform: style.transform,
+///             opacity: style.opacity,
+///             transition: style.transition,
+///         }
+///     }
+/// }
+///
+/// // the number of CSS declarations written while the container scrolls by 20px
+/// fn declarations_on_scroll(as_string: bool) -> usize {
+///     AS_STRING.with(|cell| cell.set(as_string));
+///     TRIGGERS.with(|triggers| {
+///         for (i, trigger) in triggers.iter().enumerate() {
+///             trigger.set_rect(rect(0.0, 40.0 * i as f64, 80.0, 20.0));
+///         }
+///     });
+///     let mut dom = VirtualDom::new(app);
+///     dom.rebuild_in_place();
+///     settle(&mut dom);
+///
+///     TRIGGERS.with(|triggers| {
+///         for (i, trigger) in triggers.iter().enumerate() {
+///             trigger.set_rect(rect(0.0, 40.0 * i as f64 - 20.0, 80.0, 20.0));
+///         }
+///     });
+///     let mut scroll_state = CONTEXT.with(Cell::get).unwrap().scroll_state;
+///     dom.in_runtime(|| {
+///         let mut state = scroll_state.peek().unwrap();
+///         state.state.y = 20.0;
+///         scroll_state.set(Some(state));
+///     });
+///
+///     let mut declarations = 0;
+///     for _ in 0..32 {
+///         dom.process_events();
+///         for edit in dom.render_immediate_to_vec().edits {
+///             if let Mutation::SetAttribute { value: AttributeValue::Text(value), name, .. } = edit {
+///                 declarations += if name == "style" { value.matches(':').count() } else { 1 };
+///             }
+///         }
+///     }
+///     declarations
+/// }
+///
+/// assert_eq!(declarations_on_scroll(false), 10);
+/// assert_eq!(declarations_on_scroll(true), 50);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatingStyle {
+    /// The CSS `position`, see [Strategy].
+    pub position: &'static str,
+    /// The CSS `top`: constant if the coordinates are applied through `transform`.
+    pub top: Option<String>,
+    /// The CSS `left`: constant if the coordinates are applied through `transform`.
+    pub left: Option<String>,
+    /// The CSS `margin`, reset so it does not move the element.
+    pub margin: &'static str,
+    /// The CSS `transform` to the computed coordinates, the only value that changes on
+    /// moves; `None` if it is left to the app (see [FloatingResult::inset_style_attrs]).
+    pub transform: Option<String>,
+    /// The CSS `opacity`: `0` until the result is ready.
+    pub opacity: String,
+    /// The CSS `transition`: `none` right after a jump (see
+    /// [FloatingResult::suppress_transition]), otherwise left to the app.
+    pub transition: Option<&'static str>,
+    /// The CSS `z-index`, if set with [FloatingResult::with_layer].
+    pub z_index: Option<String>,
+}
+
+impl FloatingStyle {
+    /// Returns the values as style attributes, e.g., to spread them on an element with
+    /// `div { ..style.attributes() }`. Unset values are skipped.
+    #[cfg(feature = "dioxus")]
+    pub fn attributes(&self) -> Vec<Attribute> {
+        let style =
+            |name: &'static str, value: String| Attribute::new(name, value, Some("style"), false);
+
+        let mut attributes = vec![style("position", self.position.to_string())];
+        if let Some(top) = &self.top {
+            attributes.push(style("top", top.clone()));
+        }
+        if let Some(left) = &self.left {
+            attributes.push(style("left", left.clone()));
+        }
+        attributes.push(style("margin", self.margin.to_string()));
+        if let Some(transform) = &self.transform {
+            attributes.push(style("transform", transform.clone()));
+        }
+        attributes.push(style("opacity", self.opacity.clone()));
+        if let Some(transition) = self.transition {
+            attributes.push(style("transition", transition.to_string()));
+        }
+        if let Some(z_index) = &self.z_index {
+            attributes.push(style("z-index", z_index.clone()));
+        }
+
+        attributes
+    }
+}
@@ -0,0 +1,224 @@
+use dioxus::prelude::*;
+
+use crate::{ScrollState, use_scroll_context};
+
+/// Offsets up to this many pixels from the top count as the top.
+const TOP_TOLERANCE: f64 = 1.0;
+
+/// The state of a toolbar revealed by scrolling up, see [use_scroll_reveal].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RevealState {
+    /// `true` while the toolbar should be shown.
+    pub visible: bool,
+    /// `true` while the view is scrolled to the top.
+    pub at_top: bool,
+}
+
+/// Internal: The state latched across scroll states by [use_scroll_reveal].
+#[derive(Debug, Default)]
+struct RevealLatch {
+    last_offset: Option<f64>,
+    // the direction of the current run, `true` when scrolling up
+    is_scrolling_up: Option<bool>,
+    // the distance scrolled in the current direction
+    distance: f64,
+    generation: u64,
+    // a programmatic scroll changed the generation, the offset it ends at is still to come
+    awaits_scroll: bool,
+    was_smooth_scrolling: bool,
+}
+
+impl RevealLatch {
+    /// Internal: Starts over when the scroll generation changes: the current offset is
+    /// the new baseline and, unless a reload published it, so is the next one that moves
+    /// (the scroll event of a programmatic scroll arrives after it).
+    fn reset(&mut self, generation: u64, reloaded: bool) {
+        if generation == self.generation {
+            return;
+        }
+        self.generation = generation;
+        self.last_offset = None;
+        self.awaits_scroll = !reloaded;
+    }
+
+    /// Internal: Feeds the next state and returns the new reveal state. Every offset of
+    /// a smooth programmatic scroll is a new baseline, up to the one it ends at (arrived
+    /// or interrupted).
+    fn update(
+        &mut self,
+        state: ScrollState,
+        is_smooth_scrolling: bool,
+        visible: bool,
+        threshold: f64,
+    ) -> RevealState {
+        // bouncing past the edges (e.g., iOS rubber banding) is not a direction change
+        let offset = state.state.y.clamp(0_f64, state.max_offset().y);
+        let at_top = offset <= TOP_TOLERANCE;
+        let mut last_offset = self.last_offset.replace(offset);
+        if self.awaits_scroll && last_offset.is_some_and(|last_offset| last_offset != offset) {
+            self.awaits_scroll = false;
+            last_offset = None;
+        }
+        let was_smooth_scrolling =
+            std::mem::replace(&mut self.was_smooth_scrolling, is_smooth_scrolling);
+        if is_smooth_scrolling || was_smooth_scrolling {
+            last_offset = None;
+        }
+
+        if at_top {
+            self.restart();
+            return RevealState {
+                visible: true,
+                at_top,
+            };
+        }
+        let Some(last_offset) = last_offset else {
+            self.restart();
+            return RevealState { visible, at_top };
+        };
+
+        let delta = offset - last_offset;
+        if delta == 0_f64 {
+            return RevealState { visible, at_top };
+        }
+        // a direction change starts a new run, so jitter never adds up to the threshold
+        let is_up = delta < 0_f64;
+        if self.is_scrolling_up == Some(is_up) {
+            self.distance += delta.abs();
+        } else {
+            self.is_scrolling_up = Some(is_up);
+            self.distance = delta.abs();
+        }
+
+        RevealState {
+            visible: if self.distance >= threshold {
+                is_up
+            } else {
+                visible
+            },
+            at_top,
+        }
+    }
+
+    /// Internal: Forgets the current run.
+    fn restart(&mut self) {
+        self.is_scrolling_up = None;
+        self.distance = 0_f64;
+    }
+}
+
+/// Reveals a toolbar when the nearest [crate::ScrollableView] is scrolled up and hides
+/// it when it is scrolled down, always showing it at the top.
+///
+/// The toolbar toggles only after `threshold` pixels scrolled in one direction, so the
+/// micro-scrolls of a trackpad do not make it flicker. Offsets past the edges (overscroll
+/// bounce) are clamped. After [crate::ScrollableContext::reload] and the programmatic
+/// scroll methods, the state is kept and the distance counts from where they end (see
+/// [crate::ScrollableContext::scroll_generation]): the offset published by the reload,
+/// the first offset that moves after a programmatic scroll, the last offset of a smooth
+/// one. Only the vertical axis is tracked.
+///
+/// # Panics
+/// Like [use_scroll_context], it panics outside of a [crate::ScrollableView].
+///
+/// # Example
+///
+/// With the [crate::testing] mocks, a sequence of scroll states:
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockScrollableConfig, provide_mock_scrollable, settle};
+/// use dioxus_floating::{RevealState, ScrollableContext, use_scroll_reveal};
+///
+/// thread_local! {
+///     static CONTEXT: Cell<Option<ScrollableContext>> = Cell::new(None);
+///     static REVEAL: Cell<RevealState> = Cell::new(RevealState { visible: true, at_top: true });
+/// }
+///
+/// fn app() -> Element {
+///     let rect = PixelsRect::new(PixelsVector2D::zero().to_point(), PixelsSize::new(400.0, 600.0));
+///     let mut config = MockScrollableConfig::new(rect);
+///     config.scroll_state.size = PixelsSize::new(400.0, 5000.0);
+///     let context = use_hook(|| provide_mock_scrollable(config));
+///     CONTEXT.with(|cell| cell.set(Some(context)));
+///     rsx! { Toolbar {} }
+/// }
+///
+/// #[component]
+/// fn Toolbar() -> Element {
+///     let reveal = use_scroll_reveal(24.0);
+///     REVEAL.with(|cell| cell.set(reveal()));
+///     rsx! {}
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// settle(&mut dom);
+/// let mut context = CONTEXT.with(Cell::get).unwrap();
+/// let mut scroll_to = move |dom: &mut VirtualDom, y: f64| {
+///     dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, y)));
+///     settle(dom);
+///     REVEAL.with(Cell::get)
+/// };
+///
+/// // scrolling down hides the toolbar once the distance adds up to the threshold
+/// assert!(scroll_to(&mut dom, 10.0).visible);
+/// assert!(scroll_to(&mut dom, 20.0).visible);
+/// assert_eq!(scroll_to(&mut dom, 40.0), RevealState { visible: false, at_top: false });
+///
+/// // trackpad jitter changes direction every event and never reaches it
+/// for y in [30.0, 45.0, 35.0, 50.0, 40.0] {
+///     assert!(!scroll_to(&mut dom, y).visible);
+/// }
+///
+/// // scrolling up reveals it, the top always shows it
+/// assert!(!scroll_to(&mut dom, 400.0).visible);
+/// assert!(!scroll_to(&mut dom, 390.0).visible);
+/// assert!(scroll_to(&mut dom, 360.0).visible);
+/// assert!(scroll_to(&mut dom, 370.0).visible);
+/// assert!(!scroll_to(&mut dom, 1000.0).visible);
+/// assert_eq!(scroll_to(&mut dom, 0.0), RevealState { visible: true, at_top: true });
+///
+/// // a programmatic scroll is not a user scroll, the distance counts from where it ends
+/// assert!(!scroll_to(&mut dom, 100.0).visible);
+/// dom.in_scope(ScopeId::ROOT, || {
+///     spawn(async move {
+///         context.scroll(PixelsVector2D::new(0.0, 3000.0), ScrollBehavior::Instant).await;
+///     });
+/// });
+/// settle(&mut dom);
+/// assert!(!scroll_to(&mut dom, 3000.0).visible);
+/// assert!(!scroll_to(&mut dom, 2990.0).visible);
+/// assert!(scroll_to(&mut dom, 2970.0).visible);
+/// ```
+pub fn use_scroll_reveal(threshold: f64) -> ReadSignal<RevealState> {
+    let context = use_scroll_context();
+    let mut latch = use_hook(|| CopyValue::new(RevealLatch::default()));
+    let mut latest_threshold = use_hook(|| CopyValue::new(threshold));
+    latest_threshold.set(threshold.max(0_f64));
+    let mut reveal = use_signal(|| RevealState {
+        visible: true,
+        at_top: true,
+    });
+
+    use_effect(move || {
+        let generation = (context.scroll_generation)();
+        let is_smooth_scrolling = (context.is_smooth_scrolling)();
+        let Some(state) = (context.scroll_state)() else {
+            return;
+        };
+        let reloaded = *context.reloaded_generation.peek() == Some(generation);
+        let visible = reveal.peek().visible;
+        let mut latch = latch.write();
+        latch.reset(generation, reloaded);
+        let next = latch.update(state, is_smooth_scrolling, visible, latest_threshold());
+        if *reveal.peek() != next {
+            reveal.set(next);
+        }
+    });
+
+    reveal.into()
+}
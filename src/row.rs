@@ -0,0 +1,388 @@
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use dioxus::html::geometry::PixelsSize;
+use dioxus::prelude::*;
+
+use crate::hooks::{Anchor, PlacementOverrides, use_placement_with_anchor};
+use crate::{FloatingOptions, FloatingResult, batch, use_floating};
+
+static NEXT_ROW_ITEM_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Internal: A participant of a [FloatingRow].
+#[derive(Debug, Clone)]
+struct RowItem {
+    id: usize,
+    // the position in the row, ties keep the registration order
+    index: usize,
+    element: Option<Rc<MountedData>>,
+    // the measured size, `None` until the first measurement
+    size: Option<PixelsSize>,
+}
+
+/// Floating elements rendered independently that sit side by side along the trigger edge
+/// as one unit, e.g., the button groups of a text-selection toolbar.
+///
+/// [use_floating_row] places the row: the participants registered with
+/// [use_floating_row_item] are laid out in the order of their index, `gap` pixels apart,
+/// and the pipeline positions their combined size like a single element, so Flip and
+/// Shift act on the whole row. Each participant gets the result of its slice: the row
+/// result moved to the start of the slice and aligned to the trigger side (the bottom
+/// edge of a row above the trigger). Participants that mount, unmount or change their
+/// index lay the row out again.
+///
+/// Along a [crate::Side::Left] or [crate::Side::Right] placement, the participants are
+/// stacked vertically instead.
+///
+/// The sizes of the participants are measured when their elements mount, together with
+/// the other placements (see the measurement batches of the hooks), and again on
+/// [FloatingRow::relayout].
+///
+/// # Example
+///
+/// Three groups above a selection, the middle one contextual, with the [crate::testing]
+/// mocks:
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+/// use dioxus_floating::{FloatingOptions, FloatingResult, Offset, Placement, use_floating_row, use_floating_row_item};
+///
+/// thread_local! {
+///     static SHOW_LINK: Cell<Option<Signal<bool>>> = Cell::new(None);
+///     static RESULTS: Cell<[FloatingResult; 3]> = Cell::new([FloatingResult::default(); 3]);
+/// }
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+///     let selection = use_signal(|| Some(MockElement::new(rect(100.0, 200.0, 100.0, 20.0)).mounted()));
+///     let options = FloatingOptions {
+///         placement: Placement::TopStart,
+///         placement_offset: Some(Offset::zero()),
+///         ..Default::default()
+///     };
+///     use_floating_row(selection, 4.0, options);
+///     let show_link = use_signal(|| true);
+///     SHOW_LINK.with(|cell| cell.set(Some(show_link)));
+///
+///     rsx! {
+///         Group { index: 0, width: 60.0, height: 30.0 }
+///         if show_link() {
+///             Group { index: 1, width: 80.0, height: 30.0 }
+///         }
+///         Group { index: 2, width: 40.0, height: 24.0 }
+///     }
+/// }
+///
+/// #[component]
+/// fn Group(index: usize, width: f64, height: f64) -> Element {
+///     let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, width, height)).mounted()));
+///     let slice = use_floating_row_item(index, element_ref);
+///     RESULTS.with(|cell| {
+///         let mut results = cell.get();
+///         results[index] = slice();
+///         cell.set(results);
+///     });
+///     rsx! {}
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// settle(&mut dom);
+/// let position = |index: usize| RESULTS.with(|cell| (cell.get()[index].x, cell.get()[index].y));
+///
+/// // a 188x30 row above the selection, the shorter group aligned to its bottom edge
+/// assert_eq!(position(0), (100.0, 170.0));
+/// assert_eq!(position(1), (164.0, 170.0));
+/// assert_eq!(position(2), (248.0, 176.0));
+///
+/// // the contextual group goes away: the row closes the gap
+/// let mut show_link = SHOW_LINK.with(Cell::get).unwrap();
+/// dom.in_runtime(|| show_link.set(false));
+/// settle(&mut dom);
+/// assert_eq!(position(2), (164.0, 176.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatingRow {
+    /// The placement of the whole row (reactive); its `element_size` is the combined
+    /// size of the measured participants.
+    pub result: ReadSignal<FloatingResult>,
+    // the participants, in the order of the row
+    items: Signal<Vec<RowItem>>,
+    // the first mounted participant, measured by the pipeline in place of the row
+    lead: Signal<Option<Rc<MountedData>>>,
+    // bumped by FloatingRow::relayout, the participants measure again
+    relayout: Signal<u64>,
+    gap: f64,
+    // the participants are laid out horizontally (above or below the trigger)
+    is_horizontal: bool,
+}
+
+impl FloatingRow {
+    /// Measures every participant again and lays the row out, e.g., after the content
+    /// of a group changed.
+    pub fn relayout(&self) {
+        let mut relayout = self.relayout;
+        *relayout.write() += 1;
+    }
+
+    /// Internal: Adds a participant at `index`.
+    fn insert(&mut self, id: usize, index: usize) {
+        let mut items = self.items.write();
+        items.push(RowItem {
+            id,
+            index,
+            element: None,
+            size: None,
+        });
+        items.sort_by_key(|item| (item.index, item.id));
+    }
+
+    /// Internal: Removes a participant, unless the row was dropped first.
+    fn remove(&mut self, id: usize) {
+        if let Ok(mut items) = self.items.try_write() {
+            items.retain(|item| item.id != id);
+        }
+        self.update_lead();
+    }
+
+    /// Internal: Moves a participant to `index`.
+    fn set_index(&mut self, id: usize, index: usize) {
+        let is_moved = self
+            .items
+            .peek()
+            .iter()
+            .any(|item| item.id == id && item.index != index);
+        if !is_moved {
+            return;
+        }
+        let mut items = self.items.write();
+        if let Some(item) = items.iter_mut().find(|item| item.id == id) {
+            item.index = index;
+        }
+        items.sort_by_key(|item| (item.index, item.id));
+        drop(items);
+        self.update_lead();
+    }
+
+    /// Internal: Replaces the element of a participant; a new element is measured again.
+    fn set_element(&mut self, id: usize, element: Option<Rc<MountedData>>) {
+        let is_same = self.items.peek().iter().any(|item| {
+            item.id == id
+                && match (&item.element, &element) {
+                    (Some(current), Some(element)) => Rc::ptr_eq(current, element),
+                    (current, element) => current.is_none() && element.is_none(),
+                }
+        });
+        if is_same {
+            return;
+        }
+        if let Some(item) = self.items.write().iter_mut().find(|item| item.id == id) {
+            item.size = None;
+            item.element = element;
+        }
+        self.update_lead();
+    }
+
+    /// Internal: Publishes the measured size of a participant, unless it was removed.
+    fn set_size(&mut self, id: usize, size: Option<PixelsSize>) {
+        let is_changed = self
+            .items
+            .try_peek()
+            .is_ok_and(|items| items.iter().any(|item| item.id == id && item.size != size));
+        if !is_changed {
+            return;
+        }
+        if let Some(item) = self.items.write().iter_mut().find(|item| item.id == id) {
+            item.size = size;
+        }
+    }
+
+    /// Internal: Makes the first mounted participant the element the pipeline measures.
+    fn update_lead(&mut self) {
+        let Ok(items) = self.items.try_peek() else {
+            return;
+        };
+        let lead = items.iter().find_map(|item| item.element.clone());
+        drop(items);
+        let is_same = match (&*self.lead.peek(), &lead) {
+            (Some(current), Some(lead)) => Rc::ptr_eq(current, lead),
+            (current, lead) => current.is_none() && lead.is_none(),
+        };
+        if !is_same {
+            self.lead.set(lead);
+        }
+    }
+
+    /// Internal: Returns the result of the slice of participant `id` (reactive), not
+    /// ready until the participant is measured.
+    fn slice(&self, id: usize) -> FloatingResult {
+        let result = (self.result)();
+        if !result.is_ready {
+            return FloatingResult::default();
+        }
+
+        let mut start = 0_f64;
+        for item in self.items.read().iter() {
+            let Some(size) = item.size else {
+                continue;
+            };
+            if item.id == id {
+                // aligned to the trigger side of the row
+                let (dx, dy) = if self.is_horizontal {
+                    let free = result.element_size.height - size.height;
+                    (
+                        start,
+                        if result.placement.is_top() {
+                            free
+                        } else {
+                            0_f64
+                        },
+                    )
+                } else {
+                    let free = result.element_size.width - size.width;
+                    (
+                        if result.placement.is_left() {
+                            free
+                        } else {
+                            0_f64
+                        },
+                        start,
+                    )
+                };
+                return FloatingResult {
+                    x: result.x + dx,
+                    y: result.y + dy,
+                    element_size: size,
+                    ..result
+                };
+            }
+            start += self.gap
+                + if self.is_horizontal {
+                    size.width
+                } else {
+                    size.height
+                };
+        }
+
+        FloatingResult::default()
+    }
+}
+
+/// Internal: Returns the size of the measured participants laid out `gap` pixels apart,
+/// `None` before any of them is measured.
+fn combined_size(items: &[RowItem], gap: f64, is_horizontal: bool) -> Option<PixelsSize> {
+    let sizes: Vec<PixelsSize> = items.iter().filter_map(|item| item.size).collect();
+    if sizes.is_empty() {
+        return None;
+    }
+
+    let gaps = gap * (sizes.len() - 1) as f64;
+    let (along, across): (f64, f64) = if is_horizontal {
+        (
+            sizes.iter().map(|size| size.width).sum(),
+            sizes.iter().map(|size| size.height).fold(0_f64, f64::max),
+        )
+    } else {
+        (
+            sizes.iter().map(|size| size.height).sum(),
+            sizes.iter().map(|size| size.width).fold(0_f64, f64::max),
+        )
+    };
+
+    Some(if is_horizontal {
+        PixelsSize::new(along + gaps, across)
+    } else {
+        PixelsSize::new(across, along + gaps)
+    })
+}
+
+/// Places a [FloatingRow] against `trigger_ref` and provides it to the participants
+/// below the calling component ([use_floating_row_item]).
+///
+/// `gap` is the space between two participants, in pixels. The options apply to the
+/// row as a whole, e.g., the offset from the trigger and the middleware.
+pub fn use_floating_row<T>(trigger_ref: T, gap: f64, options: FloatingOptions) -> FloatingRow
+where
+    T: Into<ReadSignal<Option<Rc<MountedData>>>>,
+{
+    let trigger_ref = trigger_ref.into();
+    let items = use_signal(Vec::<RowItem>::new);
+    let lead = use_signal(|| Option::<Rc<MountedData>>::None);
+    let relayout = use_signal(|| 0_u64);
+    // a flip keeps the axis, so the layout direction never changes
+    let is_horizontal = options.placement.is_vertical();
+    let size = use_memo(move || combined_size(&items.read(), gap, is_horizontal));
+
+    let result = use_placement_with_anchor(
+        "use_floating_row",
+        lead.into(),
+        Some(trigger_ref),
+        move || trigger_ref().map(Anchor::Trigger),
+        options,
+        PlacementOverrides {
+            element_size: Some(size.into()),
+            ..Default::default()
+        },
+    );
+
+    use_context_provider(move || FloatingRow {
+        result,
+        items,
+        lead,
+        relayout,
+        gap,
+        is_horizontal,
+    })
+}
+
+/// Registers `element_ref` at `index` in the nearest [FloatingRow] and returns the
+/// result of its slice of the row.
+///
+/// The result is not ready until the element is mounted and measured; its
+/// `element_size` is the size of the participant, the other values (e.g., the resolved
+/// placement and the shift) are the ones of the whole row.
+///
+/// # Panics
+///
+/// Panics outside of a component below [use_floating_row].
+pub fn use_floating_row_item<E>(index: usize, element_ref: E) -> ReadSignal<FloatingResult>
+where
+    E: Into<ReadSignal<Option<Rc<MountedData>>>>,
+{
+    let element_ref = element_ref.into();
+    let mut row = use_context::<FloatingRow>();
+    let floating = use_floating();
+    let id = use_hook(move || {
+        let id = NEXT_ROW_ITEM_ID.fetch_add(1, Ordering::Relaxed);
+        row.insert(id, index);
+        id
+    });
+    row.set_index(id, index);
+    use_drop(move || row.remove(id));
+
+    // measured with the other placements, whenever the element changes or on relayout
+    use_effect(move || {
+        let element = element_ref();
+        (row.relayout)();
+        row.set_element(id, element.clone());
+        let Some(element) = element else {
+            return;
+        };
+        spawn(async move {
+            let read = batch::begin_read();
+            floating.wait().await;
+            let size = element.get_client_rect().await.ok().map(|rect| rect.size);
+            read.commit(move || row.set_size(id, size));
+        });
+    });
+
+    use_memo(move || row.slice(id)).into()
+}
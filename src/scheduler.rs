@@ -0,0 +1,290 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use dioxus::core::spawn_forever;
+
+use crate::WaitStrategy;
+use crate::scrollable_view::now_ms;
+use crate::window::with_window;
+
+/// Internal: Length of a frame when no animation frame callback tells it (60Hz).
+const FRAME_MS: f64 = 1000_f64 / 60_f64;
+
+/// Counters of the time-sliced measurements in one frame, see [crate::Floating::frame_stats].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameStats {
+    /// Measurements that ran in the frame.
+    pub processed: usize,
+    /// Measurements that were deferred to a later frame because the budget was spent.
+    pub deferred: usize,
+    /// Milliseconds spent measuring in the frame.
+    pub spent_ms: f64,
+}
+
+/// Internal: Priority of a waiting measurement, higher first.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub(crate) struct Priority {
+    /// The trigger was inside the boundary when it was last published.
+    pub(crate) is_visible: bool,
+    /// When the placement was invalidated; recent invalidations go first.
+    pub(crate) invalidated_at: f64,
+}
+
+/// Internal: A measurement waiting for the budget of a later frame.
+struct Waiter {
+    instance: usize,
+    priority: Priority,
+    state: WaiterState,
+    waker: Option<Waker>,
+}
+
+/// Internal: Progress of a [Waiter].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WaiterState {
+    Waiting,
+    Granted,
+    Superseded,
+}
+
+/// Internal: Time budget bookkeeping shared by every placement hook of a window.
+#[derive(Default)]
+struct Scheduler {
+    frame_started_at: f64,
+    current: FrameStats,
+    last: FrameStats,
+    // the measurement currently running, measured against the budget when it ends
+    is_running: bool,
+    is_frame_scheduled: bool,
+    next_waiter_id: u64,
+    waiters: Vec<(u64, Waiter)>,
+}
+
+impl Scheduler {
+    /// Starts a new frame if the current one is over.
+    fn roll_frame(&mut self, now: f64) {
+        if now - self.frame_started_at >= FRAME_MS {
+            self.start_frame(now);
+        }
+    }
+
+    fn start_frame(&mut self, now: f64) {
+        if self.current != FrameStats::default() {
+            tracing::trace!(
+                "Floating frame: {} measured, {} deferred, {:.2}ms",
+                self.current.processed,
+                self.current.deferred,
+                self.current.spent_ms
+            );
+            self.last = self.current;
+        }
+        self.current = FrameStats::default();
+        self.frame_started_at = now;
+    }
+
+    /// Grants the budget to the waiting measurement with the highest priority.
+    fn grant_next(&mut self) {
+        let next = self
+            .waiters
+            .iter_mut()
+            .filter(|(_, waiter)| waiter.state == WaiterState::Waiting)
+            .max_by(|(_, a), (_, b)| {
+                a.priority
+                    .partial_cmp(&b.priority)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        if let Some((_, waiter)) = next {
+            waiter.state = WaiterState::Granted;
+            self.is_running = true;
+            self.current.processed += 1;
+            if let Some(waker) = waiter.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Internal: Waits until the frame budget allows a measurement of `instance`.
+///
+/// Without a budget the measurement runs right away. With one, measurements run one
+/// after another until the frame spent `budget_ms`; the rest wait for the next frame,
+/// visible triggers and recent invalidations first. At least one measurement runs per
+/// frame, so every placement is measured eventually.
+///
+/// Returns `None` if a newer measurement of the same instance replaced this one while
+/// it was waiting; the placement keeps its previous values until its turn anyway.
+pub(crate) async fn acquire(
+    budget_ms: Option<f64>,
+    instance: usize,
+    priority: Priority,
+) -> Option<Slot> {
+    let Some(budget_ms) = budget_ms else {
+        return Some(Slot { budget_ms: None });
+    };
+
+    let waiting = with_window(|scheduler: &mut Scheduler| {
+        scheduler.roll_frame(now_ms());
+
+        // a newer measurement of the same placement replaces the waiting one
+        for (_, waiter) in scheduler.waiters.iter_mut() {
+            if waiter.instance == instance && waiter.state == WaiterState::Waiting {
+                waiter.state = WaiterState::Superseded;
+                if let Some(waker) = waiter.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+
+        // the first measurement of a frame runs whatever the budget
+        let can_run = !scheduler.is_running
+            && (scheduler.current.spent_ms < budget_ms || scheduler.current.processed == 0)
+            && !scheduler
+                .waiters
+                .iter()
+                .any(|(_, waiter)| waiter.state == WaiterState::Waiting);
+        if can_run {
+            scheduler.is_running = true;
+            scheduler.current.processed += 1;
+            return None;
+        }
+
+        // nothing running hands the budget on: the next frame must grant it
+        let schedule = !scheduler.is_running && !scheduler.is_frame_scheduled;
+        scheduler.is_frame_scheduled |= schedule;
+
+        if scheduler.current.spent_ms >= budget_ms {
+            scheduler.current.deferred += 1;
+        }
+        let id = scheduler.next_waiter_id;
+        scheduler.next_waiter_id += 1;
+        scheduler.waiters.push((
+            id,
+            Waiter {
+                instance,
+                priority,
+                state: WaiterState::Waiting,
+                waker: None,
+            },
+        ));
+        Some((id, schedule))
+    })
+    // outside of a runtime, nothing to share the budget with
+    .flatten();
+
+    if let Some((id, schedule_frame)) = waiting {
+        if schedule_frame {
+            grant_on_next_frame();
+        }
+        if !(Turn { id }).await {
+            return None;
+        }
+    }
+
+    Some(Slot {
+        budget_ms: Some((budget_ms, now_ms())),
+    })
+}
+
+/// Internal: Resolves when a waiting measurement is granted (`true`) or superseded (`false`).
+struct Turn {
+    id: u64,
+}
+
+impl Future for Turn {
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<bool> {
+        with_window(|scheduler: &mut Scheduler| {
+            let Some(index) = scheduler.waiters.iter().position(|(id, _)| *id == self.id) else {
+                return Poll::Ready(false);
+            };
+            match scheduler.waiters[index].1.state {
+                WaiterState::Waiting => {
+                    scheduler.waiters[index].1.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+                state => {
+                    scheduler.waiters.remove(index);
+                    Poll::Ready(state == WaiterState::Granted)
+                }
+            }
+        })
+        .unwrap_or(Poll::Ready(false))
+    }
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        // a cancelled task gives its turn to the next one
+        with_window(|scheduler: &mut Scheduler| {
+            if let Some(index) = scheduler.waiters.iter().position(|(id, _)| *id == self.id) {
+                let (_, waiter) = scheduler.waiters.remove(index);
+                if waiter.state == WaiterState::Granted {
+                    scheduler.is_running = false;
+                    scheduler.grant_next();
+                }
+            }
+        });
+    }
+}
+
+/// Internal: The right to measure; dropping it accounts the spent time and hands the
+/// budget to the next waiting measurement, or schedules the next frame.
+pub(crate) struct Slot {
+    // the budget and the start of the measurement, `None` without time slicing
+    budget_ms: Option<(f64, f64)>,
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        let Some((budget_ms, started_at)) = self.budget_ms else {
+            return;
+        };
+
+        let schedule_frame = with_window(|scheduler: &mut Scheduler| {
+            let now = now_ms();
+            scheduler.current.spent_ms += now - started_at;
+            scheduler.is_running = false;
+            scheduler.roll_frame(now);
+
+            if scheduler.current.spent_ms < budget_ms {
+                scheduler.grant_next();
+                return false;
+            }
+
+            let has_waiters = scheduler
+                .waiters
+                .iter()
+                .any(|(_, waiter)| waiter.state == WaiterState::Waiting);
+            let schedule = has_waiters && !scheduler.is_frame_scheduled;
+            scheduler.is_frame_scheduled |= schedule;
+            schedule
+        })
+        .unwrap_or_default();
+
+        if schedule_frame {
+            grant_on_next_frame();
+        }
+    }
+}
+
+/// Internal: Starts a new frame on the next animation frame and grants its budget to the
+/// waiting measurements.
+fn grant_on_next_frame() {
+    // the task must outlive the component whose measurement spent the budget
+    spawn_forever(async {
+        WaitStrategy::AnimationFrame.wait().await;
+        with_window(|scheduler: &mut Scheduler| {
+            scheduler.is_frame_scheduled = false;
+            scheduler.start_frame(now_ms());
+            if !scheduler.is_running {
+                scheduler.grant_next();
+            }
+        });
+    });
+}
+
+/// Internal: Returns the counters of the last finished frame.
+pub(crate) fn last_frame_stats() -> FrameStats {
+    with_window(|scheduler: &mut Scheduler| scheduler.last).unwrap_or_default()
+}
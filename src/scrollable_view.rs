@@ -1,8 +1,51 @@
 use std::rc::Rc;
+#[cfg(feature = "components")]
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use dioxus::{html::geometry::PixelsVector2D, prelude::*};
+#[cfg(feature = "components")]
+use dioxus::html::geometry::PixelsSize;
+use dioxus::html::geometry::{PixelsRect, PixelsVector2D};
+use dioxus::prelude::*;
 
-use crate::ScrollState;
+#[cfg(feature = "components")]
+use crate::Floating;
+#[cfg(feature = "components")]
+use crate::WaitStrategy;
+#[cfg(feature = "components")]
+use crate::listener::JsListener;
+use crate::measure::Container;
+use crate::motion;
+use crate::registry::FloatingDescriptor;
+use crate::{FloatingOptions, ScrollState, TrackedAxes};
+
+#[cfg(feature = "components")]
+static NEXT_SCROLLABLE_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "components")]
+const ZOOM_SCRIPT: &str = r#"
+    // page zoom changes devicePixelRatio; resize catches most cases,
+    // the resolution query the ones without a resize (e.g., moving to another screen)
+    let last = window.devicePixelRatio || 1;
+    let query = null;
+    const check = () => {
+        const dpr = window.devicePixelRatio || 1;
+        if (dpr !== last) {
+            last = dpr;
+            watch();
+            dioxus.send(dpr);
+        }
+    };
+    const watch = () => {
+        if (query) query.removeEventListener("change", check);
+        query = window.matchMedia(`(resolution: ${last}dppx)`);
+        query.addEventListener("change", check);
+    };
+    watch();
+    window.addEventListener("resize", check);
+    await dioxus.recv();
+    window.removeEventListener("resize", check);
+    query.removeEventListener("change", check);
+"#;
 
 /// A scrollable container that provides context for floating elements.
 ///
@@ -14,6 +57,77 @@ use crate::ScrollState;
 /// Ensure you provide height and overflow styles (e.g., `h-full overflow-auto`)
 /// via the `class` or `style` props, as the component does not apply them by default.
 ///
+/// # Region configuration:
+/// `floating` sets the [Floating] engine (wait strategy, rounding) of every floating
+/// element inside, and `floating_defaults` sets [FloatingOptions] merged under the
+/// per-call options (see [FloatingOptions::with_defaults]). Both are read when the
+/// placement hooks are created.
+///
+/// # Nesting:
+/// Placement hooks use the nearest enclosing `ScrollableView` as boundary. A hook
+/// declared in the component that renders a nested view sees the outer one; hooks in
+/// its children see the nested one. To position against the outer view from inside a
+/// nested one, pin it with [FloatingOptions::context], using [crate::use_parent_scroll_context]
+/// or a [ScrollableContext] obtained with [crate::use_scroll_context] above the nested view.
+/// Scrolling any enclosing view recomputes the placements as well (with
+/// [crate::AutoUpdate::ancestor_scroll], see [ScrollableContext::ancestors]).
+///
+/// # Reverse layouts:
+/// With `reverse: true` (for `flex-direction: column-reverse` containers, e.g., chats),
+/// the published [ScrollState] uses conventional coordinates (`0` at the top) regardless of
+/// how the browser reports the offset, and the scroll methods translate back.
+///
+/// # Tracked axes:
+/// With `axes: TrackedAxes::X` (or `Y`), only scrolls along that axis publish a new
+/// [ScrollState] and call `on_scroll`; the other axis keeps its previous values (see
+/// [TrackedAxes]). Placement hooks inside measure their rects directly and are unaffected.
+///
+/// # Content wrapper:
+/// With `wrap_content: true` the children are rendered inside an inner wrapper `div`
+/// and [ScrollState::size] is taken from the wrapper's border-box size instead of the
+/// scroll metrics, updating whenever the content resizes. `display: contents` cannot be
+/// measured, so the wrapper uses `min-width: 100%; width: fit-content;` instead: it behaves
+/// like a regular block for normal content and grows with content wider than the view.
+/// Style selectors relying on direct children of the view (e.g., `> *`) see the wrapper.
+///
+/// # Scrollbars:
+/// Classic scrollbars (e.g., on Windows) take room inside the view, so the placements
+/// inside exclude them from their boundary (see [ScrollState::exclude_scrollbars]) instead
+/// of shifting floating elements under them. Set `rtl: true` for `direction: rtl` views,
+/// whose vertical scrollbar is on the left. The scrollbars are detected from the client
+/// size, which only the web renderer reports outside of scroll events.
+///
+/// # Zoom:
+/// Page and text-only zoom scale everything alike. A CSS `zoom` on the view (or an
+/// ancestor) scales its rect but, in some browsers, not its client size: the boundary is
+/// always the rect, and the client size is scaled like it before the scrollbars are told
+/// apart (see [ScrollState::zoom_factor]). The compensation is logged at debug level.
+///
+/// # Resize animations:
+/// The browser reports only a few resizes while the view is animated (e.g., next to a
+/// collapsing side panel), so the placements inside clamp against stale boundaries.
+/// With `track_resize_animation: true`, a resize starts sampling the view's rect every
+/// animation frame and publishes each change, invalidating the placements like
+/// [ScrollableContext::invalidate_measurements], until the rect is stable for
+/// 3 frames or 500ms after the last resize.
+///
+/// # Momentum scrolling:
+/// iOS may pause the scroll events of a momentum scroll while the content rubber-bands,
+/// freezing the placements inside mid-flight until they jump. On iOS and iPadOS (detected
+/// at runtime, web renderer only), the offset is read every animation frame from the end
+/// of a touch until it is stable, and the changes the events missed are published. The
+/// `on_scroll` handler only sees the scroll events. Other platforms are unaffected.
+///
+/// # Intercepting scrolls:
+/// `on_scroll_intercept` sees every proposed [ScrollState] before anything else: it
+/// returns a [ScrollIntercept] to accept, replace or veto it (e.g., to never scroll past a
+/// region that is still loading). A replaced state is published instead, a vetoed one is
+/// not published at all, and the view scrolls to the published offset itself (instantly,
+/// in the same frame) instead of flickering through a corrective scroll of the handler.
+/// The scroll event of that correction is published without being intercepted again.
+/// Only then is the state written to the context, recomputing the placements inside, and
+/// passed to `on_scroll`.
+///
 /// # Example
 ///
 /// ```rust,norun
@@ -38,6 +152,7 @@ use crate::ScrollState;
 /// #[component]
 /// fn MyDropdown() -> Element { let ctx = use_scroll_context(); rsx! {} }
 /// ```
+#[cfg(feature = "components")]
 #[component]
 pub fn ScrollableView(
     #[props(default)] id: Option<String>,
@@ -47,18 +162,144 @@ pub fn ScrollableView(
     #[props(into)] on_mouse_up: Option<EventHandler<MouseEvent>>,
     #[props(into)] on_mouse_down: Option<EventHandler<MouseEvent>>,
     #[props(into)] on_scroll: Option<EventHandler<ScrollState>>,
+    #[props(into)] on_scroll_intercept: Option<Callback<ScrollState, ScrollIntercept>>,
+    #[props(default)] wrap_content: bool,
+    #[props(default)] track_resize_animation: bool,
+    #[props(default)] reverse: bool,
+    #[props(default)] rtl: bool,
+    #[props(default)] axes: TrackedAxes,
+    #[props(default)] floating: Option<Floating>,
+    #[props(default)] floating_defaults: Option<FloatingOptions>,
     children: Element,
     #[props(extends = GlobalAttributes)] attributes: Vec<Attribute>,
 ) -> Element {
-    let floating = crate::use_floating();
+    // the engine of this region, provided to the floating elements inside
+    let inherited = crate::use_floating();
+    let floating = use_context_provider(move || floating.unwrap_or(inherited));
 
     let mut scrollable_ref = use_signal(|| Option::<Rc<MountedData>>::None);
     let mut scroll_state = use_signal(|| Option::<ScrollState>::None);
+    let mut resize_generation = use_signal(|| 0_u64);
+    let mut size_refresh_pending = use_signal(|| false);
+    // border-box size of the content wrapper, replaces the scroll size when set
+    let mut content_size = use_signal(|| Option::<PixelsSize>::None);
+    // the page time until which a resize animation is sampled, and whether it is
+    let mut resize_tracked_until = use_signal(|| 0_f64);
+    let mut is_tracking_resize = use_signal(|| false);
+    // a touch was lifted on a platform that pauses momentum scroll events (the momentum
+    // phase may follow), and whether the momentum is being sampled
+    let mut touch_released = use_hook(|| CopyValue::new(false));
+    let mut is_tracking_momentum = use_signal(|| false);
+    // the latest on_scroll_intercept, also for the momentum sampling
+    let mut interceptor = use_hook(|| ScrollInterceptor {
+        handler: CopyValue::new(None),
+        correction: CopyValue::new(None),
+    });
+    interceptor.handler.set(on_scroll_intercept);
+
+    let boundary_adjustment = use_signal(|| Option::<BoundaryAdjustment>::None);
+    let layout_generation = use_signal(|| 0_u64);
+    let mut updated_at = use_signal(|| Option::<f64>::None);
+    let smooth_scroll = use_hook(|| CopyValue::new(Option::<SmoothScroll>::None));
+    let smooth_scrolling = use_signal(|| false);
+    let mut is_reversed = use_signal(|| reverse);
+    if *is_reversed.peek() != reverse {
+        is_reversed.set(reverse);
+    }
+    let mut is_rtl = use_signal(|| rtl);
+    if *is_rtl.peek() != rtl {
+        is_rtl.set(rtl);
+    }
+    let mut tracked_axes = use_signal(|| axes);
+    if *tracked_axes.peek() != axes {
+        tracked_axes.set(axes);
+    }
+    let mut defaults = use_signal(|| floating_defaults.clone());
+    if *defaults.peek() != floating_defaults {
+        defaults.set(floating_defaults);
+    }
+    // generated once, so it is stable across renders
+    let generated_id = use_hook(|| NEXT_SCROLLABLE_ID.fetch_add(1, Ordering::Relaxed));
+    let id = id.unwrap_or_else(|| format!("dioxus-floating-scrollable-{generated_id}"));
+    let mut element_id = use_signal(|| Some(id.clone()));
+    if element_id.peek().as_ref() != Some(&id) {
+        element_id.set(Some(id.clone()));
+    }
+
+    // the enclosing view, for use_parent_scroll_context below this one
+    let parent = try_use_context::<ScrollableContext>();
+    use_context_provider(move || ParentScrollContext(parent));
+    let ancestors = use_signal(|| ScrollableContext::chain(parent));
 
-    use_context_provider(move || ScrollableContext {
+    let mut context = use_context_provider(move || ScrollableContext {
         scrollable_ref,
         scroll_state,
+        boundary_adjustment,
+        floating_defaults: defaults,
+        layout_generation,
+        updated_at,
+        reverse: is_reversed,
+        axes: tracked_axes,
+        scroll_generation: Signal::new(0),
+        ancestors,
+        rtl: is_rtl,
+        registry: Signal::new(Vec::new()),
+        active_count: Signal::new(0),
+        is_smooth_scrolling: smooth_scrolling.into(),
+        smooth_scrolling,
+        smooth_scroll,
+        reloaded_generation: CopyValue::new(None),
+        provided: None,
+        ignore_reduced_motion: false,
+        id: element_id,
+    });
+
+    use_smooth_scroll_timeout(context);
+
+    // the content may change while the page is hidden without a scroll or resize event
+    // (e.g., a collapsed tab); a state that differs is published when it is shown again
+    let page_hidden = crate::page::use_page_listener();
+    let mut was_hidden = use_hook(|| CopyValue::new(false));
+    use_effect(move || {
+        let is_hidden = page_hidden().unwrap_or_default();
+        if !was_hidden.replace(is_hidden) || is_hidden {
+            return;
+        }
+        let Some(scrollable) = scrollable_ref.peek().clone() else {
+            return;
+        };
+        spawn(async move {
+            let Some(mut state) = floating
+                .try_generate_scroll_state_from_mounted(scrollable)
+                .await
+            else {
+                return;
+            };
+            if let Some(size) = *content_size.peek() {
+                state.size = size;
+            }
+            if *is_reversed.peek() {
+                state = state.from_reversed();
+            }
+            let is_same = scroll_state.peek().is_some_and(|current| {
+                (current.size, current.bounds, current.state)
+                    == (state.size, state.bounds, state.state)
+            });
+            if !is_same {
+                scroll_state.set(Some(state));
+                updated_at.set(Some(now_ms()));
+            }
+        });
+    });
+
+    // re-measure everything when the page zoom changes
+    let zoom_listener = use_hook(move || {
+        JsListener::start(ZOOM_SCRIPT, move |dpr: f64| {
+            crate::floating::set_device_pixel_ratio(dpr);
+            spawn(async move { context.invalidate_measurements().await });
+        })
     });
+    use_drop(move || zoom_listener.stop());
 
     rsx! {
         div { id: id, class: class, style: style,
@@ -66,38 +307,145 @@ pub fn ScrollableView(
                 scrollable_ref.set(Some(evt.data.clone()));
                 let mounted_data = evt.data.clone();
                 spawn(async move {
-                    let state = floating.generate_scroll_state_from_mounted(mounted_data).await;
+                    let Some(mut state) = floating.try_generate_scroll_state_from_mounted(mounted_data).await else {
+                        return;
+                    };
+                    if let Some(size) = *content_size.peek() {
+                        state.size = size;
+                    }
+                    if *is_reversed.peek() {
+                        state = state.from_reversed();
+                    }
                     scroll_state.set(Some(state));
+                    updated_at.set(Some(now_ms()));
                 });
             },
             onresize: move |evt: ResizeEvent| {
-                scroll_state.with_mut(move |sstate| {
-                    if let Some(state) = sstate {
-                        if let Ok(size) = evt.get_border_box_size() {
+                // mounted collapsed (e.g., inside a hidden tab): the whole state is stale,
+                // measure it again now that the view has a size
+                let is_degenerate = scroll_state
+                    .peek()
+                    .is_none_or(|state| state.bounds.is_empty());
+                if is_degenerate {
+                    if let Some(scrollable) = scrollable_ref() {
+                        spawn(async move {
+                            let Some(mut state) = floating.try_generate_scroll_state_from_mounted(scrollable).await else {
+                                return;
+                            };
+                            if let Some(size) = *content_size.peek() {
+                                state.size = size;
+                            }
+                            if *is_reversed.peek() {
+                                state = state.from_reversed();
+                            }
+                            scroll_state.set(Some(state));
+                            updated_at.set(Some(now_ms()));
+                        });
+                    }
+                    return;
+                }
+
+                // the client size excludes the scrollbars, see ScrollState::exclude_scrollbars
+                let client_size = scrollable_ref
+                    .peek()
+                    .as_ref()
+                    .and_then(|scrollable| crate::measure::client_size(scrollable));
+                if let Some(size) = client_size.or(evt.get_border_box_size().ok()) {
+                    scroll_state.with_mut(move |sstate| {
+                        if let Some(state) = sstate {
                             state.bounds = size;
                         }
+                    });
+                }
 
-                        *sstate = Some(state.to_owned());
+                // every resize extends the sampling window of a running loop
+                if track_resize_animation {
+                    resize_tracked_until.set(now_ms() + RESIZE_TRACKING_MS);
+                    if let Some(scrollable) = scrollable_ref().filter(|_| !*is_tracking_resize.peek()) {
+                        is_tracking_resize.set(true);
+                        spawn(async move {
+                            track_resize(context, scrollable, resize_tracked_until).await;
+                            is_tracking_resize.set(false);
+                        });
                     }
-                });
+                }
+
+                // the content wrapper reports its own size
+                if wrap_content {
+                    return;
+                }
+
+                // every resize bumps the generation; a refresh that is already
+                // in flight picks the latest one up instead of spawning another
+                *resize_generation.write() += 1;
+                if *size_refresh_pending.peek() {
+                    return;
+                }
+
                 if let Some(scrollable) = scrollable_ref() {
+                    size_refresh_pending.set(true);
                     spawn(async move {
-                        if let Ok(size) = scrollable.get_scroll_size().await {
-                            scroll_state.with_mut(move |sstate| {
-                                if let Some(state) = sstate {
-                                    state.size = size;
-                                    *sstate = Some(state.to_owned());
-                                }
-                            });
+                        loop {
+                            let generation = *resize_generation.peek();
+                            let size = scrollable.get_scroll_size().await;
+
+                            // a newer resize arrived while measuring, the result is stale
+                            if generation != *resize_generation.peek() {
+                                continue;
+                            }
+
+                            // merge into the current state, not the one captured at spawn time
+                            if let Ok(size) = size {
+                                scroll_state.with_mut(move |sstate| {
+                                    if let Some(state) = sstate {
+                                        state.size = size;
+                                    }
+                                });
+                            }
+                            break;
                         }
+                        size_refresh_pending.set(false);
                     });
                 }
             },
             onscroll: move |evt: ScrollEvent| {
-                let new_state = floating.generate_scroll_state(evt);
-                scroll_state.set(Some(new_state));
+                let mut new_state = floating.generate_scroll_state(evt);
+                if let Some(size) = *content_size.peek() {
+                    new_state.size = size;
+                }
+                if *is_reversed.peek() {
+                    new_state = new_state.from_reversed();
+                }
+                let Some(new_state) = publish_scroll_state(context, interceptor, new_state) else {
+                    return;
+                };
                 if let Some(cb) = on_scroll { cb.call(new_state); }
+
+                // scroll events after the touch was lifted: the momentum phase
+                if *touch_released.peek()
+                    && !*is_tracking_momentum.peek()
+                    && let Some(scrollable) = scrollable_ref.peek().clone()
+                {
+                    is_tracking_momentum.set(true);
+                    spawn(async move {
+                        track_momentum(context, scrollable, touch_released, interceptor).await;
+                        touch_released.set(false);
+                        is_tracking_momentum.set(false);
+                    });
+                }
+            },
+            // the user takes over: a smooth scroll in progress is abandoned
+            onwheel: move |_| context.interrupt_smooth_scroll(),
+            ontouchstart: move |_| {
+                touch_released.set(false);
+                context.interrupt_smooth_scroll();
+            },
+            ontouchend: move |_| {
+                if crate::measure::pauses_momentum_scroll_events() {
+                    touch_released.set(true);
+                }
             },
+            onkeydown: move |_| context.interrupt_smooth_scroll(),
             onmousemove: move |evt: MouseEvent| {
                 if let Some(cb) = on_mouse_move { cb.call(evt); }
             },
@@ -105,45 +453,938 @@ pub fn ScrollableView(
                 if let Some(cb) = on_mouse_up { cb.call(evt); }
             },
             onmousedown: move |evt: MouseEvent| {
+                // e.g., on the scrollbar
+                context.interrupt_smooth_scroll();
                 if let Some(cb) = on_mouse_down { cb.call(evt); }
             },
             ..attributes,
 
-            {children}
+            if wrap_content {
+                div { style: "min-width: 100%; width: fit-content;",
+                    onresize: move |evt: ResizeEvent| {
+                        if let Ok(size) = evt.get_border_box_size() {
+                            content_size.set(Some(size));
+                            scroll_state.with_mut(move |sstate| {
+                                if let Some(state) = sstate {
+                                    state.size = size;
+                                }
+                            });
+                        }
+                    },
+                    {children}
+                }
+            } else {
+                {children}
+            }
         }
     }
 }
 
+/// Provides a [ScrollableContext] fed by an existing scroll source instead of a
+/// [ScrollableView], e.g., a virtualization library that already tracks its container.
+///
+/// `state` is published as the [ScrollState] of the context (normalized, `0` at the top)
+/// and `container_rect` is the rect of the container in viewport coordinates: the
+/// placement hooks inside use it as their boundary instead of measuring an element, and
+/// recompute when either changes. [ScrollableContext::scrollable_ref] stays `None`; the
+/// placements are not ready until both are provided.
+///
+/// [ScrollableContext::scroll] and [ScrollableContext::scroll_by] pass the clamped offset
+/// to `on_scroll_request`, for the source to scroll and publish the new state. Without a
+/// handler they are ignored with a warning, like [ScrollableContext::scroll_to] and
+/// [ScrollableContext::scroll_to_with_options], which need the element of a view.
+/// `floating` and `floating_defaults` configure the region like in a [ScrollableView].
+///
+/// # Example
+///
+/// ```rust,norun
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsVector2D};
+/// use dioxus_floating::{ScrollState, ScrollStateProvider};
+///
+/// #[component]
+/// fn VirtualList(state: Signal<Option<ScrollState>>, rect: Signal<Option<PixelsRect>>) -> Element {
+///     rsx! {
+///         ScrollStateProvider {
+///             state,
+///             container_rect: rect,
+///             on_scroll_request: move |(offset, _behavior): (PixelsVector2D, ScrollBehavior)| {
+///                 // hand it to the virtualizer, which publishes the new state
+///                 println!("scroll to {offset:?}");
+///             },
+///             // the rows with their tooltips
+///         }
+///     }
+/// }
+/// ```
+#[cfg(feature = "components")]
+#[component]
+pub fn ScrollStateProvider(
+    state: ReadSignal<Option<ScrollState>>,
+    container_rect: ReadSignal<Option<PixelsRect>>,
+    #[props(into)] on_scroll_request: Option<EventHandler<(PixelsVector2D, ScrollBehavior)>>,
+    #[props(default)] floating: Option<Floating>,
+    #[props(default)] floating_defaults: Option<FloatingOptions>,
+    children: Element,
+) -> Element {
+    let inherited = crate::use_floating();
+    use_context_provider(move || floating.unwrap_or(inherited));
+
+    let mut defaults = use_signal(|| floating_defaults.clone());
+    if *defaults.peek() != floating_defaults {
+        defaults.set(floating_defaults);
+    }
+
+    let parent = try_use_context::<ScrollableContext>();
+    use_context_provider(move || ParentScrollContext(parent));
+    let ancestors = use_signal(|| ScrollableContext::chain(parent));
+
+    let smooth_scrolling = use_signal(|| false);
+    let mut context = use_context_provider(move || ScrollableContext {
+        scrollable_ref: Signal::new(None),
+        scroll_state: Signal::new(*state.peek()),
+        boundary_adjustment: Signal::new(None),
+        floating_defaults: defaults,
+        layout_generation: Signal::new(0),
+        updated_at: Signal::new(state.peek().is_some().then(now_ms)),
+        reverse: Signal::new(false),
+        axes: Signal::new(TrackedAxes::Both),
+        scroll_generation: Signal::new(0),
+        ancestors,
+        rtl: Signal::new(false),
+        registry: Signal::new(Vec::new()),
+        active_count: Signal::new(0),
+        is_smooth_scrolling: smooth_scrolling.into(),
+        smooth_scrolling,
+        smooth_scroll: CopyValue::new(None),
+        reloaded_generation: CopyValue::new(None),
+        provided: Some(ProvidedContainer {
+            rect: container_rect,
+            on_scroll_request,
+        }),
+        ignore_reduced_motion: false,
+        id: Signal::new(None),
+    });
+    use_smooth_scroll_timeout(context);
+
+    // every new state is published like a scroll event of a view
+    use_effect(move || {
+        let state = state();
+        let extent = |state: Option<ScrollState>| state.map(|s| (s.size, s.bounds, s.state));
+        if extent(*context.scroll_state.peek()) == extent(state) {
+            return;
+        }
+        context.scroll_state.set(state);
+        context.updated_at.set(state.map(|_| now_ms()));
+        if let Some(state) = state {
+            context.observe_offset(state.state);
+        }
+    });
+
+    // the edges moved: measure the placements again
+    let mut last_rect = use_hook(|| CopyValue::new(*container_rect.peek()));
+    use_effect(move || {
+        let rect = container_rect();
+        if *last_rect.peek() != rect {
+            last_rect.set(rect);
+            *context.layout_generation.write() += 1;
+        }
+    });
+
+    rsx! {
+        {children}
+    }
+}
+
+/// Internal: How long a smooth scroll may go without a scroll event before it is
+/// considered ended, see [ScrollableContext::is_smooth_scrolling].
+#[cfg(feature = "components")]
+const SMOOTH_SCROLL_QUIET_MS: u32 = 150;
+
+/// Internal: Ends a smooth scroll that never arrives (an alignment scroll, a shrinking
+/// content) after a quiet period without scroll events.
+#[cfg(feature = "components")]
+fn use_smooth_scroll_timeout(mut context: ScrollableContext) {
+    // the scroll methods read the preference, see ScrollableContext::ignoring_reduced_motion
+    motion::use_motion_listener(true);
+    let (smooth_scroll, updated_at) = (context.smooth_scroll, context.updated_at);
+    use_effect(move || {
+        if !(context.smooth_scrolling)() {
+            return;
+        }
+        spawn(async move {
+            let mut last = (*smooth_scroll.peek(), *updated_at.peek());
+            loop {
+                WaitStrategy::Timeout(SMOOTH_SCROLL_QUIET_MS).wait().await;
+                let current = (*smooth_scroll.peek(), *updated_at.peek());
+                if current.0.is_none() {
+                    return;
+                }
+                // neither a scroll event nor a newer smooth scroll
+                if current == last {
+                    context.interrupt_smooth_scroll();
+                    return;
+                }
+                last = current;
+            }
+        });
+    });
+}
+
+/// The decision of the `on_scroll_intercept` handler of a [ScrollableView] about a
+/// proposed [ScrollState].
+#[cfg(feature = "components")]
+#[derive(Debug, Clone, Copy)]
+pub enum ScrollIntercept {
+    /// Publish the proposed state.
+    Accept,
+    /// Publish this state instead, and scroll the view to its offset.
+    Replace(ScrollState),
+    /// Keep the published state, and scroll the view back to its offset.
+    Veto,
+}
+
+/// Internal: The `on_scroll_intercept` handler of a [ScrollableView], and the offset of
+/// the corrective scroll in flight, whose event is not intercepted again.
+#[cfg(feature = "components")]
+#[derive(Clone, Copy)]
+struct ScrollInterceptor {
+    handler: CopyValue<Option<Callback<ScrollState, ScrollIntercept>>>,
+    correction: CopyValue<Option<PixelsVector2D>>,
+}
+
+#[cfg(feature = "components")]
+impl ScrollInterceptor {
+    /// Internal: Returns the state to publish instead of `proposed`, or `None` if it was
+    /// vetoed, and scrolls the view to the published offset if it differs.
+    fn intercept(
+        mut self,
+        context: ScrollableContext,
+        previous: Option<ScrollState>,
+        proposed: ScrollState,
+    ) -> Option<ScrollState> {
+        // the corrective scroll arrived
+        let correction = self
+            .correction
+            .try_peek()
+            .ok()
+            .and_then(|correction| *correction);
+        if correction.is_some_and(|correction| is_near(correction, proposed.state)) {
+            self.correction.set(None);
+            return Some(proposed);
+        }
+        let Some(handler) = self.handler.try_peek().ok().and_then(|handler| *handler) else {
+            return Some(proposed);
+        };
+
+        let published = match handler.call(proposed) {
+            ScrollIntercept::Accept => return Some(proposed),
+            ScrollIntercept::Replace(state) => Some(state),
+            ScrollIntercept::Veto => None,
+        };
+        if let Some(target) = published.or(previous).map(|state| state.state)
+            && !is_near(target, proposed.state)
+        {
+            self.correction.set(Some(target));
+            spawn(async move {
+                context.scroll(target, ScrollBehavior::Instant).await;
+            });
+        }
+
+        published
+    }
+}
+
+/// Internal: Publishes a (normalized) scroll state of the view like a scroll event, or
+/// the one `interceptor` replaces it with. Returns `None` if it changed only an untracked
+/// axis, which is not a scroll, or was vetoed.
+#[cfg(feature = "components")]
+fn publish_scroll_state(
+    mut context: ScrollableContext,
+    interceptor: ScrollInterceptor,
+    state: ScrollState,
+) -> Option<ScrollState> {
+    let previous = *context.scroll_state.peek();
+    let state = context.axes.peek().merge(previous, state)?;
+    let state = interceptor.intercept(context, previous, state)?;
+    context.scroll_state.set(Some(state));
+    context.updated_at.set(Some(now_ms()));
+    context.observe_offset(state.state);
+
+    Some(state)
+}
+
+/// Internal: The number of frames with an unchanged offset that end a momentum scroll.
+#[cfg(feature = "components")]
+const MOMENTUM_STABLE_FRAMES: u32 = 6;
+
+/// Internal: Samples the scroll offset of the view every animation frame during the
+/// momentum phase of a touch scroll and publishes the changes the scroll events missed,
+/// until it is stable, a new touch starts, the page is hidden or the view is dropped.
+///
+/// iOS may stop firing scroll events while the content rubber-bands and resume them
+/// later, which freezes the placements mid-flight and then makes them jump. Only the web
+/// renderer can read the offset outside of events; elsewhere this returns immediately.
+#[cfg(feature = "components")]
+async fn track_momentum(
+    context: ScrollableContext,
+    scrollable: Rc<MountedData>,
+    touch_released: CopyValue<bool>,
+    interceptor: ScrollInterceptor,
+) {
+    let mut last_offset = None;
+    let mut stable_frames = 0;
+
+    while stable_frames < MOMENTUM_STABLE_FRAMES {
+        if !crate::measure::next_animation_frame().await {
+            return;
+        }
+        let is_released = touch_released
+            .try_peek()
+            .is_ok_and(|is_released| *is_released);
+        // a hidden page has nothing to show, the view refreshes its state when it is back
+        if !is_released || !context.is_alive() || crate::page::is_suspended() {
+            return;
+        }
+        let Some(offset) = crate::measure::scroll_offset(&scrollable) else {
+            return;
+        };
+
+        if last_offset == Some(offset) {
+            stable_frames += 1;
+            continue;
+        }
+        stable_frames = 0;
+        last_offset = Some(offset);
+
+        let Some(current) = *context.scroll_state.peek() else {
+            return;
+        };
+        let mut state = current.with_offset(offset);
+        if *context.reverse.peek() {
+            state = state.from_reversed();
+        }
+        // a scroll event published it already
+        if state.state != current.state {
+            publish_scroll_state(context, interceptor, state);
+        }
+    }
+}
+
+/// Internal: How long a resize animation is sampled after the last resize, see
+/// `track_resize_animation` of [ScrollableView].
+#[cfg(feature = "components")]
+const RESIZE_TRACKING_MS: f64 = 500.0;
+
+/// Internal: The number of frames with an unchanged rect that end a resize animation.
+#[cfg(feature = "components")]
+const RESIZE_STABLE_FRAMES: u32 = 3;
+
+/// Internal: Samples the rect of the view every animation frame and publishes the
+/// changes, until it is stable, `tracked_until` has passed, the page is hidden or the
+/// view is dropped.
+#[cfg(feature = "components")]
+async fn track_resize(
+    mut context: ScrollableContext,
+    scrollable: Rc<MountedData>,
+    tracked_until: Signal<f64>,
+) {
+    let mut last_rect = None;
+    let mut stable_frames = 0;
+
+    while stable_frames < RESIZE_STABLE_FRAMES {
+        // a renderer without frames (e.g., headless) cannot animate
+        if !crate::measure::next_animation_frame().await {
+            return;
+        }
+        let is_tracked = tracked_until
+            .try_peek()
+            .is_ok_and(|tracked_until| now_ms() < *tracked_until);
+        if !is_tracked || !context.is_alive() || crate::page::is_suspended() {
+            return;
+        }
+        let Ok(rect) = scrollable.get_client_rect().await else {
+            return;
+        };
+        if !context.is_alive() {
+            return;
+        }
+
+        if last_rect == Some(rect) {
+            stable_frames += 1;
+            continue;
+        }
+        stable_frames = 0;
+        last_rect = Some(rect);
+
+        let bounds = crate::measure::client_size(&scrollable).unwrap_or(rect.size);
+        context.scroll_state.with_mut(|state| {
+            if let Some(state) = state {
+                state.bounds = bounds;
+            }
+        });
+        context.updated_at.set(Some(now_ms()));
+        // the edges may move without a size change, measure the placements again
+        *context.layout_generation.write() += 1;
+    }
+}
+
 /// Context provided by the [ScrollableView] component.
 ///
 /// It contains reactive signals for the scroll state and a reference to the
 /// underlying DOM element, along with methods to programmatically control scrolling.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ScrollableContext {
     /// A reactive signal containing the [MountedData] of the scrollable container.
     pub scrollable_ref: Signal<Option<Rc<MountedData>>>,
 
     /// A reactive signal containing the current [ScrollState] (dimensions, offset, etc.).
     pub scroll_state: Signal<Option<ScrollState>>,
+
+    /// A reactive signal containing the adjustment applied to the measured container
+    /// rect before it is used as the boundary. See [ScrollableContext::set_boundary_adjustment].
+    pub boundary_adjustment: Signal<Option<BoundaryAdjustment>>,
+
+    /// A reactive signal containing the [FloatingOptions] merged under the options of
+    /// every placement hook in this view. See [FloatingOptions::with_defaults].
+    pub floating_defaults: Signal<Option<FloatingOptions>>,
+
+    /// A reactive signal incremented whenever all measurements are invalidated
+    /// (e.g., after a page zoom). See [ScrollableContext::invalidate_measurements].
+    pub layout_generation: Signal<u64>,
+
+    /// Time (milliseconds since the epoch) of the last full measurement of the
+    /// [ScrollState], used by [ScrollableContext::peek_fresh].
+    pub updated_at: Signal<Option<f64>>,
+
+    /// A reactive signal, `true` if the view is a `column-reverse` container whose
+    /// [ScrollState] is normalized (see [ScrollState::from_reversed]).
+    pub reverse: Signal<bool>,
+
+    /// A reactive signal containing the [TrackedAxes] of the view.
+    pub axes: Signal<TrackedAxes>,
+
+    /// Incremented when the offset changes without the user scrolling: by
+    /// [ScrollableContext::reload] and before and after the programmatic scroll methods,
+    /// so state derived from scroll events (e.g., [crate::use_scroll_reveal]) can start over.
+    pub scroll_generation: Signal<u64>,
+
+    /// The contexts of the enclosing views, nearest first. The placement hooks
+    /// recompute when any of them scrolls, e.g., a page scrolling vertically around
+    /// a strip scrolling horizontally.
+    pub ancestors: Signal<Vec<ScrollableContext>>,
+
+    /// A reactive signal that is `true` when the vertical scrollbar of the view is on
+    /// the left (`direction: rtl`), see [ScrollState::exclude_scrollbars].
+    pub rtl: Signal<bool>,
+
+    /// The placement hooks with a mounted floating element in this view, see
+    /// [ScrollableContext::floatings].
+    pub registry: Signal<Vec<FloatingDescriptor>>,
+
+    /// The number of entries in the registry, see [ScrollableContext::active_floatings].
+    pub active_count: Signal<usize>,
+
+    /// A reactive signal, `true` while a smooth programmatic scroll issued through
+    /// [ScrollableContext::scroll], [ScrollableContext::scroll_by],
+    /// [ScrollableContext::scroll_to] or [ScrollableContext::scroll_to_with_options] is
+    /// in progress. Native user scrolls never set it.
+    ///
+    /// It is an estimate: it clears when the offset arrives at the target, when the
+    /// user takes over (wheel, touch, keys or a press in the view, see
+    /// [ScrollableContext::interrupt_smooth_scroll]) and, in a [ScrollableView], after
+    /// 150ms without scroll events. See [FloatingOptions::freeze_during_smooth_scroll].
+    pub is_smooth_scrolling: ReadSignal<bool>,
+
+    /// Internal: The writable side of [ScrollableContext::is_smooth_scrolling].
+    pub(crate) smooth_scrolling: Signal<bool>,
+
+    /// Internal: The smooth scroll in progress.
+    pub(crate) smooth_scroll: CopyValue<Option<SmoothScroll>>,
+
+    /// Internal: The [ScrollableContext::scroll_generation] of the last
+    /// [ScrollableContext::reload], which publishes the state it ends at itself.
+    pub(crate) reloaded_generation: CopyValue<Option<u64>>,
+
+    /// Internal: The container of a [ScrollStateProvider], `None` in a [ScrollableView].
+    pub(crate) provided: Option<ProvidedContainer>,
+
+    /// Internal: Smooth scrolls stay smooth with [crate::prefers_reduced_motion], see
+    /// [ScrollableContext::ignoring_reduced_motion].
+    pub(crate) ignore_reduced_motion: bool,
+
+    /// Internal: The `id` of the container, see [ScrollableContext::element_id].
+    pub(crate) id: Signal<Option<String>>,
+}
+
+/// Internal: The container of a [ScrollStateProvider]: the rect the placements inside
+/// use instead of measuring [ScrollableContext::scrollable_ref], and the handler of the
+/// programmatic scrolls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ProvidedContainer {
+    pub(crate) rect: ReadSignal<Option<PixelsRect>>,
+    pub(crate) on_scroll_request: Option<Callback<(PixelsVector2D, ScrollBehavior)>>,
+}
+
+/// Internal: A smooth programmatic scroll in progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SmoothScroll {
+    // the normalized offset it ends at, unknown for the alignment scrolls
+    target: Option<PixelsVector2D>,
+    // tells consecutive scrolls apart
+    id: u64,
+}
+
+/// Internal: Distance in pixels from the target at which a smooth scroll has arrived.
+const SMOOTH_SCROLL_ARRIVAL: f64 = 1.0;
+
+/// Internal: The context of the view enclosing a [ScrollableView].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ParentScrollContext(pub(crate) Option<ScrollableContext>);
+
+/// A function that adjusts the boundary rect of every floating element in a [ScrollableView].
+#[derive(Clone)]
+pub struct BoundaryAdjustment(Rc<dyn Fn(PixelsRect) -> PixelsRect>);
+
+impl BoundaryAdjustment {
+    /// Creates a new [BoundaryAdjustment] from a function.
+    pub fn new(adjust: impl Fn(PixelsRect) -> PixelsRect + 'static) -> Self {
+        Self(Rc::new(adjust))
+    }
+
+    /// Applies the adjustment to a boundary rect.
+    pub fn apply(&self, rect: PixelsRect) -> PixelsRect {
+        (self.0)(rect)
+    }
+
+    /// Internal: Returns `true` if both are the same adjustment (not only equal ones).
+    pub(crate) fn is_same(&self, other: &BoundaryAdjustment) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl std::fmt::Debug for BoundaryAdjustment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BoundaryAdjustment(..)")
+    }
 }
 
 impl ScrollableContext {
+    /// Returns the scrolled fraction (`0..1`) along the tracked axis, see
+    /// [ScrollState::progress]. `None` until the state has been measured. Reading it in a
+    /// component or effect subscribes to the scroll state.
+    pub fn progress(&self) -> Option<f64> {
+        let axes = (self.axes)();
+        (self.scroll_state)().map(|state| state.progress(axes))
+    }
+
+    /// Returns `true` if the view is scrolled to within `threshold` pixels of the end
+    /// of the tracked axis, see [ScrollState::is_at_end].
+    pub fn is_at_end(&self, threshold: f64) -> bool {
+        let axes = (self.axes)();
+        (self.scroll_state)().is_some_and(|state| state.is_at_end(axes, threshold))
+    }
+
+    /// Returns the number of floating elements mounted in this view (placement hooks
+    /// with a mounted element, including pinned ones from nested views), e.g., to limit
+    /// the number of pinned panels. Only changes when an element mounts or unmounts.
+    ///
+    /// # Example
+    ///
+    /// With the [crate::testing] mocks, hooks of closed and unmounted elements do not count.
+    ///
+    /// ```rust
+    /// use std::cell::Cell;
+    ///
+    /// use dioxus::prelude::*;
+    /// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+    /// use dioxus_floating::{FloatingOptions, ScrollableContext, use_placement};
+    ///
+    /// thread_local! {
+    ///     static CONTEXT: Cell<Option<ScrollableContext>> = Cell::new(None);
+    ///     static OPEN: Cell<Option<Signal<Vec<bool>>>> = Cell::new(None);
+    /// }
+    ///
+    /// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// }
+    ///
+    /// fn app() -> Element {
+    ///     let context = use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+    ///     CONTEXT.with(|cell| cell.set(Some(context)));
+    ///     // a panel is mounted per entry, open or closed
+    ///     let open = use_signal(|| vec![true, false, true]);
+    ///     OPEN.with(|cell| cell.set(Some(open)));
+    ///     rsx! {
+    ///         for (index, is_open) in open().into_iter().enumerate() {
+    ///             Panel { key: "{index}", is_open }
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// #[component]
+    /// fn Panel(is_open: bool) -> Element {
+    ///     let trigger_ref = use_signal(|| Some(MockElement::new(rect(10.0, 10.0, 40.0, 20.0)).mounted()));
+    ///     let mut element_ref = use_signal(|| None);
+    ///     use_effect(use_reactive!(|is_open| {
+    ///         element_ref.set(is_open.then(|| MockElement::new(rect(0.0, 0.0, 80.0, 40.0)).mounted()));
+    ///     }));
+    ///     let options = FloatingOptions { debug_label: Some("panel"), ..Default::default() };
+    ///     use_placement(element_ref, trigger_ref, options);
+    ///     rsx! {}
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild_in_place();
+    /// settle(&mut dom);
+    /// let context = CONTEXT.with(Cell::get).unwrap();
+    /// let mut open = OPEN.with(Cell::get).unwrap();
+    /// assert_eq!(dom.in_scope(ScopeId::ROOT, || *context.active_floatings().peek()), 2);
+    /// let floatings = dom.in_runtime(|| context.floatings());
+    /// assert!(floatings.iter().all(|floating| floating.label == Some("panel") && floating.is_ready));
+    ///
+    /// // open the second panel, unmount the third one
+    /// dom.in_runtime(|| open.set(vec![true, true]));
+    /// settle(&mut dom);
+    /// assert_eq!(dom.in_scope(ScopeId::ROOT, || *context.active_floatings().peek()), 2);
+    ///
+    /// dom.in_runtime(|| open.set(vec![false]));
+    /// settle(&mut dom);
+    /// assert_eq!(dom.in_scope(ScopeId::ROOT, || *context.active_floatings().peek()), 0);
+    /// ```
+    pub fn active_floatings(&self) -> ReadSignal<usize> {
+        self.active_count.into()
+    }
+
+    /// Returns the placement hooks with a mounted floating element in this view, e.g.,
+    /// for a dev panel that shows what is open and what it costs. Reading it in a
+    /// component or effect subscribes to their updates.
+    pub fn floatings(&self) -> Vec<FloatingDescriptor> {
+        self.registry
+            .try_read()
+            .map(|registry| registry.clone())
+            .unwrap_or_default()
+    }
+
+    /// Internal: Adds or updates the descriptor of a placement.
+    pub(crate) fn register(&mut self, descriptor: FloatingDescriptor) {
+        let Ok(registry) = self.registry.try_peek() else {
+            return;
+        };
+        let index = registry.iter().position(|entry| entry.id == descriptor.id);
+        if index.is_some_and(|index| registry[index] == descriptor) {
+            return;
+        }
+        drop(registry);
+
+        match index {
+            Some(index) => self.registry.write()[index] = descriptor,
+            None => {
+                self.registry.write().push(descriptor);
+                self.active_count.set(self.registry.peek().len());
+            }
+        }
+    }
+
+    /// Internal: Removes the descriptor of a placement, if registered.
+    pub(crate) fn unregister(&mut self, id: usize) {
+        // the view may be dropped before the hooks inside it
+        let Ok(registry) = self.registry.try_peek() else {
+            return;
+        };
+        let Some(index) = registry.iter().position(|entry| entry.id == id) else {
+            return;
+        };
+        drop(registry);
+
+        self.registry.write().remove(index);
+        if self.active_count.try_peek().is_ok() {
+            self.active_count.set(self.registry.peek().len());
+        }
+    }
+
+    /// Internal: Returns `parent` followed by its ancestors, nearest first.
+    pub(crate) fn chain(parent: Option<ScrollableContext>) -> Vec<ScrollableContext> {
+        match parent {
+            Some(parent) => std::iter::once(parent)
+                .chain(parent.ancestors.peek().iter().copied())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
     /// Forces a re-calculation of the scroll content size and current offset.
     /// Useful when the content inside changes but the container's outer bounds remain the same.
     pub async fn reload(&mut self) {
-        if let Some(data) = self.scrollable_ref.peek().as_ref() {
+        if let Some(data) = self.scrollable() {
             // Мы используем логику из Floating, которую ты уже написал
-            let floating = crate::Floating;
-            let new_state = floating
-                .generate_scroll_state_from_mounted(data.clone())
-                .await;
+            let floating = crate::Floating::default();
+            let Some(mut new_state) = floating.try_generate_scroll_state_from_mounted(data).await
+            else {
+                return;
+            };
+            // the view may have been unmounted while measuring
+            if !self.is_alive() {
+                return;
+            }
+            if *self.reverse.peek() {
+                new_state = new_state.from_reversed();
+            }
 
             // Обновляем сигнал
             self.scroll_state.set(Some(new_state));
+            self.updated_at.set(Some(now_ms()));
+            *self.scroll_generation.write() += 1;
+            self.reloaded_generation
+                .set(Some(*self.scroll_generation.peek()));
+        }
+    }
+
+    /// Internal: Increments [ScrollableContext::scroll_generation] around a programmatic
+    /// scroll, unless the view was dropped.
+    fn mark_programmatic_scroll(&self) {
+        let mut generation = self.scroll_generation;
+        if let Ok(mut generation) = generation.try_write() {
+            *generation += 1;
+        }
+    }
+
+    /// Internal: Starts tracking a smooth scroll that ends at `target` (normalized), if
+    /// known. Replaces the one in progress.
+    fn begin_smooth_scroll(&self, target: Option<PixelsVector2D>) {
+        let id = self
+            .scroll_generation
+            .try_peek()
+            .map_or(0, |generation| *generation);
+        self.set_smooth_scroll(Some(SmoothScroll { target, id }));
+    }
+
+    /// Internal: Replaces the smooth scroll in progress and updates the flag when it
+    /// changes, unless the view was dropped.
+    fn set_smooth_scroll(&self, smooth: Option<SmoothScroll>) {
+        let mut smooth_scroll = self.smooth_scroll;
+        if smooth_scroll.try_peek().is_err() {
+            return;
+        }
+        smooth_scroll.set(smooth);
+        let mut smooth_scrolling = self.smooth_scrolling;
+        if *smooth_scrolling.peek() != smooth.is_some() {
+            smooth_scrolling.set(smooth.is_some());
+        }
+    }
+
+    /// Ends the smooth scroll in progress, if any: clears
+    /// [ScrollableContext::is_smooth_scrolling] immediately.
+    ///
+    /// A [ScrollableView] calls it when the user takes over (wheel, touch, keys or a
+    /// press in the view); call it from custom scroll implementations, e.g., when a drag
+    /// starts.
+    ///
+    /// # Example
+    ///
+    /// A wheel event during a smooth scroll, with the [crate::testing] mocks:
+    ///
+    /// ```rust
+    /// use std::cell::Cell;
+    ///
+    /// use dioxus::prelude::*;
+    /// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::ScrollableContext;
+    /// use dioxus_floating::testing::{MockScrollableConfig, provide_mock_scrollable, settle};
+    ///
+    /// thread_local! {
+    ///     static FLAGS: Cell<(bool, bool)> = Cell::new((false, true));
+    /// }
+    ///
+    /// fn app() -> Element {
+    ///     let mut context = use_hook(|| {
+    ///         let rect = PixelsRect::new(PixelsVector2D::new(0.0, 0.0).to_point(), PixelsSize::new(300.0, 300.0));
+    ///         let mut config = MockScrollableConfig::new(rect);
+    ///         config.scroll_state.size = PixelsSize::new(300.0, 1000.0);
+    ///         provide_mock_scrollable(config)
+    ///     });
+    ///     use_hook(move || {
+    ///         spawn(async move {
+    ///             context.scroll(PixelsVector2D::new(0.0, 400.0), ScrollBehavior::Smooth).await;
+    ///             let during = (context.is_smooth_scrolling)();
+    ///             // the user turns the wheel
+    ///             context.interrupt_smooth_scroll();
+    ///             FLAGS.with(|flags| flags.set((during, (context.is_smooth_scrolling)())));
+    ///         })
+    ///     });
+    ///     rsx! {}
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild_in_place();
+    /// settle(&mut dom);
+    /// assert_eq!(FLAGS.with(Cell::get), (true, false));
+    /// ```
+    pub fn interrupt_smooth_scroll(&mut self) {
+        self.end_smooth_scroll();
+    }
+
+    /// Internal: Clears the smooth scroll in progress, unless the view was dropped.
+    fn end_smooth_scroll(&self) {
+        self.set_smooth_scroll(None);
+    }
+
+    /// Internal: Ends the smooth scroll in progress when `offset` (normalized) arrives at
+    /// its target.
+    pub(crate) fn observe_offset(&mut self, offset: PixelsVector2D) {
+        let is_arrived = self.smooth_scroll.try_peek().is_ok_and(|smooth_scroll| {
+            smooth_scroll
+                .and_then(|smooth_scroll| smooth_scroll.target)
+                .is_some_and(|target| is_near(target, offset))
+        });
+        if is_arrived {
+            self.end_smooth_scroll();
+        }
+    }
+
+    /// Internal: Returns the mounted scrollable element, or `None` if it is not mounted
+    /// yet or the [ScrollableView] was already dropped.
+    fn scrollable(&self) -> Option<Rc<MountedData>> {
+        self.scrollable_ref.try_peek().ok()?.clone()
+    }
+
+    /// Internal: Returns the container the placements inside measure against: the
+    /// mounted element of a [ScrollableView] (reactive) or the rect of a
+    /// [ScrollStateProvider], which bumps the layout generation when it changes. `None`
+    /// until either is available.
+    pub(crate) fn container(&self) -> Option<Container> {
+        if let Some(scrollable) = (self.scrollable_ref)() {
+            return Some(Container::Mounted(scrollable));
         }
+        let rect = (*self.provided?.rect.peek())?;
+
+        Some(Container::Provided(rect))
+    }
+
+    /// Internal: `false` once the [ScrollableView] that owns the signals was dropped.
+    ///
+    /// Async methods check it after every await, so a context captured by a task
+    /// that outlives the view never writes to dropped signals.
+    fn is_alive(&self) -> bool {
+        self.scroll_state.try_peek().is_ok()
     }
 
-    /// Programmatically scrolls the container by a given offset.
+    /// Measures the [ScrollState] immediately, updates the signal and returns the fresh state.
+    ///
+    /// Returns `None` if the view is not mounted yet. A [ScrollStateProvider] returns its
+    /// current state, which only its source can measure.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus::prelude::*;
+    /// use dioxus_floating::use_scroll_context;
+    ///
+    /// #[component]
+    /// fn OpenButton() -> Element {
+    ///     let mut ctx = use_scroll_context();
+    ///     let mut as_sheet = use_signal(|| false);
+    ///     rsx! {
+    ///         button {
+    ///             onclick: move |_| async move {
+    ///                 // a popover needs room, otherwise present a sheet
+    ///                 if let Some(state) = ctx.measure_now().await {
+    ///                     as_sheet.set(state.bounds.height < 400.0);
+    ///                 }
+    ///             },
+    ///             "Open"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub async fn measure_now(&mut self) -> Option<ScrollState> {
+        if self.provided.is_some() {
+            return *self.scroll_state.try_peek().ok()?;
+        }
+        self.scrollable()?;
+        self.reload().await;
+
+        *self.scroll_state.try_peek().ok()?
+    }
+
+    /// Returns the cached [ScrollState] if it was measured within `max_age_ms`,
+    /// otherwise measures it like [ScrollableContext::measure_now].
+    pub async fn peek_fresh(&mut self, max_age_ms: f64) -> Option<ScrollState> {
+        let cached = *self.scroll_state.try_peek().ok()?;
+        let is_fresh = self
+            .updated_at
+            .peek()
+            .is_some_and(|updated_at| now_ms() - updated_at <= max_age_ms);
+        match cached {
+            Some(state) if is_fresh => Some(state),
+            _ => self.measure_now().await,
+        }
+    }
+
+    /// Invalidates every cached measurement: re-measures the [ScrollState] and forces
+    /// the open placements in this view to measure again.
+    ///
+    /// Called automatically when the page zoom (`devicePixelRatio`) changes.
+    pub async fn invalidate_measurements(&mut self) {
+        self.reload().await;
+        if self.is_alive() {
+            *self.layout_generation.write() += 1;
+        }
+    }
+
+    /// Adjusts the boundary of every floating element in this view.
+    ///
+    /// The function receives the measured container rect and returns the usable one,
+    /// e.g., to exclude a panel that overlaps the view. Open placements are recomputed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus::prelude::*;
+    /// use dioxus::html::geometry::{PixelsRect, PixelsSize};
+    /// use dioxus_floating::use_scroll_context;
+    ///
+    /// #[component]
+    /// fn Inspector() -> Element {
+    ///     let mut ctx = use_scroll_context();
+    ///     use_hook(move || {
+    ///         // the rightmost 320px are covered by the inspector panel
+    ///         ctx.set_boundary_adjustment(|rect: PixelsRect| {
+    ///             PixelsRect::new(rect.origin, PixelsSize::new((rect.width() - 320.0).max(0.0), rect.height()))
+    ///         });
+    ///     });
+    ///     rsx! {}
+    /// }
+    /// ```
+    pub fn set_boundary_adjustment(&mut self, adjust: impl Fn(PixelsRect) -> PixelsRect + 'static) {
+        self.boundary_adjustment
+            .set(Some(BoundaryAdjustment::new(adjust)));
+    }
+
+    /// Removes the boundary adjustment. Open placements are recomputed.
+    pub fn clear_boundary_adjustment(&mut self) {
+        self.boundary_adjustment.set(None);
+    }
+
+    /// Returns the boundary for a measured container rect, with the adjustment applied.
+    pub fn adjust_boundary(&self, rect: PixelsRect) -> PixelsRect {
+        match self.boundary_adjustment.peek().as_ref() {
+            Some(adjustment) => adjustment.apply(rect),
+            None => rect,
+        }
+    }
+
+    /// Programmatically scrolls the container to the given offset.
+    ///
+    /// The offset is clamped per axis to `[0, max_scroll]` of the current [ScrollState]
+    /// (when available). Non-finite coordinates are rejected with a warning.
+    /// Returns the actually-applied offset, or `None` if nothing was scrolled.
+    ///
+    /// Inside a [ScrollStateProvider], the offset is passed to its `on_scroll_request`
+    /// handler instead (ignored with a warning without one).
+    ///
+    /// A [ScrollBehavior::Smooth] scroll is instant when the user prefers reduced motion,
+    /// unless called through [ScrollableContext::ignoring_reduced_motion]. The same holds
+    /// for the other scroll methods.
     ///
     /// # Example
     /// ```rust
@@ -162,23 +1403,292 @@ impl ScrollableContext {
     ///     rsx! {}
     /// }
     /// ```
-    pub async fn scroll(&self, coordinates: PixelsVector2D, behavior: ScrollBehavior) {
-        if let Some(data) = self.scrollable_ref.peek().as_ref() {
-            let _ = data.scroll(coordinates, behavior).await;
+    pub async fn scroll(
+        &self,
+        coordinates: PixelsVector2D,
+        behavior: ScrollBehavior,
+    ) -> Option<PixelsVector2D> {
+        let behavior = self.motion_behavior(behavior);
+        if !coordinates.x.is_finite() || !coordinates.y.is_finite() {
+            tracing::warn!(
+                "ScrollableContext::scroll called with non-finite coordinates: {coordinates:?}"
+            );
+            return None;
+        }
+
+        let offset = self.clamp_offset(coordinates);
+        let Some(data) = self.scrollable() else {
+            return self.request_scroll(offset, behavior);
+        };
+        let target = match self.scroll_state.try_peek().ok().and_then(|state| *state) {
+            Some(state) if *self.reverse.peek() => state.to_reversed_offset(offset),
+            _ => offset,
+        };
+        self.mark_programmatic_scroll();
+        self.track_scroll(offset, behavior);
+        let result = data.scroll(target, behavior).await;
+        self.mark_programmatic_scroll();
+        if let Err(err) = result {
+            // expected if the view is unmounted while scrolling
+            tracing::debug!("ScrollableContext::scroll failed: {err}");
+            self.end_smooth_scroll();
+            return None;
+        }
+
+        Some(offset)
+    }
+
+    /// Internal: Tracks a scroll to `offset` (normalized): an instant scroll ends a smooth
+    /// one, a smooth one that moves nothing never starts.
+    fn track_scroll(&self, offset: PixelsVector2D, behavior: ScrollBehavior) {
+        let current = self
+            .scroll_state
+            .try_peek()
+            .ok()
+            .and_then(|state| *state)
+            .map(|state| state.state);
+        if behavior == ScrollBehavior::Smooth
+            && current.is_none_or(|current| !is_near(current, offset))
+        {
+            self.begin_smooth_scroll(Some(offset));
+        } else {
+            self.end_smooth_scroll();
+        }
+    }
+
+    /// Internal: Passes a scroll to the `on_scroll_request` handler of a
+    /// [ScrollStateProvider]. Returns `None` without one, e.g., in a [ScrollableView] that
+    /// is not mounted yet.
+    fn request_scroll(
+        &self,
+        offset: PixelsVector2D,
+        behavior: ScrollBehavior,
+    ) -> Option<PixelsVector2D> {
+        let Some(handler) = self.provided?.on_scroll_request else {
+            tracing::warn!(
+                "ScrollableContext::scroll inside a ScrollStateProvider without an \
+                on_scroll_request handler, the scroll to {offset:?} is ignored"
+            );
+            return None;
+        };
+        self.mark_programmatic_scroll();
+        self.track_scroll(offset, behavior);
+        handler.call((offset, behavior));
+        self.mark_programmatic_scroll();
+
+        Some(offset)
+    }
+
+    /// Programmatically scrolls the container by a delta relative to the current offset.
+    ///
+    /// Has the same clamping and validation guarantees as [ScrollableContext::scroll].
+    pub async fn scroll_by(
+        &self,
+        delta: PixelsVector2D,
+        behavior: ScrollBehavior,
+    ) -> Option<PixelsVector2D> {
+        if !delta.x.is_finite() || !delta.y.is_finite() {
+            tracing::warn!("ScrollableContext::scroll_by called with non-finite delta: {delta:?}");
+            return None;
+        }
+
+        let current = match *self.scroll_state.try_peek().ok()? {
+            Some(state) => state.state,
+            None => {
+                let data = self.scrollable()?;
+                let state = crate::Floating::default()
+                    .try_generate_scroll_state_from_mounted(data)
+                    .await?;
+                if !self.is_alive() {
+                    return None;
+                }
+                if *self.reverse.peek() {
+                    state.from_reversed().state
+                } else {
+                    state.state
+                }
+            }
+        };
+
+        self.scroll(current + delta, behavior).await
+    }
+
+    /// Internal: Clamps an offset into the scrollable range of the current state.
+    fn clamp_offset(&self, offset: PixelsVector2D) -> PixelsVector2D {
+        match self.scroll_state.try_peek().ok().and_then(|state| *state) {
+            Some(state) => state.clamp_offset(offset),
+            None => PixelsVector2D::new(offset.x.max(0.0), offset.y.max(0.0)),
+        }
+    }
+
+    /// Synchronously publishes a new scroll offset, e.g., from a custom scroll implementation.
+    ///
+    /// Only the offset of the current [ScrollState] changes (clamped to the max scroll);
+    /// size and bounds are untouched. Floating elements reposition exactly as they do
+    /// for native scrolling. Does nothing until the state has been measured.
+    pub fn publish_offset(&mut self, offset: PixelsVector2D) {
+        if !offset.x.is_finite() || !offset.y.is_finite() {
+            tracing::warn!(
+                "ScrollableContext::publish_offset called with non-finite offset: {offset:?}"
+            );
+            return;
+        }
+
+        self.scroll_state.with_mut(|sstate| {
+            if let Some(state) = sstate {
+                *state = state.with_offset(state.clamp_offset(offset));
+            }
+        });
+        let state = *self.scroll_state.peek();
+        if let Some(state) = state {
+            self.observe_offset(state.state);
         }
     }
 
     /// Scrolls to a specific position (e.g., top or bottom) based on the behavior.
+    ///
+    /// Not supported inside a [ScrollStateProvider] (ignored with a warning).
     pub async fn scroll_to(&self, behavior: ScrollBehavior) {
-        if let Some(data) = self.scrollable_ref.peek().as_ref() {
-            let _ = data.scroll_to(behavior).await;
+        let behavior = self.motion_behavior(behavior);
+        let Some(data) = self.scrollable() else {
+            self.warn_unsupported("scroll_to");
+            return;
+        };
+        self.mark_programmatic_scroll();
+        self.track_alignment_scroll(behavior);
+        let result = data.scroll_to(behavior).await;
+        self.mark_programmatic_scroll();
+        if let Err(err) = result {
+            tracing::debug!("ScrollableContext::scroll_to failed: {err}");
+            self.end_smooth_scroll();
         }
     }
 
     /// Scrolls the container using advanced options (like specific element alignment).
-    pub async fn scroll_to_with_options(&self, options: ScrollToOptions) {
-        if let Some(data) = self.scrollable_ref.peek().as_ref() {
-            let _ = data.scroll_to_with_options(options).await;
+    ///
+    /// Not supported inside a [ScrollStateProvider] (ignored with a warning).
+    pub async fn scroll_to_with_options(&self, mut options: ScrollToOptions) {
+        options.behavior = self.motion_behavior(options.behavior);
+        let Some(data) = self.scrollable() else {
+            self.warn_unsupported("scroll_to_with_options");
+            return;
+        };
+        self.mark_programmatic_scroll();
+        self.track_alignment_scroll(options.behavior);
+        let result = data.scroll_to_with_options(options).await;
+        self.mark_programmatic_scroll();
+        if let Err(err) = result {
+            tracing::debug!("ScrollableContext::scroll_to_with_options failed: {err}");
+            self.end_smooth_scroll();
         }
     }
+
+    /// Returns the `id` of the container element, e.g., for an `aria-controls` or
+    /// `aria-owns` relationship with it: the `id` prop of the [ScrollableView], or one
+    /// generated when it has none, unique per view and stable across renders. `None`
+    /// in a [ScrollStateProvider], whose container is not rendered by the context.
+    ///
+    /// It is part of the context, so floating elements rendered in a portal can still
+    /// reference the view they belong to. See [crate::WidgetConfig::controls_container].
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus::prelude::*;
+    /// use dioxus_floating::use_scroll_context;
+    ///
+    /// #[component]
+    /// fn ListOption(label: String) -> Element {
+    ///     let listbox = use_scroll_context().element_id();
+    ///     rsx! { div { role: "option", "data-listbox": listbox(), "{label}" } }
+    /// }
+    /// ```
+    pub fn element_id(&self) -> ReadSignal<Option<String>> {
+        self.id.into()
+    }
+
+    /// Returns a copy of the context whose scroll methods keep [ScrollBehavior::Smooth]
+    /// when the user prefers reduced motion, e.g., for a scroll that explains where
+    /// content went. By default, they scroll instantly then (see
+    /// [crate::prefers_reduced_motion]).
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus::prelude::*;
+    /// use dioxus::html::geometry::PixelsVector2D;
+    /// use dioxus_floating::use_scroll_context;
+    ///
+    /// #[component]
+    /// fn Tour() -> Element {
+    ///     let ctx = use_scroll_context();
+    ///     rsx! {
+    ///         button {
+    ///             onclick: move |_| async move {
+    ///                 let target = PixelsVector2D::new(0.0, 800.0);
+    ///                 ctx.ignoring_reduced_motion().scroll(target, ScrollBehavior::Smooth).await;
+    ///             },
+    ///             "Next step"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn ignoring_reduced_motion(self) -> Self {
+        Self {
+            ignore_reduced_motion: true,
+            ..self
+        }
+    }
+
+    /// Internal: Returns the behavior of a programmatic scroll: instant instead of smooth
+    /// if the user prefers reduced motion.
+    fn motion_behavior(&self, behavior: ScrollBehavior) -> ScrollBehavior {
+        if behavior == ScrollBehavior::Smooth
+            && !self.ignore_reduced_motion
+            && motion::prefers_reduced_motion()
+        {
+            ScrollBehavior::Instant
+        } else {
+            behavior
+        }
+    }
+
+    /// Internal: Warns that a scroll method that needs the element of a [ScrollableView]
+    /// was called inside a [ScrollStateProvider].
+    fn warn_unsupported(&self, method: &str) {
+        if self.provided.is_some() {
+            tracing::warn!(
+                "ScrollableContext::{method} is not supported inside a ScrollStateProvider, \
+                use ScrollableContext::scroll instead"
+            );
+        }
+    }
+
+    /// Internal: Tracks a scroll that aligns the view with no known target offset: the
+    /// smooth ones end after a quiet period.
+    fn track_alignment_scroll(&self, behavior: ScrollBehavior) {
+        if behavior == ScrollBehavior::Smooth {
+            self.begin_smooth_scroll(None);
+        } else {
+            self.end_smooth_scroll();
+        }
+    }
+}
+
+/// Internal: Returns `true` if the offsets are within [SMOOTH_SCROLL_ARRIVAL] on both axes.
+fn is_near(a: PixelsVector2D, b: PixelsVector2D) -> bool {
+    (a.x - b.x).abs() <= SMOOTH_SCROLL_ARRIVAL && (a.y - b.y).abs() <= SMOOTH_SCROLL_ARRIVAL
+}
+
+/// Internal: Current time in milliseconds since the epoch.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn now_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+/// Internal: Current time in milliseconds since the epoch.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now_ms() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs_f64() * 1000_f64)
+        .unwrap_or_default()
 }
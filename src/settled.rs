@@ -0,0 +1,143 @@
+use dioxus::core::Task;
+use dioxus::prelude::*;
+
+use crate::{FloatingResult, WaitStrategy};
+
+/// The default quiet period of [use_settled_placement], in milliseconds.
+const SETTLE_QUIET_MS: u32 = 150;
+
+/// Moves up to this many pixels do not restart the quiet period.
+const SETTLE_EPSILON: f64 = 0.5;
+
+/// Internal: Returns `true` if `next` is a change worth waiting for.
+fn is_moved(settled: &FloatingResult, next: &FloatingResult) -> bool {
+    settled.is_ready != next.is_ready
+        || settled.placement != next.placement
+        || settled.is_hidden != next.is_hidden
+        || (settled.x - next.x).abs() > SETTLE_EPSILON
+        || (settled.y - next.y).abs() > SETTLE_EPSILON
+}
+
+/// Returns a low-frequency copy of a live placement (e.g., of [crate::use_placement]),
+/// for consumers that only care where the element ends up, like analytics or a
+/// mini-map marker.
+///
+/// The copy follows the live result once it has not moved by more than half a pixel
+/// (nor changed its placement, readiness or visibility) for `quiet_ms` milliseconds, by
+/// default 150. During a fling scroll, it updates once after the scroll comes to rest
+/// instead of every frame. Readiness changes are copied immediately: consumers get the
+/// first ready result without waiting for a scroll that never comes, and the reset when
+/// the element closes.
+///
+/// The quiet period uses the same timers as [WaitStrategy::Timeout].
+///
+/// # Example
+///
+/// With the [crate::testing] mocks (the timers resolve immediately):
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+/// use dioxus_floating::testing::{MockDocument, MockElement, MockScrollableConfig, MockWait, provide_mock_document, provide_mock_scrollable, settle};
+/// use dioxus_floating::{FloatingOptions, FloatingResult, Offset, ScrollableContext, use_placement, use_settled_placement};
+///
+/// thread_local! {
+///     static CONTEXT: Cell<Option<ScrollableContext>> = Cell::new(None);
+///     static DOCUMENT: Cell<Option<MockDocument>> = Cell::new(None);
+///     static TRIGGER: Cell<Option<MockElement>> = Cell::new(None);
+///     static SETTLED: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+/// }
+///
+/// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+/// }
+///
+/// fn app() -> Element {
+///     use_hook(|| {
+///         DOCUMENT.with(|cell| cell.set(Some(provide_mock_document())));
+///         let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0));
+///         config.scroll_state.size = PixelsSize::new(500.0, 2000.0);
+///         CONTEXT.with(|cell| cell.set(Some(provide_mock_scrollable(config))));
+///     });
+///     let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 100.0, 50.0)).mounted()));
+///     let trigger = use_hook(|| MockElement::new(rect(40.0, 40.0, 60.0, 20.0)));
+///     TRIGGER.with(|cell| cell.set(Some(trigger.clone())));
+///     let trigger_ref = use_signal(|| Some(trigger.mounted()));
+///     let options = FloatingOptions { placement_offset: Some(Offset::zero()), ..Default::default() };
+///     let live = use_placement(element_ref, trigger_ref, options);
+///     let settled = use_settled_placement(live, None);
+///     SETTLED.with(|cell| cell.set(settled()));
+///     rsx! {}
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// settle(&mut dom);
+///
+/// // the first ready result is copied without waiting
+/// let document = DOCUMENT.with(Cell::take).unwrap();
+/// let settled = SETTLED.with(Cell::get);
+/// assert_eq!((settled.is_ready, settled.y), (true, 60.0));
+/// assert!(!document.waits().contains(&MockWait::Timeout(150)));
+///
+/// // later moves wait for the quiet period
+/// TRIGGER.with(Cell::take).unwrap().set_rect(rect(40.0, 140.0, 60.0, 20.0));
+/// let mut context = CONTEXT.with(Cell::get).unwrap();
+/// dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, 100.0)));
+/// settle(&mut dom);
+/// assert!(document.waits().contains(&MockWait::Timeout(150)));
+/// assert_eq!(SETTLED.with(Cell::get).y, 160.0);
+/// ```
+pub fn use_settled_placement(
+    result: impl Into<ReadSignal<FloatingResult>>,
+    quiet_ms: Option<u32>,
+) -> ReadSignal<FloatingResult> {
+    let result = result.into();
+    let quiet_ms = quiet_ms.unwrap_or(SETTLE_QUIET_MS);
+    let mut settled = use_signal(|| {
+        let result = *result.peek();
+        if result.is_ready {
+            result
+        } else {
+            FloatingResult::default()
+        }
+    });
+    // the live result the running quiet period started at
+    let mut pending = use_hook(|| CopyValue::new(Option::<(FloatingResult, Task)>::None));
+
+    use_effect(move || {
+        let next = result();
+        let current = *settled.peek();
+
+        // readiness changes are copied right away, e.g., the first ready result
+        if current.is_ready != next.is_ready {
+            if let Some((_, task)) = pending.write().take() {
+                task.cancel();
+            }
+            settled.set(next);
+            return;
+        }
+
+        let reference = pending.peek().as_ref().map_or(current, |(start, _)| *start);
+        if !is_moved(&reference, &next) {
+            return;
+        }
+        // a move restarts the quiet period
+        if let Some((_, task)) = pending.write().take() {
+            task.cancel();
+        }
+        let task = spawn(async move {
+            WaitStrategy::Timeout(quiet_ms).wait().await;
+            pending.set(None);
+            let latest = *result.peek();
+            if is_moved(&settled.peek(), &latest) {
+                settled.set(latest);
+            }
+        });
+        pending.set(Some((next, task)));
+    });
+
+    settled.into()
+}
@@ -0,0 +1,131 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+
+use crate::FloatingArrowContext;
+
+static NEXT_SPOTLIGHT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A dimmed backdrop over the viewport with a rounded cutout around a trigger, e.g., for
+/// the steps of a product tour.
+///
+/// Pass the [crate::FloatingResult::trigger_rect] of the step's popover: the cutout and
+/// the popover are derived from the same measurement and move together on every
+/// recompute, without measuring the trigger again.
+///
+/// - `trigger_rect`: the rect to cut out, otherwise the `trigger_rect` of the ready
+///   placement in a [FloatingArrowContext]; renders nothing without either.
+/// - `padding`: the space between the rect and the cutout (4 by default).
+/// - `radius`: the corner radius of the cutout (4 by default).
+/// - `fill`: the color of the backdrop (`rgba(0, 0, 0, 0.5)` by default).
+/// - `ring`: the color of a 2px highlight ring around the cutout, none by default.
+///
+/// The backdrop is `position: fixed` over the viewport and lets pointer events through;
+/// render it before the popover so it stays below it.
+///
+/// # Example
+/// ```rust
+/// use std::rc::Rc;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{FloatingOptions, SpotlightOverlay, Strategy, use_placement};
+///
+/// #[component]
+/// fn TourStep() -> Element {
+///     let mut element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let mut trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+///     let placement = use_placement(element_ref, trigger_ref, FloatingOptions::default());
+///
+///     rsx! {
+///         button { onmounted: move |evt| trigger_ref.set(Some(evt.data())), "Export" }
+///         if placement().is_ready {
+///             SpotlightOverlay { trigger_rect: placement().trigger_rect, padding: 6.0, ring: "#4f8cff" }
+///         }
+///         div {
+///             onmounted: move |evt| element_ref.set(Some(evt.data())),
+///             style: placement().style(Strategy::Fixed),
+///             "Export your report as a PDF"
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn SpotlightOverlay(
+    #[props(default)] trigger_rect: Option<PixelsRect>,
+    #[props(default = 4.0)] padding: f64,
+    #[props(default = 4.0)] radius: f64,
+    #[props(default = "rgba(0, 0, 0, 0.5)".to_string(), into)] fill: String,
+    #[props(default, into)] ring: Option<String>,
+) -> Element {
+    let id = use_hook(|| NEXT_SPOTLIGHT_ID.fetch_add(1, Ordering::Relaxed));
+    let context = try_use_context::<FloatingArrowContext>();
+    let trigger_rect = trigger_rect.or_else(|| {
+        context
+            .map(|context| (context.0)())
+            .filter(|result| result.is_ready)
+            .map(|result| result.trigger_rect)
+    });
+    let Some(trigger_rect) = trigger_rect else {
+        return rsx! {};
+    };
+
+    let cutout = cutout_rect(trigger_rect, padding);
+    let (x, y) = (cutout.min_x(), cutout.min_y());
+    let (width, height) = (cutout.width(), cutout.height());
+    let radius = radius.clamp(0_f64, width.min(height) / 2_f64);
+    let mask_id = format!("dioxus-floating-spotlight-{id}");
+
+    rsx! {
+        svg {
+            "aria-hidden": "true",
+            style: "position: fixed; inset: 0; width: 100%; height: 100%; pointer-events: none;",
+            defs {
+                mask { id: "{mask_id}",
+                    rect { width: "100%", height: "100%", fill: "white" }
+                    rect {
+                        x: "{x}",
+                        y: "{y}",
+                        width: "{width}",
+                        height: "{height}",
+                        rx: "{radius}",
+                        fill: "black",
+                    }
+                }
+            }
+            rect {
+                width: "100%",
+                height: "100%",
+                fill: fill,
+                mask: "url(#{mask_id})",
+            }
+            if let Some(ring) = ring {
+                rect {
+                    x: "{x}",
+                    y: "{y}",
+                    width: "{width}",
+                    height: "{height}",
+                    rx: "{radius}",
+                    fill: "none",
+                    stroke: ring,
+                    stroke_width: "2",
+                }
+            }
+        }
+    }
+}
+
+/// Internal: Returns `rect` grown by `padding` on every side, never smaller than empty.
+fn cutout_rect(rect: PixelsRect, padding: f64) -> PixelsRect {
+    let width = (rect.width() + 2_f64 * padding).max(0_f64);
+    let height = (rect.height() + 2_f64 * padding).max(0_f64);
+
+    PixelsRect::new(
+        PixelsVector2D::new(
+            rect.center().x - width / 2_f64,
+            rect.center().y - height / 2_f64,
+        )
+        .to_point(),
+        PixelsSize::new(width, height),
+    )
+}
@@ -0,0 +1,313 @@
+use dioxus::core::{Runtime, Task, current_scope_id};
+use dioxus::prelude::*;
+
+use crate::{OpenReason, WaitStrategy, motion};
+
+/// The phases of a [FloatingState].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Phase {
+    /// Closed and not rendered (default).
+    #[default]
+    Closed,
+    /// Asked to open, waiting for [FloatingDelays::open_ms] (e.g., a hover delay).
+    OpenPending,
+    /// Open.
+    Open,
+    /// Asked to close, still open for [FloatingDelays::close_ms].
+    ClosePending,
+    /// Closed but still rendered for [FloatingDelays::closing_ms], e.g., for an exit
+    /// transition.
+    Closing,
+}
+
+impl Phase {
+    /// Returns `true` if the floating element should be rendered in this phase:
+    /// [Phase::Open], [Phase::ClosePending] and [Phase::Closing].
+    pub fn is_rendered(self) -> bool {
+        matches!(self, Phase::Open | Phase::ClosePending | Phase::Closing)
+    }
+
+    /// Returns `true` if the element is open or about to open, i.e., a toggle closes it:
+    /// [Phase::OpenPending] and [Phase::Open].
+    pub fn is_opening(self) -> bool {
+        matches!(self, Phase::OpenPending | Phase::Open)
+    }
+
+    /// Returns the phase after `event`, the transition table of [FloatingState].
+    ///
+    /// | Phase          | RequestOpen      | RequestClose      | TimerElapsed     |
+    /// |----------------|------------------|-------------------|------------------|
+    /// | `Closed`       | opening phase    | `Closed`          | `Closed`         |
+    /// | `OpenPending`  | `OpenPending`    | `Closed`          | `Open`           |
+    /// | `Open`         | `Open`           | closing phase     | `Open`           |
+    /// | `ClosePending` | `Open`           | `ClosePending`    | `Closing` phase  |
+    /// | `Closing`      | `Open`           | `Closing`         | `Closed`         |
+    ///
+    /// The opening phase is [Phase::OpenPending], or [Phase::Open] without an open delay.
+    /// The closing phase is [Phase::ClosePending], or the `Closing` phase without a close
+    /// delay, which is [Phase::Closing], or [Phase::Closed] without a closing duration.
+    /// Repeated requests keep the phase (and its running timer), a timer that elapses in a
+    /// phase without one changes nothing, and [PhaseEvent::Force] jumps to [Phase::Open]
+    /// or [Phase::Closed] from any phase.
+    pub fn next(self, event: PhaseEvent, delays: FloatingDelays) -> Phase {
+        let opening = if delays.open_ms > 0 {
+            Phase::OpenPending
+        } else {
+            Phase::Open
+        };
+        let closing = if delays.closing_ms > 0 {
+            Phase::Closing
+        } else {
+            Phase::Closed
+        };
+        let close_pending = if delays.close_ms > 0 {
+            Phase::ClosePending
+        } else {
+            closing
+        };
+
+        match (self, event) {
+            (_, PhaseEvent::Force(true)) => Phase::Open,
+            (_, PhaseEvent::Force(false)) => Phase::Closed,
+            (Phase::Closed, PhaseEvent::RequestOpen) => opening,
+            (Phase::OpenPending, PhaseEvent::RequestClose) => Phase::Closed,
+            (Phase::OpenPending, PhaseEvent::TimerElapsed) => Phase::Open,
+            (Phase::Open, PhaseEvent::RequestClose) => close_pending,
+            (Phase::ClosePending | Phase::Closing, PhaseEvent::RequestOpen) => Phase::Open,
+            (Phase::ClosePending, PhaseEvent::TimerElapsed) => closing,
+            (Phase::Closing, PhaseEvent::TimerElapsed) => Phase::Closed,
+            (phase, _) => phase,
+        }
+    }
+
+    /// Internal: Returns the timer the phase runs, in milliseconds.
+    fn timer_ms(self, delays: FloatingDelays) -> Option<u32> {
+        match self {
+            Phase::OpenPending => Some(delays.open_ms),
+            Phase::ClosePending => Some(delays.close_ms),
+            Phase::Closing => Some(delays.closing_ms),
+            Phase::Closed | Phase::Open => None,
+        }
+    }
+}
+
+/// The events of the transition table, see [Phase::next].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseEvent {
+    /// [FloatingState::request_open].
+    RequestOpen,
+    /// [FloatingState::request_close].
+    RequestClose,
+    /// [FloatingState::force], `true` to open.
+    Force(bool),
+    /// The timer of the current phase elapsed.
+    TimerElapsed,
+}
+
+/// The delays of a [FloatingState], in milliseconds. A zero delay skips its phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FloatingDelays {
+    /// How long [Phase::OpenPending] lasts, e.g., the hover delay of a tooltip.
+    pub open_ms: u32,
+    /// How long [Phase::ClosePending] lasts, e.g., the grace period that lets the pointer
+    /// travel from a trigger to its menu.
+    pub close_ms: u32,
+    /// How long [Phase::Closing] lasts, e.g., the duration of an exit transition. Skipped
+    /// when the user prefers reduced motion, see [FloatingState::set_ignore_reduced_motion].
+    pub closing_ms: u32,
+}
+
+/// Why a floating element was asked to close, see [FloatingState::request_close].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The pointer toggled the trigger or left it.
+    Pointer,
+    /// The focus left the trigger.
+    Blur,
+    /// `Escape` was pressed.
+    Escape,
+    /// A tap outside of the trigger and the floating element.
+    OutsidePress,
+}
+
+/// The open state of a floating element as a cancellation-safe state machine: open and
+/// close requests, delays and exit transitions (see [Phase::next] for the transition
+/// table).
+///
+/// The timers of the delays are owned by the state: a request that changes the phase
+/// cancels the running timer, so a close during the open delay never opens the element,
+/// and a request to open during the closing transition brings it back without waiting.
+/// Requests that keep the phase keep the running timer, e.g., repeated `pointerenter`
+/// events do not extend a hover delay.
+///
+/// [crate::use_floating_widget] is built on it ([crate::FloatingWidget::state]); custom
+/// widgets create one with [use_floating_state]. It needs no element: render the floating
+/// element while [FloatingState::is_rendered].
+///
+/// # Example
+///
+/// A tooltip with a hover delay that the pointer leaves before it opens:
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{CloseReason, FloatingDelays, FloatingState, OpenReason, Phase, use_floating_state};
+///
+/// thread_local! {
+///     static STATE: Cell<Option<FloatingState>> = Cell::new(None);
+/// }
+///
+/// fn app() -> Element {
+///     let state = use_floating_state(FloatingDelays { open_ms: 300, ..Default::default() });
+///     STATE.with(|cell| cell.set(Some(state)));
+///     rsx! {}
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// let mut state = STATE.with(Cell::get).unwrap();
+///
+/// dom.in_runtime(|| state.request_open(Some(OpenReason::Pointer)));
+/// assert_eq!(dom.in_runtime(|| (state.phase)()), Phase::OpenPending);
+///
+/// // the pointer leaves during the delay: the delay is cancelled
+/// dom.in_runtime(|| state.request_close(Some(CloseReason::Pointer)));
+/// assert_eq!(dom.in_runtime(|| (state.phase)()), Phase::Closed);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatingState {
+    /// The current phase.
+    pub phase: ReadSignal<Phase>,
+    // the writable side of `phase`
+    current: Signal<Phase>,
+    open_reason: Signal<Option<OpenReason>>,
+    close_reason: Signal<Option<CloseReason>>,
+    delays: CopyValue<FloatingDelays>,
+    // keep the closing phase with reduced motion
+    ignore_reduced_motion: CopyValue<bool>,
+    // the timer of the current phase, if it has one
+    timer: CopyValue<Option<Task>>,
+    // the scope the timers run in
+    scope: ScopeId,
+}
+
+impl FloatingState {
+    /// Creates a closed state owned by the current scope; must be called inside the Dioxus
+    /// runtime. Components use [use_floating_state].
+    pub fn new(delays: FloatingDelays) -> Self {
+        let current = Signal::new(Phase::Closed);
+        Self {
+            phase: current.into(),
+            current,
+            open_reason: Signal::new(None),
+            close_reason: Signal::new(None),
+            delays: CopyValue::new(delays),
+            ignore_reduced_motion: CopyValue::new(false),
+            timer: CopyValue::new(None),
+            scope: current_scope_id(),
+        }
+    }
+
+    /// Returns `true` if the floating element should be rendered (reactive), see
+    /// [Phase::is_rendered].
+    pub fn is_rendered(&self) -> bool {
+        (self.current)().is_rendered()
+    }
+
+    /// Returns the reason of the request that last opened the element (reactive).
+    pub fn open_reason(&self) -> Option<OpenReason> {
+        (self.open_reason)()
+    }
+
+    /// Returns the reason of the request that last closed the element (reactive).
+    pub fn close_reason(&self) -> Option<CloseReason> {
+        (self.close_reason)()
+    }
+
+    /// Returns the delays.
+    pub fn delays(&self) -> FloatingDelays {
+        *self.delays.peek()
+    }
+
+    /// Replaces the delays; a running timer keeps its duration.
+    pub fn set_delays(&mut self, delays: FloatingDelays) {
+        self.delays.set(delays);
+    }
+
+    /// Keeps [Phase::Closing] when the user prefers reduced motion (`false` by default),
+    /// e.g., for an exit transition that only fades. Otherwise the element closes without
+    /// it then, as if [FloatingDelays::closing_ms] was zero (see
+    /// [crate::prefers_reduced_motion]); the hover delays are kept either way.
+    pub fn set_ignore_reduced_motion(&mut self, ignore: bool) {
+        self.ignore_reduced_motion.set(ignore);
+    }
+
+    /// Asks the element to open, after [FloatingDelays::open_ms]. `reason` is `None` for
+    /// programmatic requests.
+    pub fn request_open(&mut self, reason: Option<OpenReason>) {
+        if self.apply(PhaseEvent::RequestOpen) {
+            self.open_reason.set(reason);
+        }
+    }
+
+    /// Asks the element to close, after [FloatingDelays::close_ms] and a
+    /// [FloatingDelays::closing_ms] long [Phase::Closing]. `reason` is `None` for
+    /// programmatic requests.
+    pub fn request_close(&mut self, reason: Option<CloseReason>) {
+        if self.apply(PhaseEvent::RequestClose) {
+            self.close_reason.set(reason);
+        }
+    }
+
+    /// Opens (`true`) or closes the element immediately, skipping every delay and
+    /// cancelling a running one.
+    pub fn force(&mut self, is_open: bool) {
+        self.apply(PhaseEvent::Force(is_open));
+    }
+
+    /// Internal: Moves to the phase after `event` and starts its timer. Returns `true`
+    /// if the phase changed.
+    fn apply(&mut self, event: PhaseEvent) -> bool {
+        let current = *self.current.peek();
+        let mut delays = *self.delays.peek();
+        if !*self.ignore_reduced_motion.peek() && motion::prefers_reduced_motion() {
+            delays.closing_ms = 0;
+        }
+        let next = current.next(event, delays);
+        // a repeated request keeps the running timer
+        if next == current {
+            return false;
+        }
+
+        if let Some(task) = self.timer.write().take() {
+            task.cancel();
+        }
+        self.current.set(next);
+        if let Some(ms) = next.timer_ms(delays) {
+            let mut state = *self;
+            let task = Runtime::current().spawn(self.scope, async move {
+                WaitStrategy::Timeout(ms).wait().await;
+                state.timer.set(None);
+                state.apply(PhaseEvent::TimerElapsed);
+            });
+            self.timer.set(Some(task));
+        }
+
+        true
+    }
+}
+
+/// Creates a [FloatingState] for the lifetime of the component; its timers stop when the
+/// component unmounts.
+pub fn use_floating_state(delays: FloatingDelays) -> FloatingState {
+    let state = use_hook(|| FloatingState::new(delays));
+    motion::use_motion_listener(true);
+    use_drop(move || {
+        if let Ok(Some(task)) = state.timer.try_peek().map(|timer| *timer) {
+            task.cancel();
+        }
+    });
+
+    state
+}
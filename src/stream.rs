@@ -0,0 +1,244 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use dioxus::core::ReactiveContext;
+use dioxus::prelude::*;
+use futures_channel::mpsc::UnboundedReceiver;
+use futures_core::Stream;
+
+use crate::{Floating, FloatingOptions, PlacementOutput, ScrollableContext, page};
+
+impl Floating {
+    /// Returns the placements of an element next to `anchor` in the view of `context`, as
+    /// a [Stream], for code outside of components, e.g., a controller task that owns
+    /// business logic.
+    ///
+    /// The stream subscribes to the same sources as the placement hooks: the refs, the
+    /// [crate::ScrollState] and [ScrollableContext::layout_generation] of the view and
+    /// its ancestors, the boundary adjustment and the region defaults. It yields one
+    /// [PlacementOutput] per recompute, measured when it is polled: changes that arrive
+    /// while the consumer is busy are coalesced, so a slow consumer skips to the latest
+    /// placement instead of working through a backlog. Unmounted refs (`None`) wait.
+    /// The stream ends once either ref or the view is dropped, noticed at the next
+    /// change of the other inputs (a dropped signal notifies nobody), e.g., a scroll.
+    /// Dropping the stream removes its subscriptions. While the page is hidden (see
+    /// [Floating::is_suspended]), nothing is measured; the changes collapse into one
+    /// placement once it is shown again.
+    ///
+    /// The boundary is the container of the view with its adjustment; the viewport
+    /// boundaries of the hooks ([crate::Boundary::Viewport],
+    /// [FloatingOptions::clip_to_visual_viewport]) are not applied.
+    ///
+    /// # Panics
+    /// Outside of the Dioxus runtime.
+    ///
+    /// # Example
+    /// A task spawned by a controller, with the [crate::testing] mocks:
+    /// ```rust
+    /// use std::cell::RefCell;
+    ///
+    /// use dioxus::prelude::*;
+    /// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+    /// use dioxus_floating::{FloatingOptions, Offset, PlacementOutput, use_floating};
+    /// use futures_util::StreamExt;
+    ///
+    /// thread_local! {
+    ///     static OUTPUTS: RefCell<Vec<PlacementOutput>> = RefCell::new(Vec::new());
+    /// }
+    ///
+    /// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// }
+    ///
+    /// fn app() -> Element {
+    ///     let context = use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+    ///     let floating = use_floating();
+    ///     let element = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    ///     let trigger = use_signal(|| Some(MockElement::new(rect(100.0, 100.0, 80.0, 20.0)).mounted()));
+    ///     use_hook(|| {
+    ///         let options = FloatingOptions { placement_offset: Some(Offset::zero()), ..Default::default() };
+    ///         let mut placements = floating.placement_stream(context, element, trigger, options);
+    ///         spawn(async move {
+    ///             while let Some(output) = placements.next().await {
+    ///                 OUTPUTS.with(|outputs| outputs.borrow_mut().push(output));
+    ///             }
+    ///         });
+    ///     });
+    ///     rsx! {}
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild_in_place();
+    /// settle(&mut dom);
+    ///
+    /// let outputs = OUTPUTS.with(|outputs| outputs.take());
+    /// assert_eq!(outputs.len(), 1);
+    /// assert_eq!((outputs[0].x, outputs[0].y), (100.0, 120.0));
+    /// ```
+    pub fn placement_stream<E, A>(
+        &self,
+        context: ScrollableContext,
+        element_ref: E,
+        anchor: A,
+        options: FloatingOptions,
+    ) -> impl Stream<Item = PlacementOutput> + use<E, A>
+    where
+        E: Into<ReadSignal<Option<Rc<MountedData>>>>,
+        A: Into<ReadSignal<Option<Rc<MountedData>>>>,
+    {
+        let (reactive, changes) = ReactiveContext::new();
+
+        PlacementStream {
+            floating: *self,
+            context,
+            element_ref: element_ref.into(),
+            anchor: anchor.into(),
+            options,
+            reactive,
+            changes,
+            is_stale: true,
+            measurement: None,
+            is_done: false,
+        }
+    }
+}
+
+/// Internal: A measurement of [PlacementStream], `None` if a rect could not be measured.
+type Measurement = Pin<Box<dyn Future<Output = Option<PlacementOutput>>>>;
+
+/// Internal: The inputs of the next measurement of a [PlacementStream].
+enum Inputs {
+    /// Every input is available.
+    Ready(Measurement),
+    /// A ref or the view is not mounted yet.
+    Waiting,
+    /// A ref or the view was dropped.
+    Dropped,
+}
+
+/// Internal: The stream returned by [Floating::placement_stream].
+struct PlacementStream {
+    floating: Floating,
+    context: ScrollableContext,
+    element_ref: ReadSignal<Option<Rc<MountedData>>>,
+    anchor: ReadSignal<Option<Rc<MountedData>>>,
+    options: FloatingOptions,
+    // subscribed to the inputs read by the last `inputs`, notifies `changes`
+    reactive: ReactiveContext,
+    changes: UnboundedReceiver<()>,
+    // an input changed since the last measurement started
+    is_stale: bool,
+    measurement: Option<Measurement>,
+    is_done: bool,
+}
+
+impl PlacementStream {
+    /// Internal: Reads the inputs, subscribing to them, and returns the measurement.
+    fn inputs(&self) -> Inputs {
+        let context = self.context;
+        let (element_ref, anchor) = (self.element_ref, self.anchor);
+        self.reactive.reset_and_run_in(|| {
+            let (Ok(element), Ok(anchor), Ok(scroll_state)) = (
+                element_ref.try_read().map(|element| element.clone()),
+                anchor.try_read().map(|anchor| anchor.clone()),
+                context.scroll_state.try_read().map(|state| *state),
+            ) else {
+                return Inputs::Dropped;
+            };
+            let _ = (context.layout_generation)();
+            for ancestor in context.ancestors.read().iter() {
+                let _ = ancestor.scroll_state.try_read();
+                let _ = ancestor.layout_generation.try_read();
+            }
+            let _ = (context.boundary_adjustment).read();
+            // suspended with the hooks and views of the window, see Floating::is_suspended
+            let is_hidden = page::running_listener()
+                .is_some_and(|hidden| hidden.try_read().is_ok_and(|hidden| *hidden == Some(true)));
+            if is_hidden {
+                return Inputs::Waiting;
+            }
+            let options = match (context.floating_defaults)() {
+                Some(defaults) => self.options.clone().with_defaults(&defaults),
+                None => self.options.clone(),
+            };
+            let zip = element
+                .zip(anchor)
+                .zip(scroll_state)
+                .zip(context.container());
+            let Some((((element, anchor), scroll_state), container)) = zip else {
+                return Inputs::Waiting;
+            };
+
+            let floating = self.floating;
+            Inputs::Ready(Box::pin(async move {
+                floating.wait().await;
+                let (scrollable, element, trigger) = floating
+                    .measure_on_trigger(
+                        scroll_state,
+                        container,
+                        element,
+                        anchor,
+                        options.measure_untransformed,
+                    )
+                    .await;
+                let boundary = context.adjust_boundary(scrollable);
+
+                Some(floating.compute_measured(boundary, element, trigger?, options))
+            }))
+        })
+    }
+}
+
+impl Stream for PlacementStream {
+    type Item = PlacementOutput;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<PlacementOutput>> {
+        let this = self.get_mut();
+        if this.is_done {
+            return Poll::Ready(None);
+        }
+        // skip to the latest inputs: every queued change is one recompute
+        while let Poll::Ready(Some(())) = Pin::new(&mut this.changes).poll_next(cx) {
+            this.is_stale = true;
+        }
+
+        // a measurement in progress finishes first, so a steady stream of changes (e.g.,
+        // a fling scroll) never starves the consumer
+        if this.measurement.is_none() && std::mem::take(&mut this.is_stale) {
+            match this.inputs() {
+                Inputs::Ready(measurement) => this.measurement = Some(measurement),
+                Inputs::Waiting => {}
+                Inputs::Dropped => {
+                    this.is_done = true;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+        let Some(measurement) = this.measurement.as_mut() else {
+            return Poll::Pending;
+        };
+        let Poll::Ready(output) = measurement.as_mut().poll(cx) else {
+            return Poll::Pending;
+        };
+        this.measurement = None;
+        match output {
+            Some(output) => Poll::Ready(Some(output)),
+            // the anchor could not be measured, e.g., removed: the next change retries
+            None if this.is_stale => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for PlacementStream {
+    fn drop(&mut self) {
+        // the context itself lives until its scope is dropped, its subscriptions do not
+        self.reactive.reset_and_run_in(|| {});
+    }
+}
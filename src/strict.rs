@@ -0,0 +1,56 @@
+use crate::{Axes, FloatingOptions, Middleware};
+
+/// Internal: Reports a misuse of the crate. Debug builds with the `strict` feature panic
+/// with the message; otherwise it does nothing and the caller keeps its lenient behavior.
+#[track_caller]
+#[inline]
+pub(crate) fn misuse(message: impl FnOnce() -> String) {
+    if cfg!(all(feature = "strict", debug_assertions)) {
+        panic!("dioxus-floating strict mode: {}", message());
+    }
+}
+
+/// Internal: Reports non-finite offsets and padding, and Flip options without
+/// [Middleware::Flip].
+#[track_caller]
+pub(crate) fn check_options(options: &FloatingOptions) {
+    let offset = &options.offset;
+    if !offset.is_finite() {
+        misuse(|| format!("non-finite offset {offset:?} in FloatingOptions"));
+    }
+    if let Some(offset) = options.placement_offset
+        && (!offset.main_axis.is_finite() || !offset.cross_axis.is_finite())
+    {
+        misuse(|| format!("non-finite placement offset {offset:?} in FloatingOptions"));
+    }
+    if !options.padding.is_finite() {
+        misuse(|| format!("non-finite padding {} in FloatingOptions", options.padding));
+    }
+    if !options.min_visible.is_finite() || options.min_visible < 0_f64 {
+        misuse(|| {
+            format!(
+                "min_visible {} in FloatingOptions is not a finite non-negative number",
+                options.min_visible
+            )
+        });
+    }
+    if !options.boundary_padding.is_finite() {
+        misuse(|| {
+            format!(
+                "non-finite boundary padding {:?} in FloatingOptions",
+                options.boundary_padding
+            )
+        });
+    }
+
+    let has_flip_options = options.flip_axes != Axes::Main || !options.preserve_alignment;
+    if has_flip_options && !options.middleware.contains(&Middleware::Flip) {
+        misuse(|| {
+            format!(
+                "Flip options (flip_axes: {:?}, preserve_alignment: {}) have no effect \
+                without Middleware::Flip in {:?}",
+                options.flip_axes, options.preserve_alignment, options.middleware
+            )
+        });
+    }
+}
@@ -0,0 +1,655 @@
+//! Utilities for testing placement hooks without a browser.
+//!
+//! The hooks measure elements through [MountedData]. [MockElement] backs a [MountedData]
+//! with scripted rects, and [provide_mock_scrollable] installs a [ScrollableContext]
+//! backed by a scripted [ScrollState], so driving a [VirtualDom] with [settle] makes
+//! `use_placement` produce deterministic [crate::FloatingResult]s. [provide_mock_document]
+//! records the waits of the hooks (see [crate::WaitStrategy]).
+//!
+//! # Example
+//! ```rust
+//! use std::cell::Cell;
+//!
+//! use dioxus::prelude::*;
+//! use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+//! use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+//! use dioxus_floating::{FloatingOptions, FloatingResult, Offset, Placement, use_placement};
+//!
+//! thread_local! {
+//!     static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+//! }
+//!
+//! fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+//!     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+//! }
+//!
+//! fn app() -> Element {
+//!     use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+//!     rsx! { Popover {} }
+//! }
+//!
+//! #[component]
+//! fn Popover() -> Element {
+//!     let trigger = use_hook(|| MockElement::new(rect(100.0, 100.0, 80.0, 20.0)));
+//!     let element = use_hook(|| MockElement::new(rect(0.0, 0.0, 200.0, 100.0)));
+//!     let trigger_ref = use_signal(|| Some(trigger.mounted()));
+//!     let element_ref = use_signal(|| Some(element.mounted()));
+//!
+//!     let options = FloatingOptions {
+//!         placement_offset: Some(Offset::zero()),
+//!         placement: Placement::BottomStart,
+//!         ..Default::default()
+//!     };
+//!     let placement = use_placement(element_ref, trigger_ref, options);
+//!     RESULT.with(|result| result.set(placement()));
+//!
+//!     rsx! {}
+//! }
+//!
+//! let mut dom = VirtualDom::new(app);
+//! dom.rebuild_in_place();
+//! settle(&mut dom);
+//!
+//! let result = RESULT.with(Cell::get);
+//! assert!(result.is_ready);
+//! assert_eq!((result.x, result.y), (100.0, 120.0));
+//! assert_eq!(result.placement, Placement::BottomStart);
+//! ```
+
+use std::cell::{Cell, RefCell};
+#[cfg(feature = "components")]
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use dioxus::document::{Document, Eval, EvalError, Evaluator};
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::html::{MountedError, MountedResult, RenderedElementBacking};
+use dioxus::prelude::*;
+use dioxus::signals::Owner;
+
+use crate::measure::{FRAME_SCRIPT, timeout_script};
+use crate::{Floating, ScrollState, ScrollableContext, TrackedAxes, WaitStrategy};
+
+/// Maximum number of render and task rounds [settle] runs.
+const SETTLE_ROUNDS: usize = 32;
+
+/// A scripted element: every [MountedData] created by [MockElement::mounted] reports
+/// its current rect, scroll size and scroll offset.
+#[derive(Debug, Clone)]
+pub struct MockElement {
+    rect: Rc<Cell<PixelsRect>>,
+    scroll_size: Rc<Cell<PixelsSize>>,
+    scroll_offset: Rc<Cell<PixelsVector2D>>,
+    // offsets still to be scrolled through by itself, see script_scroll
+    #[cfg(feature = "components")]
+    scroll_script: Rc<RefCell<VecDeque<PixelsVector2D>>>,
+    layout_size: Rc<Cell<Option<PixelsSize>>>,
+    hold: Rc<RefCell<MockHold>>,
+    scroll_size_hold: Rc<RefCell<MockHold>>,
+    is_removed: Rc<Cell<bool>>,
+    round_trips: Rc<Cell<usize>>,
+}
+
+/// Internal: Measurements held back by [MockElement::hold] (client rects) and
+/// [MockElement::hold_next_scroll_size] (scroll sizes).
+#[derive(Debug, Default)]
+struct MockHold {
+    is_held: bool,
+    // measurements still to be held by hold_next
+    next: usize,
+    // bumped by every release, see hold_next
+    epoch: u64,
+    // requested and not resolved yet
+    in_flight: usize,
+    waiting: Vec<Waker>,
+}
+
+impl MockHold {
+    /// Internal: Starts a measurement; returns the epoch it is held in if it is held by
+    /// `hold_next`, until the next release.
+    fn request(&mut self) -> Option<u64> {
+        self.in_flight += 1;
+        (self.next > 0).then(|| {
+            self.next -= 1;
+            self.epoch
+        })
+    }
+
+    /// Internal: Returns `true` and wakes the measurement up on the next release while it
+    /// is held, or else marks it as resolved.
+    fn is_holding(&mut self, held_in: Option<u64>, waker: &Waker) -> bool {
+        if self.is_held || held_in == Some(self.epoch) {
+            self.waiting.push(waker.clone());
+            return true;
+        }
+        self.in_flight -= 1;
+        false
+    }
+
+    /// Internal: Releases every held measurement.
+    fn release(hold: &RefCell<MockHold>) {
+        let waiting = {
+            let mut hold = hold.borrow_mut();
+            hold.is_held = false;
+            hold.epoch += 1;
+            std::mem::take(&mut hold.waiting)
+        };
+        waiting.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl MockElement {
+    /// Creates a mock element with a client rect; the scroll size equals the rect size.
+    pub fn new(rect: PixelsRect) -> Self {
+        Self {
+            rect: Rc::new(Cell::new(rect)),
+            scroll_size: Rc::new(Cell::new(rect.size)),
+            scroll_offset: Rc::new(Cell::new(PixelsVector2D::zero())),
+            #[cfg(feature = "components")]
+            scroll_script: Rc::default(),
+            layout_size: Rc::default(),
+            hold: Rc::default(),
+            scroll_size_hold: Rc::default(),
+            is_removed: Rc::default(),
+            round_trips: Rc::default(),
+        }
+    }
+
+    /// Sets the client rect reported by the next measurements.
+    pub fn set_rect(&self, rect: PixelsRect) {
+        self.rect.set(rect);
+    }
+
+    /// Sets the scroll size reported by the next measurements.
+    pub fn set_scroll_size(&self, size: PixelsSize) {
+        self.scroll_size.set(size);
+    }
+
+    /// Sets the scroll offset reported by the next measurements, e.g., of a scroll by the
+    /// user. `scroll` calls on the element set it as well.
+    pub fn set_scroll_offset(&self, offset: PixelsVector2D) {
+        self.scroll_offset.set(offset);
+    }
+
+    /// Scripts a scroll the element does by itself without scroll events, e.g., the
+    /// momentum phase of a touch scroll on iOS (see [emulate_momentum_pauses]): each read
+    /// of the offset outside of an event moves it to the next of `offsets`, and it stays at
+    /// the last one.
+    #[cfg(feature = "components")]
+    pub fn script_scroll(&self, offsets: impl IntoIterator<Item = PixelsVector2D>) {
+        *self.scroll_script.borrow_mut() = offsets.into_iter().collect();
+    }
+
+    /// Sets the layout size, the size before CSS transforms (e.g., a scaled entry
+    /// animation), reported by the next measurements with
+    /// [crate::FloatingOptions::measure_untransformed]. Unset, it is the size of the rect.
+    pub fn set_layout_size(&self, size: Option<PixelsSize>) {
+        self.layout_size.set(size);
+    }
+
+    /// Internal: Returns the layout size, see [MockElement::set_layout_size].
+    pub(crate) fn layout_size(&self) -> PixelsSize {
+        self.layout_size.get().unwrap_or(self.rect.get().size)
+    }
+
+    /// Returns the current scroll offset (changed by `scroll` calls on the element).
+    pub fn scroll_offset(&self) -> PixelsVector2D {
+        self.scroll_offset.get()
+    }
+
+    /// Returns how many measurements were read from the element so far. Like the DOM
+    /// reads of the web renderer, a [ScrollState] read in one step counts once, see
+    /// [Floating::generate_scroll_state_from_mounted].
+    pub fn round_trips(&self) -> usize {
+        self.round_trips.get()
+    }
+
+    /// Internal: Reads the client size, scroll size and scroll offset in one step.
+    /// `None` once the element is removed.
+    pub(crate) fn read_scroll_state(&self) -> Option<ScrollState> {
+        self.count_round_trip();
+        (!self.is_removed.get()).then(|| ScrollState {
+            size: self.scroll_size.get(),
+            bounds: self.rect.get().size,
+            state: self.scroll_offset.get(),
+        })
+    }
+
+    /// Internal: Reads the scroll offset outside of an event and advances the script of
+    /// [MockElement::script_scroll]. `None` once the element is removed.
+    #[cfg(feature = "components")]
+    pub(crate) fn sample_scroll_offset(&self) -> Option<PixelsVector2D> {
+        self.count_round_trip();
+        if self.is_removed.get() {
+            return None;
+        }
+        if let Some(offset) = self.scroll_script.borrow_mut().pop_front() {
+            self.scroll_offset.set(offset);
+        }
+
+        Some(self.scroll_offset.get())
+    }
+
+    /// Internal: Counts a measurement, see [MockElement::round_trips].
+    fn count_round_trip(&self) {
+        self.round_trips.set(self.round_trips.get() + 1);
+    }
+
+    /// Holds back client rect measurements until [MockElement::release], e.g., to
+    /// simulate a slow layout. Held measurements report the rect current at the release.
+    ///
+    /// # Example
+    ///
+    /// A font size change moves the trigger; [crate::FloatingResult::is_pending] is set
+    /// while the new measurement is in flight.
+    ///
+    /// ```rust
+    /// use std::cell::Cell;
+    ///
+    /// use dioxus::prelude::*;
+    /// use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+    /// use dioxus_floating::testing::{MockElement, MockScrollableConfig, provide_mock_scrollable, settle};
+    /// use dioxus_floating::{FloatingOptions, FloatingResult, Offset, ScrollableContext, use_placement};
+    ///
+    /// thread_local! {
+    ///     static TRIGGER: MockElement = MockElement::new(rect(100.0, 100.0, 80.0, 20.0));
+    ///     static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+    ///     static CONTEXT: Cell<Option<ScrollableContext>> = Cell::new(None);
+    /// }
+    ///
+    /// fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    ///     PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+    /// }
+    ///
+    /// fn app() -> Element {
+    ///     let context = use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+    ///     CONTEXT.with(|cell| cell.set(Some(context)));
+    ///     rsx! { Popover {} }
+    /// }
+    ///
+    /// #[component]
+    /// fn Popover() -> Element {
+    ///     let element = use_hook(|| MockElement::new(rect(0.0, 0.0, 100.0, 50.0)));
+    ///     let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    ///     let element_ref = use_signal(|| Some(element.mounted()));
+    ///
+    ///     let options = FloatingOptions { placement_offset: Some(Offset::zero()), ..Default::default() };
+    ///     let placement = use_placement(element_ref, trigger_ref, options);
+    ///     RESULT.with(|result| result.set(placement()));
+    ///
+    ///     rsx! {}
+    /// }
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild_in_place();
+    /// settle(&mut dom);
+    /// assert!(!RESULT.with(Cell::get).is_pending);
+    ///
+    /// // the trigger grows, its layout takes a while
+    /// TRIGGER.with(|trigger| {
+    ///     trigger.hold();
+    ///     trigger.set_rect(rect(100.0, 100.0, 80.0, 40.0));
+    /// });
+    /// let mut generation = CONTEXT.with(Cell::get).unwrap().layout_generation;
+    /// dom.in_runtime(|| *generation.write() += 1);
+    /// settle(&mut dom);
+    ///
+    /// let result = RESULT.with(Cell::get);
+    /// assert!(result.is_pending);
+    /// assert_eq!(result.y, 120.0);
+    ///
+    /// TRIGGER.with(MockElement::release);
+    /// settle(&mut dom);
+    ///
+    /// let result = RESULT.with(Cell::get);
+    /// assert!(!result.is_pending);
+    /// assert_eq!(result.y, 140.0);
+    /// ```
+    pub fn hold(&self) {
+        self.hold.borrow_mut().is_held = true;
+    }
+
+    /// Holds back only the next client rect measurement until [MockElement::release];
+    /// the ones after it resolve right away, e.g., to let a newer measurement finish
+    /// before an older one.
+    pub fn hold_next(&self) {
+        self.hold.borrow_mut().next += 1;
+    }
+
+    /// Resolves the measurements held back by [MockElement::hold] and
+    /// [MockElement::hold_next].
+    pub fn release(&self) {
+        MockHold::release(&self.hold);
+    }
+
+    /// Holds back only the next scroll size measurement until
+    /// [MockElement::release_scroll_size], like a response delayed on its way back: it
+    /// reports the scroll size of when it was requested, and the ones after it resolve
+    /// right away.
+    pub fn hold_next_scroll_size(&self) {
+        self.scroll_size_hold.borrow_mut().next += 1;
+    }
+
+    /// Resolves the scroll size measurements held back by
+    /// [MockElement::hold_next_scroll_size].
+    pub fn release_scroll_size(&self) {
+        MockHold::release(&self.scroll_size_hold);
+    }
+
+    /// Returns how many scroll size measurements are requested and not resolved yet.
+    pub fn scroll_sizes_in_flight(&self) -> usize {
+        self.scroll_size_hold.borrow().in_flight
+    }
+
+    /// Removes the element from the document, as a route change would: the measurements
+    /// in flight (e.g., held by [MockElement::hold]) and every later one fail like the
+    /// ones of the renderer for an element that is gone.
+    pub fn remove(&self) {
+        self.is_removed.set(true);
+        self.release();
+        self.release_scroll_size();
+    }
+
+    /// Returns a [MountedData] backed by this mock, e.g., for an element ref signal.
+    pub fn mounted(&self) -> Rc<MountedData> {
+        Rc::new(MountedData::new(self.clone()))
+    }
+}
+
+/// Internal: The error of a measurement of a removed element, see [MockElement::remove].
+fn removed<T>() -> MountedResult<T> {
+    Err(MountedError::OperationFailed(
+        "the element was removed".into(),
+    ))
+}
+
+impl RenderedElementBacking for MockElement {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_scroll_offset(&self) -> Pin<Box<dyn Future<Output = MountedResult<PixelsVector2D>>>> {
+        self.count_round_trip();
+        let offset = if self.is_removed.get() {
+            removed()
+        } else {
+            Ok(self.scroll_offset.get())
+        };
+        Box::pin(async move { offset })
+    }
+
+    fn get_scroll_size(&self) -> Pin<Box<dyn Future<Output = MountedResult<PixelsSize>>>> {
+        self.count_round_trip();
+        // a held response reports the size of when it was requested
+        let size = self.scroll_size.get();
+        let (mock, hold) = (self.clone(), self.scroll_size_hold.clone());
+        let held_in = hold.borrow_mut().request();
+        Box::pin(std::future::poll_fn(move |cx| {
+            if hold.borrow_mut().is_holding(held_in, cx.waker()) {
+                return Poll::Pending;
+            }
+            if mock.is_removed.get() {
+                return Poll::Ready(removed());
+            }
+            Poll::Ready(Ok(size))
+        }))
+    }
+
+    fn get_client_rect(&self) -> Pin<Box<dyn Future<Output = MountedResult<PixelsRect>>>> {
+        self.count_round_trip();
+        let (mock, hold) = (self.clone(), self.hold.clone());
+        // held by hold_next until the epoch changes
+        let held_in = hold.borrow_mut().request();
+        Box::pin(std::future::poll_fn(move |cx| {
+            if hold.borrow_mut().is_holding(held_in, cx.waker()) {
+                return Poll::Pending;
+            }
+            if mock.is_removed.get() {
+                return Poll::Ready(removed());
+            }
+            Poll::Ready(Ok(mock.rect.get()))
+        }))
+    }
+
+    fn scroll(
+        &self,
+        coordinates: PixelsVector2D,
+        _behavior: ScrollBehavior,
+    ) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        if self.is_removed.get() {
+            return Box::pin(async { removed() });
+        }
+        self.scroll_offset.set(coordinates);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Configuration of the mock scrollable installed by [provide_mock_scrollable].
+#[derive(Debug, Clone, Copy)]
+pub struct MockScrollableConfig {
+    /// Client rect of the scrollable container (the boundary).
+    pub rect: PixelsRect,
+    /// Initial scroll state; script later states through [ScrollableContext::scroll_state].
+    pub scroll_state: ScrollState,
+    /// The `id` of the container, see [ScrollableContext::element_id]. None by default.
+    pub id: Option<&'static str>,
+}
+
+impl MockScrollableConfig {
+    /// Creates a non-scrolled container whose content fits its rect.
+    pub fn new(rect: PixelsRect) -> Self {
+        Self {
+            rect,
+            scroll_state: ScrollState {
+                size: rect.size,
+                bounds: rect.size,
+                state: PixelsVector2D::zero(),
+            },
+            id: None,
+        }
+    }
+}
+
+/// Installs a mock [ScrollableContext] and a [Floating] engine with [WaitStrategy::None]
+/// (browser timers are not available in tests) in the current component.
+///
+/// Call it once, e.g., in `use_hook` of the root component, and set new states through
+/// the returned context to script scrolling. Called below another mock scrollable, it
+/// is nested in it (see [ScrollableContext::ancestors]).
+pub fn provide_mock_scrollable(config: MockScrollableConfig) -> ScrollableContext {
+    provide_context(Floating::builder().wait(WaitStrategy::None).build());
+
+    let scrollable = MockElement::new(config.rect);
+    scrollable.set_scroll_size(config.scroll_state.size);
+    // nested like a ScrollableView inside another one
+    let parent = try_consume_context::<ScrollableContext>();
+    let smooth_scrolling = Signal::new(false);
+    provide_context(ScrollableContext {
+        scrollable_ref: Signal::new(Some(scrollable.mounted())),
+        scroll_state: Signal::new(Some(config.scroll_state)),
+        boundary_adjustment: Signal::new(None),
+        floating_defaults: Signal::new(None),
+        layout_generation: Signal::new(0),
+        updated_at: Signal::new(None),
+        reverse: Signal::new(false),
+        axes: Signal::new(TrackedAxes::Both),
+        scroll_generation: Signal::new(0),
+        ancestors: Signal::new(ScrollableContext::chain(parent)),
+        rtl: Signal::new(false),
+        registry: Signal::new(Vec::new()),
+        active_count: Signal::new(0),
+        is_smooth_scrolling: smooth_scrolling.into(),
+        smooth_scrolling,
+        smooth_scroll: CopyValue::new(None),
+        reloaded_generation: CopyValue::new(None),
+        provided: None,
+        ignore_reduced_motion: false,
+        id: Signal::new(config.id.map(String::from)),
+    })
+}
+
+/// Runs renders, effects and ready tasks of the [VirtualDom] for a fixed number of rounds.
+///
+/// The mock measurements resolve immediately, so one call settles every placement.
+pub fn settle(dom: &mut VirtualDom) {
+    for _ in 0..SETTLE_ROUNDS {
+        dom.process_events();
+        dom.render_immediate_to_vec();
+    }
+}
+
+/// Reports a `visibilitychange` of the page in the current window, as the browser would:
+/// the hooks suspend while `hidden` and recompute once it is shown again (see
+/// [Floating::is_suspended]).
+///
+/// Must be called inside the runtime of the window, e.g., in [VirtualDom::in_runtime].
+pub fn set_page_hidden(hidden: bool) {
+    crate::page::report(hidden);
+}
+
+/// Makes the current window behave like iOS, which may pause the scroll events of a
+/// momentum scroll: a [crate::ScrollableView] then samples its offset every frame after
+/// the touch is lifted (see [MockElement::script_scroll]). Keep `settle` bounded by
+/// scripting a scroll that comes to rest.
+///
+/// Must be called inside the runtime of the window, e.g., in [VirtualDom::in_runtime].
+#[cfg(feature = "components")]
+pub fn emulate_momentum_pauses(enabled: bool) {
+    crate::window::with_window(|emulated: &mut crate::measure::EmulatedMomentumPauses| {
+        emulated.0 = enabled;
+    });
+}
+
+/// Reports a finished pointer press in the current window, as the document listener of
+/// [crate::WidgetConfig::outside_press] would: the pointer moved up to `travel` CSS
+/// pixels from where it went down, `scrolled` tells if a container scrolled meanwhile,
+/// and `owners` are the ids of the floating elements the press started in or on the
+/// trigger of (see [crate::FloatingWidget::floating_id]).
+///
+/// Must be called inside the runtime of the window, e.g., in [VirtualDom::in_runtime].
+#[cfg(feature = "interactions")]
+pub fn report_press(travel: f64, scrolled: bool, owners: &[&str]) {
+    crate::dismiss::report(
+        travel,
+        scrolled,
+        owners.iter().map(|owner| owner.to_string()).collect(),
+    );
+}
+
+/// A wait recorded by [MockDocument].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MockWait {
+    /// An animation frame.
+    Frame,
+    /// A timeout of the given number of milliseconds.
+    Timeout(u32),
+}
+
+/// A scripted document installed by [provide_mock_document]: animation frames and
+/// timeouts resolve immediately and are recorded, other scripts (e.g., listeners) never
+/// report anything.
+///
+/// The listener scripts (the ones waiting in `await dioxus.recv()`) are counted until
+/// they are told to remove their listeners, see [MockDocument::listeners].
+///
+/// Frames resolve immediately, so continuous per-frame sources (e.g., the resize
+/// sampling of [crate::AutoUpdate]) never let [settle] return while the page is visible;
+/// keep them off in tests with a mock document.
+#[derive(Clone, Default)]
+pub struct MockDocument {
+    waits: Rc<RefCell<Vec<MockWait>>>,
+    listeners: Rc<Cell<usize>>,
+    owner: Owner,
+}
+
+impl MockDocument {
+    /// Returns the recorded waits, oldest first.
+    pub fn waits(&self) -> Vec<MockWait> {
+        self.waits.borrow().clone()
+    }
+
+    /// Forgets the recorded waits.
+    pub fn clear(&self) {
+        self.waits.borrow_mut().clear();
+    }
+
+    /// Returns the number of listener scripts that were started and not stopped yet,
+    /// i.e., the JS listeners a real document would still run. `0` once every component
+    /// that installed one is unmounted.
+    pub fn listeners(&self) -> usize {
+        self.listeners.get()
+    }
+}
+
+impl Document for MockDocument {
+    fn eval(&self, js: String) -> Eval {
+        let wait = if js == FRAME_SCRIPT {
+            Some(MockWait::Frame)
+        } else {
+            // the number is the only part that varies
+            js.split(|c: char| !c.is_ascii_digit())
+                .filter_map(|part| part.parse::<u32>().ok())
+                .find(|ms| js == timeout_script(*ms))
+                .map(MockWait::Timeout)
+        };
+        if let Some(wait) = wait {
+            self.waits.borrow_mut().push(wait);
+        }
+        // a listener runs until it receives `true`, see JsListener::stop
+        let listener = (wait.is_none() && js.contains("await dioxus.recv()")).then(|| {
+            self.listeners.set(self.listeners.get() + 1);
+            self.listeners.clone()
+        });
+
+        let evaluator: Box<dyn Evaluator> = Box::new(MockEvaluator {
+            reply: wait.is_some(),
+            listener: Cell::new(listener),
+        });
+        Eval::new(self.owner.insert(evaluator))
+    }
+}
+
+/// Internal: Answers a recorded wait once, stays silent for everything else. A listener
+/// leaves the count of its document once it is stopped.
+struct MockEvaluator {
+    reply: bool,
+    listener: Cell<Option<Rc<Cell<usize>>>>,
+}
+
+impl Evaluator for MockEvaluator {
+    fn send(&self, data: serde_json::Value) -> Result<(), EvalError> {
+        if data == serde_json::Value::Bool(true)
+            && let Some(listeners) = self.listener.take()
+        {
+            listeners.set(listeners.get() - 1);
+        }
+        Ok(())
+    }
+
+    fn poll_recv(&mut self, _cx: &mut Context<'_>) -> Poll<Result<serde_json::Value, EvalError>> {
+        if std::mem::take(&mut self.reply) {
+            Poll::Ready(Ok(serde_json::Value::Bool(true)))
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn poll_join(&mut self, _cx: &mut Context<'_>) -> Poll<Result<serde_json::Value, EvalError>> {
+        Poll::Pending
+    }
+}
+
+/// Installs a [MockDocument] in the current component, so `document::eval` of the
+/// hooks below it is answered by the mock.
+///
+/// Call it once, e.g., in `use_hook` of the root component, and keep the returned
+/// handle to inspect the recorded waits.
+pub fn provide_mock_document() -> MockDocument {
+    let document = MockDocument::default();
+    provide_context(Rc::new(document.clone()) as Rc<dyn Document>);
+
+    document
+}
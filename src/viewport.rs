@@ -0,0 +1,64 @@
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+
+use crate::listener::SharedListener;
+
+const VISUAL_VIEWPORT_SCRIPT: &str = r#"
+    const viewport = window.visualViewport;
+    // without the API, nothing is reported and the hooks keep the layout viewport
+    if (!viewport) {
+        await dioxus.recv();
+        return;
+    }
+    const report = () => dioxus.send([
+        viewport.offsetLeft, viewport.offsetTop, viewport.width, viewport.height,
+    ]);
+    viewport.addEventListener("resize", report);
+    viewport.addEventListener("scroll", report);
+    report();
+    await dioxus.recv();
+    viewport.removeEventListener("resize", report);
+    viewport.removeEventListener("scroll", report);
+"#;
+
+// shared by every use_visual_viewport hook of a window
+static VISUAL_VIEWPORT: SharedListener<(f64, f64, f64, f64), PixelsRect> =
+    SharedListener::new(VISUAL_VIEWPORT_SCRIPT, |(x, y, width, height)| {
+        PixelsRect::new(
+            PixelsVector2D::new(x, y).to_point(),
+            PixelsSize::new(width, height),
+        )
+    });
+
+/// Returns the rect of the visual viewport in client coordinates, the part of the page
+/// that is actually visible, e.g., above an on-screen keyboard.
+///
+/// Tracks the `resize` and `scroll` events of the VisualViewport API through a single
+/// listener shared by all hook instances and removed after the last one unmounts.
+/// `None` in browsers without the API and outside of the web.
+///
+/// See [crate::FloatingOptions::clip_to_visual_viewport] to keep floating elements out
+/// from under the keyboard.
+pub fn use_visual_viewport() -> ReadSignal<Option<PixelsRect>> {
+    let viewport = use_hook(|| {
+        VISUAL_VIEWPORT
+            .subscribe()
+            .unwrap_or_else(|| Signal::new(None))
+    });
+    use_drop(|| VISUAL_VIEWPORT.unsubscribe());
+
+    viewport.into()
+}
+
+/// Internal: [use_visual_viewport] for a placement hook; `None` (and no listener)
+/// unless `enabled`.
+pub(crate) fn use_clip_viewport(enabled: bool) -> Option<ReadSignal<Option<PixelsRect>>> {
+    let viewport = use_hook(move || enabled.then(|| VISUAL_VIEWPORT.subscribe()).flatten());
+    use_drop(move || {
+        if viewport.is_some() {
+            VISUAL_VIEWPORT.unsubscribe();
+        }
+    });
+
+    viewport.map(Into::into)
+}
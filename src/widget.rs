@@ -0,0 +1,455 @@
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use dioxus::core::{AttributeValue, Event};
+use dioxus::html::PlatformEventData;
+use dioxus::html::events as html_events;
+use dioxus::prelude::*;
+
+use crate::dismiss::PRESSES;
+use crate::hooks::{Anchor, PlacementOverrides, use_placement_with_anchor};
+use crate::{
+    CloseReason, Floating, FloatingArrowContext, FloatingDelays, FloatingOptions, FloatingResult,
+    FloatingState, OpenReason, PointerType, ScrollableContext, Strategy, use_floating_state,
+};
+
+static NEXT_WIDGET_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The default [WidgetConfig::outside_press_slop], in CSS pixels.
+const OUTSIDE_PRESS_SLOP: f64 = 10_f64;
+
+/// Merges two attribute bundles (e.g., the props of two interaction hooks).
+///
+/// - Handlers for the same event are chained: the handler from `first` runs, then the one from `second`.
+/// - `class` values are concatenated with a space and `style` values with `; `, so the
+///   positioning style of a floating element survives user styles.
+/// - For any other duplicate attribute, the value from `second` wins.
+///
+/// # Example
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_floating::merge_props;
+///
+/// #[component]
+/// fn Button(#[props(extends = GlobalAttributes)] attributes: Vec<Attribute>) -> Element {
+///     let own = vec![Attribute::new("class", "button", None, false)];
+///     rsx! {
+///         button { ..merge_props(own, attributes), "Click me" }
+///     }
+/// }
+/// ```
+pub fn merge_props(first: Vec<Attribute>, second: Vec<Attribute>) -> Vec<Attribute> {
+    let mut merged = first;
+
+    for attribute in second {
+        let existing = merged
+            .iter_mut()
+            .find(|a| a.name == attribute.name && a.namespace == attribute.namespace);
+        let Some(existing) = existing else {
+            merged.push(attribute);
+            continue;
+        };
+
+        existing.value = match (&existing.value, attribute.value) {
+            (AttributeValue::Listener(before), AttributeValue::Listener(after)) => {
+                let before = before.clone();
+                AttributeValue::listener(move |evt: Event<PlatformEventData>| {
+                    let evt = evt.into_any();
+                    before.call(evt.clone());
+                    after.call(evt);
+                })
+            }
+            (AttributeValue::Text(before), AttributeValue::Text(after))
+                if attribute.name == "class" =>
+            {
+                AttributeValue::Text(format!("{before} {after}"))
+            }
+            (AttributeValue::Text(before), AttributeValue::Text(after))
+                if attribute.name == "style" =>
+            {
+                AttributeValue::Text(format!("{}; {after}", before.trim_end_matches(';')))
+            }
+            (_, value) => value,
+        };
+        existing.volatile |= attribute.volatile;
+    }
+
+    merged
+}
+
+/// Configuration for [use_floating_widget].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidgetConfig {
+    /// Positioning options passed to [crate::use_placement].
+    pub options: FloatingOptions,
+    /// Toggle on trigger click.
+    pub click: bool,
+    /// Open while the pointer is over the trigger.
+    pub hover: bool,
+    /// Open while the trigger has focus.
+    pub focus: bool,
+    /// Close on `Escape` (on the trigger or the floating element).
+    pub dismiss: bool,
+    /// The open and close delays of [FloatingWidget::state], e.g., a hover delay. None by
+    /// default.
+    pub delays: FloatingDelays,
+    /// Close on a tap outside of the trigger and the floating element.
+    ///
+    /// Only taps count: a press that moves more than [WidgetConfig::outside_press_slop],
+    /// scrolls any container (e.g., a `ScrollableView`) while the pointer is down or is
+    /// taken over by the browser to pan does not close the element, so a touch that
+    /// scrolls the page behind a popover, or pans its content and leaves it mid-pan, keeps
+    /// it open. Where the press started decides if it is outside, not where it ended.
+    pub outside_press: bool,
+    /// How far (in CSS pixels) a pointer may move between `pointerdown` and `pointerup`
+    /// to still count as a tap for [WidgetConfig::outside_press]. Defaults to 10.
+    pub outside_press_slop: f64,
+    /// Measure the trigger when the pointer enters it or it gets focus, so opening it
+    /// is faster, see [Floating::prewarm].
+    pub prewarm: bool,
+    /// ARIA role of the floating element (e.g., `menu`, `listbox`, `tooltip`).
+    pub role: &'static str,
+    /// Adds the `id` of the enclosing `ScrollableView` (see
+    /// [ScrollableContext::element_id]) to the `aria-controls` of the trigger, after the
+    /// floating element, e.g., for a combobox that also scrolls the list it sits in.
+    pub controls_container: bool,
+}
+
+impl Default for WidgetConfig {
+    /// Returns a click-toggled, dismissible `dialog`.
+    fn default() -> Self {
+        Self {
+            options: FloatingOptions::default(),
+            click: true,
+            hover: false,
+            focus: false,
+            dismiss: true,
+            delays: FloatingDelays::default(),
+            outside_press: false,
+            outside_press_slop: OUTSIDE_PRESS_SLOP,
+            prewarm: false,
+            role: "dialog",
+            controls_container: false,
+        }
+    }
+}
+
+/// The state and props of a floating widget, see [use_floating_widget].
+#[derive(Debug, Clone, Copy)]
+pub struct FloatingWidget {
+    /// `true` while the floating element should be rendered, see
+    /// [FloatingState::is_rendered]. Writing it opens or closes the element immediately.
+    pub is_open: Signal<bool>,
+    /// The open state machine behind [FloatingWidget::is_open], with the
+    /// [WidgetConfig::delays].
+    pub state: FloatingState,
+    /// The placement of the floating element.
+    pub placement: ReadSignal<FloatingResult>,
+    /// A reactive signal containing the [MountedData] of the trigger.
+    pub trigger_ref: Signal<Option<Rc<MountedData>>>,
+    /// A reactive signal containing the [MountedData] of the floating element.
+    pub element_ref: Signal<Option<Rc<MountedData>>>,
+    /// How the element was last opened, set by the trigger handlers.
+    pub open_reason: Signal<Option<OpenReason>>,
+    /// The pointer of the last pointer interaction with the trigger (`None` after the
+    /// keyboard), see [FloatingOptions::touch_offset_extra].
+    pub pointer_type: Signal<Option<PointerType>>,
+    id: usize,
+    click: bool,
+    hover: bool,
+    focus: bool,
+    dismiss: bool,
+    // the engine that pre-measures the trigger, if enabled
+    prewarm: Option<Floating>,
+    role: &'static str,
+    strategy: Strategy,
+    // the enclosing view, if any
+    container: Option<ScrollableContext>,
+    controls_container: bool,
+}
+
+impl FloatingWidget {
+    /// Opens the floating element, after the open delay.
+    pub fn open(&mut self) {
+        self.state.request_open(*self.open_reason.peek());
+        self.sync();
+    }
+
+    /// Closes the floating element, after the close delay.
+    pub fn close(&mut self) {
+        self.close_with(None);
+    }
+
+    /// Toggles the floating element.
+    pub fn toggle(&mut self) {
+        self.toggle_with(None);
+    }
+
+    /// Internal: Closes the floating element for `reason`.
+    fn close_with(&mut self, reason: Option<CloseReason>) {
+        self.state.request_close(reason);
+        self.sync();
+    }
+
+    /// Internal: Toggles the floating element, closing it for `reason`.
+    fn toggle_with(&mut self, reason: Option<CloseReason>) {
+        if self.state.phase.peek().is_opening() {
+            self.close_with(reason);
+        } else {
+            self.open();
+        }
+    }
+
+    /// Internal: Updates [FloatingWidget::is_open] from the phase of the state.
+    fn sync(&mut self) {
+        let is_rendered = self.state.phase.peek().is_rendered();
+        if *self.is_open.peek() != is_rendered {
+            self.is_open.set(is_rendered);
+            if !is_rendered {
+                self.element_ref.set(None);
+            }
+        }
+    }
+
+    /// Returns the `id` of the floating element, referenced by the trigger's `aria-controls`.
+    pub fn floating_id(&self) -> String {
+        format!("dioxus-floating-{}", self.id)
+    }
+
+    /// Returns the `id` of the enclosing `ScrollableView`, see
+    /// [ScrollableContext::element_id]. Reading it subscribes to changes of the id.
+    pub fn container_id(&self) -> Option<String> {
+        self.container
+            .and_then(|container| container.element_id().cloned())
+    }
+
+    /// Internal: Returns the ids referenced by the trigger's `aria-controls`.
+    fn controls(&self) -> String {
+        match self.container_id().filter(|_| self.controls_container) {
+            Some(container) => format!("{} {container}", self.floating_id()),
+            None => self.floating_id(),
+        }
+    }
+
+    /// Returns the merged attributes and handlers for the trigger element.
+    ///
+    /// Handlers run in the order: open reason tracking, prewarm, click, hover, focus, dismiss.
+    pub fn trigger_props(&self) -> Vec<Attribute> {
+        let mut widget = *self;
+        let mut trigger_ref = self.trigger_ref;
+        let mut open_reason = self.open_reason;
+        let mut pointer_type = self.pointer_type;
+
+        let mut props = vec![
+            html_events::onmounted(move |evt: MountedEvent| trigger_ref.set(Some(evt.data()))),
+            // the activation keys fire before the click they cause
+            html_events::onkeydown(move |evt: KeyboardEvent| {
+                if matches!(evt.key(), Key::Enter | Key::ArrowDown | Key::ArrowUp)
+                    || evt.key() == Key::Character(" ".to_string())
+                {
+                    open_reason.set(Some(OpenReason::Keyboard));
+                    pointer_type.set(None);
+                }
+            }),
+            html_events::onpointerdown(move |evt: PointerEvent| {
+                open_reason.set(Some(OpenReason::Pointer));
+                pointer_type.set(PointerType::parse(&evt.pointer_type()));
+            }),
+            Attribute::new("aria-haspopup", self.role, None, false),
+            Attribute::new("aria-expanded", (self.is_open)().to_string(), None, false),
+            Attribute::new("aria-controls", self.controls(), None, false),
+        ];
+
+        if let Some(floating) = self.prewarm {
+            let prewarm = move || {
+                if let Some(trigger) = trigger_ref.peek().clone() {
+                    floating.prewarm(trigger);
+                }
+            };
+            props = merge_props(
+                props,
+                vec![
+                    html_events::onpointerenter(move |_: PointerEvent| prewarm()),
+                    html_events::onfocus(move |_: FocusEvent| prewarm()),
+                ],
+            );
+        }
+        if self.click {
+            props = merge_props(
+                props,
+                vec![html_events::onclick(move |_: MouseEvent| {
+                    widget.toggle_with(Some(CloseReason::Pointer))
+                })],
+            );
+        }
+        if self.hover {
+            props = merge_props(
+                props,
+                vec![
+                    html_events::onmouseenter(move |_: MouseEvent| widget.open()),
+                    html_events::onmouseleave(move |_: MouseEvent| {
+                        widget.close_with(Some(CloseReason::Pointer))
+                    }),
+                ],
+            );
+        }
+        if self.focus {
+            props = merge_props(
+                props,
+                vec![
+                    html_events::onfocus(move |_: FocusEvent| widget.open()),
+                    html_events::onblur(move |_: FocusEvent| {
+                        widget.close_with(Some(CloseReason::Blur))
+                    }),
+                ],
+            );
+        }
+        if self.dismiss {
+            props = merge_props(props, vec![self.dismiss_handler()]);
+        }
+
+        props
+    }
+
+    /// Returns the merged attributes and handlers for the floating element,
+    /// including its positioning style (see [FloatingResult::style]).
+    pub fn floating_props(&self) -> Vec<Attribute> {
+        let mut element_ref = self.element_ref;
+        let placement = (self.placement)();
+        let strategy = self.strategy;
+
+        let mut props = vec![
+            html_events::onmounted(move |evt: MountedEvent| element_ref.set(Some(evt.data()))),
+            Attribute::new("id", self.floating_id(), None, false),
+            Attribute::new("role", self.role, None, false),
+            Attribute::new("style", placement.style(strategy), None, false),
+        ];
+
+        if self.dismiss {
+            props = merge_props(props, vec![self.dismiss_handler()]);
+        }
+
+        props
+    }
+
+    /// Internal: Closes the widget on `Escape`.
+    fn dismiss_handler(&self) -> Attribute {
+        let mut widget = *self;
+        html_events::onkeydown(move |evt: KeyboardEvent| {
+            if evt.key() == Key::Escape {
+                widget.close_with(Some(CloseReason::Escape));
+            }
+        })
+    }
+}
+
+/// Builds a complete floating widget (dropdown, tooltip, popover) with one hook call.
+///
+/// It combines the interactions selected in the [WidgetConfig] with [crate::use_placement]
+/// and returns merged prop bundles for the trigger and the floating element.
+/// The [OpenReason] and [PointerType] are tracked from the trigger events, so keyboard-opened
+/// elements avoid flipping away (see [FloatingOptions::open_reason]) and touch-opened ones
+/// keep a larger gap (see [FloatingOptions::touch_offset_extra]).
+/// Use [merge_props] to add your own handlers to them.
+///
+/// The handlers drive a [FloatingState], so with [WidgetConfig::delays] a pointer that
+/// leaves a hover trigger during the open delay never opens it, and one that comes back
+/// during the close delay keeps it open.
+///
+/// The placement is provided as a [FloatingArrowContext], so a `FloatingArrow` inside
+/// the floating element needs no `result` prop.
+///
+/// # Example
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_floating::{use_floating_widget, WidgetConfig};
+///
+/// #[component]
+/// fn Dropdown() -> Element {
+///     let widget = use_floating_widget(WidgetConfig { role: "menu", ..Default::default() });
+///
+///     rsx! {
+///         button { ..widget.trigger_props(), "Open" }
+///         if (widget.is_open)() {
+///             div { ..widget.floating_props(), "Menu content" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_floating_widget(config: WidgetConfig) -> FloatingWidget {
+    let is_open = use_signal(|| false);
+    let trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+    let id = use_hook(|| NEXT_WIDGET_ID.fetch_add(1, Ordering::Relaxed));
+    let open_reason = use_signal(|| Option::<OpenReason>::None);
+    let pointer_type = use_signal(|| Option::<PointerType>::None);
+    let strategy = config.options.strategy;
+    let floating = crate::use_floating();
+    let presses = use_hook(|| config.outside_press.then(|| PRESSES.subscribe()).flatten());
+    use_drop(move || {
+        if presses.is_some() {
+            PRESSES.unsubscribe();
+        }
+    });
+    let slop = config.outside_press_slop;
+    let mut state = use_floating_state(config.delays);
+    let placement = use_placement_with_anchor(
+        "use_floating_widget",
+        element_ref.into(),
+        Some(trigger_ref.into()),
+        move || trigger_ref().map(Anchor::Trigger),
+        config.options,
+        PlacementOverrides {
+            open_reason: Some(open_reason.into()),
+            pointer_type: Some(pointer_type.into()),
+            element_size: None,
+        },
+    );
+    use_context_provider(move || FloatingArrowContext(placement));
+
+    let mut widget = FloatingWidget {
+        is_open,
+        state,
+        placement,
+        trigger_ref,
+        element_ref,
+        open_reason,
+        pointer_type,
+        id,
+        click: config.click,
+        hover: config.hover,
+        focus: config.focus,
+        dismiss: config.dismiss,
+        prewarm: config.prewarm.then_some(floating),
+        role: config.role,
+        strategy,
+        container: try_use_context::<ScrollableContext>(),
+        controls_container: config.controls_container,
+    };
+    use_effect(move || {
+        let Some(presses) = presses else {
+            return;
+        };
+        let is_outside_tap = presses
+            .read()
+            .as_ref()
+            .is_some_and(|press| press.is_outside_tap(&widget.floating_id(), slop));
+        if is_outside_tap {
+            widget.close_with(Some(CloseReason::OutsidePress));
+        }
+    });
+    // the timers of the state move the phase
+    use_effect(move || {
+        (state.phase)();
+        widget.sync();
+    });
+    // writes to is_open skip the delays
+    use_effect(move || {
+        let is_open = is_open();
+        if is_open != state.phase.peek().is_rendered() {
+            state.force(is_open);
+        }
+    });
+
+    widget
+}
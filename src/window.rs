@@ -0,0 +1,71 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use dioxus::core::Runtime;
+
+thread_local! {
+    // the shared state of every window (Dioxus runtime) on this thread by the address of
+    // its runtime, see with_window
+    static WINDOWS: RefCell<HashMap<*const Runtime, Window>> = RefCell::new(HashMap::new());
+}
+
+/// Internal: The shared state of the hooks of one runtime, one value per type.
+struct Window {
+    // keeps the address of the runtime from being reused while the entry exists
+    runtime: Weak<Runtime>,
+    states: HashMap<TypeId, Rc<dyn Any>>,
+}
+
+/// Internal: Runs `f` with the state of type `S` of the current window, created with
+/// [Default] on first use. `None` outside of a Dioxus runtime.
+///
+/// Every `VirtualDom` has its own runtime, and `dioxus-desktop` runs one per window on
+/// the same thread, so state shared by the hooks (document listeners, measurement
+/// batches, the frame budget, the device pixel ratio) must never be a plain thread
+/// local: a popover in one window would see the presses and the viewport of another.
+///
+/// The windows are kept here rather than in a context of their root scope: the hooks
+/// reach the state from tasks and drop handlers, also while a `VirtualDom` is dropped
+/// and its root scope is gone already. The states of closed windows are dropped when
+/// the next window is added.
+pub(crate) fn with_window<S: Default + 'static, R>(f: impl FnOnce(&mut S) -> R) -> Option<R> {
+    let runtime = Runtime::try_current()?;
+    let (state, closed) = WINDOWS.with(|windows| {
+        let mut windows = windows.borrow_mut();
+        let key = Rc::as_ptr(&runtime);
+        let mut closed = Vec::new();
+        if !windows.contains_key(&key) {
+            // dropped outside of the borrow, in case a state refers to another one
+            windows.retain(|_, window| {
+                let is_open = window.runtime.strong_count() > 0;
+                if !is_open {
+                    closed.push(std::mem::take(&mut window.states));
+                }
+                is_open
+            });
+            windows.insert(
+                key,
+                Window {
+                    runtime: Rc::downgrade(&runtime),
+                    states: HashMap::new(),
+                },
+            );
+        }
+
+        let states = &mut windows.get_mut(&key)?.states;
+        let state = states
+            .entry(TypeId::of::<S>())
+            .or_insert_with(|| Rc::new(RefCell::new(S::default())))
+            .clone()
+            .downcast::<RefCell<S>>()
+            .ok()?;
+
+        Some((state, closed))
+    })?;
+    drop(closed);
+
+    let result = f(&mut state.borrow_mut());
+    Some(result)
+}
@@ -0,0 +1,125 @@
+//! [Middleware::Arrow] points the arrow at the center of the trigger on the final
+//! placement: after a flip, a shift and within the corner radius.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{
+    ArrowPosition, Floating, FloatingOptions, Middleware, Offset, Placement, Side,
+};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn with_arrow(placement: Placement) -> FloatingOptions {
+    FloatingOptions {
+        middleware: vec![
+            Middleware::Flip,
+            Middleware::Shift,
+            Middleware::Arrow {
+                width: 10.0,
+                corner_radius: 4.0,
+            },
+        ],
+        placement,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    }
+}
+
+/// Places a 100x50 element in a 500x500 boundary and returns its position and arrow.
+fn place(trigger: PixelsRect, options: FloatingOptions) -> ((f64, f64), Option<ArrowPosition>) {
+    let output = Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        trigger,
+        options,
+    );
+
+    ((output.x, output.y), output.arrow)
+}
+
+#[test]
+fn centered_on_the_trigger() {
+    let (position, arrow) = place(
+        rect(200.0, 100.0, 80.0, 20.0),
+        with_arrow(Placement::BottomStart),
+    );
+    let arrow = arrow.unwrap();
+
+    assert_eq!(position, (200.0, 120.0));
+    assert_eq!(arrow.edge, Side::Top);
+    assert_eq!((arrow.x(), arrow.y()), (Some(35.0), None));
+}
+
+#[test]
+fn follows_a_shift() {
+    // near the right edge: shifted 40px to the left, the trigger center is 60px in
+    let (position, arrow) = place(
+        rect(440.0, 100.0, 40.0, 20.0),
+        with_arrow(Placement::BottomStart),
+    );
+
+    assert_eq!(position, (400.0, 120.0));
+    assert_eq!(arrow.unwrap().x(), Some(55.0));
+}
+
+#[test]
+fn stays_inside_the_corner_radius() {
+    // the trigger center is 95px into the 100px wide element
+    let (_, arrow) = place(
+        rect(490.0, 100.0, 10.0, 20.0),
+        with_arrow(Placement::BottomStart),
+    );
+    assert_eq!(arrow.unwrap().x(), Some(86.0));
+
+    // and 5px before it
+    let (position, arrow) = place(
+        rect(-10.0, 100.0, 10.0, 20.0),
+        with_arrow(Placement::BottomEnd),
+    );
+    assert_eq!(position.0, 0.0);
+    assert_eq!(arrow.unwrap().x(), Some(4.0));
+}
+
+#[test]
+fn follows_a_flip() {
+    // no room below
+    let (position, arrow) = place(
+        rect(200.0, 470.0, 80.0, 20.0),
+        with_arrow(Placement::BottomStart),
+    );
+    let arrow = arrow.unwrap();
+
+    assert_eq!(position, (200.0, 420.0));
+    assert_eq!(arrow.edge, Side::Bottom);
+    assert_eq!(arrow.x(), Some(35.0));
+}
+
+#[test]
+fn along_the_vertical_edge_of_horizontal_placements() {
+    let (_, arrow) = place(
+        rect(100.0, 200.0, 20.0, 30.0),
+        with_arrow(Placement::RightStart),
+    );
+    let arrow = arrow.unwrap();
+    assert_eq!(arrow.edge, Side::Left);
+    assert_eq!((arrow.x(), arrow.y()), (None, Some(10.0)));
+
+    // near the bottom edge: shifted 30px up
+    let (position, arrow) = place(
+        rect(100.0, 480.0, 20.0, 20.0),
+        with_arrow(Placement::RightStart),
+    );
+    assert_eq!(position, (120.0, 450.0));
+    assert_eq!(arrow.unwrap().y(), Some(35.0));
+}
+
+#[test]
+fn none_without_the_middleware() {
+    let options = FloatingOptions {
+        middleware: vec![Middleware::Flip, Middleware::Shift],
+        ..with_arrow(Placement::BottomStart)
+    };
+
+    assert_eq!(place(rect(200.0, 100.0, 80.0, 20.0), options).1, None);
+}
@@ -0,0 +1,147 @@
+//! [Middleware::Auto] places the element on the side with the most free space, within
+//! the allowed placements, before Shift runs.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{
+    Floating, FloatingOptions, Middleware, Offset, Padding, PaddingValue, Placement,
+    PlacementOutput,
+};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn auto(allowed: &'static [Placement]) -> FloatingOptions {
+    FloatingOptions {
+        middleware: vec![Middleware::Auto { allowed }, Middleware::Shift],
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    }
+}
+
+/// Places a 100x50 element in a 500x500 boundary.
+fn place(trigger: PixelsRect, options: FloatingOptions) -> PlacementOutput {
+    Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        trigger,
+        options,
+    )
+}
+
+#[test]
+fn picks_the_side_with_the_most_space() {
+    let cases = [
+        // near an edge, the opposite side has the most room
+        (rect(200.0, 460.0, 40.0, 20.0), Placement::TopStart),
+        (rect(200.0, 20.0, 40.0, 20.0), Placement::BottomStart),
+        (rect(460.0, 200.0, 20.0, 40.0), Placement::LeftStart),
+        (rect(20.0, 200.0, 20.0, 40.0), Placement::RightStart),
+    ];
+
+    for (trigger, expected) in cases {
+        assert_eq!(place(trigger, auto(&[])).placement, expected);
+    }
+}
+
+#[test]
+fn ties_keep_the_preferred_side() {
+    // the same room above and below, less on the sides
+    let trigger = rect(200.0, 240.0, 100.0, 20.0);
+
+    assert_eq!(place(trigger, auto(&[])).placement, Placement::BottomStart);
+    let options = FloatingOptions {
+        placement: Placement::TopEnd,
+        ..auto(&[])
+    };
+    assert_eq!(place(trigger, options).placement, Placement::TopEnd);
+}
+
+#[test]
+fn the_boundary_padding_is_measured() {
+    // slightly more room below
+    let trigger = rect(200.0, 220.0, 100.0, 20.0);
+    assert_eq!(place(trigger, auto(&[])).placement, Placement::BottomStart);
+
+    // a 100px tall toolbar over the bottom of the boundary
+    let options = FloatingOptions {
+        boundary_padding: Padding {
+            bottom: PaddingValue::Px(100.0),
+            ..Default::default()
+        },
+        ..auto(&[])
+    };
+    assert_eq!(place(trigger, options).placement, Placement::TopStart);
+}
+
+#[test]
+fn only_the_allowed_placements() {
+    // near the right edge: the left side has the most room, but only top and bottom
+    // are allowed
+    let trigger = rect(440.0, 300.0, 40.0, 20.0);
+    assert_eq!(place(trigger, auto(&[])).placement, Placement::LeftStart);
+
+    let vertical = &[Placement::BottomStart, Placement::TopStart];
+    assert_eq!(
+        place(trigger, auto(vertical)).placement,
+        Placement::TopStart
+    );
+
+    // the alignment that fits wins on the roomiest side
+    let aligned = &[
+        Placement::TopStart,
+        Placement::TopEnd,
+        Placement::BottomStart,
+    ];
+    let output = place(trigger, auto(aligned));
+    assert_eq!(output.placement, Placement::TopEnd);
+    assert_eq!((output.x, output.y), (380.0, 250.0));
+}
+
+#[test]
+fn shift_runs_on_the_chosen_side() {
+    let trigger = rect(440.0, 460.0, 40.0, 20.0);
+    let output = place(trigger, auto(&[Placement::TopStart]));
+
+    assert_eq!(output.placement, Placement::TopStart);
+    // shifted back into the boundary from x = 440
+    assert_eq!((output.x, output.y), (400.0, 410.0));
+    assert_eq!(output.shift, PixelsVector2D::new(-40.0, 0.0));
+    assert!(!output.alignment_changed);
+}
+
+#[test]
+fn explained_before_the_base() {
+    let options = FloatingOptions {
+        middleware: vec![Middleware::auto(), Middleware::Flip],
+        explain: true,
+        ..auto(&[])
+    };
+    let output = place(rect(200.0, 460.0, 40.0, 20.0), options);
+
+    let names: Vec<_> = output.steps.iter().map(|step| step.name).collect();
+    assert_eq!(names[..3], ["auto", "base", "flip"]);
+    assert_eq!(output.steps[0].after, (200.0, 410.0));
+    assert!(output.steps[2].reason.starts_with("skipped"));
+}
+
+#[test]
+fn the_middleware_stays_copy() {
+    const VERTICAL: &[Placement] = &[Placement::BottomStart, Placement::TopStart];
+    let presets = [Middleware::Flip, Middleware::Auto { allowed: VERTICAL }];
+    let chosen = presets[1];
+
+    assert_eq!(chosen, presets[1]);
+    assert_eq!(
+        place(
+            rect(440.0, 200.0, 40.0, 20.0),
+            FloatingOptions {
+                middleware: vec![chosen, Middleware::Shift],
+                ..auto(&[])
+            },
+        )
+        .placement,
+        Placement::BottomStart
+    );
+}
@@ -0,0 +1,151 @@
+//! The measurements of all open popovers are read in one batch before any result is
+//! published: a slow read holds back the writes of every placement of the batch, which
+//! are then published together.
+//!
+//! The mock reads resolve right away, so the reads are held to be in flight together like
+//! the asynchronous reads of the browser.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::{Cell, RefCell};
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{FloatingOptions, FloatingResult, Offset, ScrollableContext, use_placement};
+
+const POPOVERS: usize = 5;
+
+/// The mocks of one popover.
+#[derive(Clone)]
+struct Mocks {
+    trigger: MockElement,
+    element: MockElement,
+}
+
+thread_local! {
+    static MOCKS: RefCell<Vec<Mocks>> = const { RefCell::new(Vec::new()) };
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static RESULTS: RefCell<Vec<FloatingResult>> = const { RefCell::new(Vec::new()) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    use_hook(|| {
+        let context =
+            provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 1000.0)));
+        CONTEXT.with(|cell| cell.set(Some(context)));
+        MOCKS.with(|cell| {
+            *cell.borrow_mut() = (0..POPOVERS)
+                .map(|index| Mocks {
+                    trigger: MockElement::new(trigger_rect(index, 0.0)),
+                    element: MockElement::new(rect(0.0, 0.0, 100.0, 50.0)),
+                })
+                .collect()
+        });
+        RESULTS.with(|cell| *cell.borrow_mut() = vec![FloatingResult::default(); POPOVERS]);
+    });
+    rsx! {
+        for index in 0..POPOVERS {
+            Popover { key: "{index}", index }
+        }
+    }
+}
+
+#[component]
+fn Popover(index: usize) -> Element {
+    let mocks = MOCKS.with(|cell| cell.borrow()[index].clone());
+    let element_ref = use_signal(|| Some(mocks.element.mounted()));
+    let trigger_ref = use_signal(|| Some(mocks.trigger.mounted()));
+    let options = FloatingOptions {
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULTS.with(|cell| cell.borrow_mut()[index] = placement());
+    rsx! {}
+}
+
+/// The trigger of popover `index`, moved right by `x`.
+fn trigger_rect(index: usize, x: f64) -> PixelsRect {
+    rect(100.0 + x, 150.0 * index as f64, 80.0, 20.0)
+}
+
+fn mocks() -> Vec<Mocks> {
+    MOCKS.with(|cell| cell.borrow().clone())
+}
+
+/// The horizontal positions of the popovers.
+fn positions() -> Vec<f64> {
+    RESULTS.with(|cell| cell.borrow().iter().map(|result| result.x).collect())
+}
+
+fn round_trips() -> Vec<usize> {
+    mocks()
+        .iter()
+        .map(|mocks| mocks.element.round_trips())
+        .collect()
+}
+
+/// Moves every trigger right by `x` and measures all popovers again.
+fn move_triggers(dom: &mut VirtualDom, x: f64) {
+    for (index, mocks) in mocks().iter().enumerate() {
+        mocks.trigger.set_rect(trigger_rect(index, x));
+    }
+    let mut generation = CONTEXT.with(Cell::get).unwrap().layout_generation;
+    dom.in_runtime(|| *generation.write() += 1);
+    settle(dom);
+}
+
+#[test]
+fn a_slow_read_holds_back_every_write_of_the_batch() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    assert_eq!(positions(), vec![100.0; POPOVERS]);
+
+    let before = round_trips();
+    let elements: Vec<MockElement> = mocks().into_iter().map(|mocks| mocks.element).collect();
+    elements.iter().for_each(MockElement::hold);
+    move_triggers(&mut dom, 50.0);
+    // every popover is being read
+    let read = round_trips();
+    assert!(before.iter().zip(&read).all(|(before, read)| read > before));
+
+    // all but the slowest read are done, none of the results is written yet
+    let (slow, done) = elements.split_last().unwrap();
+    for element in done {
+        element.release();
+        settle(&mut dom);
+        assert_eq!(positions(), vec![100.0; POPOVERS]);
+    }
+    let results = RESULTS.with(|cell| cell.borrow().clone());
+    assert!(results.iter().all(|result| result.is_pending));
+
+    // the last read of the batch publishes all of them
+    slow.release();
+    settle(&mut dom);
+    assert_eq!(positions(), vec![150.0; POPOVERS]);
+    let results = RESULTS.with(|cell| cell.borrow().clone());
+    assert!(results.iter().all(|result| !result.is_pending));
+}
+
+#[test]
+fn a_batch_reads_each_element_once() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    let before = round_trips();
+    move_triggers(&mut dom, 20.0);
+    let after = round_trips();
+    let per_popover: Vec<usize> = before.iter().zip(&after).map(|(b, a)| a - b).collect();
+    // one read per popover, none caused by the writes of the others
+    assert_eq!(per_popover, vec![1; POPOVERS]);
+    assert_eq!(positions(), vec![120.0; POPOVERS]);
+}
@@ -0,0 +1,100 @@
+//! Center alignment with odd sizes lands on halves; rounded toward the trigger, the
+//! offset from the trigger stays the same while scrolling back and forth.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{Floating, FloatingOptions, Offset, Placement, Rounding};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+/// Returns the rounded position of a 100x50 element centered on `trigger`.
+fn place(trigger: PixelsRect, placement: Placement) -> (f64, f64) {
+    let options = FloatingOptions {
+        placement,
+        placement_offset: Some(Offset::zero()),
+        middleware: Vec::new(),
+        ..Default::default()
+    };
+    let output = Floating::builder()
+        .rounding(Rounding::Pixel)
+        .build()
+        .compute_placement(
+            rect(-1000.0, -1000.0, 3000.0, 3000.0),
+            rect(0.0, 0.0, 100.0, 50.0),
+            trigger,
+            options,
+        );
+
+    (output.x, output.y)
+}
+
+/// Scrolls by alternating deltas of ±1px from `start` and returns the trigger positions.
+fn wobble(start: f64) -> Vec<f64> {
+    (0..8).map(|step| start - f64::from(step % 2)).collect()
+}
+
+#[test]
+fn the_centering_is_exact_at_any_position() {
+    let options = FloatingOptions {
+        placement: Placement::BottomCenter,
+        placement_offset: Some(Offset::zero()),
+        middleware: Vec::new(),
+        ..Default::default()
+    };
+
+    // an 81px wide trigger: 9.5px on each side
+    for x in [-300.25, -0.5, 0.0, 0.1 + 0.2, 1e6 + 0.5] {
+        let output = Floating::default().compute_placement(
+            rect(-1e7, -1e7, 2e7, 2e7),
+            rect(0.0, 0.0, 100.0, 50.0),
+            rect(x, 0.0, 81.0, 20.0),
+            options.clone(),
+        );
+        assert_eq!(output.x, x - 9.5, "{x}");
+    }
+}
+
+#[test]
+fn no_wobble_on_the_cross_axis_of_vertical_placements() {
+    // the element starts at -0.5 / 0.5 around the sign change
+    for start in [10.0, 200.0, -200.0] {
+        let offsets: Vec<f64> = wobble(start)
+            .into_iter()
+            .map(|x| place(rect(x, 100.0, 81.0, 20.0), Placement::BottomCenter).0 - x)
+            .collect();
+
+        assert!(
+            offsets.iter().all(|offset| *offset == -9.0),
+            "{start}: {offsets:?}"
+        );
+    }
+}
+
+#[test]
+fn no_wobble_on_the_cross_axis_of_horizontal_placements() {
+    // a 21px tall trigger: the element starts 14.5px above it
+    for placement in [Placement::LeftCenter, Placement::RightCenter] {
+        let positions: Vec<(f64, f64)> = wobble(15.0)
+            .into_iter()
+            .map(|y| place(rect(300.0, y, 40.0, 21.0), placement))
+            .collect();
+
+        for (position, y) in positions.iter().zip(wobble(15.0)) {
+            assert_eq!(position.1 - y, -14.0, "{placement:?}: {positions:?}");
+        }
+        // the moves follow the scroll exactly: no 2px jumps
+        for pair in positions.windows(2) {
+            assert_eq!((pair[1].1 - pair[0].1).abs(), 1.0, "{placement:?}");
+        }
+    }
+}
+
+#[test]
+fn halves_of_the_main_axis_round_toward_the_trigger() {
+    // a trigger at a half pixel: the gap of a Left placement shrinks rather than grows
+    let trigger = rect(300.5, 100.0, 40.0, 20.0);
+
+    assert_eq!(place(trigger, Placement::LeftStart).0, 201.0);
+    assert_eq!(place(trigger, Placement::RightStart).0, 340.0);
+}
@@ -0,0 +1,141 @@
+//! [ScrollableContext::element_id]: the id of a [ScrollableView] (given or generated,
+//! stable and unique), available to the floating elements inside and to the
+//! `aria-controls` of a widget trigger.
+
+#![cfg(all(feature = "components", feature = "interactions"))]
+
+use std::cell::{Cell, RefCell};
+
+use dioxus::core::AttributeValue;
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{MockScrollableConfig, provide_mock_scrollable, settle};
+use dioxus_floating::{
+    ScrollState, ScrollStateProvider, ScrollableView, WidgetConfig, use_floating_widget,
+    use_scroll_context,
+};
+
+thread_local! {
+    static IDS: RefCell<Vec<Option<String>>> = const { RefCell::new(Vec::new()) };
+    static CONTAINER: Cell<Option<&'static str>> = const { Cell::new(None) };
+    static CONTROLS_CONTAINER: Cell<bool> = const { Cell::new(false) };
+    static GENERATION: Cell<Option<Signal<u64>>> = const { Cell::new(None) };
+    static CONTROLS: RefCell<String> = const { RefCell::new(String::new()) };
+    static CONTAINER_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+/// Records the id of the enclosing view on every render.
+#[component]
+fn Listbox(generation: u64) -> Element {
+    let id = use_scroll_context().element_id();
+    IDS.with(|ids| ids.borrow_mut().push(id()));
+    rsx! {}
+}
+
+fn views() -> Element {
+    let generation = use_signal(|| 0_u64);
+    GENERATION.with(|cell| cell.set(Some(generation)));
+    let generation = generation();
+    rsx! {
+        ScrollableView { Listbox { generation } }
+        ScrollableView { Listbox { generation } }
+        ScrollableView { id: "results", Listbox { generation } }
+        ScrollStateProvider {
+            state: Signal::new(Option::<ScrollState>::None),
+            container_rect: Signal::new(Option::<PixelsRect>::None),
+            Listbox { generation }
+        }
+    }
+}
+
+fn widget_app() -> Element {
+    use_hook(|| {
+        if CONTAINER.with(Cell::get).is_some() {
+            provide_mock_scrollable(MockScrollableConfig {
+                id: CONTAINER.with(Cell::get),
+                ..MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))
+            });
+        }
+    });
+    let widget = use_floating_widget(WidgetConfig {
+        role: "listbox",
+        controls_container: CONTROLS_CONTAINER.with(Cell::get),
+        ..Default::default()
+    });
+    let controls = widget
+        .trigger_props()
+        .into_iter()
+        .find(|attribute| attribute.name == "aria-controls")
+        .map(|attribute| match attribute.value {
+            AttributeValue::Text(text) => text,
+            other => panic!("{other:?}"),
+        })
+        .unwrap();
+    let expected = match (CONTROLS_CONTAINER.with(Cell::get), widget.container_id()) {
+        (true, Some(container)) => format!("{} {container}", widget.floating_id()),
+        _ => widget.floating_id(),
+    };
+    assert_eq!(controls, expected);
+    CONTROLS.with(|cell| *cell.borrow_mut() = controls);
+    CONTAINER_ID.with(|cell| *cell.borrow_mut() = widget.container_id());
+    rsx! {}
+}
+
+/// Returns the `aria-controls` of the trigger and the container id of the widget.
+fn widget(container: Option<&'static str>, controls_container: bool) -> (String, Option<String>) {
+    CONTAINER.with(|cell| cell.set(container));
+    CONTROLS_CONTAINER.with(|cell| cell.set(controls_container));
+    let mut dom = VirtualDom::new(widget_app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    (
+        CONTROLS.with(|cell| cell.take()),
+        CONTAINER_ID.with(|cell| cell.take()),
+    )
+}
+
+#[test]
+fn views_have_stable_unique_ids() {
+    let mut dom = VirtualDom::new(views);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    let first = IDS.with(|ids| ids.take());
+    assert_eq!(first.len(), 4);
+
+    let generated: Vec<String> = first[..2].iter().flatten().cloned().collect();
+    assert_eq!(generated.len(), 2);
+    assert_ne!(generated[0], generated[1]);
+    assert_eq!(first[2].as_deref(), Some("results"));
+    // no rendered container
+    assert_eq!(first[3], None);
+
+    // re-rendered: the same ids
+    let mut generation = GENERATION.with(Cell::get).unwrap();
+    dom.in_runtime(|| generation += 1);
+    settle(&mut dom);
+    let again = IDS.with(|ids| ids.take());
+    assert_eq!(again, first);
+}
+
+#[test]
+fn the_trigger_controls_the_container() {
+    let (controls, container) = widget(Some("results"), true);
+
+    assert_eq!(container.as_deref(), Some("results"));
+    assert!(controls.ends_with(" results"), "{controls}");
+}
+
+#[test]
+fn only_the_floating_element_by_default() {
+    let (controls, container) = widget(Some("results"), false);
+    assert_eq!(container.as_deref(), Some("results"));
+    assert!(!controls.contains("results"));
+
+    // outside of any view
+    assert_eq!(widget(None, true).1, None);
+}
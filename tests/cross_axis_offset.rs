@@ -0,0 +1,158 @@
+//! [Offset]: the main axis is the gap from the trigger on every side, the cross axis
+//! moves the element away from the edge it is aligned to. The deprecated `OffsetOptions`
+//! keep their absolute x and y offsets.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{
+    Floating, FloatingOptions, Middleware, Offset, Placement, PlacementOutput, PointerType,
+};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn options(placement: Placement, offset: Offset) -> FloatingOptions {
+    FloatingOptions {
+        middleware: vec![Middleware::Flip, Middleware::Shift],
+        placement,
+        placement_offset: Some(offset),
+        ..Default::default()
+    }
+}
+
+/// Places a 100x50 element in a 500x500 boundary.
+fn place(trigger: PixelsRect, options: FloatingOptions) -> PlacementOutput {
+    Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        trigger,
+        options,
+    )
+}
+
+#[test]
+fn every_placement() {
+    let trigger = rect(200.0, 200.0, 80.0, 20.0);
+    for (placement, position) in [
+        (Placement::TopStart, (212.0, 142.0)),
+        (Placement::TopCenter, (202.0, 142.0)),
+        (Placement::TopEnd, (168.0, 142.0)),
+        (Placement::BottomStart, (212.0, 228.0)),
+        (Placement::BottomCenter, (202.0, 228.0)),
+        (Placement::BottomEnd, (168.0, 228.0)),
+        (Placement::LeftStart, (92.0, 212.0)),
+        (Placement::LeftCenter, (92.0, 197.0)),
+        (Placement::LeftEnd, (92.0, 158.0)),
+        (Placement::RightStart, (288.0, 212.0)),
+        (Placement::RightCenter, (288.0, 197.0)),
+        (Placement::RightEnd, (288.0, 158.0)),
+    ] {
+        let output = place(trigger, options(placement, Offset::new(8.0, 12.0)));
+        assert_eq!((output.x, output.y), position, "{placement:?}");
+    }
+}
+
+#[test]
+fn a_number_is_the_gap() {
+    assert_eq!(Offset::from(8.0), Offset::new(8.0, 0.0));
+
+    let trigger = rect(200.0, 200.0, 80.0, 20.0);
+    let output = place(trigger, options(Placement::BottomStart, 8.0.into()));
+    assert_eq!((output.x, output.y), (200.0, 228.0));
+    let output = place(trigger, options(Placement::RightStart, 8.0.into()));
+    assert_eq!((output.x, output.y), (288.0, 200.0));
+}
+
+#[test]
+fn negative_values_overlap_the_trigger() {
+    let trigger = rect(200.0, 200.0, 80.0, 20.0);
+    let output = place(
+        trigger,
+        options(Placement::BottomStart, Offset::new(-10.0, -4.0)),
+    );
+
+    assert_eq!((output.x, output.y), (196.0, 210.0));
+    assert!(output.overlaps_trigger);
+}
+
+#[test]
+fn flips_keep_the_gap() {
+    // no room below
+    let trigger = rect(200.0, 460.0, 80.0, 20.0);
+    let output = place(
+        trigger,
+        options(Placement::BottomStart, Offset::new(8.0, 12.0)),
+    );
+
+    assert_eq!(output.placement, Placement::TopStart);
+    assert_eq!((output.x, output.y), (212.0, 402.0));
+    // the room above, minus the gap
+    assert_eq!(output.available.height, 452.0);
+}
+
+#[test]
+fn the_touch_gap_is_on_the_main_axis() {
+    let trigger = rect(200.0, 200.0, 80.0, 20.0);
+    for (placement, position) in [
+        (Placement::TopStart, (200.0, 126.0)),
+        (Placement::RightStart, (304.0, 200.0)),
+    ] {
+        let options = FloatingOptions {
+            open_pointer_type: Some(PointerType::Touch),
+            touch_offset_extra: 16.0,
+            ..options(placement, 8.0.into())
+        };
+        let output = place(trigger, options);
+        assert_eq!((output.x, output.y), position, "{placement:?}");
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn the_deprecated_offsets_stay_absolute() {
+    let trigger = rect(200.0, 200.0, 80.0, 20.0);
+    for (placement, position) in [
+        (Placement::BottomStart, (208.0, 232.0)),
+        (Placement::TopEnd, (188.0, 138.0)),
+        (Placement::RightStart, (288.0, 212.0)),
+    ] {
+        let options = FloatingOptions {
+            placement,
+            offset: dioxus_floating::OffsetOptions::new(8.0, 12.0),
+            ..Default::default()
+        };
+        let output = place(trigger, options);
+        assert_eq!((output.x, output.y), position, "{placement:?}");
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn a_placement_offset_replaces_the_deprecated_one() {
+    let trigger = rect(200.0, 200.0, 80.0, 20.0);
+    let options = FloatingOptions {
+        offset: dioxus_floating::OffsetOptions::rect(30.0),
+        ..options(Placement::BottomStart, Offset::zero())
+    };
+    let output = place(trigger, options);
+    assert_eq!((output.x, output.y), (200.0, 220.0));
+}
+
+#[test]
+fn the_default_offset_is_unchanged() {
+    // 1px along x and y, whatever the placement
+    let trigger = rect(200.0, 200.0, 80.0, 20.0);
+    for (placement, position) in [
+        (Placement::BottomStart, (201.0, 221.0)),
+        (Placement::BottomCenter, (191.0, 221.0)),
+        (Placement::BottomEnd, (181.0, 221.0)),
+        (Placement::RightCenter, (281.0, 186.0)),
+    ] {
+        let options = FloatingOptions {
+            placement,
+            ..Default::default()
+        };
+        let output = place(trigger, options);
+        assert_eq!((output.x, output.y), position, "{placement:?}");
+    }
+}
@@ -0,0 +1,119 @@
+//! A scroll container under a CSS `zoom` reports its rect in the zoomed scale and its
+//! client size (the [ScrollState::bounds]) unzoomed in some browsers. The boundary is the
+//! rect; the client size only tells the scrollbars apart, once scaled like the rect.
+
+use dioxus_floating::ScrollState;
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn state(size: (f64, f64), bounds: (f64, f64)) -> ScrollState {
+    ScrollState {
+        size: PixelsSize::new(size.0, size.1),
+        bounds: PixelsSize::new(bounds.0, bounds.1),
+        state: PixelsVector2D::zero(),
+    }
+}
+
+#[test]
+fn a_zoomed_view_without_scrollbars_keeps_its_rect() {
+    // `zoom: 1.25` on a 400x300 view whose content fits
+    let view = rect(10.0, 20.0, 500.0, 375.0);
+    let state = state((400.0, 300.0), (400.0, 300.0));
+
+    assert_eq!(state.zoom_factor(view), Some(1.25));
+    assert_eq!(state.exclude_scrollbars(view, false), view);
+}
+
+#[test]
+fn scrollbars_of_a_zoomed_view_are_scaled() {
+    // a classic 17px scrollbar next to a 383px wide client area, zoomed by 1.25
+    let view = rect(0.0, 0.0, 500.0, 375.0);
+    let state = state((383.0, 2000.0), (383.0, 300.0));
+
+    assert_eq!(state.zoom_factor(view), Some(1.25));
+    assert_eq!(state.scrollbar_gutter(view), PixelsSize::new(21.25, 0.0));
+    assert_eq!(
+        state.exclude_scrollbars(view, true),
+        rect(21.25, 0.0, 478.75, 375.0)
+    );
+
+    // zoomed out by 0.8
+    let view = rect(0.0, 0.0, 320.0, 240.0);
+    assert_eq!(state.zoom_factor(view), Some(0.8));
+    let gutter = state.scrollbar_gutter(view);
+    assert!((gutter.width - 13.6).abs() < 1e-9, "{gutter:?}");
+}
+
+#[test]
+fn unzoomed_views_are_unaffected() {
+    // scrollbars on both axes of a square view are not a scale
+    let view = rect(0.0, 0.0, 300.0, 300.0);
+    let state = state((900.0, 900.0), (283.0, 283.0));
+    assert_eq!(state.zoom_factor(view), None);
+    assert_eq!(state.scrollbar_gutter(view), PixelsSize::new(17.0, 17.0));
+
+    // neither are borders
+    let state = self::state((396.0, 2000.0), (396.0, 300.0));
+    assert_eq!(state.zoom_factor(rect(0.0, 0.0, 400.0, 304.0)), None);
+}
+
+#[test]
+fn axes_that_disagree_are_not_a_scale() {
+    // both axes without scrollbars, scaled differently (e.g., a stale client size)
+    let view = rect(0.0, 0.0, 500.0, 450.0);
+    let state = state((400.0, 300.0), (400.0, 300.0));
+
+    assert_eq!(state.zoom_factor(view), None);
+}
+
+#[cfg(feature = "hooks")]
+mod placement {
+    use std::cell::Cell;
+
+    use dioxus::prelude::*;
+    use dioxus_floating::testing::{
+        MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+    };
+    use dioxus_floating::{FloatingOptions, FloatingResult, Offset, Placement, use_placement};
+
+    use super::*;
+
+    thread_local! {
+        static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+    }
+
+    fn app() -> Element {
+        use_hook(|| {
+            let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 375.0));
+            config.scroll_state = state((383.0, 2000.0), (383.0, 300.0));
+            provide_mock_scrollable(config)
+        });
+        let element_ref =
+            use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 100.0, 50.0)).mounted()));
+        let trigger_ref =
+            use_signal(|| Some(MockElement::new(rect(400.0, 100.0, 60.0, 20.0)).mounted()));
+        let options = FloatingOptions {
+            placement: Placement::BottomStart,
+            placement_offset: Some(Offset::zero()),
+            ..Default::default()
+        };
+        let placement = use_placement(element_ref, trigger_ref, options);
+        RESULT.with(|cell| cell.set(placement()));
+        rsx! {}
+    }
+
+    #[test]
+    fn shift_stops_at_the_zoomed_scrollbar() {
+        let mut dom = VirtualDom::new(app);
+        dom.rebuild_in_place();
+        settle(&mut dom);
+
+        // the 21.25px scrollbar, not the 117px difference of the two scales
+        let result = RESULT.with(Cell::get);
+        assert!(result.is_ready);
+        assert_eq!((result.x, result.y), (378.75, 120.0));
+    }
+}
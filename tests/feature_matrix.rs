@@ -0,0 +1,80 @@
+//! Compile checks of the cargo features: every combination must build and expose the
+//! items of its features through the prelude, and its doctests must pass. Run both for
+//! each combination (the CI does):
+//!
+//! ```sh
+//! for features in engine hooks hooks,components hooks,interactions portal; do
+//!     cargo test --no-default-features --features "$features" --test feature_matrix
+//!     cargo test --no-default-features --features "$features" --doc
+//! done
+//! cargo test --test feature_matrix
+//! ```
+//!
+//! The dev-dependencies enable the default features of `dioxus`; check that the engine
+//! alone does not depend on Dioxus at all with
+//! `cargo tree --no-default-features --features engine -e normal`.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::prelude::*;
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+#[test]
+fn engine_computes_placements() {
+    let options = FloatingOptions {
+        placement_offset: Some(Offset::zero()),
+        placement: Placement::BottomStart,
+        ..Default::default()
+    };
+    let output = Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        rect(40.0, 40.0, 60.0, 20.0),
+        options,
+    );
+
+    assert_eq!((output.x, output.y), (40.0, 60.0));
+    assert_eq!(output.placement, Placement::BottomStart);
+    assert_eq!(
+        FloatingResult::default()
+            .style_attrs(Strategy::Fixed)
+            .opacity,
+        "0"
+    );
+}
+
+// the features enable the ones they build on
+const _: () = assert!(!cfg!(feature = "components") || cfg!(feature = "hooks"));
+const _: () = assert!(!cfg!(feature = "interactions") || cfg!(feature = "hooks"));
+const _: () = assert!(!cfg!(feature = "portal") || cfg!(feature = "components"));
+const _: () = assert!(
+    !cfg!(feature = "desktop") || cfg!(all(feature = "components", feature = "interactions"))
+);
+
+#[cfg(feature = "hooks")]
+#[test]
+fn hooks_are_exported() {
+    let _: fn() -> Floating = use_floating;
+    let _: fn() -> ScrollableContext = use_scroll_context;
+    let _ = dioxus_floating::testing::settle;
+}
+
+#[cfg(feature = "components")]
+#[test]
+fn components_are_exported() {
+    let _ = ScrollableView;
+    let _ = FloatingArrow;
+    let _ = FloatingProvider;
+    let _ = dioxus_floating::LazyFloating;
+}
+
+#[cfg(feature = "interactions")]
+#[test]
+fn interactions_are_exported() {
+    let _ = use_floating_widget;
+    let _ = dioxus_floating::use_pointer_position;
+    assert!(merge_props(Vec::new(), Vec::new()).is_empty());
+    assert_eq!(WidgetConfig::default().role, "dialog");
+}
@@ -0,0 +1,145 @@
+//! [Middleware::Flip] only moves the element to the opposite side if it overflows the
+//! boundary less there; an element that fits on neither side stays on the roomier one.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{
+    Axes, Floating, FloatingOptions, Middleware, Offset, Placement, PlacementOutput,
+};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn flip(placement: Placement) -> FloatingOptions {
+    FloatingOptions {
+        middleware: vec![Middleware::Flip],
+        placement,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    }
+}
+
+/// The coordinates of a `height` tall element next to `trigger` in a 500x300 boundary.
+#[allow(deprecated)]
+fn place(height: f64, trigger: PixelsRect, options: FloatingOptions) -> (f64, f64) {
+    Floating::default().calculate_placement(
+        rect(0.0, 0.0, 500.0, 300.0),
+        rect(0.0, 0.0, 100.0, height),
+        trigger,
+        options,
+    )
+}
+
+/// The resolved placement of [place].
+fn output(height: f64, trigger: PixelsRect, options: FloatingOptions) -> PlacementOutput {
+    Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 300.0),
+        rect(0.0, 0.0, 100.0, height),
+        trigger,
+        options,
+    )
+}
+
+#[test]
+fn flips_up_near_the_bottom_edge() {
+    // 20px too low below, room enough above
+    let trigger = rect(100.0, 250.0, 80.0, 20.0);
+
+    assert_eq!(
+        place(50.0, trigger, flip(Placement::BottomStart)),
+        (100.0, 200.0)
+    );
+    assert_eq!(
+        output(50.0, trigger, flip(Placement::BottomStart)).placement,
+        Placement::TopStart
+    );
+}
+
+#[test]
+fn flips_down_near_the_top_edge() {
+    let trigger = rect(100.0, 30.0, 80.0, 20.0);
+
+    assert_eq!(
+        place(50.0, trigger, flip(Placement::TopStart)),
+        (100.0, 50.0)
+    );
+}
+
+#[test]
+fn stays_below_when_above_has_less_room() {
+    // below: 140..340, 40px out; above: -80..120, 80px out
+    let trigger = rect(100.0, 120.0, 80.0, 20.0);
+
+    assert_eq!(
+        place(200.0, trigger, flip(Placement::BottomStart)),
+        (100.0, 140.0)
+    );
+    assert_eq!(
+        output(200.0, trigger, flip(Placement::BottomStart)).placement,
+        Placement::BottomStart
+    );
+}
+
+#[test]
+fn stays_above_when_below_has_less_room() {
+    // above: -40..160, 40px out; below: 180..380, 80px out
+    let trigger = rect(100.0, 160.0, 80.0, 20.0);
+
+    assert_eq!(
+        place(200.0, trigger, flip(Placement::TopStart)),
+        (100.0, -40.0)
+    );
+}
+
+#[test]
+fn flips_to_the_side_that_overflows_less() {
+    // below: 200..400, 100px out; above: -20..180, 20px out
+    let trigger = rect(100.0, 180.0, 80.0, 20.0);
+
+    assert_eq!(
+        place(200.0, trigger, flip(Placement::BottomStart)),
+        (100.0, -20.0)
+    );
+}
+
+#[test]
+fn keeps_the_preferred_side_on_a_tie() {
+    // 50px out on either side
+    let trigger = rect(100.0, 140.0, 80.0, 20.0);
+
+    assert_eq!(
+        place(210.0, trigger, flip(Placement::BottomStart)),
+        (100.0, 160.0)
+    );
+}
+
+#[test]
+fn shift_handles_the_rest_on_the_roomier_side() {
+    let options = FloatingOptions {
+        middleware: vec![Middleware::Flip, Middleware::Shift],
+        shift_axes: Axes::Both,
+        ..flip(Placement::BottomStart)
+    };
+    let output = output(200.0, rect(100.0, 120.0, 80.0, 20.0), options);
+
+    // kept below, pushed up against the bottom edge
+    assert_eq!(output.placement, Placement::BottomStart);
+    assert_eq!((output.x, output.y), (100.0, 100.0));
+}
+
+#[test]
+fn horizontal_placements() {
+    // right: 420..520, 20px out; left: -60..40, 60px out
+    let trigger = rect(40.0, 100.0, 380.0, 20.0);
+    assert_eq!(
+        place(50.0, trigger, flip(Placement::RightStart)),
+        (420.0, 100.0)
+    );
+
+    // right: 430..530, 30px out; left: 320..420, fits
+    let trigger = rect(420.0, 100.0, 10.0, 20.0);
+    assert_eq!(
+        place(50.0, trigger, flip(Placement::RightStart)),
+        (320.0, 100.0)
+    );
+}
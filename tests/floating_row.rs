@@ -0,0 +1,148 @@
+//! [FloatingRow] as one unit: Flip and Shift move the whole row, side placements stack
+//! the participants, and [FloatingRow::relayout] picks up new sizes.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::{Cell, RefCell};
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    FloatingOptions, FloatingResult, FloatingRow, Offset, Placement, use_floating_row,
+    use_floating_row_item,
+};
+
+thread_local! {
+    static SETUP: Cell<(PixelsRect, Placement)> =
+        Cell::new((rect(0.0, 0.0, 0.0, 0.0), Placement::BottomStart));
+    static GROUPS: RefCell<Vec<MockElement>> = const { RefCell::new(Vec::new()) };
+    static ROW: Cell<Option<FloatingRow>> = const { Cell::new(None) };
+    static RESULTS: RefCell<Vec<FloatingResult>> = const { RefCell::new(Vec::new()) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+    let (trigger, placement) = SETUP.with(Cell::get);
+    let trigger_ref = use_signal(|| Some(MockElement::new(trigger).mounted()));
+    let options = FloatingOptions {
+        placement,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    };
+    let row = use_floating_row(trigger_ref, 4.0, options);
+    ROW.with(|cell| cell.set(Some(row)));
+    let count = GROUPS.with(|groups| groups.borrow().len());
+
+    rsx! {
+        for index in 0..count {
+            Group { key: "{index}", index }
+        }
+    }
+}
+
+#[component]
+fn Group(index: usize) -> Element {
+    let element_ref = use_signal(|| Some(GROUPS.with(|groups| groups.borrow()[index].mounted())));
+    let slice = use_floating_row_item(index, element_ref);
+    RESULTS.with(|results| {
+        let mut results = results.borrow_mut();
+        results.resize(index + 1, FloatingResult::default());
+        results[index] = slice();
+    });
+    rsx! {}
+}
+
+/// Returns a settled dom with a row of groups of `sizes` at `placement` of `trigger`.
+fn dom(trigger: PixelsRect, placement: Placement, sizes: &[(f64, f64)]) -> VirtualDom {
+    SETUP.with(|cell| cell.set((trigger, placement)));
+    GROUPS.with(|groups| {
+        *groups.borrow_mut() = sizes
+            .iter()
+            .map(|&(w, h)| MockElement::new(rect(0.0, 0.0, w, h)))
+            .collect()
+    });
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    dom
+}
+
+fn positions() -> Vec<(f64, f64)> {
+    RESULTS.with(|results| {
+        results
+            .borrow()
+            .iter()
+            .map(|result| {
+                assert!(result.is_ready);
+                (result.x, result.y)
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn the_row_flips_as_a_unit() {
+    // no room above for the 30px tall row
+    let _dom = dom(
+        rect(100.0, 10.0, 100.0, 20.0),
+        Placement::TopStart,
+        &[(60.0, 30.0), (40.0, 24.0)],
+    );
+
+    // below, aligned to the top edge of the row
+    assert_eq!(positions(), vec![(100.0, 30.0), (164.0, 30.0)]);
+    let row = ROW.with(Cell::get).unwrap();
+    assert_eq!(row.result.peek().placement, Placement::BottomStart);
+}
+
+#[test]
+fn the_row_shifts_as_a_unit() {
+    let _dom = dom(
+        rect(400.0, 100.0, 100.0, 20.0),
+        Placement::BottomStart,
+        &[(60.0, 30.0), (80.0, 30.0), (40.0, 30.0)],
+    );
+
+    // the 188px row ends at the boundary edge
+    assert_eq!(
+        positions(),
+        vec![(312.0, 120.0), (376.0, 120.0), (460.0, 120.0)]
+    );
+}
+
+#[test]
+fn side_placements_stack_the_participants() {
+    let _dom = dom(
+        rect(100.0, 100.0, 80.0, 20.0),
+        Placement::LeftStart,
+        &[(60.0, 30.0), (40.0, 24.0)],
+    );
+
+    // a 60x58 column left of the trigger, aligned to its right edge
+    assert_eq!(positions(), vec![(40.0, 100.0), (60.0, 134.0)]);
+}
+
+#[test]
+fn relayout_measures_the_participants_again() {
+    let mut dom = dom(
+        rect(100.0, 100.0, 80.0, 20.0),
+        Placement::BottomStart,
+        &[(60.0, 30.0), (40.0, 30.0)],
+    );
+    assert_eq!(positions(), vec![(100.0, 120.0), (164.0, 120.0)]);
+
+    GROUPS.with(|groups| groups.borrow()[0].set_rect(rect(0.0, 0.0, 100.0, 30.0)));
+    let row = ROW.with(Cell::get).unwrap();
+    dom.in_runtime(|| row.relayout());
+    settle(&mut dom);
+
+    assert_eq!(positions(), vec![(100.0, 120.0), (204.0, 120.0)]);
+}
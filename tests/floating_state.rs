@@ -0,0 +1,314 @@
+//! The open/close state machine: the transition table of [Phase::next] for every phase
+//! and event, and [FloatingState] requests that arrive while a delay is pending.
+
+#![cfg(feature = "interactions")]
+
+use std::cell::Cell;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockDocument, MockScrollableConfig, MockWait, provide_mock_document, provide_mock_scrollable,
+    settle,
+};
+use dioxus_floating::{
+    CloseReason, FloatingDelays, FloatingState, FloatingWidget, OpenReason, Phase, PhaseEvent,
+    WidgetConfig, use_floating_state, use_floating_widget,
+};
+
+use Phase::{ClosePending, Closed, Closing, Open, OpenPending};
+use PhaseEvent::{Force, RequestClose, RequestOpen, TimerElapsed};
+
+const PHASES: [Phase; 5] = [Closed, OpenPending, Open, ClosePending, Closing];
+const EVENTS: [PhaseEvent; 5] = [
+    RequestOpen,
+    RequestClose,
+    Force(true),
+    Force(false),
+    TimerElapsed,
+];
+
+const DELAYS: FloatingDelays = FloatingDelays {
+    open_ms: 100,
+    close_ms: 200,
+    closing_ms: 300,
+};
+
+/// Asserts `table[phase][event]` (in the order of [PHASES] and [EVENTS]) for every pair.
+fn assert_table(delays: FloatingDelays, table: [[Phase; 5]; 5]) {
+    for (phase, row) in PHASES.into_iter().zip(table) {
+        for (event, expected) in EVENTS.into_iter().zip(row) {
+            assert_eq!(
+                phase.next(event, delays),
+                expected,
+                "{phase:?} + {event:?} with {delays:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn transition_table_with_delays() {
+    assert_table(
+        DELAYS,
+        [
+            // RequestOpen, RequestClose, Force(true), Force(false), TimerElapsed
+            [OpenPending, Closed, Open, Closed, Closed],
+            [OpenPending, Closed, Open, Closed, Open],
+            [Open, ClosePending, Open, Closed, Open],
+            [Open, ClosePending, Open, Closed, Closing],
+            [Open, Closing, Open, Closed, Closed],
+        ],
+    );
+}
+
+#[test]
+fn transition_table_without_delays() {
+    assert_table(
+        FloatingDelays::default(),
+        [
+            // RequestOpen, RequestClose, Force(true), Force(false), TimerElapsed
+            [Open, Closed, Open, Closed, Closed],
+            [OpenPending, Closed, Open, Closed, Open],
+            [Open, Closed, Open, Closed, Open],
+            [Open, ClosePending, Open, Closed, Closed],
+            [Open, Closing, Open, Closed, Closed],
+        ],
+    );
+}
+
+#[test]
+fn zero_delays_skip_only_their_phase() {
+    let no_close_delay = FloatingDelays {
+        close_ms: 0,
+        ..DELAYS
+    };
+    assert_eq!(Open.next(RequestClose, no_close_delay), Closing);
+
+    let no_closing = FloatingDelays {
+        closing_ms: 0,
+        ..DELAYS
+    };
+    assert_eq!(Open.next(RequestClose, no_closing), ClosePending);
+    assert_eq!(ClosePending.next(TimerElapsed, no_closing), Closed);
+
+    let no_open_delay = FloatingDelays {
+        open_ms: 0,
+        ..DELAYS
+    };
+    assert_eq!(Closed.next(RequestOpen, no_open_delay), Open);
+}
+
+#[test]
+fn rendered_and_opening_phases() {
+    let rendered: Vec<_> = PHASES
+        .into_iter()
+        .filter(|phase| phase.is_rendered())
+        .collect();
+    assert_eq!(rendered, vec![Open, ClosePending, Closing]);
+    let opening: Vec<_> = PHASES
+        .into_iter()
+        .filter(|phase| phase.is_opening())
+        .collect();
+    assert_eq!(opening, vec![OpenPending, Open]);
+}
+
+thread_local! {
+    static STATE: Cell<Option<FloatingState>> = const { Cell::new(None) };
+    static DOCUMENT: Cell<Option<MockDocument>> = const { Cell::new(None) };
+}
+
+fn state_app() -> Element {
+    use_hook(|| DOCUMENT.with(|cell| cell.set(Some(provide_mock_document()))));
+    let state = use_floating_state(DELAYS);
+    STATE.with(|cell| cell.set(Some(state)));
+    rsx! {}
+}
+
+/// Returns a dom with a [FloatingState] with [DELAYS], the state and the mock document
+/// that resolves its timers.
+fn state_dom() -> (VirtualDom, FloatingState, MockDocument) {
+    let mut dom = VirtualDom::new(state_app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    (
+        dom,
+        STATE.with(Cell::get).unwrap(),
+        DOCUMENT.with(Cell::take).unwrap(),
+    )
+}
+
+fn phase(dom: &VirtualDom, state: FloatingState) -> Phase {
+    dom.in_runtime(|| (state.phase)())
+}
+
+#[test]
+fn timers_move_through_every_phase() {
+    let (mut dom, mut state, document) = state_dom();
+
+    dom.in_runtime(|| state.request_open(Some(OpenReason::Keyboard)));
+    assert_eq!(phase(&dom, state), OpenPending);
+    settle(&mut dom);
+    assert_eq!(phase(&dom, state), Open);
+
+    dom.in_runtime(|| state.request_close(Some(CloseReason::Escape)));
+    assert_eq!(phase(&dom, state), ClosePending);
+    settle(&mut dom);
+    assert_eq!(phase(&dom, state), Closed);
+
+    assert_eq!(
+        document.waits(),
+        vec![
+            MockWait::Timeout(100),
+            MockWait::Timeout(200),
+            MockWait::Timeout(300)
+        ]
+    );
+    let reasons = dom.in_runtime(|| (state.open_reason(), state.close_reason()));
+    assert_eq!(
+        reasons,
+        (Some(OpenReason::Keyboard), Some(CloseReason::Escape))
+    );
+}
+
+#[test]
+fn close_during_the_open_delay_cancels_it() {
+    let (mut dom, mut state, document) = state_dom();
+
+    dom.in_runtime(|| state.request_open(Some(OpenReason::Pointer)));
+    dom.in_runtime(|| state.request_close(Some(CloseReason::Pointer)));
+    assert_eq!(phase(&dom, state), Closed);
+    settle(&mut dom);
+
+    // the cancelled delay never elapsed
+    assert_eq!(phase(&dom, state), Closed);
+    assert_eq!(document.waits(), vec![]);
+}
+
+#[test]
+fn open_during_the_close_delay_keeps_it_open() {
+    let (mut dom, mut state, document) = state_dom();
+
+    dom.in_runtime(|| state.force(true));
+    dom.in_runtime(|| state.request_close(None));
+    assert_eq!(phase(&dom, state), ClosePending);
+    dom.in_runtime(|| state.request_open(None));
+    settle(&mut dom);
+
+    assert_eq!(phase(&dom, state), Open);
+    assert_eq!(document.waits(), vec![]);
+}
+
+#[test]
+fn reopen_during_the_closing_transition() {
+    let (mut dom, mut state, document) = state_dom();
+
+    dom.in_runtime(|| {
+        state.set_delays(FloatingDelays {
+            close_ms: 0,
+            ..DELAYS
+        })
+    });
+    dom.in_runtime(|| state.force(true));
+    dom.in_runtime(|| state.request_close(None));
+    assert_eq!(phase(&dom, state), Closing);
+    // back to open without the open delay
+    dom.in_runtime(|| state.request_open(None));
+    assert_eq!(phase(&dom, state), Open);
+    settle(&mut dom);
+
+    assert_eq!(phase(&dom, state), Open);
+    assert_eq!(document.waits(), vec![]);
+}
+
+#[test]
+fn repeated_requests_keep_the_running_timer() {
+    let (mut dom, mut state, document) = state_dom();
+
+    for _ in 0..3 {
+        dom.in_runtime(|| state.request_open(Some(OpenReason::Pointer)));
+    }
+    // programmatic requests too
+    dom.in_runtime(|| state.request_open(None));
+    settle(&mut dom);
+
+    assert_eq!(phase(&dom, state), Open);
+    assert_eq!(document.waits(), vec![MockWait::Timeout(100)]);
+    // the reason of the request that opened it
+    assert_eq!(
+        dom.in_runtime(|| state.open_reason()),
+        Some(OpenReason::Pointer)
+    );
+}
+
+#[test]
+fn force_cancels_a_pending_delay() {
+    let (mut dom, mut state, document) = state_dom();
+
+    dom.in_runtime(|| state.request_open(None));
+    dom.in_runtime(|| state.force(false));
+    settle(&mut dom);
+    assert_eq!(phase(&dom, state), Closed);
+
+    dom.in_runtime(|| state.request_open(None));
+    dom.in_runtime(|| state.force(true));
+    dom.in_runtime(|| state.request_close(None));
+    dom.in_runtime(|| state.force(true));
+    settle(&mut dom);
+    assert_eq!(phase(&dom, state), Open);
+
+    assert_eq!(document.waits(), vec![]);
+}
+
+thread_local! {
+    static WIDGET: Cell<Option<FloatingWidget>> = const { Cell::new(None) };
+}
+
+fn widget_app() -> Element {
+    use_hook(|| {
+        DOCUMENT.with(|cell| cell.set(Some(provide_mock_document())));
+        let boundary = PixelsRect::new(
+            PixelsVector2D::new(0.0, 0.0).to_point(),
+            PixelsSize::new(500.0, 500.0),
+        );
+        provide_mock_scrollable(MockScrollableConfig::new(boundary));
+    });
+    let widget = use_floating_widget(WidgetConfig {
+        delays: FloatingDelays {
+            closing_ms: 300,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    WIDGET.with(|cell| cell.set(Some(widget)));
+    rsx! {}
+}
+
+#[test]
+fn widget_is_open_follows_the_state() {
+    let mut dom = VirtualDom::new(widget_app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    let mut widget = WIDGET.with(Cell::get).unwrap();
+    let is_open = move |dom: &VirtualDom| dom.in_runtime(|| *widget.is_open.peek());
+
+    dom.in_runtime(|| widget.open());
+    assert!(is_open(&dom));
+
+    // still rendered for the closing transition
+    dom.in_runtime(|| widget.close());
+    assert_eq!(phase(&dom, widget.state), Closing);
+    assert!(is_open(&dom));
+    settle(&mut dom);
+    assert!(!is_open(&dom));
+
+    // writing is_open skips the delays
+    let mut is_open_signal = widget.is_open;
+    dom.in_runtime(|| is_open_signal.set(true));
+    settle(&mut dom);
+    assert_eq!(phase(&dom, widget.state), Open);
+    dom.in_runtime(|| is_open_signal.set(false));
+    settle(&mut dom);
+    assert_eq!(phase(&dom, widget.state), Closed);
+}
@@ -0,0 +1,171 @@
+//! With [dioxus_floating::FloatingBuilder::frame_budget], the measurements beyond the
+//! budget of a frame spill into the next frames, one frame after another, until every
+//! placement is measured; without a budget they all run in the same frame.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::{Cell, RefCell};
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockDocument, MockElement, MockScrollableConfig, MockWait, provide_mock_document,
+    provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    Floating, FloatingOptions, FloatingResult, Offset, ScrollableContext, WaitStrategy,
+    use_floating, use_floating_with, use_placement,
+};
+
+const BADGES: usize = 4;
+
+thread_local! {
+    static BUDGET: Cell<Option<f64>> = const { Cell::new(None) };
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static FLOATING: Cell<Option<Floating>> = const { Cell::new(None) };
+    static TRIGGERS: RefCell<Vec<MockElement>> = const { RefCell::new(Vec::new()) };
+    static RESULTS: RefCell<Vec<FloatingResult>> = const { RefCell::new(Vec::new()) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    use_hook(|| {
+        let context =
+            provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 1000.0)));
+        CONTEXT.with(|cell| cell.set(Some(context)));
+        TRIGGERS.with(|cell| {
+            *cell.borrow_mut() = (0..BADGES)
+                .map(|index| MockElement::new(trigger_rect(index, 0.0)))
+                .collect()
+        });
+        RESULTS.with(|cell| *cell.borrow_mut() = vec![FloatingResult::default(); BADGES]);
+    });
+    rsx! { Dashboard {} }
+}
+
+/// The badges, sharing the frame budget of the test.
+#[component]
+fn Dashboard() -> Element {
+    let builder = Floating::builder().wait(WaitStrategy::None);
+    let builder = match BUDGET.with(Cell::get) {
+        Some(budget_ms) => builder.frame_budget(budget_ms),
+        None => builder,
+    };
+    use_floating_with(builder.build());
+    rsx! {
+        for index in 0..BADGES {
+            Badge { key: "{index}", index }
+        }
+    }
+}
+
+#[component]
+fn Badge(index: usize) -> Element {
+    FLOATING.with(|cell| cell.set(Some(use_floating())));
+    let trigger = TRIGGERS.with(|cell| cell.borrow()[index].clone());
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 60.0, 20.0)).mounted()));
+    let trigger_ref = use_signal(|| Some(trigger.mounted()));
+    let options = FloatingOptions {
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULTS.with(|cell| cell.borrow_mut()[index] = placement());
+    rsx! {}
+}
+
+fn trigger_rect(index: usize, x: f64) -> dioxus_floating::geometry::PixelsRect {
+    rect(100.0 + x, 100.0 * index as f64, 40.0, 20.0)
+}
+
+/// Starts the dashboard with a budget of `budget_ms` per frame.
+fn start(budget_ms: Option<f64>) -> (VirtualDom, MockDocument) {
+    BUDGET.with(|cell| cell.set(budget_ms));
+    let mut dom = VirtualDom::new(app);
+    // the frames of the budget are requested by the root scope, like the renderer's
+    let document = dom.in_scope(ScopeId::ROOT, provide_mock_document);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    (dom, document)
+}
+
+fn frames(document: &MockDocument) -> usize {
+    document
+        .waits()
+        .iter()
+        .filter(|wait| **wait == MockWait::Frame)
+        .count()
+}
+
+fn positions() -> Vec<f64> {
+    RESULTS.with(|cell| cell.borrow().iter().map(|result| result.x).collect())
+}
+
+/// Moves every trigger right by `x` and invalidates the placements of all badges.
+fn invalidate(dom: &mut VirtualDom, x: f64) {
+    TRIGGERS.with(|cell| {
+        for (index, trigger) in cell.borrow().iter().enumerate() {
+            trigger.set_rect(trigger_rect(index, x));
+        }
+    });
+    let mut generation = CONTEXT.with(Cell::get).unwrap().layout_generation;
+    dom.in_runtime(|| *generation.write() += 1);
+}
+
+/// Moves every trigger right by `x` and measures all badges again.
+fn move_triggers(dom: &mut VirtualDom, x: f64) {
+    invalidate(dom, x);
+    settle(dom);
+}
+
+#[test]
+fn without_a_budget_everything_runs_in_one_frame() {
+    let (mut dom, document) = start(None);
+    assert_eq!(positions(), vec![100.0; BADGES]);
+
+    document.clear();
+    move_triggers(&mut dom, 30.0);
+    assert_eq!(positions(), vec![130.0; BADGES]);
+    assert_eq!(frames(&document), 0);
+}
+
+#[test]
+fn work_over_the_budget_spills_into_the_next_frames() {
+    // a spent budget: one measurement per frame
+    let (mut dom, document) = start(Some(0.0));
+    assert_eq!(positions(), vec![100.0; BADGES]);
+
+    document.clear();
+    move_triggers(&mut dom, 30.0);
+    assert_eq!(positions(), vec![130.0; BADGES]);
+    // a measurement per frame: each badge waits for a frame of its own, except the first
+    // one if the frame of the last measurement is over already
+    let frames = frames(&document);
+    assert!((BADGES - 1..=BADGES).contains(&frames), "{frames} frames");
+
+    let floating = FLOATING.with(Cell::get).unwrap();
+    let stats = dom.in_runtime(|| floating.frame_stats());
+    assert_eq!(stats.processed, 1);
+}
+
+#[test]
+fn deferred_placements_keep_their_position() {
+    let (mut dom, _document) = start(Some(0.0));
+    invalidate(&mut dom, 30.0);
+
+    // frame by frame: each badge is either at the old or at the new position
+    for _ in 0..32 {
+        dom.process_events();
+        dom.render_immediate_to_vec();
+        let results = RESULTS.with(|cell| cell.borrow().clone());
+        for result in results {
+            assert!(result.is_ready);
+            assert!([100.0, 130.0].contains(&result.x), "{}", result.x);
+        }
+    }
+    assert_eq!(positions(), vec![130.0; BADGES]);
+}
@@ -0,0 +1,176 @@
+//! [Middleware::Hide] reports a trigger that left the boundary: hidden once no part of
+//! it is inside, clipped while only a part is.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{Floating, FloatingOptions, Middleware, Offset, Placement, PlacementOutput};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn options() -> FloatingOptions {
+    FloatingOptions {
+        middleware: vec![Middleware::Flip, Middleware::Shift, Middleware::Hide],
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    }
+}
+
+/// Places a 100x50 element in a 500x500 boundary.
+fn place(trigger: PixelsRect, options: FloatingOptions) -> PlacementOutput {
+    Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        trigger,
+        options,
+    )
+}
+
+fn visibility(output: &PlacementOutput) -> (bool, bool) {
+    (output.is_hidden, output.is_trigger_clipped)
+}
+
+#[test]
+fn inside_the_boundary() {
+    let output = place(rect(100.0, 100.0, 80.0, 20.0), options());
+    assert_eq!(visibility(&output), (false, false));
+
+    // touching the edge is inside
+    let output = place(rect(0.0, 0.0, 80.0, 20.0), options());
+    assert_eq!(visibility(&output), (false, false));
+}
+
+#[test]
+fn partially_clipped_by_any_edge() {
+    for trigger in [
+        rect(100.0, -10.0, 80.0, 20.0),
+        rect(100.0, 490.0, 80.0, 20.0),
+        rect(-40.0, 100.0, 80.0, 20.0),
+        rect(460.0, 100.0, 80.0, 20.0),
+    ] {
+        let output = place(trigger, options());
+        assert_eq!(visibility(&output), (false, true), "{trigger:?}");
+    }
+}
+
+#[test]
+fn scrolled_out() {
+    let output = place(rect(100.0, -40.0, 80.0, 20.0), options());
+    assert_eq!(visibility(&output), (true, false));
+    // the position is still computed against the full trigger
+    assert_eq!(output.trigger_rect, rect(100.0, -40.0, 80.0, 20.0));
+    assert_eq!((output.x, output.y), (100.0, -20.0));
+}
+
+#[test]
+fn only_with_the_middleware() {
+    let options = FloatingOptions {
+        middleware: vec![Middleware::Flip, Middleware::Shift],
+        ..options()
+    };
+
+    let output = place(rect(100.0, -40.0, 80.0, 20.0), options.clone());
+    assert_eq!(visibility(&output), (false, false));
+    let output = place(rect(100.0, -10.0, 80.0, 20.0), options);
+    assert_eq!(visibility(&output), (false, false));
+}
+
+#[test]
+fn unbounded_elements_are_never_hidden() {
+    let options = FloatingOptions {
+        unbounded: true,
+        ..options()
+    };
+
+    let output = place(rect(620.0, 100.0, 20.0, 20.0), options);
+    assert_eq!(visibility(&output), (false, false));
+}
+
+#[test]
+fn with_anchor_to_visible() {
+    let options = FloatingOptions {
+        anchor_to_visible: true,
+        ..options()
+    };
+
+    // anchored to the visible part, still reported as clipped
+    let output = place(rect(-40.0, 100.0, 80.0, 20.0), options.clone());
+    assert_eq!(visibility(&output), (false, true));
+    assert_eq!(output.x, 0.0);
+
+    let output = place(rect(-140.0, 100.0, 80.0, 20.0), options);
+    assert_eq!(visibility(&output), (true, false));
+}
+
+#[cfg(feature = "hooks")]
+mod hooks {
+    use std::cell::{Cell, RefCell};
+
+    use dioxus::html::geometry::{PixelsSize, PixelsVector2D};
+    use dioxus::prelude::*;
+    use dioxus_floating::testing::{
+        MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+    };
+    use dioxus_floating::{FloatingResult, ScrollableContext, use_placement};
+
+    use super::{options, rect};
+
+    thread_local! {
+        static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+        static TRIGGER: RefCell<Option<MockElement>> = const { RefCell::new(None) };
+        static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+    }
+
+    fn app() -> Element {
+        use_hook(|| {
+            let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0));
+            config.scroll_state.size = PixelsSize::new(500.0, 2000.0);
+            CONTEXT.with(|cell| cell.set(Some(provide_mock_scrollable(config))));
+        });
+        let trigger = use_hook(|| MockElement::new(rect(100.0, 100.0, 80.0, 20.0)));
+        TRIGGER.with(|cell| *cell.borrow_mut() = Some(trigger.clone()));
+        let element_ref =
+            use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 100.0, 50.0)).mounted()));
+        let trigger_ref = use_signal(|| Some(trigger.mounted()));
+        let placement = use_placement(element_ref, trigger_ref, options());
+        RESULT.with(|cell| cell.set(placement()));
+        rsx! {}
+    }
+
+    /// Scrolls the view to `y`, the trigger moving with the content.
+    fn scroll_to(dom: &mut VirtualDom, y: f64) -> FloatingResult {
+        TRIGGER.with(|cell| {
+            let trigger = cell.borrow();
+            trigger
+                .as_ref()
+                .unwrap()
+                .set_rect(rect(100.0, 100.0 - y, 80.0, 20.0));
+        });
+        let mut context = CONTEXT.with(Cell::get).unwrap();
+        dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, y)));
+        settle(dom);
+        RESULT.with(Cell::get)
+    }
+
+    #[test]
+    fn follows_the_scroll() {
+        let mut dom = VirtualDom::new(app);
+        dom.rebuild_in_place();
+        settle(&mut dom);
+        let result = RESULT.with(Cell::get);
+        assert!(result.is_ready && !result.is_hidden && !result.is_trigger_clipped);
+
+        let result = scroll_to(&mut dom, 110.0);
+        assert!(result.is_trigger_clipped && !result.is_hidden);
+
+        let result = scroll_to(&mut dom, 300.0);
+        assert!(result.is_hidden && !result.is_trigger_clipped);
+        assert_eq!(result.y, -180.0);
+
+        // back with a fresh position
+        let result = scroll_to(&mut dom, 50.0);
+        assert!(!result.is_hidden && !result.is_trigger_clipped);
+        assert_eq!((result.x, result.y), (100.0, 70.0));
+    }
+}
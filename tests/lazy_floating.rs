@@ -0,0 +1,185 @@
+//! [LazyFloating] mounts its children once the trigger is near the view or open, and
+//! unmounts them after an idle period closed and off-screen.
+
+#![cfg(feature = "components")]
+
+use std::cell::{Cell, RefCell};
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockDocument, MockElement, MockScrollableConfig, MockWait, provide_mock_document,
+    provide_mock_scrollable, settle,
+};
+use dioxus_floating::{FloatingOptions, LazyFloating, ScrollableContext, use_placement};
+
+thread_local! {
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static DOCUMENT: RefCell<Option<MockDocument>> = const { RefCell::new(None) };
+    static OPEN: Cell<Option<Signal<Option<usize>>>> = const { Cell::new(None) };
+    // the trigger of the last row, moved by a layout change
+    static LAST_TRIGGER: RefCell<Option<MockElement>> = const { RefCell::new(None) };
+    static MOUNTED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+    static DROPPED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+    static READY: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+/// A 400x500 view 50px below the top of the page, with 5000px of content and rows at
+/// 10px, 1000px and 3000px into it.
+fn app() -> Element {
+    use_hook(|| {
+        DOCUMENT.with(|cell| *cell.borrow_mut() = Some(provide_mock_document()));
+        let mut config = MockScrollableConfig::new(rect(0.0, 50.0, 400.0, 500.0));
+        config.scroll_state.size = PixelsSize::new(400.0, 5000.0);
+        let context = provide_mock_scrollable(config);
+        CONTEXT.with(|cell| cell.set(Some(context)));
+    });
+    let open = use_signal(|| Option::<usize>::None);
+    OPEN.with(|cell| cell.set(Some(open)));
+
+    rsx! {
+        for (index, y) in [10.0, 1000.0, 3000.0].into_iter().enumerate() {
+            Row { key: "{index}", index, y, open: open() == Some(index) }
+        }
+    }
+}
+
+#[component]
+fn Row(index: usize, y: f64, open: bool) -> Element {
+    let trigger = use_hook(|| MockElement::new(rect(20.0, 50.0 + y, 100.0, 20.0)));
+    if index == 2 {
+        LAST_TRIGGER.with(|cell| *cell.borrow_mut() = Some(trigger.clone()));
+    }
+    let trigger_ref = use_signal(|| Some(trigger.mounted()));
+
+    rsx! {
+        LazyFloating { trigger: trigger_ref, open,
+            Info { index, trigger_ref }
+        }
+    }
+}
+
+/// The popover of a row, recording its lifecycle.
+#[component]
+fn Info(index: usize, trigger_ref: Signal<Option<std::rc::Rc<MountedData>>>) -> Element {
+    use_hook(|| MOUNTED.with(|mounted| mounted.borrow_mut().push(index)));
+    use_drop(move || DROPPED.with(|dropped| dropped.borrow_mut().push(index)));
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    let placement = use_placement(element_ref, trigger_ref, FloatingOptions::default());
+    if placement().is_ready {
+        READY.with(|ready| {
+            if !ready.borrow().contains(&index) {
+                ready.borrow_mut().push(index);
+            }
+        });
+    }
+    rsx! {}
+}
+
+fn start() -> VirtualDom {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    dom
+}
+
+fn scroll_to(dom: &mut VirtualDom, y: f64) {
+    let mut context = CONTEXT.with(Cell::get).unwrap();
+    dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, y)));
+    settle(dom);
+}
+
+fn set_open(dom: &mut VirtualDom, index: Option<usize>) {
+    let mut open = OPEN.with(Cell::get).unwrap();
+    dom.in_runtime(|| open.set(index));
+    settle(dom);
+}
+
+fn take(list: &'static std::thread::LocalKey<RefCell<Vec<usize>>>) -> Vec<usize> {
+    list.with(|list| list.take())
+}
+
+fn idle_waits() -> usize {
+    DOCUMENT.with(|cell| {
+        let document = cell.borrow();
+        let waits = document.as_ref().unwrap().waits();
+        waits
+            .into_iter()
+            .filter(|wait| *wait == MockWait::Timeout(5000))
+            .count()
+    })
+}
+
+#[test]
+fn only_rows_near_the_view_mount() {
+    let _dom = start();
+
+    assert_eq!(take(&MOUNTED), [0]);
+    assert_eq!(take(&READY), [0]);
+    assert_eq!(idle_waits(), 0);
+}
+
+#[test]
+fn scrolling_mounts_and_idle_unmounts() {
+    let mut dom = start();
+    take(&MOUNTED);
+
+    // the second row is within 200px of the view, the first one off-screen
+    scroll_to(&mut dom, 320.0);
+    assert_eq!(take(&MOUNTED), [1]);
+    assert_eq!(take(&READY), [0, 1]);
+    // the mock timers elapse at once
+    assert_eq!(idle_waits(), 1);
+    assert_eq!(take(&DROPPED), [0]);
+
+    // back at the top
+    scroll_to(&mut dom, 0.0);
+    assert_eq!(take(&MOUNTED), [0]);
+    assert_eq!(take(&DROPPED), [1]);
+}
+
+#[test]
+fn open_rows_mount_anywhere() {
+    let mut dom = start();
+    take(&MOUNTED);
+
+    set_open(&mut dom, Some(2));
+    assert_eq!(take(&MOUNTED), [2]);
+    assert!(take(&READY).contains(&2));
+    // kept while open, even off-screen
+    assert_eq!(idle_waits(), 0);
+
+    set_open(&mut dom, None);
+    assert_eq!(idle_waits(), 1);
+    assert_eq!(take(&DROPPED), [2]);
+
+    // unmounting the rows drops the children with them
+    drop(dom);
+    assert_eq!(take(&DROPPED), [0]);
+}
+
+#[test]
+fn a_layout_change_measures_the_trigger_again() {
+    let mut dom = start();
+    take(&MOUNTED);
+
+    // moved to the top without scrolling, e.g., rows removed above it
+    LAST_TRIGGER.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .unwrap()
+            .set_rect(rect(20.0, 300.0, 100.0, 20.0))
+    });
+    scroll_to(&mut dom, 0.0);
+    assert!(take(&MOUNTED).is_empty());
+
+    let context = CONTEXT.with(Cell::get).unwrap();
+    let mut generation = context.layout_generation;
+    dom.in_runtime(|| generation += 1);
+    settle(&mut dom);
+    assert_eq!(take(&MOUNTED), [2]);
+}
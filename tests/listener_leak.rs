@@ -0,0 +1,118 @@
+//! Every JS listener installed by the hooks is removed again: once the popovers that
+//! started them are unmounted, the count of running listeners returns to 0, also after
+//! many mount cycles.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::{Cell, RefCell};
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockDocument, MockElement, MockScrollableConfig, provide_mock_document,
+    provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    AnnounceOptions, AutoUpdate, FloatingOptions, use_page_suspended, use_placement,
+    use_reduced_motion, use_visual_viewport,
+};
+
+thread_local! {
+    static DOCUMENT: RefCell<Option<MockDocument>> = const { RefCell::new(None) };
+    static OPEN: Cell<Option<Signal<usize>>> = const { Cell::new(None) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    use_hook(|| {
+        DOCUMENT.with(|cell| *cell.borrow_mut() = Some(provide_mock_document()));
+        provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)));
+    });
+    let open = use_signal(|| 0);
+    OPEN.with(|cell| cell.set(Some(open)));
+    rsx! {
+        for index in 0..open() {
+            Popover { key: "{index}" }
+        }
+    }
+}
+
+/// A popover with every listener source of [AutoUpdate], an announced placement and the
+/// shared listeners of the window.
+///
+/// The resize sampling reads the rects every frame without a JS listener, and the mock
+/// frames resolve immediately, so it stays off.
+#[component]
+fn Popover() -> Element {
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    let trigger_ref =
+        use_signal(|| Some(MockElement::new(rect(100.0, 100.0, 80.0, 20.0)).mounted()));
+    let options = FloatingOptions {
+        auto_update: AutoUpdate {
+            element_resize: false,
+            trigger_resize: false,
+            ..AutoUpdate::all()
+        },
+        announce: Some(AnnounceOptions::default()),
+        ..Default::default()
+    };
+    use_placement(element_ref, trigger_ref, options);
+    use_page_suspended();
+    use_visual_viewport();
+    use_reduced_motion();
+    rsx! {}
+}
+
+fn start() -> VirtualDom {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    dom
+}
+
+fn set_open(dom: &mut VirtualDom, count: usize) {
+    let mut open = OPEN.with(Cell::get).unwrap();
+    dom.in_runtime(|| open.set(count));
+    settle(dom);
+}
+
+fn listeners() -> usize {
+    DOCUMENT.with(|cell| cell.borrow().as_ref().unwrap().listeners())
+}
+
+#[test]
+fn unmounting_removes_every_listener() {
+    let mut dom = start();
+    assert_eq!(listeners(), 0);
+
+    set_open(&mut dom, 3);
+    let running = listeners();
+    assert!(running > 0);
+
+    set_open(&mut dom, 0);
+    assert_eq!(listeners(), 0);
+}
+
+#[test]
+fn mount_cycles_do_not_accumulate_listeners() {
+    let mut dom = start();
+    set_open(&mut dom, 1);
+    let one = listeners();
+    set_open(&mut dom, 3);
+    let three = listeners();
+    // the shared listeners run once, the others once per popover
+    assert!(three > one);
+
+    for _ in 0..5 {
+        set_open(&mut dom, 0);
+        assert_eq!(listeners(), 0);
+        set_open(&mut dom, 3);
+        assert_eq!(listeners(), three);
+        set_open(&mut dom, 1);
+        assert_eq!(listeners(), one);
+    }
+}
@@ -0,0 +1,130 @@
+//! Options changed after the placement hooks were created apply live, without a remount
+//! or a new measurement, and equal options recompute nothing.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    FloatingOptions, FloatingResult, Offset, Placement, ScrollableContext, UpdateReason,
+    use_placement,
+};
+
+thread_local! {
+    static TRIGGER: MockElement = MockElement::new(rect(100.0, 100.0, 80.0, 20.0));
+    static HANDLES: Cell<Option<Handles>> = const { Cell::new(None) };
+    static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+}
+
+#[derive(Clone, Copy)]
+struct Handles {
+    context: ScrollableContext,
+    compact: Signal<bool>,
+    renders: Signal<u32>,
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    let context = use_hook(|| {
+        let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0));
+        config.scroll_state.size = PixelsSize::new(500.0, 2000.0);
+        provide_mock_scrollable(config)
+    });
+    // a global setting, e.g., a "compact mode" toggle
+    let compact = use_signal(|| false);
+    let renders = use_signal(|| 0_u32);
+    HANDLES.with(|cell| {
+        cell.set(Some(Handles {
+            context,
+            compact,
+            renders,
+        }))
+    });
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    let _ = renders();
+    let options = FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(if compact() {
+            Offset::zero()
+        } else {
+            Offset::new(8.0, 8.0)
+        }),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULT.with(|cell| cell.set(placement()));
+    rsx! {}
+}
+
+fn result() -> FloatingResult {
+    RESULT.with(Cell::get)
+}
+
+#[test]
+fn changed_options_apply_live() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    let handles = HANDLES.with(Cell::get).unwrap();
+    assert_eq!(result().y, 128.0);
+
+    let mut compact = handles.compact;
+    dom.in_runtime(|| compact.set(true));
+    settle(&mut dom);
+    assert_eq!(result().y, 120.0);
+    assert_eq!(result().update_reason, UpdateReason::Manual);
+
+    dom.in_runtime(|| compact.set(false));
+    settle(&mut dom);
+    assert_eq!(result().y, 128.0);
+}
+
+#[test]
+fn equal_options_recompute_nothing() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    let handles = HANDLES.with(Cell::get).unwrap();
+
+    let mut context = handles.context;
+    TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 60.0, 80.0, 20.0)));
+    dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, 40.0)));
+    settle(&mut dom);
+    assert_eq!(
+        (result().y, result().update_reason),
+        (88.0, UpdateReason::Scroll)
+    );
+
+    // a render with the same options keeps the result of the scroll
+    let mut renders = handles.renders;
+    dom.in_runtime(|| *renders.write() += 1);
+    settle(&mut dom);
+    assert_eq!(
+        (result().y, result().update_reason),
+        (88.0, UpdateReason::Scroll)
+    );
+}
+
+#[test]
+fn toggled_options_apply_every_time() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    let mut compact = HANDLES.with(Cell::get).unwrap().compact;
+
+    // the same two positions in turn, for the same trigger and element
+    for (is_compact, y) in [(true, 120.0), (false, 128.0), (true, 120.0), (false, 128.0)] {
+        dom.in_runtime(|| compact.set(is_compact));
+        settle(&mut dom);
+        assert_eq!(result().y, y);
+    }
+}
@@ -0,0 +1,69 @@
+//! [FloatingOptions::measure_untransformed] sizes the element by its layout size, so an
+//! entry animation that scales it in does not misalign the first Center placement.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{FloatingOptions, FloatingResult, Offset, Placement, use_placement};
+
+thread_local! {
+    static UNTRANSFORMED: Cell<bool> = const { Cell::new(false) };
+    static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+    let element_ref = use_signal(|| {
+        // a 200x100 element on the first frame of a scale(0.95) entry animation
+        let element = MockElement::new(rect(5.0, 2.5, 190.0, 95.0));
+        element.set_layout_size(Some(PixelsSize::new(200.0, 100.0)));
+        Some(element.mounted())
+    });
+    let trigger_ref =
+        use_signal(|| Some(MockElement::new(rect(100.0, 100.0, 80.0, 20.0)).mounted()));
+    let options = FloatingOptions {
+        placement: Placement::BottomCenter,
+        placement_offset: Some(Offset::zero()),
+        measure_untransformed: UNTRANSFORMED.with(Cell::get),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULT.with(|cell| cell.set(placement()));
+    rsx! {}
+}
+
+fn result(untransformed: bool) -> FloatingResult {
+    UNTRANSFORMED.with(|cell| cell.set(untransformed));
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    RESULT.with(Cell::get)
+}
+
+#[test]
+fn the_bounding_rect_includes_the_transform() {
+    let result = result(false);
+
+    assert_eq!(result.element_size, PixelsSize::new(190.0, 95.0));
+    assert_eq!((result.x, result.y), (45.0, 120.0));
+}
+
+#[test]
+fn the_layout_size_ignores_the_transform() {
+    let result = result(true);
+
+    // centered below the trigger once the animation ends
+    assert_eq!(result.element_size, PixelsSize::new(200.0, 100.0));
+    assert_eq!((result.x, result.y), (40.0, 120.0));
+}
@@ -0,0 +1,105 @@
+//! The placement hooks keep the durations of their recent measurements, see
+//! [FloatingDescriptor::timing], and report themselves as slow once 90% of them are over
+//! the [FloatingBuilder::slow_measurement_budget].
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    Floating, FloatingDescriptor, FloatingOptions, MeasurementTiming, ScrollableContext,
+    WaitStrategy, use_placement,
+};
+
+thread_local! {
+    static ELEMENT: MockElement = MockElement::new(rect(0.0, 0.0, 200.0, 100.0));
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    let context = use_hook(|| {
+        let context =
+            provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)));
+        provide_context(
+            Floating::builder()
+                .wait(WaitStrategy::None)
+                .slow_measurement_budget(5.0)
+                .build(),
+        );
+        context
+    });
+    CONTEXT.with(|cell| cell.set(Some(context)));
+    rsx! { Popover {} }
+}
+
+#[component]
+fn Popover() -> Element {
+    let element_ref = use_signal(|| Some(ELEMENT.with(MockElement::mounted)));
+    let trigger_ref =
+        use_signal(|| Some(MockElement::new(rect(100.0, 100.0, 80.0, 20.0)).mounted()));
+    let options = FloatingOptions {
+        debug_label: Some("menu"),
+        ..Default::default()
+    };
+    use_placement(element_ref, trigger_ref, options);
+    rsx! {}
+}
+
+fn timing(dom: &VirtualDom) -> MeasurementTiming {
+    let context = CONTEXT.with(Cell::get).unwrap();
+    let floatings: Vec<FloatingDescriptor> = dom.in_runtime(|| context.floatings());
+    assert_eq!(floatings.len(), 1);
+    assert_eq!(floatings[0].label, Some("menu"));
+
+    floatings[0].timing
+}
+
+/// Measures the placement again, with the element taking at least `ms` to measure.
+fn measure_slowly(dom: &mut VirtualDom, ms: u64) {
+    let mut generation = CONTEXT.with(Cell::get).unwrap().layout_generation;
+    ELEMENT.with(MockElement::hold);
+    dom.in_runtime(|| *generation.write() += 1);
+    settle(dom);
+    std::thread::sleep(Duration::from_millis(ms));
+    ELEMENT.with(MockElement::release);
+    settle(dom);
+}
+
+#[test]
+fn consistently_slow_measurements_are_reported() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    let first = timing(&dom);
+    assert_eq!(first.count, 1);
+    assert_eq!(first.budget_ms, 5.0);
+    assert!(!first.is_slow);
+
+    // a single slow one is not enough
+    measure_slowly(&mut dom, 8);
+    let timing_after_one = timing(&dom);
+    assert_eq!(timing_after_one.count, 2);
+    assert!(timing_after_one.last_ms.is_some_and(|ms| ms >= 8.0));
+    assert_eq!(timing_after_one.over_budget, 1);
+    assert!(!timing_after_one.is_slow);
+
+    for _ in 0..9 {
+        measure_slowly(&mut dom, 8);
+    }
+    let slow = timing(&dom);
+    assert_eq!(slow.count, 11);
+    assert_eq!(slow.over_budget, 10);
+    assert!(slow.p90_ms >= 8.0);
+    assert!(slow.is_slow);
+}
@@ -0,0 +1,114 @@
+//! [FloatingOptions::min_visible] against the middleware: it runs last and wins over the
+//! Shift tether and disabled middleware, and leaves positions that show enough alone.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{Floating, FloatingOptions, Offset, Placement, PlacementOutput};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+const BOUNDARY: (f64, f64, f64, f64) = (0.0, 0.0, 500.0, 500.0);
+
+fn compute(trigger: PixelsRect, element: PixelsSize, options: FloatingOptions) -> PlacementOutput {
+    let (x, y, w, h) = BOUNDARY;
+    Floating::default().compute_placement(
+        rect(x, y, w, h),
+        rect(0.0, 0.0, element.width, element.height),
+        trigger,
+        FloatingOptions {
+            placement_offset: Some(Offset::zero()),
+            ..options
+        },
+    )
+}
+
+fn bottom_start(min_visible: f64) -> FloatingOptions {
+    FloatingOptions {
+        placement: Placement::BottomStart,
+        min_visible,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn overrides_the_tether_on_both_sides() {
+    let element = PixelsSize::new(200.0, 100.0);
+
+    // the tether keeps the element beside a trigger scrolled out to the left
+    let left = rect(-300.0, 100.0, 100.0, 20.0);
+    assert_eq!(compute(left, element, bottom_start(0.0)).x, -200.0);
+    let output = compute(left, element, bottom_start(40.0));
+    assert_eq!((output.x, output.y), (-160.0, 120.0));
+    assert!(output.min_visible_engaged);
+
+    // and to the right
+    let right = rect(700.0, 100.0, 100.0, 20.0);
+    assert_eq!(compute(right, element, bottom_start(0.0)).x, 500.0);
+    let output = compute(right, element, bottom_start(40.0));
+    assert_eq!(output.x, 460.0);
+    assert!(output.min_visible_engaged);
+}
+
+#[test]
+fn applies_without_middleware() {
+    let options = FloatingOptions {
+        middleware: Vec::new(),
+        ..bottom_start(30.0)
+    };
+
+    // below the boundary: neither flipped nor shifted back
+    let output = compute(
+        rect(100.0, 600.0, 80.0, 20.0),
+        PixelsSize::new(200.0, 100.0),
+        options,
+    );
+    assert_eq!((output.x, output.y), (100.0, 470.0));
+    assert!(output.min_visible_engaged);
+}
+
+#[test]
+fn is_limited_by_the_element_and_the_boundary() {
+    // a 20px tall element is kept whole
+    let output = compute(
+        rect(100.0, 100.0, 80.0, 20.0),
+        PixelsSize::new(200.0, 20.0),
+        FloatingOptions {
+            middleware: Vec::new(),
+            ..bottom_start(50.0)
+        },
+    );
+    assert_eq!(output.y, 120.0);
+    assert!(!output.min_visible_engaged);
+
+    let output = compute(
+        rect(100.0, 700.0, 80.0, 20.0),
+        PixelsSize::new(200.0, 20.0),
+        FloatingOptions {
+            middleware: Vec::new(),
+            ..bottom_start(50.0)
+        },
+    );
+    assert_eq!(output.y, 480.0);
+
+    // more than the boundary: as much as fits
+    let output = compute(
+        rect(-900.0, 100.0, 80.0, 20.0),
+        PixelsSize::new(800.0, 100.0),
+        bottom_start(600.0),
+    );
+    assert_eq!(output.x, -300.0);
+}
+
+#[test]
+fn leaves_visible_enough_positions_alone() {
+    // partially clipped by the tether, but 100px of it are visible
+    let trigger = rect(-150.0, 100.0, 100.0, 20.0);
+    let element = PixelsSize::new(200.0, 100.0);
+
+    let without = compute(trigger, element, bottom_start(0.0));
+    let with = compute(trigger, element, bottom_start(100.0));
+    assert_eq!(with.x, -50.0);
+    assert_eq!((with.x, with.y), (without.x, without.y));
+    assert!(!with.min_visible_engaged);
+}
@@ -0,0 +1,223 @@
+//! On iOS, a [dioxus_floating::ScrollableView] samples its offset every frame during the
+//! momentum phase of a touch scroll, which may pause the scroll events, and publishes the
+//! offsets they missed until the view comes to rest. Other platforms keep to the events.
+
+#![cfg(feature = "components")]
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use dioxus::core::{ElementId, Mutation, Mutations};
+use dioxus::html::PlatformEventData;
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockDocument, MockElement, MockWait, emulate_momentum_pauses, provide_mock_document, settle,
+};
+use dioxus_floating::{ScrollState, ScrollableView, use_scroll_context};
+use dioxus_html::{
+    SerializedHtmlEventConverter, SerializedScrollData, SerializedTouchData, set_event_converter,
+};
+
+thread_local! {
+    static VIEW: MockElement = MockElement::new(rect(0.0, 0.0, 400.0, 200.0));
+    static SCROLLED: RefCell<Vec<f64>> = const { RefCell::new(Vec::new()) };
+    static PUBLISHED: Cell<Option<f64>> = const { Cell::new(None) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+/// The offsets the view glides through after the last scroll event, coming to rest.
+const GLIDE: [f64; 5] = [140.0, 180.0, 210.0, 225.0, 230.0];
+
+fn app() -> Element {
+    rsx! {
+        ScrollableView {
+            on_scroll: move |state: ScrollState| {
+                SCROLLED.with(|cell| cell.borrow_mut().push(state.state.y));
+            },
+            Content {}
+        }
+    }
+}
+
+/// Backs the view with the mock and follows the published offset.
+#[component]
+fn Content() -> Element {
+    let mut context = use_scroll_context();
+    use_hook(|| {
+        context
+            .scrollable_ref
+            .set(Some(VIEW.with(MockElement::mounted)))
+    });
+    let published = (context.scroll_state)().map(|state| state.state.y);
+    PUBLISHED.with(|cell| cell.set(published));
+    rsx! {}
+}
+
+/// The rendered view, with the element of its listeners.
+struct View {
+    dom: VirtualDom,
+    document: MockDocument,
+    listeners: HashMap<String, ElementId>,
+}
+
+/// Renders the view, on a platform that pauses momentum scroll events if `pauses`.
+fn start(pauses: bool) -> View {
+    set_event_converter(Box::new(SerializedHtmlEventConverter));
+    VIEW.with(|view| {
+        view.set_scroll_size(PixelsSize::new(400.0, 2000.0));
+        view.set_scroll_offset(PixelsVector2D::zero());
+        view.script_scroll([]);
+    });
+    let mut dom = VirtualDom::new(app);
+    let document = dom.in_scope(ScopeId::ROOT, provide_mock_document);
+    dom.in_runtime(|| emulate_momentum_pauses(pauses));
+    let mut mutations = Mutations::default();
+    dom.rebuild(&mut mutations);
+    let listeners = mutations
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Mutation::NewEventListener { name, id } => Some((name.clone(), *id)),
+            _ => None,
+        })
+        .collect();
+    settle(&mut dom);
+    SCROLLED.with(|cell| cell.borrow_mut().clear());
+    document.clear();
+
+    View {
+        dom,
+        document,
+        listeners,
+    }
+}
+
+impl View {
+    fn fire(&mut self, name: &str, data: Box<dyn Any>) {
+        let event = Event::new(Rc::new(PlatformEventData::new(data)) as Rc<dyn Any>, false);
+        self.dom
+            .runtime()
+            .handle_event(name, event, self.listeners[name]);
+        settle(&mut self.dom);
+    }
+
+    fn touch(&mut self, name: &str) {
+        let data: SerializedTouchData = serde_json::from_value(serde_json::json!({
+            "alt_key": false,
+            "ctrl_key": false,
+            "meta_key": false,
+            "shift_key": false,
+            "touches": [],
+            "changed_touches": [],
+            "target_touches": [],
+        }))
+        .unwrap();
+        self.fire(name, Box::new(data));
+    }
+
+    /// Fires the scroll event of the view at offset `y`, as the browser would.
+    fn scroll(&mut self, y: f64) {
+        VIEW.with(|element| element.set_scroll_offset(PixelsVector2D::new(0.0, y)));
+        let data = SerializedScrollData {
+            scroll_top: y,
+            scroll_left: 0.0,
+            scroll_width: 400,
+            scroll_height: 2000,
+            client_width: 400,
+            client_height: 200,
+        };
+        self.fire("scroll", Box::new(data));
+    }
+
+    /// Drags the content to `y` and lifts the finger, which leaves it gliding through
+    /// `glide` with a single scroll event at `released_at`.
+    fn fling(&mut self, y: f64, released_at: f64, glide: &[f64]) {
+        self.touch("touchstart");
+        self.scroll(y);
+        self.touch("touchend");
+        VIEW.with(|element| {
+            element.script_scroll(glide.iter().map(|&y| PixelsVector2D::new(0.0, y)))
+        });
+        self.scroll(released_at);
+    }
+
+    fn frames(&self) -> usize {
+        self.document
+            .waits()
+            .iter()
+            .filter(|wait| **wait == MockWait::Frame)
+            .count()
+    }
+}
+
+fn scrolled() -> Vec<f64> {
+    SCROLLED.with(|cell| cell.borrow().clone())
+}
+
+fn published() -> Option<f64> {
+    PUBLISHED.with(Cell::get)
+}
+
+fn view_offset() -> f64 {
+    VIEW.with(MockElement::scroll_offset).y
+}
+
+#[test]
+fn offsets_missed_by_the_events_are_published() {
+    let mut view = start(true);
+
+    view.fling(100.0, 120.0, &GLIDE);
+    assert_eq!(published(), Some(230.0));
+    // the sampled offsets are published, the callback still follows the events
+    assert_eq!(scrolled(), [100.0, 120.0]);
+}
+
+#[test]
+fn sampling_stops_at_rest() {
+    let mut view = start(true);
+
+    view.fling(100.0, 120.0, &GLIDE);
+    // a frame per offset of the glide and the stable frames that end it
+    let frames = view.frames();
+    assert!(
+        (GLIDE.len()..=GLIDE.len() + 8).contains(&frames),
+        "{frames} frames"
+    );
+
+    // at rest: no more frames, and offsets outside of events are not read anymore
+    view.document.clear();
+    VIEW.with(|element| element.script_scroll([PixelsVector2D::new(0.0, 400.0)]));
+    settle(&mut view.dom);
+    assert_eq!(view.frames(), 0);
+    assert_eq!(published(), Some(230.0));
+    assert_eq!(view_offset(), 230.0);
+}
+
+#[test]
+fn a_drag_without_release_is_not_sampled() {
+    let mut view = start(true);
+
+    view.touch("touchstart");
+    VIEW.with(|element| element.script_scroll(GLIDE.iter().map(|&y| PixelsVector2D::new(0.0, y))));
+    view.scroll(100.0);
+    assert_eq!(view.frames(), 0);
+    assert_eq!(published(), Some(100.0));
+}
+
+#[test]
+fn other_platforms_keep_to_the_events() {
+    let mut view = start(false);
+
+    view.fling(100.0, 120.0, &GLIDE);
+    assert_eq!(view.frames(), 0);
+    assert_eq!(published(), Some(120.0));
+    assert_eq!(scrolled(), [100.0, 120.0]);
+    // the glide was never read
+    assert_eq!(view_offset(), 120.0);
+}
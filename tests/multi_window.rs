@@ -0,0 +1,119 @@
+//! Several windows on one thread (e.g., `dioxus-desktop`): each `VirtualDom` places its
+//! popovers in its own viewport, and a measurement in one window never holds back the
+//! results of another.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    FloatingOptions, FloatingResult, Offset, Placement, ScrollableContext, use_placement,
+};
+
+thread_local! {
+    static ELEMENT_A: MockElement = MockElement::new(rect(0.0, 0.0, 200.0, 100.0));
+    static ELEMENT_B: MockElement = MockElement::new(rect(0.0, 0.0, 200.0, 100.0));
+    static CONTEXT_A: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static CONTEXT_B: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static RESULT_A: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+    static RESULT_B: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+/// A popover below a trigger at (250, 100), next to the right edge of narrow viewports.
+fn popover(viewport: PixelsRect, element: MockElement) -> (ScrollableContext, FloatingResult) {
+    let context = use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(viewport)));
+    let element_ref = use_signal(|| Some(element.mounted()));
+    let trigger_ref =
+        use_signal(|| Some(MockElement::new(rect(250.0, 100.0, 40.0, 20.0)).mounted()));
+    let options = FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+
+    (context, placement())
+}
+
+/// The primary window, 800px wide.
+fn window_a() -> Element {
+    let (context, result) = popover(rect(0.0, 0.0, 800.0, 600.0), ELEMENT_A.with(Clone::clone));
+    CONTEXT_A.with(|cell| cell.set(Some(context)));
+    RESULT_A.with(|cell| cell.set(result));
+    rsx! {}
+}
+
+/// A secondary window, 400px wide.
+fn window_b() -> Element {
+    let (context, result) = popover(rect(0.0, 0.0, 400.0, 300.0), ELEMENT_B.with(Clone::clone));
+    CONTEXT_B.with(|cell| cell.set(Some(context)));
+    RESULT_B.with(|cell| cell.set(result));
+    rsx! {}
+}
+
+fn windows() -> (VirtualDom, VirtualDom) {
+    let mut a = VirtualDom::new(window_a);
+    let mut b = VirtualDom::new(window_b);
+    a.rebuild_in_place();
+    b.rebuild_in_place();
+    settle(&mut a);
+    settle(&mut b);
+
+    (a, b)
+}
+
+/// Invalidates the measurements of a window.
+fn invalidate(
+    dom: &VirtualDom,
+    context: &'static std::thread::LocalKey<Cell<Option<ScrollableContext>>>,
+) {
+    let mut generation = context.with(Cell::get).unwrap().layout_generation;
+    dom.in_runtime(|| *generation.write() += 1);
+}
+
+#[test]
+fn each_window_places_in_its_own_viewport() {
+    let (_a, _b) = windows();
+
+    let a = RESULT_A.with(Cell::get);
+    let b = RESULT_B.with(Cell::get);
+    assert!(a.is_ready && b.is_ready);
+    // room enough in the primary window
+    assert_eq!((a.x, a.y), (250.0, 120.0));
+    // shifted back into the narrower secondary window
+    assert_eq!((b.x, b.y), (200.0, 120.0));
+}
+
+#[test]
+fn a_slow_window_does_not_hold_back_another() {
+    let (mut a, mut b) = windows();
+
+    // both windows measure at the same time, the primary one never finishes
+    ELEMENT_A.with(MockElement::hold);
+    ELEMENT_A.with(|element| element.set_rect(rect(0.0, 0.0, 300.0, 100.0)));
+    ELEMENT_B.with(|element| element.set_rect(rect(0.0, 0.0, 300.0, 100.0)));
+    invalidate(&a, &CONTEXT_A);
+    invalidate(&b, &CONTEXT_B);
+    settle(&mut a);
+    settle(&mut b);
+
+    assert!(RESULT_A.with(Cell::get).is_pending);
+    let b_result = RESULT_B.with(Cell::get);
+    assert!(!b_result.is_pending);
+    assert_eq!(b_result.x, 100.0);
+
+    ELEMENT_A.with(MockElement::release);
+    settle(&mut a);
+    let a_result = RESULT_A.with(Cell::get);
+    assert!(!a_result.is_pending);
+    assert_eq!(a_result.element_size.width, 300.0);
+}
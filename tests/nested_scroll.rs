@@ -0,0 +1,158 @@
+//! A popover anchored in a horizontal strip that sits in a vertically scrolling page
+//! follows the scrolls of both views: the strip is the nearest one, the page an
+//! ancestor ([dioxus_floating::AutoUpdate::ancestor_scroll]).
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    FloatingOptions, FloatingResult, Offset, Placement, ScrollableContext, use_placement,
+};
+
+thread_local! {
+    static TRIGGER: MockElement = MockElement::new(rect(100.0, 220.0, 80.0, 20.0));
+    static VIEWS: Cell<Option<(ScrollableContext, ScrollableContext)>> =
+        const { Cell::new(None) };
+    static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+    static OFFSETS: Cell<Offsets> = Cell::new(Offsets::default());
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+/// The offsets the page and the strip are scrolled to.
+#[derive(Clone, Copy, Default)]
+struct Offsets {
+    page_y: f64,
+    strip_x: f64,
+}
+
+fn app() -> Element {
+    use_hook(|| {
+        let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0));
+        config.scroll_state.size = PixelsSize::new(500.0, 2000.0);
+        provide_mock_scrollable(config);
+    });
+    rsx! { Strip {} }
+}
+
+/// The horizontally scrolling strip, 200px down the page.
+#[component]
+fn Strip() -> Element {
+    use_hook(|| {
+        let page = consume_context::<ScrollableContext>();
+        let mut config = MockScrollableConfig::new(rect(0.0, 200.0, 500.0, 150.0));
+        config.scroll_state.size = PixelsSize::new(2000.0, 150.0);
+        let strip = provide_mock_scrollable(config);
+        VIEWS.with(|cell| cell.set(Some((page, strip))));
+    });
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 100.0, 50.0)).mounted()));
+    let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    let options = FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULT.with(|cell| cell.set(placement()));
+    rsx! {}
+}
+
+fn position() -> (f64, f64) {
+    let result = RESULT.with(Cell::get);
+    (result.x, result.y)
+}
+
+/// Moves the strip and the trigger on screen as the browser would for `offsets`.
+fn layout(offsets: Offsets) -> ScrollableContext {
+    OFFSETS.with(|cell| cell.set(offsets));
+    let (_, strip) = VIEWS.with(Cell::get).unwrap();
+    let strip_element = strip
+        .scrollable_ref
+        .peek()
+        .as_ref()
+        .and_then(|data| data.downcast::<MockElement>().cloned())
+        .unwrap();
+    strip_element.set_rect(rect(0.0, 200.0 - offsets.page_y, 500.0, 150.0));
+    TRIGGER.with(|trigger| {
+        trigger.set_rect(rect(
+            100.0 - offsets.strip_x,
+            220.0 - offsets.page_y,
+            80.0,
+            20.0,
+        ))
+    });
+
+    strip
+}
+
+/// Scrolls only the page, the enclosing view.
+fn scroll_page(dom: &mut VirtualDom, y: f64) {
+    let offsets = Offsets {
+        page_y: y,
+        ..OFFSETS.with(Cell::get)
+    };
+    layout(offsets);
+    let (mut page, _) = VIEWS.with(Cell::get).unwrap();
+    dom.in_runtime(|| page.publish_offset(PixelsVector2D::new(0.0, y)));
+    settle(dom);
+}
+
+/// Scrolls only the strip, the nearest view.
+fn scroll_strip(dom: &mut VirtualDom, x: f64) {
+    let offsets = Offsets {
+        strip_x: x,
+        ..OFFSETS.with(Cell::get)
+    };
+    let mut strip = layout(offsets);
+    dom.in_runtime(|| strip.publish_offset(PixelsVector2D::new(x, 0.0)));
+    settle(dom);
+}
+
+fn start() -> VirtualDom {
+    OFFSETS.with(|cell| cell.set(Offsets::default()));
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    dom
+}
+
+#[test]
+fn follows_the_scroll_of_the_page() {
+    let mut dom = start();
+    assert_eq!(position(), (100.0, 240.0));
+
+    scroll_page(&mut dom, 60.0);
+    assert_eq!(position(), (100.0, 180.0));
+}
+
+#[test]
+fn follows_the_scroll_of_the_strip() {
+    let mut dom = start();
+
+    scroll_strip(&mut dom, 40.0);
+    assert_eq!(position(), (60.0, 240.0));
+}
+
+#[test]
+fn stays_glued_when_both_scroll() {
+    let mut dom = start();
+
+    for (page_y, strip_x) in [(30.0, 20.0), (80.0, 50.0), (10.0, 0.0)] {
+        scroll_page(&mut dom, page_y);
+        assert_eq!(
+            position(),
+            (100.0 - OFFSETS.with(Cell::get).strip_x, 240.0 - page_y)
+        );
+        scroll_strip(&mut dom, strip_x);
+        assert_eq!(position(), (100.0 - strip_x, 240.0 - page_y));
+    }
+}
@@ -0,0 +1,97 @@
+//! A floating element that changes the boundary it is positioned against (e.g., it
+//! makes a scrollbar appear at one position) settles on its first position instead of
+//! alternating between two.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::{Cell, RefCell};
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{FloatingOptions, Offset, Placement, ScrollableContext, use_placement};
+
+thread_local! {
+    static TRIGGER: MockElement = MockElement::new(rect(100.0, 300.0, 80.0, 20.0));
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static PUBLISHED: RefCell<Vec<(f64, Placement)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    let mut context = use_hook(|| {
+        provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)))
+    });
+    CONTEXT.with(|cell| cell.set(Some(context)));
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 150.0)).mounted()));
+    let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    let options = FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+
+    // below the trigger the element extends the content: the boundary loses 100px,
+    // which flips it above, where it no longer does
+    use_effect(move || {
+        let result = placement();
+        if !result.is_ready {
+            return;
+        }
+        if result.placement.is_top() {
+            context.clear_boundary_adjustment();
+        } else {
+            context.set_boundary_adjustment(|rect| {
+                PixelsRect::new(
+                    rect.origin,
+                    PixelsSize::new(rect.width(), rect.height() - 100.0),
+                )
+            });
+        }
+    });
+
+    let result = placement();
+    if result.is_ready {
+        PUBLISHED.with(|published| {
+            let mut published = published.borrow_mut();
+            if published.last() != Some(&(result.y, result.placement)) {
+                published.push((result.y, result.placement));
+            }
+        });
+    }
+    rsx! {}
+}
+
+#[test]
+fn alternating_positions_settle_on_the_first_one() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    settle(&mut dom);
+
+    // one round trip, then it stays below the trigger
+    let published = PUBLISHED.with(|published| published.borrow().clone());
+    assert_eq!(
+        published,
+        vec![
+            (320.0, Placement::BottomStart),
+            (150.0, Placement::TopStart),
+            (320.0, Placement::BottomStart),
+        ]
+    );
+
+    // the trigger moves: the latch is released
+    TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 100.0, 80.0, 20.0)));
+    let mut context = CONTEXT.with(Cell::get).unwrap();
+    dom.in_runtime(|| *context.layout_generation.write() += 1);
+    settle(&mut dom);
+
+    let published = PUBLISHED.with(|published| published.borrow().clone());
+    assert_eq!(published.last(), Some(&(120.0, Placement::BottomStart)));
+}
@@ -0,0 +1,115 @@
+//! [WidgetConfig::outside_press] closes a widget on taps outside of it only: a press that
+//! travels farther than the slop or scrolls a container is a pan and keeps it open, and so
+//! does a press that started in the floating element or on its trigger.
+
+#![cfg(feature = "interactions")]
+
+use std::cell::Cell;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockScrollableConfig, provide_mock_scrollable, report_press, settle,
+};
+use dioxus_floating::{FloatingWidget, WidgetConfig, use_floating_widget};
+
+thread_local! {
+    static CONFIG: Cell<(bool, f64)> = const { Cell::new((true, 10.0)) };
+    static WIDGET: Cell<Option<FloatingWidget>> = const { Cell::new(None) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    use_hook(|| provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0))));
+    let (outside_press, outside_press_slop) = CONFIG.with(Cell::get);
+    let widget = use_floating_widget(WidgetConfig {
+        outside_press,
+        outside_press_slop,
+        ..Default::default()
+    });
+    WIDGET.with(|cell| cell.set(Some(widget)));
+    rsx! {}
+}
+
+/// Starts an open widget, with `outside_press` and the slop of `config`.
+fn start(config: (bool, f64)) -> VirtualDom {
+    CONFIG.with(|cell| cell.set(config));
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    let mut widget = widget();
+    dom.in_runtime(|| widget.open());
+    settle(&mut dom);
+    assert!(is_open(&dom));
+
+    dom
+}
+
+fn widget() -> FloatingWidget {
+    WIDGET.with(Cell::get).unwrap()
+}
+
+fn is_open(dom: &VirtualDom) -> bool {
+    let widget = widget();
+    dom.in_runtime(|| *widget.is_open.peek())
+}
+
+/// Reports a press as the document listener would and returns if the widget is still open.
+fn press(dom: &mut VirtualDom, travel: f64, scrolled: bool, owners: &[&str]) -> bool {
+    dom.in_runtime(|| report_press(travel, scrolled, owners));
+    settle(dom);
+    is_open(dom)
+}
+
+#[test]
+fn a_tap_outside_closes() {
+    let mut dom = start((true, 10.0));
+    assert!(!press(&mut dom, 2.0, false, &[]));
+}
+
+#[test]
+fn a_press_moving_exactly_the_slop_is_a_tap() {
+    let mut dom = start((true, 10.0));
+    assert!(!press(&mut dom, 10.0, false, &[]));
+}
+
+#[test]
+fn a_pan_past_the_slop_keeps_it_open() {
+    let mut dom = start((true, 10.0));
+    assert!(press(&mut dom, 10.5, false, &[]));
+    assert!(press(&mut dom, 240.0, false, &[]));
+}
+
+#[test]
+fn a_press_that_scrolls_keeps_it_open() {
+    // e.g., a fling of a ScrollableView that barely moved the pointer
+    let mut dom = start((true, 10.0));
+    assert!(press(&mut dom, 0.0, true, &[]));
+}
+
+#[test]
+fn a_press_started_inside_keeps_it_open() {
+    let mut dom = start((true, 10.0));
+    let id = widget().floating_id();
+    // started in the floating element or on the trigger (through `aria-controls`), and
+    // released outside of it
+    assert!(press(&mut dom, 0.0, false, &[id.as_str()]));
+    // the floating element of another widget is outside
+    assert!(!press(&mut dom, 0.0, false, &["dioxus-floating-other"]));
+}
+
+#[test]
+fn the_slop_is_configurable() {
+    let mut dom = start((true, 24.0));
+    assert!(press(&mut dom, 30.0, false, &[]));
+    assert!(!press(&mut dom, 20.0, false, &[]));
+}
+
+#[test]
+fn taps_are_ignored_without_outside_press() {
+    let mut dom = start((false, 10.0));
+    assert!(press(&mut dom, 0.0, false, &[]));
+}
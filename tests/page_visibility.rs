@@ -0,0 +1,206 @@
+//! A hidden page suspends the placements: the invalidations collapse into one recompute
+//! on the first frame after it is shown again, and the per-frame sampling pauses.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockDocument, MockElement, MockScrollableConfig, MockWait, provide_mock_document,
+    provide_mock_scrollable, set_page_hidden, settle,
+};
+use dioxus_floating::{
+    AutoUpdate, Floating, FloatingOptions, FloatingResult, Offset, Placement, ScrollableContext,
+    use_floating, use_page_suspended, use_placement,
+};
+use futures_util::StreamExt;
+
+type Ref = Signal<Option<Rc<MountedData>>>;
+
+thread_local! {
+    static TRIGGER: MockElement = MockElement::new(rect(100.0, 100.0, 80.0, 20.0));
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static DOCUMENT: RefCell<Option<MockDocument>> = const { RefCell::new(None) };
+    static FLOATING: Cell<Option<Floating>> = const { Cell::new(None) };
+    static REFS: Cell<Option<(Ref, Ref)>> = const { Cell::new(None) };
+    static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+    static SUSPENDED: Cell<bool> = const { Cell::new(false) };
+    static SAMPLED: Cell<Option<Signal<bool>>> = const { Cell::new(None) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    use_hook(|| {
+        DOCUMENT.with(|cell| *cell.borrow_mut() = Some(provide_mock_document()));
+        let context =
+            provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)));
+        CONTEXT.with(|cell| cell.set(Some(context)));
+    });
+    FLOATING.with(|cell| cell.set(Some(use_floating())));
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    REFS.with(|cell| cell.set(Some((element_ref, trigger_ref))));
+    let options = FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULT.with(|cell| cell.set(placement()));
+    let suspended = use_page_suspended();
+    SUSPENDED.with(|cell| cell.set(suspended()));
+    let sampled = use_signal(|| false);
+    SAMPLED.with(|cell| cell.set(Some(sampled)));
+    rsx! {
+        if sampled() {
+            Sampled {}
+        }
+    }
+}
+
+/// A popover whose size is sampled every animation frame.
+#[component]
+fn Sampled() -> Element {
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    let trigger_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 80.0, 20.0)).mounted()));
+    let options = FloatingOptions {
+        auto_update: AutoUpdate {
+            element_resize: true,
+            ..AutoUpdate::none()
+        },
+        ..Default::default()
+    };
+    use_placement(element_ref, trigger_ref, options);
+    rsx! {}
+}
+
+fn start() -> VirtualDom {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    dom
+}
+
+fn set_hidden(dom: &mut VirtualDom, hidden: bool) {
+    dom.in_runtime(|| set_page_hidden(hidden));
+    settle(dom);
+}
+
+/// Moves the trigger and invalidates the measurements.
+fn move_trigger(dom: &mut VirtualDom, x: f64) {
+    TRIGGER.with(|trigger| trigger.set_rect(rect(x, 100.0, 80.0, 20.0)));
+    let mut generation = CONTEXT.with(Cell::get).unwrap().layout_generation;
+    dom.in_runtime(|| *generation.write() += 1);
+    settle(dom);
+}
+
+fn position() -> (f64, f64) {
+    let result = RESULT.with(Cell::get);
+    (result.x, result.y)
+}
+
+fn document() -> MockDocument {
+    DOCUMENT.with(|cell| cell.borrow().clone().unwrap())
+}
+
+#[test]
+fn invalidations_while_hidden_collapse_into_one_recompute() {
+    let mut dom = start();
+    assert_eq!(position(), (100.0, 120.0));
+    // the reads of one recompute
+    let before = TRIGGER.with(MockElement::round_trips);
+    move_trigger(&mut dom, 120.0);
+    let per_recompute = TRIGGER.with(MockElement::round_trips) - before;
+    assert!(per_recompute > 0);
+
+    set_hidden(&mut dom, true);
+    assert!(SUSPENDED.with(Cell::get));
+    let floating = FLOATING.with(Cell::get).unwrap();
+    assert!(dom.in_runtime(|| floating.is_suspended()));
+    let hidden_at = TRIGGER.with(MockElement::round_trips);
+    for x in [150.0, 200.0, 250.0] {
+        move_trigger(&mut dom, x);
+    }
+    // nothing measured, the last position stays
+    assert_eq!(TRIGGER.with(MockElement::round_trips), hidden_at);
+    assert_eq!(position(), (120.0, 120.0));
+
+    set_hidden(&mut dom, false);
+    assert!(!SUSPENDED.with(Cell::get));
+    assert!(!dom.in_runtime(|| floating.is_suspended()));
+    assert_eq!(
+        TRIGGER.with(MockElement::round_trips),
+        hidden_at + per_recompute
+    );
+    assert_eq!(position(), (250.0, 120.0));
+}
+
+#[test]
+fn resumes_on_the_first_visible_frame() {
+    let mut dom = start();
+    document().clear();
+    // the engine of the mocks does not wait
+    move_trigger(&mut dom, 150.0);
+    assert_eq!(document().waits(), []);
+
+    set_hidden(&mut dom, true);
+    set_hidden(&mut dom, false);
+    // without any change: recomputed after the frame that follows `visibilitychange`
+    assert_eq!(document().waits(), [MockWait::Frame]);
+    let result = RESULT.with(Cell::get);
+    assert!(result.is_ready && !result.is_pending);
+    assert_eq!(position(), (150.0, 120.0));
+}
+
+#[test]
+fn per_frame_sampling_waits_for_the_page() {
+    let mut dom = start();
+    set_hidden(&mut dom, true);
+    document().clear();
+
+    // the mock frames resolve immediately, a running sampling would record them
+    let mut sampled = SAMPLED.with(Cell::get).unwrap();
+    dom.in_runtime(|| sampled.set(true));
+    settle(&mut dom);
+    assert_eq!(document().waits(), []);
+}
+
+#[test]
+fn a_stream_waits_for_the_page() {
+    let mut dom = start();
+    let outputs = Rc::new(RefCell::new(Vec::new()));
+    let floating = FLOATING.with(Cell::get).unwrap();
+    let context = CONTEXT.with(Cell::get).unwrap();
+    let (element, trigger) = REFS.with(Cell::get).unwrap();
+    let received = outputs.clone();
+    dom.in_scope(ScopeId::APP, || {
+        let options = FloatingOptions {
+            placement_offset: Some(Offset::zero()),
+            ..Default::default()
+        };
+        let mut placements = floating.placement_stream(context, element, trigger, options);
+        spawn(async move {
+            while let Some(output) = placements.next().await {
+                received.borrow_mut().push((output.x, output.y));
+            }
+        });
+    });
+    settle(&mut dom);
+    assert_eq!(outputs.take(), [(100.0, 120.0)]);
+
+    set_hidden(&mut dom, true);
+    for x in [150.0, 200.0] {
+        move_trigger(&mut dom, x);
+    }
+    assert_eq!(outputs.take(), []);
+
+    set_hidden(&mut dom, false);
+    assert_eq!(outputs.take(), [(200.0, 120.0)]);
+}
@@ -0,0 +1,181 @@
+//! [Floating::placement_stream]: a placement per recompute for code outside of
+//! components, coalesced for slow consumers, ending with its refs.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use dioxus::core::Task;
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{Floating, FloatingOptions, Offset, ScrollableContext, WaitStrategy};
+use futures_util::StreamExt;
+
+type Ref = Signal<Option<Rc<MountedData>>>;
+
+thread_local! {
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static SHOW: Cell<Option<Signal<bool>>> = const { Cell::new(None) };
+    static TRIGGER: RefCell<Option<MockElement>> = const { RefCell::new(None) };
+    static REFS: Cell<Option<(Ref, Ref)>> = const { Cell::new(None) };
+    static OUTPUTS: RefCell<Vec<(f64, f64)>> = const { RefCell::new(Vec::new()) };
+    static ENDED: Cell<bool> = const { Cell::new(false) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    use_hook(|| {
+        let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0));
+        config.scroll_state.size = PixelsSize::new(500.0, 2000.0);
+        CONTEXT.with(|cell| cell.set(Some(provide_mock_scrollable(config))));
+    });
+    let show = use_signal(|| true);
+    SHOW.with(|cell| cell.set(Some(show)));
+    rsx! {
+        if show() {
+            Popover {}
+        }
+    }
+}
+
+/// Owns the refs; the stream is consumed elsewhere.
+#[component]
+fn Popover() -> Element {
+    let trigger = use_hook(|| MockElement::new(rect(100.0, 100.0, 80.0, 20.0)));
+    TRIGGER.with(|cell| *cell.borrow_mut() = Some(trigger.clone()));
+    let element = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    let trigger = use_signal(|| Some(trigger.mounted()));
+    REFS.with(|cell| cell.set(Some((element, trigger))));
+    rsx! {}
+}
+
+/// Renders the app and consumes a stream from a task of the root component.
+fn start() -> (VirtualDom, Task) {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    let context = CONTEXT.with(Cell::get).unwrap();
+    let (element, trigger) = REFS.with(Cell::get).unwrap();
+    let task = dom.in_scope(ScopeId::APP, || {
+        let floating = Floating::builder().wait(WaitStrategy::None).build();
+        let options = FloatingOptions {
+            placement_offset: Some(Offset::zero()),
+            ..Default::default()
+        };
+        let mut placements = floating.placement_stream(context, element, trigger, options);
+        spawn(async move {
+            while let Some(output) = placements.next().await {
+                OUTPUTS.with(|outputs| outputs.borrow_mut().push((output.x, output.y)));
+            }
+            ENDED.with(|ended| ended.set(true));
+        })
+    });
+    settle(&mut dom);
+
+    (dom, task)
+}
+
+fn outputs() -> Vec<(f64, f64)> {
+    OUTPUTS.with(|outputs| outputs.take())
+}
+
+/// Scrolls the view to each offset in turn, the trigger moving with the content,
+/// without running the tasks in between.
+fn scroll(dom: &mut VirtualDom, offsets: &[f64]) {
+    let mut context = CONTEXT.with(Cell::get).unwrap();
+    for y in offsets {
+        TRIGGER.with(|cell| {
+            let trigger = cell.borrow();
+            trigger
+                .as_ref()
+                .unwrap()
+                .set_rect(rect(100.0, 100.0 - y, 80.0, 20.0));
+        });
+        dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, *y)));
+    }
+    settle(dom);
+}
+
+#[test]
+fn a_placement_per_recompute() {
+    let (mut dom, _task) = start();
+    assert_eq!(outputs(), [(100.0, 120.0)]);
+
+    scroll(&mut dom, &[40.0]);
+    assert_eq!(outputs(), [(100.0, 80.0)]);
+
+    // a layout change
+    let mut generation = CONTEXT.with(Cell::get).unwrap().layout_generation;
+    TRIGGER.with(|cell| {
+        let trigger = cell.borrow();
+        trigger
+            .as_ref()
+            .unwrap()
+            .set_rect(rect(200.0, 60.0, 80.0, 20.0));
+    });
+    dom.in_runtime(|| generation += 1);
+    settle(&mut dom);
+    assert_eq!(outputs(), [(200.0, 80.0)]);
+
+    // nothing changed
+    settle(&mut dom);
+    assert!(outputs().is_empty());
+}
+
+#[test]
+fn a_slow_consumer_skips_to_the_latest() {
+    let (mut dom, _task) = start();
+    outputs();
+
+    scroll(&mut dom, &[10.0, 20.0, 30.0, 40.0]);
+    assert_eq!(outputs(), [(100.0, 80.0)]);
+}
+
+#[test]
+fn waits_for_unmounted_refs() {
+    let (mut dom, _task) = start();
+    outputs();
+
+    let (_, mut trigger) = REFS.with(Cell::get).unwrap();
+    let mounted = dom.in_runtime(|| trigger.take());
+    settle(&mut dom);
+    assert!(outputs().is_empty());
+    assert!(!ENDED.with(Cell::get));
+
+    dom.in_runtime(|| trigger.set(mounted));
+    settle(&mut dom);
+    assert_eq!(outputs(), [(100.0, 120.0)]);
+}
+
+#[test]
+fn ends_with_its_refs() {
+    let (mut dom, _task) = start();
+    outputs();
+
+    let mut show = SHOW.with(Cell::get).unwrap();
+    dom.in_runtime(|| show.set(false));
+    settle(&mut dom);
+    // noticed at the next change
+    scroll(&mut dom, &[40.0]);
+    assert!(outputs().is_empty());
+    assert!(ENDED.with(Cell::get));
+}
+
+#[test]
+fn dropping_the_stream_unsubscribes() {
+    let (mut dom, task) = start();
+    outputs();
+
+    dom.in_runtime(|| task.cancel());
+    settle(&mut dom);
+    scroll(&mut dom, &[40.0]);
+    assert!(outputs().is_empty());
+}
@@ -0,0 +1,179 @@
+//! With [prefers_reduced_motion], the animated helpers change instantly: smooth scrolls,
+//! the transitions of [SuppressTransitions] and the closing phase of [FloatingState],
+//! unless a call opts out.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+
+use dioxus::core::Runtime;
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    FloatingDelays, FloatingOptions, FloatingResult, FloatingState, Offset, Phase, Placement,
+    ScrollableContext, SuppressTransitions, prefers_reduced_motion, set_reduced_motion_override,
+    use_floating_state, use_placement,
+};
+
+thread_local! {
+    static TRIGGER: MockElement = MockElement::new(rect(100.0, 100.0, 80.0, 20.0));
+    static IGNORE: Cell<bool> = const { Cell::new(false) };
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static STATE: Cell<Option<FloatingState>> = const { Cell::new(None) };
+    static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    let context = use_hook(|| {
+        let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0));
+        config.scroll_state.size = PixelsSize::new(500.0, 2000.0);
+        provide_mock_scrollable(config)
+    });
+    CONTEXT.with(|cell| cell.set(Some(context)));
+    let mut state = use_floating_state(FloatingDelays {
+        closing_ms: 200,
+        ..Default::default()
+    });
+    use_hook(|| state.set_ignore_reduced_motion(IGNORE.with(Cell::get)));
+    STATE.with(|cell| cell.set(Some(state)));
+
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    let options = FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        suppress_transitions: Some(SuppressTransitions {
+            ignore_reduced_motion: IGNORE.with(Cell::get),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULT.with(|cell| cell.set(placement()));
+    rsx! {}
+}
+
+/// Returns a settled dom, with the preference and the opt-out of every helper.
+fn dom(reduce: bool, ignore: bool) -> VirtualDom {
+    set_reduced_motion_override(Some(reduce));
+    IGNORE.with(|cell| cell.set(ignore));
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    dom
+}
+
+/// Scrolls smoothly to `y` and returns whether the scroll was tracked as smooth.
+fn smooth_scroll(dom: &mut VirtualDom, context: ScrollableContext, y: f64) -> bool {
+    dom.in_runtime(|| {
+        Runtime::current().spawn(ScopeId::ROOT, async move {
+            context
+                .scroll(PixelsVector2D::new(0.0, y), ScrollBehavior::Smooth)
+                .await;
+        })
+    });
+    settle(dom);
+
+    dom.in_runtime(|| *context.is_smooth_scrolling.peek())
+}
+
+#[test]
+fn the_override_wins() {
+    assert!(!prefers_reduced_motion());
+    set_reduced_motion_override(Some(true));
+    assert!(prefers_reduced_motion());
+    set_reduced_motion_override(None);
+    assert!(!prefers_reduced_motion());
+}
+
+#[test]
+fn smooth_scrolls_are_instant() {
+    let mut dom = dom(true, false);
+    let context = CONTEXT.with(Cell::get).unwrap();
+
+    assert!(!smooth_scroll(&mut dom, context, 300.0));
+    // the offset still arrives
+    let scrollable = dom.in_runtime(|| context.scrollable_ref.peek().clone().unwrap());
+    let offset = scrollable
+        .downcast::<MockElement>()
+        .map(MockElement::scroll_offset);
+    assert_eq!(offset, Some(PixelsVector2D::new(0.0, 300.0)));
+
+    // opted out for one call
+    assert!(smooth_scroll(
+        &mut dom,
+        context.ignoring_reduced_motion(),
+        600.0
+    ));
+}
+
+#[test]
+fn smooth_scrolls_without_the_preference() {
+    let mut dom = dom(false, false);
+    let context = CONTEXT.with(Cell::get).unwrap();
+
+    assert!(smooth_scroll(&mut dom, context, 300.0));
+}
+
+#[test]
+fn every_move_disables_the_transitions() {
+    for (reduce, ignore, suppressed) in [
+        (true, false, true),
+        (true, true, false),
+        (false, false, false),
+    ] {
+        let mut dom = dom(reduce, ignore);
+        let result = RESULT.with(Cell::get);
+        assert!(result.is_ready);
+        assert_eq!(
+            result.suppress_transition, suppressed,
+            "first, {reduce} {ignore}"
+        );
+
+        // a small tracking move, no jump
+        let mut context = CONTEXT.with(Cell::get).unwrap();
+        TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 96.0, 80.0, 20.0)));
+        dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, 4.0)));
+        settle(&mut dom);
+        let result = RESULT.with(Cell::get);
+        assert_eq!(result.y, 116.0);
+        assert_eq!(
+            result.suppress_transition, suppressed,
+            "moved, {reduce} {ignore}"
+        );
+        TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 100.0, 80.0, 20.0)));
+    }
+}
+
+#[test]
+fn the_closing_phase_is_skipped() {
+    for (reduce, ignore, closed) in [
+        (true, false, Phase::Closed),
+        (true, true, Phase::Closing),
+        (false, false, Phase::Closing),
+    ] {
+        let mut dom = dom(reduce, ignore);
+        let mut state = STATE.with(Cell::get).unwrap();
+        dom.in_runtime(|| state.request_open(None));
+        settle(&mut dom);
+        assert_eq!(dom.in_runtime(|| *state.phase.peek()), Phase::Open);
+
+        dom.in_runtime(|| state.request_close(None));
+        assert_eq!(
+            dom.in_runtime(|| *state.phase.peek()),
+            closed,
+            "{reduce} {ignore}"
+        );
+        // the closing timer (if any) elapses
+        settle(&mut dom);
+        assert_eq!(dom.in_runtime(|| *state.phase.peek()), Phase::Closed);
+    }
+}
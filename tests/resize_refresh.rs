@@ -0,0 +1,175 @@
+//! Back-to-back resizes of a [dioxus_floating::ScrollableView] refresh its scroll size
+//! with a single measurement in flight: a response that arrives after a newer resize is
+//! stale and measured again, so the published size never goes back to an older one.
+
+#![cfg(feature = "components")]
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use dioxus::core::{ElementId, Mutation, Mutations};
+use dioxus::html::PlatformEventData;
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{MockElement, settle};
+use dioxus_floating::{ScrollState, ScrollableView, use_scroll_context};
+use dioxus_html::{
+    SerializedHtmlEventConverter, SerializedResizeData, SerializedScrollData, set_event_converter,
+};
+
+thread_local! {
+    static VIEW: MockElement = MockElement::new(rect(0.0, 0.0, 400.0, 200.0));
+    // the published state of every render
+    static STATES: RefCell<Vec<ScrollState>> = const { RefCell::new(Vec::new()) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    rsx! {
+        ScrollableView { Content {} }
+    }
+}
+
+/// Backs the view with the mock and records the published states.
+#[component]
+fn Content() -> Element {
+    let mut context = use_scroll_context();
+    use_hook(|| {
+        context
+            .scrollable_ref
+            .set(Some(VIEW.with(MockElement::mounted)))
+    });
+    if let Some(state) = (context.scroll_state)() {
+        STATES.with(|cell| cell.borrow_mut().push(state));
+    }
+    rsx! {}
+}
+
+/// The rendered view, with the element of its listeners.
+struct View {
+    dom: VirtualDom,
+    listeners: HashMap<String, ElementId>,
+}
+
+impl View {
+    /// Renders the view, scrolled to the top of a 2000px high content.
+    fn start() -> Self {
+        set_event_converter(Box::new(SerializedHtmlEventConverter));
+        STATES.with(|cell| cell.borrow_mut().clear());
+        VIEW.with(|view| view.set_scroll_size(PixelsSize::new(400.0, 2000.0)));
+        let mut dom = VirtualDom::new(app);
+        let mut mutations = Mutations::default();
+        dom.rebuild(&mut mutations);
+        let listeners = mutations
+            .edits
+            .iter()
+            .filter_map(|edit| match edit {
+                Mutation::NewEventListener { name, id } => Some((name.clone(), *id)),
+                _ => None,
+            })
+            .collect();
+        settle(&mut dom);
+
+        let mut view = Self { dom, listeners };
+        view.fire(
+            "scroll",
+            Box::new(SerializedScrollData {
+                scroll_top: 0.0,
+                scroll_left: 0.0,
+                scroll_width: 400,
+                scroll_height: 2000,
+                client_width: 400,
+                client_height: 200,
+            }),
+        );
+        view
+    }
+
+    fn fire(&mut self, name: &str, data: Box<dyn Any>) {
+        let event = Event::new(Rc::new(PlatformEventData::new(data)) as Rc<dyn Any>, false);
+        self.dom
+            .runtime()
+            .handle_event(name, event, self.listeners[name]);
+        settle(&mut self.dom);
+    }
+
+    /// Resizes the view to a height of `height` while its content grows to
+    /// `content_height`, as a window drag would.
+    fn resize(&mut self, height: f64, content_height: f64) {
+        VIEW.with(|view| view.set_scroll_size(PixelsSize::new(400.0, content_height)));
+        let size = PixelsSize::new(400.0, height);
+        self.fire("resize", Box::new(SerializedResizeData::new(size, size)));
+    }
+}
+
+fn states() -> Vec<ScrollState> {
+    STATES.with(|cell| cell.borrow().clone())
+}
+
+fn content_heights() -> Vec<f64> {
+    states().iter().map(|state| state.size.height).collect()
+}
+
+fn in_flight() -> usize {
+    VIEW.with(MockElement::scroll_sizes_in_flight)
+}
+
+#[test]
+fn a_late_response_does_not_go_back_to_an_older_size() {
+    let mut view = View::start();
+    assert!(content_heights().iter().all(|height| *height == 2000.0));
+
+    // the response of the first resize is delayed past the second one; a refresh of its
+    // own for the second one would be held as well
+    VIEW.with(|view| {
+        view.hold_next_scroll_size();
+        view.hold_next_scroll_size();
+    });
+    view.resize(250.0, 2500.0);
+    view.resize(300.0, 3000.0);
+    // the bounds follow the events right away, the refreshes are coalesced
+    let state = *states().last().unwrap();
+    assert_eq!(state.bounds, PixelsSize::new(400.0, 300.0));
+    assert_eq!(state.state, PixelsVector2D::zero());
+    assert_eq!(in_flight(), 1);
+
+    // the stale response is measured again instead of published
+    VIEW.with(MockElement::release_scroll_size);
+    settle(&mut view.dom);
+    assert_eq!(in_flight(), 1);
+    assert_eq!(content_heights().last(), Some(&2000.0));
+    VIEW.with(MockElement::release_scroll_size);
+    settle(&mut view.dom);
+    assert_eq!(in_flight(), 0);
+    assert_eq!(content_heights().last(), Some(&3000.0));
+    assert!(
+        !content_heights().contains(&2500.0),
+        "{:?}",
+        content_heights()
+    );
+}
+
+#[test]
+fn a_single_resize_refreshes_the_size() {
+    let mut view = View::start();
+
+    VIEW.with(MockElement::hold_next_scroll_size);
+    view.resize(250.0, 2500.0);
+    assert_eq!(in_flight(), 1);
+    // the bounds are published before the size
+    assert_eq!(content_heights().last(), Some(&2000.0));
+    assert_eq!(
+        states().last().unwrap().bounds,
+        PixelsSize::new(400.0, 250.0)
+    );
+
+    VIEW.with(MockElement::release_scroll_size);
+    settle(&mut view.dom);
+    assert_eq!(in_flight(), 0);
+    assert_eq!(content_heights().last(), Some(&2500.0));
+}
@@ -0,0 +1,226 @@
+//! The `on_scroll_intercept` handler of a [dioxus_floating::ScrollableView] accepts,
+//! replaces or vetoes every proposed scroll state before it is published; the view scrolls
+//! to the published offset itself, and the scroll event of that correction is published
+//! without being intercepted again, so a clamp never feeds back into itself.
+
+#![cfg(feature = "components")]
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use dioxus::core::{ElementId, Mutation, Mutations};
+use dioxus::html::PlatformEventData;
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{MockElement, settle};
+use dioxus_floating::{ScrollIntercept, ScrollState, ScrollableView, use_scroll_context};
+use dioxus_html::{SerializedHtmlEventConverter, SerializedScrollData, set_event_converter};
+
+/// The furthest offset the handler lets the view scroll to, e.g., the start of a region
+/// that is still loading.
+const LOADED_UNTIL: f64 = 300.0;
+
+thread_local! {
+    static VIEW: MockElement = MockElement::new(rect(0.0, 0.0, 400.0, 200.0));
+    // how the handler decides: clamp to LOADED_UNTIL, veto everything or accept
+    static MODE: Cell<Mode> = const { Cell::new(Mode::Clamp) };
+    static INTERCEPTED: RefCell<Vec<f64>> = const { RefCell::new(Vec::new()) };
+    static SCROLLED: RefCell<Vec<f64>> = const { RefCell::new(Vec::new()) };
+    static PUBLISHED: Cell<Option<f64>> = const { Cell::new(None) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    // no handler at all
+    Unhandled,
+    Accept,
+    Clamp,
+    Veto,
+}
+
+fn app() -> Element {
+    let intercept = use_callback(move |proposed: ScrollState| {
+        INTERCEPTED.with(|cell| cell.borrow_mut().push(proposed.state.y));
+        match MODE.with(Cell::get) {
+            Mode::Unhandled | Mode::Accept => ScrollIntercept::Accept,
+            Mode::Clamp if proposed.state.y > LOADED_UNTIL => {
+                let mut clamped = proposed;
+                clamped.state.y = LOADED_UNTIL;
+                ScrollIntercept::Replace(clamped)
+            }
+            Mode::Clamp => ScrollIntercept::Accept,
+            Mode::Veto => ScrollIntercept::Veto,
+        }
+    });
+    let is_intercepted = !matches!(MODE.with(Cell::get), Mode::Unhandled);
+    rsx! {
+        ScrollableView {
+            on_scroll_intercept: is_intercepted.then_some(intercept),
+            on_scroll: move |state: ScrollState| {
+                SCROLLED.with(|cell| cell.borrow_mut().push(state.state.y));
+            },
+            Content {}
+        }
+    }
+}
+
+/// Backs the view with the mock and follows the published offset.
+#[component]
+fn Content() -> Element {
+    let mut context = use_scroll_context();
+    use_hook(|| {
+        context
+            .scrollable_ref
+            .set(Some(VIEW.with(MockElement::mounted)))
+    });
+    let published = (context.scroll_state)().map(|state| state.state.y);
+    PUBLISHED.with(|cell| cell.set(published));
+    rsx! {}
+}
+
+/// Renders the view and returns the element with its `onscroll` listener.
+fn start(mode: Mode) -> (VirtualDom, ElementId) {
+    set_event_converter(Box::new(SerializedHtmlEventConverter));
+    MODE.with(|cell| cell.set(mode));
+    VIEW.with(|view| {
+        view.set_scroll_size(PixelsSize::new(400.0, 2000.0));
+        view.set_scroll_offset(PixelsVector2D::zero());
+    });
+    let mut dom = VirtualDom::new(app);
+    let mut mutations = Mutations::default();
+    dom.rebuild(&mut mutations);
+    let view = mutations
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            Mutation::NewEventListener { name, id } if name == "scroll" => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    settle(&mut dom);
+    INTERCEPTED.with(|cell| cell.borrow_mut().clear());
+    SCROLLED.with(|cell| cell.borrow_mut().clear());
+
+    (dom, view)
+}
+
+/// Fires the scroll event of the view at offset `y`, as the browser would.
+fn scroll_event(dom: &mut VirtualDom, view: ElementId, y: f64) {
+    VIEW.with(|element| element.set_scroll_offset(PixelsVector2D::new(0.0, y)));
+    let data = SerializedScrollData {
+        scroll_top: y,
+        scroll_left: 0.0,
+        scroll_width: 400,
+        scroll_height: 2000,
+        client_width: 400,
+        client_height: 200,
+    };
+    let event = Event::new(
+        Rc::new(PlatformEventData::new(Box::new(data))) as Rc<dyn Any>,
+        false,
+    );
+    dom.runtime().handle_event("scroll", event, view);
+    settle(dom);
+}
+
+fn intercepted() -> Vec<f64> {
+    INTERCEPTED.with(|cell| cell.borrow().clone())
+}
+
+fn scrolled() -> Vec<f64> {
+    SCROLLED.with(|cell| cell.borrow().clone())
+}
+
+fn published() -> Option<f64> {
+    PUBLISHED.with(Cell::get)
+}
+
+fn view_offset() -> f64 {
+    VIEW.with(MockElement::scroll_offset).y
+}
+
+#[test]
+fn without_a_handler_every_state_is_published() {
+    let (mut dom, view) = start(Mode::Unhandled);
+
+    scroll_event(&mut dom, view, 120.0);
+    scroll_event(&mut dom, view, 900.0);
+    assert!(intercepted().is_empty());
+    assert_eq!(scrolled(), [120.0, 900.0]);
+    assert_eq!(published(), Some(900.0));
+    assert_eq!(view_offset(), 900.0);
+}
+
+#[test]
+fn accepted_states_are_published() {
+    let (mut dom, view) = start(Mode::Accept);
+
+    scroll_event(&mut dom, view, 120.0);
+    assert_eq!(intercepted(), [120.0]);
+    assert_eq!(scrolled(), [120.0]);
+    assert_eq!(published(), Some(120.0));
+    // nothing to correct
+    assert_eq!(view_offset(), 120.0);
+}
+
+#[test]
+fn a_replaced_state_is_published_and_scrolled_to() {
+    let (mut dom, view) = start(Mode::Clamp);
+
+    scroll_event(&mut dom, view, 500.0);
+    assert_eq!(intercepted(), [500.0]);
+    // the clamped state instead of the proposed one, before the view is corrected
+    assert_eq!(scrolled(), [300.0]);
+    assert_eq!(published(), Some(300.0));
+    assert_eq!(view_offset(), LOADED_UNTIL);
+
+    // the event of the correction is not intercepted again
+    scroll_event(&mut dom, view, LOADED_UNTIL);
+    assert_eq!(intercepted(), [500.0]);
+    assert_eq!(scrolled(), [300.0, 300.0]);
+    assert_eq!(published(), Some(300.0));
+}
+
+#[test]
+fn a_vetoed_state_is_not_published() {
+    let (mut dom, view) = start(Mode::Accept);
+    scroll_event(&mut dom, view, 80.0);
+
+    MODE.with(|cell| cell.set(Mode::Veto));
+    scroll_event(&mut dom, view, 140.0);
+    assert_eq!(intercepted(), [80.0, 140.0]);
+    assert_eq!(scrolled(), [80.0]);
+    assert_eq!(published(), Some(80.0));
+    // scrolled back to the published offset
+    assert_eq!(view_offset(), 80.0);
+
+    scroll_event(&mut dom, view, 80.0);
+    assert_eq!(intercepted(), [80.0, 140.0]);
+    assert_eq!(published(), Some(80.0));
+}
+
+#[test]
+fn corrections_do_not_recompute_endlessly() {
+    let (mut dom, view) = start(Mode::Clamp);
+
+    // a fling past the loaded region: every event is clamped once, each correction
+    // arrives once and is published without another intercept
+    for y in [350.0, 420.0, 600.0] {
+        scroll_event(&mut dom, view, y);
+        assert_eq!(view_offset(), LOADED_UNTIL);
+        scroll_event(&mut dom, view, LOADED_UNTIL);
+    }
+    assert_eq!(intercepted(), [350.0, 420.0, 600.0]);
+    assert_eq!(scrolled(), [300.0; 6]);
+
+    // within the loaded region, scrolls pass through again
+    scroll_event(&mut dom, view, 200.0);
+    assert_eq!(intercepted(), [350.0, 420.0, 600.0, 200.0]);
+    assert_eq!(published(), Some(200.0));
+    assert_eq!(view_offset(), 200.0);
+}
@@ -0,0 +1,140 @@
+//! The re-baselining of [dioxus_floating::use_scroll_reveal] after offset changes that
+//! are not user scrolls: the distance to the threshold counts from the offset a reload
+//! publishes, from the first offset that moves after a programmatic scroll and from the
+//! offset a smooth scroll ends at.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+
+use dioxus::core::Runtime;
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{RevealState, ScrollableContext, use_scroll_reveal};
+
+const THRESHOLD: f64 = 24.0;
+
+thread_local! {
+    static VIEW: MockElement = MockElement::new(rect(0.0, 0.0, 400.0, 600.0));
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static REVEAL: Cell<RevealState> = const { Cell::new(RevealState { visible: true, at_top: true }) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    let context = use_hook(|| {
+        let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 400.0, 600.0));
+        config.scroll_state.size = PixelsSize::new(400.0, 5000.0);
+        let mut context = provide_mock_scrollable(config);
+        // measured by the reloads
+        VIEW.with(|view| view.set_scroll_size(PixelsSize::new(400.0, 5000.0)));
+        context
+            .scrollable_ref
+            .set(Some(VIEW.with(MockElement::mounted)));
+        context
+    });
+    CONTEXT.with(|cell| cell.set(Some(context)));
+    rsx! { Toolbar {} }
+}
+
+#[component]
+fn Toolbar() -> Element {
+    let reveal = use_scroll_reveal(THRESHOLD);
+    REVEAL.with(|cell| cell.set(reveal()));
+    rsx! {}
+}
+
+/// Returns a settled dom whose toolbar was hidden by scrolling down and revealed again
+/// by scrolling up to 400.
+fn dom() -> (VirtualDom, ScrollableContext) {
+    VIEW.with(|view| view.set_scroll_offset(PixelsVector2D::zero()));
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    let context = CONTEXT.with(Cell::get).unwrap();
+
+    assert!(!scroll_to(&mut dom, context, 500.0).visible);
+    assert!(scroll_to(&mut dom, context, 400.0).visible);
+    (dom, context)
+}
+
+/// Publishes the offset of a scroll event at `y`.
+fn scroll_to(dom: &mut VirtualDom, mut context: ScrollableContext, y: f64) -> RevealState {
+    VIEW.with(|view| view.set_scroll_offset(PixelsVector2D::new(0.0, y)));
+    dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, y)));
+    settle(dom);
+    REVEAL.with(Cell::get)
+}
+
+/// Runs `task` with the context to completion.
+fn run<F: Future<Output = ()> + 'static>(
+    dom: &mut VirtualDom,
+    context: ScrollableContext,
+    task: impl FnOnce(ScrollableContext) -> F,
+) {
+    let task = task(context);
+    dom.in_runtime(|| Runtime::current().spawn(ScopeId::ROOT, task));
+    settle(dom);
+}
+
+#[test]
+fn a_reload_counts_from_the_offset_it_publishes() {
+    let (mut dom, context) = dom();
+
+    // the content moved the offset without a scroll event
+    VIEW.with(|view| view.set_scroll_offset(PixelsVector2D::new(0.0, 3000.0)));
+    run(&mut dom, context, |mut context| async move {
+        context.reload().await
+    });
+    assert_eq!(
+        REVEAL.with(Cell::get),
+        RevealState {
+            visible: true,
+            at_top: false
+        }
+    );
+
+    // the next scroll events are user scrolls
+    assert!(scroll_to(&mut dom, context, 3010.0).visible);
+    assert!(!scroll_to(&mut dom, context, 3030.0).visible);
+}
+
+#[test]
+fn a_programmatic_scroll_counts_from_where_it_ends() {
+    let (mut dom, context) = dom();
+
+    run(&mut dom, context, |context| async move {
+        context
+            .scroll(PixelsVector2D::new(0.0, 3000.0), ScrollBehavior::Instant)
+            .await;
+    });
+    // its scroll event arrives after it returns
+    assert!(scroll_to(&mut dom, context, 3000.0).visible);
+
+    assert!(scroll_to(&mut dom, context, 3010.0).visible);
+    assert!(!scroll_to(&mut dom, context, 3030.0).visible);
+}
+
+#[test]
+fn a_smooth_scroll_counts_from_where_it_arrives() {
+    let (mut dom, context) = dom();
+
+    run(&mut dom, context, |context| async move {
+        context
+            .scroll(PixelsVector2D::new(0.0, 3000.0), ScrollBehavior::Smooth)
+            .await;
+    });
+    for y in [1000.0, 2000.0, 3000.0] {
+        assert!(scroll_to(&mut dom, context, y).visible);
+    }
+    assert!(!dom.in_runtime(|| *context.is_smooth_scrolling.peek()));
+
+    assert!(scroll_to(&mut dom, context, 3010.0).visible);
+    assert!(!scroll_to(&mut dom, context, 3030.0).visible);
+}
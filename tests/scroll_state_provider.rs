@@ -0,0 +1,149 @@
+//! [ScrollStateProvider] feeds the placements inside from caller-provided signals: the
+//! provided rect is the boundary, new states and rects recompute them, and the scroll
+//! methods of the context are passed to the `on_scroll_request` handler.
+
+#![cfg(feature = "components")]
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use dioxus::core::Runtime;
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{MockElement, settle};
+use dioxus_floating::{
+    Floating, FloatingOptions, FloatingResult, Offset, Placement, ScrollState, ScrollStateProvider,
+    ScrollableContext, WaitStrategy, use_placement, use_scroll_context,
+};
+
+thread_local! {
+    static TRIGGER: MockElement = MockElement::new(rect(100.0, 100.0, 80.0, 20.0));
+    static SOURCE: Cell<Option<Source>> = const { Cell::new(None) };
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+    static REQUESTS: RefCell<Vec<PixelsVector2D>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The signals of the external scroll source, e.g., a virtualization library.
+#[derive(Clone, Copy)]
+struct Source {
+    state: Signal<Option<ScrollState>>,
+    rect: Signal<Option<PixelsRect>>,
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn scroll_state(y: f64) -> ScrollState {
+    ScrollState {
+        size: PixelsSize::new(500.0, 2000.0),
+        bounds: PixelsSize::new(500.0, 500.0),
+        state: PixelsVector2D::new(0.0, y),
+    }
+}
+
+fn app() -> Element {
+    let state = use_signal(|| Some(scroll_state(0.0)));
+    let rect = use_signal(|| Some(rect(0.0, 0.0, 500.0, 500.0)));
+    SOURCE.with(|cell| cell.set(Some(Source { state, rect })));
+
+    rsx! {
+        ScrollStateProvider {
+            state,
+            container_rect: rect,
+            on_scroll_request: move |(offset, _): (PixelsVector2D, ScrollBehavior)| {
+                REQUESTS.with(|requests| requests.borrow_mut().push(offset));
+            },
+            floating: Floating::builder().wait(WaitStrategy::None).build(),
+            Dropdown {}
+        }
+    }
+}
+
+#[component]
+fn Dropdown() -> Element {
+    CONTEXT.with(|cell| cell.set(Some(use_scroll_context())));
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    let options = FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULT.with(|cell| cell.set(placement()));
+    rsx! {}
+}
+
+/// Returns a settled dom and the source signals.
+fn dom() -> (VirtualDom, Source) {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    (dom, SOURCE.with(Cell::get).unwrap())
+}
+
+fn result() -> FloatingResult {
+    RESULT.with(Cell::get)
+}
+
+#[test]
+fn the_provided_rect_is_the_boundary() {
+    let (mut dom, source) = dom();
+
+    assert!(result().is_ready);
+    assert_eq!((result().x, result().y), (100.0, 120.0));
+    let context = CONTEXT.with(Cell::get).unwrap();
+    assert!(dom.in_runtime(|| context.scrollable_ref.peek().is_none()));
+
+    // the container moved up (e.g., the page scrolled): no room below anymore
+    let mut rect_signal = source.rect;
+    dom.in_runtime(|| rect_signal.set(Some(rect(0.0, -300.0, 500.0, 500.0))));
+    settle(&mut dom);
+    assert_eq!(result().placement, Placement::TopStart);
+    assert_eq!((result().x, result().y), (100.0, 0.0));
+}
+
+#[test]
+fn new_states_recompute_the_placements() {
+    let (mut dom, source) = dom();
+
+    // the source scrolled the trigger up by 40px
+    let mut state = source.state;
+    TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 60.0, 80.0, 20.0)));
+    dom.in_runtime(|| state.set(Some(scroll_state(40.0))));
+    settle(&mut dom);
+
+    assert_eq!(result().y, 80.0);
+    let context = CONTEXT.with(Cell::get).unwrap();
+    let published = dom.in_runtime(|| context.scroll_state.peek().unwrap().state);
+    assert_eq!(published, PixelsVector2D::new(0.0, 40.0));
+}
+
+#[test]
+fn scrolls_are_passed_to_the_handler() {
+    let (mut dom, _) = dom();
+    let context = CONTEXT.with(Cell::get).unwrap();
+
+    let applied = Rc::new(Cell::new(None));
+    let result = applied.clone();
+    dom.in_runtime(|| {
+        Runtime::current().spawn(ScopeId::ROOT, async move {
+            // clamped to the max scroll
+            let offset = context
+                .scroll(PixelsVector2D::new(0.0, 5000.0), ScrollBehavior::Instant)
+                .await;
+            result.set(Some(offset));
+        })
+    });
+    settle(&mut dom);
+
+    let max = PixelsVector2D::new(0.0, 1500.0);
+    assert_eq!(applied.get(), Some(Some(max)));
+    assert_eq!(
+        REQUESTS.with(|requests| requests.borrow().clone()),
+        vec![max]
+    );
+}
@@ -0,0 +1,143 @@
+//! [FloatingResult::shared_edge] and [FloatingResult::contact_range] of computed
+//! placements: every side and alignment, flipped, offset and shifted elements.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{Axes, Floating, FloatingOptions, FloatingResult, Offset, Placement, Side};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+/// Computes the placement of a 100x50 element in a 500x500 boundary.
+fn place(trigger: PixelsRect, options: FloatingOptions) -> FloatingResult {
+    let output = Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        trigger,
+        options,
+    );
+
+    FloatingResult {
+        x: output.x,
+        y: output.y,
+        is_ready: true,
+        placement: output.placement,
+        shift: output.shift,
+        trigger_rect: output.trigger_rect,
+        element_size: output.element_size,
+        ..Default::default()
+    }
+}
+
+fn flush(placement: Placement) -> FloatingOptions {
+    FloatingOptions {
+        placement,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    }
+}
+
+fn contact(result: FloatingResult) -> Option<(Side, (f64, f64))> {
+    assert_eq!(
+        result.shared_edge().is_some(),
+        result.contact_range().is_some()
+    );
+
+    result.shared_edge().zip(result.contact_range())
+}
+
+#[test]
+fn every_placement_shares_the_edge_facing_the_trigger() {
+    // a 60x30 trigger in the middle of the boundary
+    let trigger = rect(220.0, 235.0, 60.0, 30.0);
+    let cases = [
+        (Placement::TopStart, Side::Bottom, (0.0, 60.0)),
+        (Placement::TopCenter, Side::Bottom, (20.0, 80.0)),
+        (Placement::TopEnd, Side::Bottom, (40.0, 100.0)),
+        (Placement::BottomStart, Side::Top, (0.0, 60.0)),
+        (Placement::BottomCenter, Side::Top, (20.0, 80.0)),
+        (Placement::BottomEnd, Side::Top, (40.0, 100.0)),
+        // the 30px tall trigger along the 50px tall edge
+        (Placement::LeftStart, Side::Right, (0.0, 30.0)),
+        (Placement::LeftCenter, Side::Right, (10.0, 40.0)),
+        (Placement::LeftEnd, Side::Right, (20.0, 50.0)),
+        (Placement::RightStart, Side::Left, (0.0, 30.0)),
+        (Placement::RightCenter, Side::Left, (10.0, 40.0)),
+        (Placement::RightEnd, Side::Left, (20.0, 50.0)),
+    ];
+
+    for (placement, edge, range) in cases {
+        let result = place(trigger, flush(placement));
+        assert_eq!(result.placement, placement);
+        assert_eq!(contact(result), Some((edge, range)), "{placement:?}");
+    }
+}
+
+#[test]
+fn the_edge_follows_a_flip() {
+    // no room below
+    let trigger = rect(100.0, 470.0, 60.0, 20.0);
+    let result = place(trigger, flush(Placement::BottomStart));
+
+    assert_eq!(result.placement, Placement::TopStart);
+    assert_eq!(contact(result), Some((Side::Bottom, (0.0, 60.0))));
+}
+
+#[test]
+fn an_offset_breaks_the_contact() {
+    let trigger = rect(100.0, 100.0, 60.0, 20.0);
+    let options = FloatingOptions {
+        placement_offset: Some(Offset::new(4.0, 4.0)),
+        ..flush(Placement::BottomStart)
+    };
+
+    assert_eq!(contact(place(trigger, options)), None);
+}
+
+#[test]
+fn a_cross_axis_shift_narrows_the_contact() {
+    // aligned at its start, the element would overflow the right edge by 50px
+    let trigger = rect(450.0, 100.0, 40.0, 20.0);
+    let result = place(trigger, flush(Placement::BottomStart));
+
+    assert_eq!(result.x, 400.0);
+    assert_eq!(contact(result), Some((Side::Top, (50.0, 90.0))));
+}
+
+#[test]
+fn a_shift_past_the_trigger_breaks_the_contact() {
+    // the trigger scrolled out to the left: the tether keeps the element beside it,
+    // touching at the corner only
+    let trigger = rect(-300.0, 100.0, 100.0, 20.0);
+    let result = place(trigger, flush(Placement::BottomStart));
+
+    assert_eq!(result.x, -200.0);
+    assert_eq!(contact(result), None);
+}
+
+#[test]
+fn a_main_axis_shift_over_the_trigger_breaks_the_contact() {
+    // fits neither below nor above: shifted up over the trigger
+    let trigger = rect(100.0, 40.0, 60.0, 20.0);
+    let options = FloatingOptions {
+        shift_axes: Axes::Both,
+        ..flush(Placement::BottomStart)
+    };
+    let output = Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 100.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        trigger,
+        options,
+    );
+    assert!(output.overlaps_trigger);
+
+    let result = FloatingResult {
+        x: output.x,
+        y: output.y,
+        placement: output.placement,
+        trigger_rect: output.trigger_rect,
+        element_size: output.element_size,
+        ..Default::default()
+    };
+    assert_eq!(contact(result), None);
+}
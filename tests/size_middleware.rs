@@ -0,0 +1,173 @@
+//! [Middleware::Size] limits the element to the room between the trigger and the
+//! boundary, on the side with the most room when it fits on neither.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{
+    Floating, FloatingOptions, Middleware, Offset, Padding, PaddingValue, Placement,
+    PlacementOutput,
+};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn sized(placement: Placement) -> FloatingOptions {
+    FloatingOptions {
+        middleware: vec![Middleware::Flip, Middleware::Shift, Middleware::Size],
+        placement,
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    }
+}
+
+/// Places an element of `height` (200px wide) in a 500x500 boundary.
+fn place(trigger: PixelsRect, height: f64, options: FloatingOptions) -> PlacementOutput {
+    Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 200.0, height),
+        trigger,
+        options,
+    )
+}
+
+#[test]
+fn a_fitting_element_is_not_moved() {
+    let trigger = rect(100.0, 180.0, 80.0, 20.0);
+    let output = place(trigger, 100.0, sized(Placement::BottomStart));
+
+    assert_eq!((output.x, output.y), (100.0, 200.0));
+    assert_eq!(
+        (output.max_width, output.max_height),
+        (Some(500.0), Some(300.0))
+    );
+    assert_eq!(output.element_size, PixelsSize::new(200.0, 100.0));
+}
+
+#[test]
+fn a_long_menu_is_limited_below_the_trigger() {
+    let trigger = rect(100.0, 180.0, 80.0, 20.0);
+    let output = place(trigger, 2000.0, sized(Placement::BottomStart));
+
+    assert_eq!(output.placement, Placement::BottomStart);
+    assert_eq!(output.max_height, Some(300.0));
+    // placed at the limited size: no shift over the trigger
+    assert_eq!(output.y, 200.0);
+    assert_eq!(output.element_size, PixelsSize::new(200.0, 300.0));
+    assert!(!output.overlaps_trigger);
+}
+
+#[test]
+fn the_side_with_the_most_room_wins() {
+    // 80px below, 400px above
+    let trigger = rect(100.0, 400.0, 80.0, 20.0);
+    let output = place(trigger, 2000.0, sized(Placement::BottomStart));
+
+    assert_eq!(output.placement, Placement::TopStart);
+    assert_eq!(output.max_height, Some(400.0));
+    assert_eq!(output.y, 0.0);
+
+    // fits on neither side, but the preferred one has more room
+    let trigger = rect(100.0, 300.0, 80.0, 20.0);
+    let output = place(trigger, 2000.0, sized(Placement::TopStart));
+    assert_eq!(output.placement, Placement::TopStart);
+    assert_eq!((output.y, output.max_height), (0.0, Some(300.0)));
+}
+
+#[test]
+fn flips_to_a_side_where_it_fits() {
+    // 180px above, 300px below
+    let trigger = rect(100.0, 180.0, 80.0, 20.0);
+    let output = place(trigger, 250.0, sized(Placement::TopStart));
+
+    assert_eq!(output.placement, Placement::BottomStart);
+    assert_eq!((output.y, output.max_height), (200.0, Some(300.0)));
+    assert_eq!(output.element_size.height, 250.0);
+}
+
+#[test]
+fn without_flip_the_preferred_side_is_limited() {
+    let trigger = rect(100.0, 400.0, 80.0, 20.0);
+    let options = FloatingOptions {
+        middleware: vec![Middleware::Shift, Middleware::Size],
+        ..sized(Placement::BottomStart)
+    };
+    let output = place(trigger, 2000.0, options);
+
+    assert_eq!(output.placement, Placement::BottomStart);
+    assert_eq!((output.y, output.max_height), (420.0, Some(80.0)));
+}
+
+#[test]
+fn the_offset_and_the_boundary_padding_are_taken() {
+    let trigger = rect(100.0, 180.0, 80.0, 20.0);
+    let options = FloatingOptions {
+        placement_offset: Some(Offset::new(4.0, 4.0)),
+        boundary_padding: Padding {
+            bottom: PaddingValue::Px(50.0),
+            ..Default::default()
+        },
+        ..sized(Placement::BottomStart)
+    };
+    let output = place(trigger, 2000.0, options);
+
+    assert_eq!(output.max_height, Some(246.0));
+    assert_eq!(output.y, 204.0);
+}
+
+#[test]
+fn the_padding_is_taken() {
+    let trigger = rect(100.0, 180.0, 80.0, 20.0);
+    let options = FloatingOptions {
+        padding: 10.0,
+        ..sized(Placement::BottomStart)
+    };
+    let output = place(trigger, 2000.0, options);
+
+    assert_eq!(
+        (output.max_width, output.max_height),
+        (Some(480.0), Some(290.0))
+    );
+    // the bottom edge stays 10px inside the boundary
+    assert_eq!(output.y + output.element_size.height, 490.0);
+}
+
+#[test]
+fn along_the_width_of_horizontal_placements() {
+    // 380px to the left, 40px to the right
+    let trigger = rect(380.0, 100.0, 80.0, 20.0);
+    let output = place(trigger, 100.0, sized(Placement::RightStart));
+
+    assert_eq!(output.placement, Placement::LeftStart);
+    assert_eq!(
+        (output.max_width, output.max_height),
+        (Some(380.0), Some(500.0))
+    );
+    assert_eq!(output.x, 180.0);
+}
+
+#[test]
+fn unbounded_elements_are_not_limited() {
+    let trigger = rect(100.0, 400.0, 80.0, 20.0);
+    let options = FloatingOptions {
+        unbounded: true,
+        ..sized(Placement::BottomStart)
+    };
+    let output = place(trigger, 2000.0, options);
+
+    assert_eq!(output.placement, Placement::BottomStart);
+    assert_eq!((output.max_width, output.max_height), (None, None));
+    assert_eq!(output.element_size.height, 2000.0);
+}
+
+#[test]
+fn explained_before_the_base() {
+    let options = FloatingOptions {
+        explain: true,
+        ..sized(Placement::BottomStart)
+    };
+    let output = place(rect(100.0, 400.0, 80.0, 20.0), 2000.0, options);
+
+    let names: Vec<_> = output.steps.iter().map(|step| step.name).collect();
+    assert_eq!(names[..3], ["size", "base", "flip"]);
+    assert_eq!(output.steps[0].after, (100.0, 0.0));
+}
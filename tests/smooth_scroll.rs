@@ -0,0 +1,161 @@
+//! The smooth scroll estimate of [ScrollableContext::is_smooth_scrolling]: set by smooth
+//! programmatic scrolls only, cleared on arrival, on user input and by instant scrolls,
+//! and the placements frozen by [FloatingOptions::freeze_during_smooth_scroll].
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+
+use dioxus::core::Runtime;
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    FloatingOptions, FloatingResult, Offset, Placement, ScrollableContext, use_placement,
+};
+
+thread_local! {
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static TRIGGER: MockElement = MockElement::new(rect(100.0, 100.0, 80.0, 20.0));
+    static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+    static FREEZE: Cell<bool> = const { Cell::new(true) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    let context = use_hook(|| {
+        let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0));
+        config.scroll_state.size = PixelsSize::new(500.0, 2000.0);
+        provide_mock_scrollable(config)
+    });
+    CONTEXT.with(|cell| cell.set(Some(context)));
+    rsx! { Popover {} }
+}
+
+#[component]
+fn Popover() -> Element {
+    let element_ref = use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 200.0, 100.0)).mounted()));
+    let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    let options = FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        freeze_during_smooth_scroll: FREEZE.with(Cell::get),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULT.with(|cell| cell.set(placement()));
+    rsx! {}
+}
+
+/// Returns a settled dom with a popover below the trigger and the mock context.
+fn dom(freeze: bool) -> (VirtualDom, ScrollableContext) {
+    FREEZE.with(|cell| cell.set(freeze));
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    assert_eq!(RESULT.with(Cell::get).y, 120.0);
+
+    (dom, CONTEXT.with(Cell::get).unwrap())
+}
+
+fn is_smooth_scrolling(dom: &VirtualDom, context: ScrollableContext) -> bool {
+    dom.in_runtime(|| *context.is_smooth_scrolling.peek())
+}
+
+/// Runs a scroll to `y` with `behavior` through the context to completion.
+fn scroll(dom: &mut VirtualDom, context: ScrollableContext, y: f64, behavior: ScrollBehavior) {
+    dom.in_runtime(|| {
+        Runtime::current().spawn(ScopeId::ROOT, async move {
+            context.scroll(PixelsVector2D::new(0.0, y), behavior).await;
+        })
+    });
+    settle(dom);
+}
+
+/// Moves the trigger like a scroll of the content to `y` and publishes the offset.
+fn scroll_frame(dom: &VirtualDom, mut context: ScrollableContext, y: f64) {
+    TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 100.0 - y, 80.0, 20.0)));
+    dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, y)));
+}
+
+#[test]
+fn user_scrolls_never_set_the_flag() {
+    let (mut dom, context) = dom(false);
+
+    scroll_frame(&dom, context, 40.0);
+    settle(&mut dom);
+
+    assert!(!is_smooth_scrolling(&dom, context));
+    assert_eq!(RESULT.with(Cell::get).y, 80.0);
+}
+
+#[test]
+fn wheel_during_a_smooth_scroll_clears_the_flag_immediately() {
+    let (mut dom, mut context) = dom(false);
+
+    scroll(&mut dom, context, 300.0, ScrollBehavior::Smooth);
+    assert!(is_smooth_scrolling(&dom, context));
+    scroll_frame(&dom, context, 50.0);
+    assert!(is_smooth_scrolling(&dom, context));
+
+    // the ScrollableView calls it on wheel events
+    dom.in_runtime(|| context.interrupt_smooth_scroll());
+    assert!(!is_smooth_scrolling(&dom, context));
+}
+
+#[test]
+fn arrival_at_the_target_clears_the_flag() {
+    let (mut dom, context) = dom(false);
+
+    scroll(&mut dom, context, 300.0, ScrollBehavior::Smooth);
+    scroll_frame(&dom, context, 299.5);
+    assert!(!is_smooth_scrolling(&dom, context));
+}
+
+#[test]
+fn instant_and_empty_scrolls_do_not_set_the_flag() {
+    let (mut dom, context) = dom(false);
+
+    // already there: nothing to animate
+    scroll(&mut dom, context, 0.0, ScrollBehavior::Smooth);
+    assert!(!is_smooth_scrolling(&dom, context));
+
+    // an instant scroll replaces a smooth one
+    scroll(&mut dom, context, 300.0, ScrollBehavior::Smooth);
+    assert!(is_smooth_scrolling(&dom, context));
+    scroll(&mut dom, context, 100.0, ScrollBehavior::Instant);
+    assert!(!is_smooth_scrolling(&dom, context));
+}
+
+#[test]
+fn frozen_placements_hold_and_recompute_once_at_the_end() {
+    let (mut dom, mut context) = dom(true);
+
+    scroll(&mut dom, context, 60.0, ScrollBehavior::Smooth);
+    for y in [20.0, 40.0] {
+        scroll_frame(&dom, context, y);
+        settle(&mut dom);
+        assert_eq!(RESULT.with(Cell::get).y, 120.0);
+    }
+
+    // the user takes over at 40: the placement snaps to the final position
+    dom.in_runtime(|| context.interrupt_smooth_scroll());
+    settle(&mut dom);
+    assert_eq!(RESULT.with(Cell::get).y, 80.0);
+}
+
+#[test]
+fn placements_follow_smooth_scrolls_without_the_option() {
+    let (mut dom, context) = dom(false);
+
+    scroll(&mut dom, context, 60.0, ScrollBehavior::Smooth);
+    scroll_frame(&dom, context, 20.0);
+    settle(&mut dom);
+
+    assert_eq!(RESULT.with(Cell::get).y, 100.0);
+}
@@ -0,0 +1,95 @@
+//! A measurement that resolves after a newer one is discarded: the placement never goes
+//! back to the rects of an older layout.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{FloatingOptions, FloatingResult, Offset, ScrollableContext, use_placement};
+
+thread_local! {
+    static TRIGGER: MockElement = MockElement::new(rect(100.0, 100.0, 80.0, 20.0));
+    static ELEMENT: MockElement = MockElement::new(rect(0.0, 0.0, 200.0, 100.0));
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    let context = use_hook(|| {
+        provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)))
+    });
+    CONTEXT.with(|cell| cell.set(Some(context)));
+    let element_ref = use_signal(|| Some(ELEMENT.with(MockElement::mounted)));
+    let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    let options = FloatingOptions {
+        placement_offset: Some(Offset::zero()),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULT.with(|cell| cell.set(placement()));
+    rsx! {}
+}
+
+/// Moves the trigger and invalidates the measurements.
+fn move_trigger(dom: &mut VirtualDom, y: f64) {
+    TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, y, 80.0, 20.0)));
+    let mut generation = CONTEXT.with(Cell::get).unwrap().layout_generation;
+    dom.in_runtime(|| *generation.write() += 1);
+    settle(dom);
+}
+
+fn y() -> f64 {
+    RESULT.with(Cell::get).y
+}
+
+#[test]
+fn a_late_measurement_is_discarded() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    assert_eq!(y(), 120.0);
+
+    // the trigger is read at y=150, then the element read takes a while
+    ELEMENT.with(MockElement::hold_next);
+    move_trigger(&mut dom, 150.0);
+    assert!(RESULT.with(Cell::get).is_pending);
+    assert_eq!(y(), 120.0);
+
+    // a newer measurement finishes first; it is published with the batch of the
+    // older one
+    move_trigger(&mut dom, 200.0);
+    assert_eq!(y(), 120.0);
+
+    // the older one resolves last, with the trigger of its own layout
+    ELEMENT.with(MockElement::release);
+    settle(&mut dom);
+    let result = RESULT.with(Cell::get);
+    assert_eq!(result.y, 220.0);
+    assert!(!result.is_pending);
+}
+
+#[test]
+fn measurements_in_order_are_published() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    // the older measurement resolves first, then the newer one
+    ELEMENT.with(MockElement::hold);
+    move_trigger(&mut dom, 150.0);
+    ELEMENT.with(MockElement::release);
+    settle(&mut dom);
+    assert_eq!(y(), 170.0);
+
+    move_trigger(&mut dom, 200.0);
+    assert_eq!(y(), 220.0);
+}
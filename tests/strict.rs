@@ -0,0 +1,218 @@
+//! The misuse detection of the `strict` feature: every misuse panics with a descriptive
+//! message in debug builds, and is tolerated as before without the feature. Run both:
+//!
+//! ```sh
+//! cargo test --test strict
+//! cargo test --features strict --test strict
+//! ```
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{Axes, Floating, FloatingOptions, Offset};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn compute(options: FloatingOptions) -> (f64, f64) {
+    let output = Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        rect(40.0, 40.0, 60.0, 20.0),
+        options,
+    );
+
+    (output.x, output.y)
+}
+
+fn nan_offset() -> FloatingOptions {
+    FloatingOptions {
+        placement_offset: Some(Offset::new(f64::NAN, 0.0)),
+        ..Default::default()
+    }
+}
+
+fn flip_options_without_flip() -> FloatingOptions {
+    FloatingOptions {
+        middleware: Vec::new(),
+        flip_axes: Axes::Both,
+        ..Default::default()
+    }
+}
+
+#[cfg(all(feature = "strict", debug_assertions))]
+mod strict {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::sync::Once;
+
+    use dioxus::prelude::*;
+    use dioxus_floating::testing::{
+        MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+    };
+    use dioxus_floating::{Middleware, ScrollableContext, use_placement};
+
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "dioxus-floating strict mode: non-finite offset")]
+    fn nan_offset_panics() {
+        compute(nan_offset());
+    }
+
+    #[test]
+    #[should_panic(expected = "dioxus-floating strict mode: non-finite padding NaN")]
+    fn nan_padding_panics() {
+        compute(FloatingOptions {
+            padding: f64::NAN,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "have no effect without Middleware::Flip in []")]
+    fn flip_options_without_flip_panic() {
+        compute(flip_options_without_flip());
+    }
+
+    #[test]
+    fn flip_options_with_flip_are_fine() {
+        compute(FloatingOptions {
+            middleware: vec![Middleware::Flip],
+            ..flip_options_without_flip()
+        });
+    }
+
+    /// Runs `f` and returns the strict mode panics of this thread; the renderer catches
+    /// the panics of components and tasks, so `should_panic` does not see them.
+    fn strict_panics(f: impl FnOnce()) -> Vec<String> {
+        thread_local! {
+            static PANICS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+        static HOOK: Once = Once::new();
+
+        HOOK.call_once(|| {
+            let default_hook = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                let message = info
+                    .payload()
+                    .downcast_ref::<String>()
+                    .filter(|message| message.starts_with("dioxus-floating strict mode"));
+                match message {
+                    Some(message) => {
+                        PANICS.with(|panics| panics.borrow_mut().push(message.clone()))
+                    }
+                    None => default_hook(info),
+                }
+            }));
+        });
+        PANICS.with(|panics| panics.borrow_mut().clear());
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+        PANICS.with(|panics| panics.take())
+    }
+
+    #[test]
+    fn placement_outside_of_a_view_panics() {
+        fn app() -> Element {
+            let element_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+            let trigger_ref = use_signal(|| Option::<Rc<MountedData>>::None);
+            use_placement(element_ref, trigger_ref, FloatingOptions::default());
+            rsx! {}
+        }
+
+        let panics = strict_panics(|| VirtualDom::new(app).rebuild_in_place());
+        assert_eq!(panics.len(), 1, "{panics:?}");
+        assert!(panics[0].contains("hook used outside of ScrollableView"));
+    }
+
+    #[test]
+    fn zero_rects_panic() {
+        thread_local! {
+            static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+        }
+
+        fn app() -> Element {
+            let context = use_hook(|| {
+                provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)))
+            });
+            CONTEXT.with(|cell| cell.set(Some(context)));
+            let element_ref =
+                use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 0.0, 0.0)).mounted()));
+            let trigger_ref =
+                use_signal(|| Some(MockElement::new(rect(40.0, 40.0, 0.0, 0.0)).mounted()));
+            use_placement(element_ref, trigger_ref, FloatingOptions::default());
+            rsx! {}
+        }
+
+        let panics = strict_panics(|| {
+            let mut dom = VirtualDom::new(app);
+            dom.rebuild_in_place();
+            settle(&mut dom);
+            // every scroll state measures again
+            let mut scroll_state = CONTEXT.with(Cell::get).unwrap().scroll_state;
+            for y in 1..=3 {
+                dom.in_runtime(|| {
+                    scroll_state.with_mut(|state| {
+                        if let Some(state) = state {
+                            state.state.y = f64::from(y);
+                        }
+                    })
+                });
+                settle(&mut dom);
+            }
+        });
+        assert!(
+            panics
+                .iter()
+                .any(|message| message.contains("zero-size rects measured 3 times in a row")),
+            "{panics:?}"
+        );
+    }
+
+    #[test]
+    fn zero_width_triggers_are_fine() {
+        thread_local! {
+            static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+        }
+
+        fn app() -> Element {
+            let context = use_hook(|| {
+                provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)))
+            });
+            CONTEXT.with(|cell| cell.set(Some(context)));
+            let element_ref =
+                use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 100.0, 50.0)).mounted()));
+            // a vertical line in a chart
+            let trigger_ref =
+                use_signal(|| Some(MockElement::new(rect(40.0, 40.0, 0.0, 120.0)).mounted()));
+            use_placement(element_ref, trigger_ref, FloatingOptions::default());
+            rsx! {}
+        }
+
+        let panics = strict_panics(|| {
+            let mut dom = VirtualDom::new(app);
+            dom.rebuild_in_place();
+            settle(&mut dom);
+            let mut context = CONTEXT.with(Cell::get).unwrap();
+            for _ in 0..3 {
+                dom.in_runtime(|| *context.layout_generation.write() += 1);
+                settle(&mut dom);
+            }
+        });
+        assert!(panics.is_empty(), "{panics:?}");
+    }
+}
+
+#[cfg(not(all(feature = "strict", debug_assertions)))]
+mod lenient {
+    use super::*;
+
+    #[test]
+    fn misuse_is_tolerated() {
+        // the flip options are ignored, the element stays below the trigger
+        let (_, y) = compute(flip_options_without_flip());
+        assert!(y >= 60.0);
+        // the gap of the default Bottom placement
+        let (_, y) = compute(nan_offset());
+        assert!(y.is_nan());
+    }
+}
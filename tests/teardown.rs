@@ -0,0 +1,163 @@
+//! Mounting and unmounting a scrollable view with open popovers while their measurements
+//! are in flight runs clean: no panics, no writes to dropped signals, and the placements
+//! of the next mount are not held up by the ones that were torn down.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::{Cell, RefCell};
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    AutoUpdate, FloatingOptions, FloatingResult, Offset, ScrollableContext, use_placement,
+};
+
+const POPOVERS: usize = 4;
+
+thread_local! {
+    static MOUNTED: Cell<Option<Signal<bool>>> = const { Cell::new(None) };
+    static CONTEXT: Cell<Option<ScrollableContext>> = const { Cell::new(None) };
+    static ELEMENTS: RefCell<Vec<MockElement>> = const { RefCell::new(Vec::new()) };
+    static RESULTS: RefCell<Vec<FloatingResult>> = const { RefCell::new(Vec::new()) };
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    let mounted = use_signal(|| true);
+    MOUNTED.with(|cell| cell.set(Some(mounted)));
+    rsx! {
+        if mounted() {
+            View {}
+        }
+    }
+}
+
+/// The scrollable view of a route, with a column of open popovers.
+#[component]
+fn View() -> Element {
+    use_hook(|| {
+        let context =
+            provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)));
+        CONTEXT.with(|cell| cell.set(Some(context)));
+        ELEMENTS.with(|cell| cell.borrow_mut().clear());
+        RESULTS.with(|cell| *cell.borrow_mut() = vec![FloatingResult::default(); POPOVERS]);
+    });
+    rsx! {
+        for index in 0..POPOVERS {
+            Popover { key: "{index}", index }
+        }
+    }
+}
+
+#[component]
+fn Popover(index: usize) -> Element {
+    let element = use_hook(|| {
+        let element = MockElement::new(rect(0.0, 0.0, 100.0, 50.0));
+        ELEMENTS.with(|cell| cell.borrow_mut().push(element.clone()));
+        element
+    });
+    let top = 100.0 * index as f64;
+    let element_ref = use_signal(|| Some(element.mounted()));
+    let trigger_ref = use_signal(|| Some(MockElement::new(rect(50.0, top, 80.0, 20.0)).mounted()));
+    let options = FloatingOptions {
+        placement_offset: Some(Offset::zero()),
+        auto_update: AutoUpdate {
+            window_resize: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULTS.with(|cell| cell.borrow_mut()[index] = placement());
+    rsx! {}
+}
+
+fn set_mounted(dom: &mut VirtualDom, mounted: bool) {
+    let mut signal = MOUNTED.with(Cell::get).unwrap();
+    dom.in_runtime(|| signal.set(mounted));
+    settle(dom);
+}
+
+/// Invalidates the measurements of the view; `hold` keeps them in flight.
+fn remeasure(dom: &mut VirtualDom, hold: bool) {
+    if hold {
+        ELEMENTS.with(|cell| cell.borrow().iter().for_each(MockElement::hold));
+    }
+    let mut generation = CONTEXT.with(Cell::get).unwrap().layout_generation;
+    dom.in_runtime(|| *generation.write() += 1);
+    settle(dom);
+}
+
+fn elements() -> Vec<MockElement> {
+    ELEMENTS.with(|cell| cell.borrow().clone())
+}
+
+/// The popovers are placed below their triggers.
+fn assert_placed() {
+    let results = RESULTS.with(|cell| cell.borrow().clone());
+    for (index, result) in results.into_iter().enumerate() {
+        assert!(result.is_ready && !result.is_pending, "popover {index}");
+        assert_eq!((result.x, result.y), (50.0, 100.0 * index as f64 + 20.0));
+    }
+}
+
+#[test]
+fn unmounting_with_measurements_in_flight() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+    assert_placed();
+
+    for _ in 0..20 {
+        remeasure(&mut dom, true);
+        let held = elements();
+        set_mounted(&mut dom, false);
+        // the torn down measurements resolve after their scopes are gone
+        held.iter().for_each(MockElement::release);
+        settle(&mut dom);
+
+        set_mounted(&mut dom, true);
+        assert_placed();
+    }
+}
+
+#[test]
+fn elements_removed_before_the_unmount() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    for _ in 0..20 {
+        remeasure(&mut dom, true);
+        // the route change removes the elements first, their measurements fail
+        elements().iter().for_each(MockElement::remove);
+        settle(&mut dom);
+        remeasure(&mut dom, false);
+        set_mounted(&mut dom, false);
+
+        set_mounted(&mut dom, true);
+        assert_placed();
+    }
+}
+
+#[test]
+fn rapid_toggles_without_settling() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    // mounted and unmounted again before the first measurements finish
+    let mut mounted = MOUNTED.with(Cell::get).unwrap();
+    for index in 0..50 {
+        dom.in_runtime(|| mounted.set(index % 2 == 1));
+        dom.process_events();
+        dom.render_immediate_to_vec();
+    }
+    settle(&mut dom);
+    assert_placed();
+}
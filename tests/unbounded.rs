@@ -0,0 +1,137 @@
+//! [FloatingOptions::unbounded] lets the element follow its anchor outside the boundary,
+//! and clearing it keeps the element in the boundary again on the next computation.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{Floating, FloatingOptions, Offset, Padding, PaddingValue, Placement};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn options(unbounded: bool) -> FloatingOptions {
+    FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        min_visible: 20.0,
+        anchor_to_visible: true,
+        unbounded,
+        ..Default::default()
+    }
+}
+
+fn place(trigger: PixelsRect, options: FloatingOptions) -> (f64, f64, Placement, bool) {
+    let output = Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 100.0, 50.0),
+        trigger,
+        options,
+    );
+
+    (output.x, output.y, output.placement, output.is_hidden)
+}
+
+#[test]
+fn bounded_elements_are_kept_in_the_boundary() {
+    // no room below: flipped; past the right edge: shifted back
+    let trigger = rect(450.0, 470.0, 20.0, 20.0);
+    assert_eq!(
+        place(trigger, options(false)),
+        (400.0, 420.0, Placement::TopStart, false)
+    );
+}
+
+#[test]
+fn unbounded_elements_follow_the_anchor() {
+    let trigger = rect(450.0, 470.0, 20.0, 20.0);
+    assert_eq!(
+        place(trigger, options(true)),
+        (450.0, 490.0, Placement::BottomStart, false)
+    );
+
+    // the anchor outside the boundary (e.g., dragged into a sibling drop zone): neither
+    // hidden nor pulled back by min_visible
+    let trigger = rect(620.0, 100.0, 20.0, 20.0);
+    assert_eq!(
+        place(trigger, options(true)),
+        (620.0, 120.0, Placement::BottomStart, false)
+    );
+}
+
+#[test]
+fn a_negative_boundary_padding_widens_the_boundary() {
+    let options = FloatingOptions {
+        boundary_padding: Padding {
+            right: PaddingValue::Px(-200.0),
+            ..Default::default()
+        },
+        // the anchor is measured against the container itself
+        anchor_to_visible: false,
+        ..options(false)
+    };
+
+    // shifted against the edge 200px right of the container
+    let trigger = rect(620.0, 100.0, 20.0, 20.0);
+    assert_eq!(
+        place(trigger, options),
+        (600.0, 120.0, Placement::BottomStart, false)
+    );
+}
+
+#[cfg(feature = "hooks")]
+mod hooks {
+    use std::cell::Cell;
+
+    use dioxus::prelude::*;
+    use dioxus_floating::testing::{
+        MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+    };
+    use dioxus_floating::{FloatingResult, use_placement};
+
+    use super::{options, rect};
+
+    thread_local! {
+        static DRAGGING: Cell<Option<Signal<bool>>> = const { Cell::new(None) };
+        static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+    }
+
+    fn app() -> Element {
+        use_hook(|| {
+            provide_mock_scrollable(MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0)))
+        });
+        let dragging = use_signal(|| false);
+        DRAGGING.with(|cell| cell.set(Some(dragging)));
+        let element_ref =
+            use_signal(|| Some(MockElement::new(rect(0.0, 0.0, 100.0, 50.0)).mounted()));
+        // the pointer, past the right edge of the container
+        let trigger_ref =
+            use_signal(|| Some(MockElement::new(rect(620.0, 100.0, 20.0, 20.0)).mounted()));
+        let placement = use_placement(element_ref, trigger_ref, options(dragging()));
+        RESULT.with(|cell| cell.set(placement()));
+        rsx! {}
+    }
+
+    fn position() -> (f64, f64) {
+        let result = RESULT.with(Cell::get);
+        assert!(result.is_ready);
+        (result.x, result.y)
+    }
+
+    #[test]
+    fn toggling_the_bounds_at_runtime() {
+        let mut dom = VirtualDom::new(app);
+        dom.rebuild_in_place();
+        settle(&mut dom);
+        let mut dragging = DRAGGING.with(Cell::get).unwrap();
+        // kept 20px inside the boundary
+        assert_eq!(position(), (480.0, 120.0));
+
+        dom.in_runtime(|| dragging.set(true));
+        settle(&mut dom);
+        assert_eq!(position(), (620.0, 120.0));
+
+        // the drop: clamped again right away
+        dom.in_runtime(|| dragging.set(false));
+        settle(&mut dom);
+        assert_eq!(position(), (480.0, 120.0));
+    }
+}
@@ -0,0 +1,148 @@
+//! [FloatingResult::update_reason] attributes each published result to the input that
+//! triggered its measurement, and a change of the resolved side to the middleware.
+
+#![cfg(feature = "hooks")]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use dioxus::html::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus::prelude::*;
+use dioxus_floating::testing::{
+    MockElement, MockScrollableConfig, provide_mock_scrollable, settle,
+};
+use dioxus_floating::{
+    FloatingOptions, FloatingResult, FloatingUpdate, Offset, Placement, ScrollableContext,
+    UpdateReason, use_floating_update, use_placement,
+};
+
+thread_local! {
+    static TRIGGER: MockElement = MockElement::new(rect(100.0, 100.0, 80.0, 20.0));
+    static ELEMENT: MockElement = MockElement::new(rect(0.0, 0.0, 200.0, 100.0));
+    static HANDLES: Cell<Option<Handles>> = const { Cell::new(None) };
+    static RESULT: Cell<FloatingResult> = Cell::new(FloatingResult::default());
+}
+
+#[derive(Clone, Copy)]
+struct Handles {
+    context: ScrollableContext,
+    update: FloatingUpdate,
+    trigger_ref: Signal<Option<Rc<MountedData>>>,
+}
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+fn app() -> Element {
+    let context = use_hook(|| {
+        let mut config = MockScrollableConfig::new(rect(0.0, 0.0, 500.0, 500.0));
+        config.scroll_state.size = PixelsSize::new(500.0, 2000.0);
+        provide_mock_scrollable(config)
+    });
+    let update = use_floating_update();
+    let element_ref = use_signal(|| Some(ELEMENT.with(MockElement::mounted)));
+    let trigger_ref = use_signal(|| Some(TRIGGER.with(MockElement::mounted)));
+    HANDLES.with(|cell| {
+        cell.set(Some(Handles {
+            context,
+            update,
+            trigger_ref,
+        }))
+    });
+    let options = FloatingOptions {
+        placement: Placement::BottomStart,
+        placement_offset: Some(Offset::zero()),
+        update: Some(update),
+        ..Default::default()
+    };
+    let placement = use_placement(element_ref, trigger_ref, options);
+    RESULT.with(|cell| cell.set(placement()));
+    rsx! {}
+}
+
+/// Returns a settled dom with a ready placement below the trigger and the handles.
+fn dom() -> (VirtualDom, Handles) {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    settle(&mut dom);
+
+    (dom, HANDLES.with(Cell::get).unwrap())
+}
+
+fn result() -> FloatingResult {
+    RESULT.with(Cell::get)
+}
+
+/// Measures the placements again, like a layout change of the view.
+fn invalidate(dom: &mut VirtualDom, mut context: ScrollableContext) {
+    dom.in_runtime(|| *context.layout_generation.write() += 1);
+    settle(dom);
+}
+
+#[test]
+fn the_first_ready_result_is_initial() {
+    let (_dom, _) = dom();
+
+    assert!(result().is_ready);
+    assert_eq!(result().update_reason, UpdateReason::Initial);
+}
+
+#[test]
+fn scrolls_resizes_and_manual_updates() {
+    let (mut dom, handles) = dom();
+    let (mut context, update) = (handles.context, handles.update);
+
+    TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 60.0, 80.0, 20.0)));
+    dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, 40.0)));
+    settle(&mut dom);
+    assert_eq!(
+        (result().y, result().update_reason),
+        (80.0, UpdateReason::Scroll)
+    );
+
+    ELEMENT.with(|element| element.set_rect(rect(0.0, 0.0, 200.0, 120.0)));
+    invalidate(&mut dom, context);
+    assert_eq!(result().update_reason, UpdateReason::Resize);
+    assert_eq!(result().element_size.height, 120.0);
+
+    // the same position, published for another reason
+    dom.in_runtime(|| update.update());
+    settle(&mut dom);
+    assert_eq!(
+        (result().y, result().update_reason),
+        (80.0, UpdateReason::Manual)
+    );
+}
+
+#[test]
+fn flips_win_over_the_measured_reason() {
+    let (mut dom, handles) = dom();
+
+    // no room below anymore
+    TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 420.0, 80.0, 20.0)));
+    invalidate(&mut dom, handles.context);
+    assert_eq!(result().placement, Placement::TopStart);
+    assert_eq!(result().update_reason, UpdateReason::Flip);
+
+    // staying on top is a plain scroll again
+    let mut context = handles.context;
+    TRIGGER.with(|trigger| trigger.set_rect(rect(100.0, 400.0, 80.0, 20.0)));
+    dom.in_runtime(|| context.publish_offset(PixelsVector2D::new(0.0, 20.0)));
+    settle(&mut dom);
+    assert_eq!(result().placement, Placement::TopStart);
+    assert_eq!(result().update_reason, UpdateReason::Scroll);
+}
+
+#[test]
+fn a_new_trigger_node_changes_the_anchor() {
+    let (mut dom, handles) = dom();
+
+    let mut trigger_ref = handles.trigger_ref;
+    let trigger = MockElement::new(rect(300.0, 100.0, 80.0, 20.0));
+    dom.in_runtime(|| trigger_ref.set(Some(trigger.mounted())));
+    settle(&mut dom);
+
+    assert_eq!(result().x, 300.0);
+    assert_eq!(result().update_reason, UpdateReason::AnchorChanged);
+}
@@ -0,0 +1,95 @@
+//! Triggers without a width or height (e.g., the bounding box of a vertical or horizontal
+//! line in an SVG chart) are valid anchors: every placement aligns to their single edge.
+
+use dioxus_floating::geometry::{PixelsRect, PixelsSize, PixelsVector2D};
+use dioxus_floating::{Floating, FloatingOptions, Offset, Placement, PlacementOutput};
+
+fn rect(x: f64, y: f64, w: f64, h: f64) -> PixelsRect {
+    PixelsRect::new(PixelsVector2D::new(x, y).to_point(), PixelsSize::new(w, h))
+}
+
+/// Places a 60x20 element in a 500x500 boundary, far from its edges.
+fn compute(trigger: PixelsRect, placement: Placement, anchor_to_visible: bool) -> PlacementOutput {
+    Floating::default().compute_placement(
+        rect(0.0, 0.0, 500.0, 500.0),
+        rect(0.0, 0.0, 60.0, 20.0),
+        trigger,
+        FloatingOptions {
+            placement,
+            placement_offset: Some(Offset::zero()),
+            anchor_to_visible,
+            ..Default::default()
+        },
+    )
+}
+
+/// Asserts the position at every placement, with and without anchoring to the visible
+/// part of the trigger.
+fn assert_positions(trigger: PixelsRect, expected: [(Placement, (f64, f64)); 12]) {
+    for (placement, position) in expected {
+        for anchor_to_visible in [false, true] {
+            let output = compute(trigger, placement, anchor_to_visible);
+            assert_eq!(
+                (output.x, output.y),
+                position,
+                "{placement:?}, anchor_to_visible: {anchor_to_visible}"
+            );
+            assert_eq!(output.placement, placement, "{placement:?}");
+            assert!(!output.is_hidden, "{placement:?}");
+        }
+    }
+}
+
+#[test]
+fn zero_width_triggers() {
+    // a vertical line from (250, 200) to (250, 240)
+    assert_positions(
+        rect(250.0, 200.0, 0.0, 40.0),
+        [
+            (Placement::TopStart, (250.0, 180.0)),
+            (Placement::TopCenter, (220.0, 180.0)),
+            (Placement::TopEnd, (190.0, 180.0)),
+            (Placement::BottomStart, (250.0, 240.0)),
+            (Placement::BottomCenter, (220.0, 240.0)),
+            (Placement::BottomEnd, (190.0, 240.0)),
+            (Placement::LeftStart, (190.0, 200.0)),
+            (Placement::LeftCenter, (190.0, 210.0)),
+            (Placement::LeftEnd, (190.0, 220.0)),
+            (Placement::RightStart, (250.0, 200.0)),
+            (Placement::RightCenter, (250.0, 210.0)),
+            (Placement::RightEnd, (250.0, 220.0)),
+        ],
+    );
+}
+
+#[test]
+fn zero_height_triggers() {
+    // a horizontal line from (200, 250) to (280, 250)
+    assert_positions(
+        rect(200.0, 250.0, 80.0, 0.0),
+        [
+            (Placement::TopStart, (200.0, 230.0)),
+            (Placement::TopCenter, (210.0, 230.0)),
+            (Placement::TopEnd, (220.0, 230.0)),
+            (Placement::BottomStart, (200.0, 250.0)),
+            (Placement::BottomCenter, (210.0, 250.0)),
+            (Placement::BottomEnd, (220.0, 250.0)),
+            (Placement::LeftStart, (140.0, 250.0)),
+            (Placement::LeftCenter, (140.0, 240.0)),
+            (Placement::LeftEnd, (140.0, 230.0)),
+            (Placement::RightStart, (280.0, 250.0)),
+            (Placement::RightCenter, (280.0, 240.0)),
+            (Placement::RightEnd, (280.0, 230.0)),
+        ],
+    );
+}
+
+#[test]
+fn zero_extent_triggers_outside_of_the_boundary_are_hidden() {
+    let output = compute(rect(600.0, 200.0, 0.0, 40.0), Placement::BottomStart, true);
+    assert!(output.is_hidden);
+
+    // on the boundary edge, a line is still visible
+    let output = compute(rect(500.0, 200.0, 0.0, 40.0), Placement::BottomEnd, true);
+    assert!(!output.is_hidden);
+}